@@ -0,0 +1,62 @@
+//! Compares classifying five card tricks from packed bits against building
+//! each hand. Run with `cargo bench --features simd`.
+
+use pusoy_dos2::cards::{get_rank_array, get_suit_array, PlayedCard};
+use pusoy_dos2::game::{classify_tricks, Hand};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 20;
+
+fn get_tricks() -> Vec<[PlayedCard; 5]> {
+    let cards: Vec<PlayedCard> = get_rank_array()[4..].iter()
+        .flat_map(|&rank| {
+            get_suit_array()[..2].iter()
+                .map(move |&suit| PlayedCard::new(rank, suit, false))
+                .collect::<Vec<PlayedCard>>()
+        })
+        .collect();
+
+    let n = cards.len();
+    let mut tricks = vec![];
+    for a in 0..n {
+        for b in a + 1..n {
+            for c in b + 1..n {
+                for d in c + 1..n {
+                    for e in d + 1..n {
+                        tricks.push([cards[a], cards[b], cards[c], cards[d], cards[e]]);
+                    }
+                }
+            }
+        }
+    }
+    tricks
+}
+
+fn time<F: FnMut()>(mut run: F) -> Duration {
+    run();
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        run();
+    }
+    start.elapsed() / RUNS as u32
+}
+
+fn main() {
+    let tricks = get_tricks();
+
+    let built = time(|| {
+        for cards in tricks.iter() {
+            black_box(Hand::build(cards.to_vec()).and_then(|hand| hand.get_trick_type()));
+        }
+    });
+    let masked = time(|| {
+        black_box(classify_tricks(black_box(&tricks), true));
+    });
+
+    let per_trick = |elapsed: Duration| elapsed.as_nanos() as f64 / tricks.len() as f64;
+    println!("{} tricks", tricks.len());
+    println!("Hand::build      {:>8.1} ns/trick", per_trick(built));
+    println!("classify_tricks  {:>8.1} ns/trick", per_trick(masked));
+    println!("speedup          {:>8.1}x", built.as_secs_f64() / masked.as_secs_f64());
+}
@@ -0,0 +1,132 @@
+//! Compares playing rollouts out on full clones of a round against
+//! simulation forks. Run with `cargo bench --bench fork`.
+
+use pusoy_dos2::ai::get_legal_moves;
+use pusoy_dos2::cards::{get_suit_array, PlayedCard};
+use pusoy_dos2::game::{Game, Player, PlayerMetadata, Round, RoundBuilder, Ruleset};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 20;
+const ROLLOUTS: usize = 200;
+const OPENING_MOVES: usize = 12;
+
+/// A four player game a few moves in, with every player named, so both
+/// the metadata and the move history have something in them.
+fn get_round() -> Round {
+    let ids: Vec<String> = ["a", "b", "c", "d"].iter().map(|id| id.to_string()).collect();
+    let dealt = Game::new(1, 0, &ids, &get_suit_array(), Ruleset::default())
+        .unwrap()
+        .get_round()
+        .clone();
+    let players: Vec<Player> = ids.iter()
+        .map(|id| dealt.get_player(id).unwrap().with_metadata(PlayerMetadata {
+            display_name: Some(format!("Player {}", id)),
+            avatar: Some("fox".to_string()),
+            is_bot: true,
+        }))
+        .collect();
+
+    let mut round = RoundBuilder::new()
+        .players(players)
+        .next_player(&dealt.get_next_player().unwrap())
+        .build()
+        .unwrap();
+    for _ in 0..OPENING_MOVES {
+        round = play_lowest(&round);
+    }
+    round
+}
+
+/// Plays the first hand that isn't a pass, or passes if there's nothing
+/// else.
+fn play_lowest(round: &Round) -> Round {
+    let player_id = round.get_next_player().unwrap();
+    let cards = get_legal_moves(round, &player_id).into_iter()
+        .find(|cards| !cards.is_empty())
+        .unwrap_or_default();
+    round.submit_move(&player_id, cards).unwrap()
+}
+
+fn play_out(mut round: Round) -> Round {
+    while round.get_next_player().is_some() {
+        round = play_lowest(&round);
+    }
+    round
+}
+
+/// The moves one rollout from `round` makes, to replay without working
+/// out legal moves each time.
+fn get_rollout_moves(round: &Round) -> Vec<(String, Vec<PlayedCard>)> {
+    let mut round = round.clone();
+    let mut moves = vec![];
+    while let Some(player_id) = round.get_next_player() {
+        let next = play_lowest(&round);
+        let played = next.last_n_moves(1).pop().unwrap().hand.to_cards();
+        moves.push((player_id, played));
+        round = next;
+    }
+    moves
+}
+
+fn replay(mut round: Round, moves: &[(String, Vec<PlayedCard>)]) -> Round {
+    for (player_id, cards) in moves {
+        round.submit_move_mut(player_id, cards.clone()).unwrap();
+    }
+    round
+}
+
+fn time<F: FnMut()>(mut run: F) -> Duration {
+    run();
+    let start = Instant::now();
+    for _ in 0..RUNS {
+        run();
+    }
+    start.elapsed() / RUNS as u32
+}
+
+fn main() {
+    let round = get_round();
+
+    let cloned = time(|| {
+        for _ in 0..ROLLOUTS {
+            black_box(play_out(round.clone()));
+        }
+    });
+    let forked = time(|| {
+        for _ in 0..ROLLOUTS {
+            black_box(play_out(round.fork_for_simulation()));
+        }
+    });
+
+    let moves = get_rollout_moves(&round);
+    let cloned_replay = time(|| {
+        for _ in 0..ROLLOUTS {
+            black_box(replay(round.clone(), &moves));
+        }
+    });
+    let forked_replay = time(|| {
+        for _ in 0..ROLLOUTS {
+            black_box(replay(round.fork_for_simulation(), &moves));
+        }
+    });
+
+    let per_rollout = |elapsed: Duration| elapsed.as_micros() as f64 / ROLLOUTS as f64;
+    println!("{} rollouts, {} moves replayed in each", ROLLOUTS, moves.len());
+    println!("                     with legal moves   replayed");
+    println!(
+        "clone                {:>10.1} us {:>10.1} us",
+        per_rollout(cloned),
+        per_rollout(cloned_replay)
+    );
+    println!(
+        "fork_for_simulation  {:>10.1} us {:>10.1} us",
+        per_rollout(forked),
+        per_rollout(forked_replay)
+    );
+    println!(
+        "speedup              {:>10.2}x  {:>10.2}x",
+        cloned.as_secs_f64() / forked.as_secs_f64(),
+        cloned_replay.as_secs_f64() / forked_replay.as_secs_f64()
+    );
+}
@@ -1,5 +1,47 @@
+mod agents;
+mod audit;
+mod card_tracker;
+mod coaching;
+mod controls;
 mod cpu;
+#[cfg(feature = "std")]
+mod difficulty;
+mod encoding;
+mod endgame;
 mod hand_sorting;
+mod heuristic;
+mod inference;
+mod legal_moves;
+#[cfg(feature = "std")]
+mod mcts;
+mod opponent_model;
+mod partition;
+mod sampling;
+mod selfplay;
+mod strength;
+mod tournament;
+mod trick_odds;
 
+pub use self::agents::*;
+pub use self::audit::*;
+pub use self::card_tracker::*;
+pub use self::coaching::*;
+pub use self::controls::*;
 pub use self::cpu::*;
+#[cfg(feature = "std")]
+pub use self::difficulty::*;
+pub use self::encoding::*;
+pub use self::endgame::*;
 pub use self::hand_sorting::*;
+pub use self::heuristic::*;
+pub use self::inference::*;
+pub use self::legal_moves::*;
+#[cfg(feature = "std")]
+pub use self::mcts::*;
+pub use self::opponent_model::*;
+pub use self::partition::*;
+pub use self::sampling::*;
+pub use self::selfplay::*;
+pub use self::strength::*;
+pub use self::tournament::*;
+pub use self::trick_odds::*;
@@ -0,0 +1,292 @@
+use super::{get_legal_moves, get_move, get_move_cost};
+use crate::cards::PlayedCard;
+use crate::game::Round;
+use crate::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Something that can decide which cards to play for a seat.
+pub trait Agent {
+    fn choose_move(&mut self, round: &Round, player_id: &str) -> Vec<PlayedCard>;
+
+    /// A short name for the kind of agent, kept with the moves it makes.
+    fn get_name(&self) -> String {
+        "agent".to_string()
+    }
+
+    /// The seed the next move will be chosen with, for agents that make
+    /// random choices. A new agent built from this seed chooses the same
+    /// move from the same position.
+    fn get_seed(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// The original rule-of-thumb cpu player.
+#[derive(Debug, Default, Clone)]
+pub struct CpuAgent;
+
+impl Agent for CpuAgent {
+    /// The cpu's move, or the lowest legal hand when the round would turn
+    /// the cpu's move down.
+    fn choose_move(&mut self, round: &Round, player_id: &str) -> Vec<PlayedCard> {
+        let cards = get_move(
+            round.get_last_move(),
+            round.get_player(player_id),
+            round.get_ruleset(),
+            &round.get_order_context(),
+        ).unwrap_or_default();
+
+        if round.validate_move(player_id, &cards).is_ok() {
+            cards
+        } else {
+            LowestCardAgent.choose_move(round, player_id)
+        }
+    }
+
+    fn get_name(&self) -> String {
+        "cpu".to_string()
+    }
+}
+
+/// Picks uniformly from the legal moves. Each move is chosen with a seed
+/// of its own, drawn from the one before, so any single move can be
+/// replayed.
+#[derive(Debug, Clone)]
+pub struct RandomAgent {
+    next_seed: u64,
+}
+
+impl RandomAgent {
+    pub fn new(seed: u64) -> RandomAgent {
+        RandomAgent { next_seed: seed }
+    }
+}
+
+impl Agent for RandomAgent {
+    fn choose_move(&mut self, round: &Round, player_id: &str) -> Vec<PlayedCard> {
+        let mut rng = StdRng::seed_from_u64(self.next_seed);
+        let chosen = get_legal_moves(round, player_id)
+            .choose(&mut rng)
+            .cloned()
+            .unwrap_or_default();
+        self.next_seed = rng.gen();
+
+        chosen
+    }
+
+    fn get_name(&self) -> String {
+        "random".to_string()
+    }
+
+    fn get_seed(&self) -> Option<u64> {
+        Some(self.next_seed)
+    }
+}
+
+/// Always plays the legal move with the lowest top card, preferring fewer
+/// cards on a tie, and only passes when nothing can be played.
+#[derive(Debug, Default, Clone)]
+pub struct LowestCardAgent;
+
+impl Agent for LowestCardAgent {
+    fn choose_move(&mut self, round: &Round, player_id: &str) -> Vec<PlayedCard> {
+        let orders = round.get_order_context();
+
+        get_legal_moves(round, player_id)
+            .into_iter()
+            .filter(|cards| !cards.is_empty())
+            .min_by_key(|cards| get_move_cost(cards, &orders))
+            .unwrap_or_default()
+    }
+
+    fn get_name(&self) -> String {
+        "lowest_card".to_string()
+    }
+}
+
+/// What to play for a seat whose player has gone away.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AutoPlay {
+    /// Pass whenever passing is allowed, otherwise play the lowest legal
+    /// hand
+    PassOnly,
+    /// Play the lowest legal hand, passing only when nothing beats the
+    /// table
+    LowestLegal,
+}
+
+impl AutoPlay {
+    /// A move that `round` will accept from `player_id`, or `None` if it
+    /// isn't their turn.
+    pub fn get_move(self, round: &Round, player_id: &str) -> Option<Vec<PlayedCard>> {
+        if round.get_next_player().as_deref() != Some(player_id) {
+            return None;
+        }
+
+        let lowest = LowestCardAgent.choose_move(round, player_id);
+        let cards = match self {
+            AutoPlay::PassOnly if round.validate_move(player_id, &[]).is_ok() => vec![],
+            _ => lowest,
+        };
+
+        if round.validate_move(player_id, &cards).is_ok() {
+            Some(cards)
+        } else {
+            get_legal_moves(round, player_id).into_iter().next()
+        }
+    }
+}
+
+impl Agent for AutoPlay {
+    fn choose_move(&mut self, round: &Round, player_id: &str) -> Vec<PlayedCard> {
+        self.get_move(round, player_id).unwrap_or_default()
+    }
+
+    fn get_name(&self) -> String {
+        match self {
+            AutoPlay::PassOnly => "auto_play_pass_only".to_string(),
+            AutoPlay::LowestLegal => "auto_play_lowest_legal".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{Game, Hand, Player, RoundBuilder, Ruleset, RulesetPreset, TrickRanking};
+    use crate::scenario::parse_cards;
+
+    fn get_round() -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("4C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("9C 6H 6S 3H").unwrap()))
+            .next_player("b")
+            .last_move(
+                Hand::Single(PlayedCard::new(Rank::Five, Suit::Hearts, false)),
+                "a"
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn auto_play_always_opens_with_the_lowest_card() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("9S 3C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("4C").unwrap()))
+            .build()
+            .unwrap();
+        let three = vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)];
+
+        assert_eq!(AutoPlay::PassOnly.get_move(&round, "a"), Some(three.clone()));
+        assert_eq!(AutoPlay::LowestLegal.get_move(&round, "a"), Some(three));
+        assert_eq!(AutoPlay::LowestLegal.get_move(&round, "b"), None);
+    }
+
+    #[test]
+    fn auto_play_policies_differ_once_play_is_under_way() {
+        let round = get_round();
+
+        assert_eq!(AutoPlay::PassOnly.get_move(&round, "b"), Some(vec![]));
+        assert_eq!(
+            AutoPlay::LowestLegal.get_move(&round, "b"),
+            Some(vec![PlayedCard::new(Rank::Six, Suit::Hearts, false)])
+        );
+    }
+
+    #[test]
+    fn lowest_card_agent_plays_the_lowest_beating_card() {
+        let mut agent = LowestCardAgent;
+
+        assert_eq!(
+            agent.choose_move(&get_round(), "b"),
+            vec![PlayedCard::new(Rank::Six, Suit::Hearts, false)]
+        );
+    }
+
+    #[test]
+    fn lowest_card_agent_passes_when_it_cannot_play() {
+        let mut agent = LowestCardAgent;
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("4C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("3H").unwrap()))
+            .next_player("b")
+            .last_move(
+                Hand::Single(PlayedCard::new(Rank::Five, Suit::Hearts, false)),
+                "a"
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(agent.choose_move(&round, "b"), vec![]);
+    }
+
+    #[test]
+    fn random_agent_only_plays_legal_moves() {
+        let round = get_round();
+        let legal_moves = get_legal_moves(&round, "b");
+        let mut agent = RandomAgent::new(7);
+
+        for _ in 0..20 {
+            let chosen = agent.choose_move(&round, "b");
+            assert!(legal_moves.contains(&chosen));
+        }
+    }
+
+    #[test]
+    fn random_agent_is_reproducible_from_a_seed() {
+        let round = get_round();
+        let mut first = RandomAgent::new(42);
+        let mut second = RandomAgent::new(42);
+
+        for _ in 0..10 {
+            assert_eq!(
+                first.choose_move(&round, "b"),
+                second.choose_move(&round, "b")
+            );
+        }
+    }
+
+    #[test]
+    fn cpu_agent_wraps_the_rule_based_ai() {
+        let mut agent = CpuAgent;
+
+        assert_eq!(
+            agent.choose_move(&get_round(), "b"),
+            vec![PlayedCard::new(Rank::Nine, Suit::Clubs, false)]
+        );
+    }
+
+    #[test]
+    fn cpu_agent_only_suggests_moves_the_ruleset_allows() {
+        let ids = vec!["a".to_string(), "b".to_string(), "c".to_string(), "d".to_string()];
+        let rulesets = [
+            Ruleset { twos_in_straights: false, ..Ruleset::default() },
+            Ruleset {
+                trick_ranking: TrickRanking { straight: None, ..TrickRanking::standard() },
+                ..Ruleset::default()
+            },
+            RulesetPreset::SuitFlushes.get_ruleset(),
+        ];
+
+        for &ruleset in rulesets.iter() {
+            for _ in 0..5 {
+                let mut game = Game::new(1, 0, &ids, &get_suit_array(), ruleset).unwrap();
+
+                while let Some(next) = game.get_next_player() {
+                    let cards = CpuAgent.choose_move(game.get_round(), &next);
+                    assert_eq!(game.suggest_move(&next), Some(cards.clone()));
+                    assert!(
+                        game.get_round().validate_move(&next, &cards).is_ok(),
+                        "{:?} suggested {:?}", ruleset, cards
+                    );
+                    game.play_move(&next, cards).unwrap();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use super::{get_legal_moves, Agent};
+use crate::cards::PlayedCard;
+use crate::game::Round;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// What an automated player decided a move from, kept with the move so a
+/// disputed decision can be checked: a new agent of the same kind built
+/// from `seed` chooses the same move from the same position.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecisionAudit {
+    /// The kind of agent, as given by `Agent::get_name`
+    pub agent: String,
+    /// The seed the move was chosen with, for agents that make random
+    /// choices
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// `Round::state_hash` of the position the move was chosen from
+    pub state_hash: u64,
+    /// How many legal moves there were to choose from
+    pub legal_moves: usize,
+}
+
+impl DecisionAudit {
+    /// Whether `round` is the position the decision was made from.
+    pub fn is_from(&self, round: &Round) -> bool {
+        round.state_hash() == self.state_hash
+    }
+}
+
+/// Asks `agent` for a move and notes what it decided from.
+pub fn choose_audited_move(
+    agent: &mut dyn Agent,
+    round: &Round,
+    player_id: &str,
+) -> (Vec<PlayedCard>, DecisionAudit) {
+    let audit = DecisionAudit {
+        agent: agent.get_name(),
+        seed: agent.get_seed(),
+        state_hash: round.state_hash(),
+        legal_moves: get_legal_moves(round, player_id).len(),
+    };
+
+    (agent.choose_move(round, player_id), audit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{LowestCardAgent, RandomAgent};
+    use crate::scenario::Scenario;
+
+    #[test]
+    fn audited_moves_replay_from_their_seed() {
+        let round = Scenario::new()
+            .player("a", "3C 4C 5D 9H KS")
+            .player("b", "6C 7C")
+            .build()
+            .unwrap();
+        let mut agent = RandomAgent::new(11);
+        agent.choose_move(&round, "a");
+
+        let (cards, audit) = choose_audited_move(&mut agent, &round, "a");
+
+        assert_eq!(audit.agent, "random");
+        assert!(audit.is_from(&round));
+        assert_eq!(audit.legal_moves, get_legal_moves(&round, "a").len());
+        let mut replayed = RandomAgent::new(audit.seed.unwrap());
+        assert_eq!(replayed.choose_move(&round, "a"), cards);
+
+        let (_, audit) = choose_audited_move(&mut LowestCardAgent, &round, "a");
+        assert_eq!(audit.seed, None);
+    }
+}
@@ -0,0 +1,86 @@
+use crate::cards::{Card, PlayedCard};
+use crate::game::Round;
+use crate::prelude::*;
+
+/// The cards `viewer` hasn't seen yet: everything that is neither in their
+/// own hand nor already played. Cards are crossed off as they're observed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardTracker {
+    viewer: String,
+    unseen: Vec<Card>,
+}
+
+impl CardTracker {
+    pub fn new(viewer: &str, unseen: Vec<Card>) -> CardTracker {
+        CardTracker {
+            viewer: viewer.to_string(),
+            unseen,
+        }
+    }
+
+    /// Starts tracking from the round as it stands, treating every card in
+    /// an opponent's hand as unseen.
+    pub fn from_round(round: &Round, viewer: &str) -> CardTracker {
+        let mut unseen: Vec<Card> = round.get_players().into_iter()
+            .filter(|p| p.get_id() != viewer)
+            .flat_map(|p| p.get_hand())
+            .collect();
+        unseen.sort();
+
+        CardTracker::new(viewer, unseen)
+    }
+
+    /// Crosses off the cards in a move. The viewer's own moves are ignored,
+    /// since they've seen those cards all along.
+    pub fn observe(&mut self, player_id: &str, cards: &[PlayedCard]) {
+        if player_id == self.viewer {
+            return;
+        }
+
+        for &card in cards {
+            let position = self.unseen.iter().position(|&c| {
+                if card.get_is_joker() {
+                    c.get_rank().is_none()
+                } else {
+                    c.get_rank() == Some(card.get_rank())
+                        && c.get_suit() == Some(card.get_suit())
+                }
+            });
+
+            if let Some(i) = position {
+                self.unseen.remove(i);
+            }
+        }
+    }
+
+    pub fn get_viewer(&self) -> String {
+        self.viewer.clone()
+    }
+
+    pub fn get_unseen(&self) -> Vec<Card> {
+        self.unseen.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{Player, RoundBuilder};
+    use crate::scenario::parse_cards;
+
+    #[test]
+    fn it_crosses_off_cards_played_by_opponents() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("5C JK").unwrap()))
+            .build()
+            .unwrap();
+        let mut tracker = CardTracker::from_round(&round, "a");
+
+        tracker.observe("a", &[PlayedCard::new(Rank::Three, Suit::Clubs, false)]);
+        tracker.observe("b", &[PlayedCard::new(Rank::Two, Suit::Spades, true)]);
+
+        assert_eq!(tracker.get_unseen(), parse_cards("5C").unwrap());
+    }
+}
@@ -0,0 +1,148 @@
+use super::{evaluate, get_legal_moves};
+use crate::cards::PlayedCard;
+use crate::game::{Annotation, GameRecord, Round, VerifyError};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How a move compares with the one the engine prefers, by how much of
+/// `evaluate`'s 0 to 1 scale it gave up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoveQuality {
+    Best,
+    Good,
+    Inaccuracy,
+    Mistake,
+    Blunder,
+}
+
+impl MoveQuality {
+    pub fn from_delta(delta: f32) -> MoveQuality {
+        match delta {
+            d if d <= 0.005 => MoveQuality::Best,
+            d if d < 0.05 => MoveQuality::Good,
+            d if d < 0.15 => MoveQuality::Inaccuracy,
+            d if d < 0.3 => MoveQuality::Mistake,
+            _ => MoveQuality::Blunder,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoveReview {
+    /// The played move when nothing did better
+    pub best_move: Vec<PlayedCard>,
+    /// `evaluate` for the mover after the best move
+    pub best_eval: f32,
+    /// `evaluate` for the mover after the move they played
+    pub played_eval: f32,
+    /// How much the played move gave up against the best one
+    pub delta: f32,
+    pub quality: MoveQuality,
+}
+
+/// Compares `cards`, played by `player_id` in `round`, with every other
+/// legal move they had. `None` if the move isn't legal.
+pub fn review_move(round: &Round, player_id: &str, cards: &[PlayedCard]) -> Option<MoveReview> {
+    let played = round.submit_move(player_id, cards.to_vec()).ok()?;
+    let played_eval = evaluate(&played, player_id);
+
+    let (best_move, best_eval) = get_legal_moves(round, player_id).into_iter()
+        .filter_map(|cards| {
+            let after = round.submit_move(player_id, cards.clone()).ok()?;
+            Some((cards, evaluate(&after, player_id)))
+        })
+        .fold((cards.to_vec(), played_eval), |best, candidate| {
+            if candidate.1 > best.1 { candidate } else { best }
+        });
+    let delta = best_eval - played_eval;
+
+    Some(MoveReview {
+        best_move,
+        best_eval,
+        played_eval,
+        delta,
+        quality: MoveQuality::from_delta(delta),
+    })
+}
+
+/// Reviews every move of `record` with `review_move` and attaches the
+/// result as an `Annotation::Review`, replacing any earlier review.
+pub fn review_game(record: &mut GameRecord) -> Result<(), VerifyError> {
+    let mut round = record.initial_round.clone();
+
+    for (index, recorded) in record.moves.iter_mut().enumerate() {
+        let next = round.submit_move(&recorded.player_id, recorded.cards.clone())
+            .map_err(|error| VerifyError::IllegalMove {
+                index,
+                player_id: recorded.player_id.clone(),
+                error,
+            })?;
+        let review = review_move(&round, &recorded.player_id, &recorded.cards)
+            .expect("the move was just played");
+
+        recorded.annotations.retain(|a| !matches!(a, Annotation::Review(_)));
+        recorded.annotations.push(Annotation::Review(review));
+        round = next;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_played_cards, Scenario};
+
+    #[test]
+    fn moves_are_measured_against_the_best_one() {
+        let round = Scenario::new()
+            .player("a", "2S 2H 5C 7D")
+            .player("b", "9D 9S 10C 10H")
+            .lead("a")
+            .next_player("a")
+            .build()
+            .unwrap();
+        let cards = |cards| parse_played_cards(cards).unwrap();
+
+        let best_move = review_move(&round, "a", &cards("7D")).unwrap().best_move;
+        let best = review_move(&round, "a", &best_move).unwrap();
+        assert_eq!(best.quality, MoveQuality::Best);
+        assert_eq!(best.best_move, best_move);
+        assert_eq!(best.delta, 0.0);
+
+        let reviews: Vec<MoveReview> = get_legal_moves(&round, "a").iter()
+            .map(|cards| review_move(&round, "a", cards).unwrap())
+            .collect();
+        assert!(reviews.iter().all(|review| review.best_eval == best.best_eval));
+        assert!(reviews.iter().any(|review| review.delta > 0.0));
+        assert_eq!(review_move(&round, "a", &cards("9D")), None);
+    }
+
+    #[test]
+    fn bigger_deltas_are_worse_moves() {
+        assert_eq!(MoveQuality::from_delta(0.0), MoveQuality::Best);
+        assert_eq!(MoveQuality::from_delta(0.1), MoveQuality::Inaccuracy);
+        assert_eq!(MoveQuality::from_delta(0.5), MoveQuality::Blunder);
+    }
+
+    #[test]
+    fn reviews_are_attached_to_every_move() {
+        let round = Scenario::new()
+            .player("a", "3C 2S")
+            .player("b", "4C 5C")
+            .build()
+            .unwrap();
+        let mut record = GameRecord::new(round);
+        record.record_move("a", parse_played_cards("3C").unwrap());
+        record.record_move("b", parse_played_cards("5C").unwrap());
+
+        review_game(&mut record).unwrap();
+        review_game(&mut record).unwrap();
+
+        for recorded in record.moves.iter() {
+            assert_eq!(recorded.annotations.len(), 1);
+            assert!(matches!(recorded.annotations[0], Annotation::Review(_)));
+        }
+    }
+}
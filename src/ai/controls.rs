@@ -0,0 +1,143 @@
+use super::{get_candidate_moves, get_combinations, CardTracker};
+use crate::cards::{Card, PlayedCard};
+use crate::game::{compare_hands_with_ruleset, Hand, OrderContext, Ruleset};
+use crate::prelude::*;
+
+/// Every hand that can be formed from `hand` which none of the unseen cards
+/// could beat, however they're split between the opponents. Controls are
+/// judged against the orders given, so a later reversal can undo them.
+///
+/// An unseen joker may stand in for any card. While one is still out no
+/// five-card trick counts as a control, as a wild card could complete
+/// almost anything.
+pub fn find_controls(
+    hand: &[Card],
+    tracker: &CardTracker,
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> Vec<Vec<PlayedCard>> {
+    let unseen = tracker.get_unseen();
+
+    get_candidate_moves(hand, ruleset, orders)
+        .into_iter()
+        .filter(|cards| !cards.is_empty())
+        .filter(|cards| match Hand::try_build_in_order(cards.clone(), ruleset, orders) {
+            Ok(candidate) => !can_be_beaten(
+                candidate, &unseen, ruleset, orders
+            ),
+            Err(_) => false,
+        })
+        .collect()
+}
+
+fn can_be_beaten(
+    hand: Hand,
+    unseen: &[Card],
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> bool {
+    let size = hand.get_card_count();
+    let jokers = unseen.iter().filter(|c| c.get_rank().is_none()).count();
+    let natural_cards: Vec<PlayedCard> = unseen.iter()
+        .filter_map(|c| match *c {
+            Card::Standard { rank, suit, .. } => {
+                Some(PlayedCard::new(rank, suit, false))
+            },
+            _ => None,
+        })
+        .collect();
+
+    if size == 5 && jokers > 0 {
+        return true;
+    }
+
+    let beats = |cards: Vec<PlayedCard>| match Hand::try_build_in_order(cards, ruleset, orders) {
+        Ok(other) => compare_hands_with_ruleset(
+            hand, other, ruleset, orders
+        ),
+        Err(_) => false,
+    };
+
+    (0..=jokers.min(size)).any(|wild| {
+        get_combinations(&natural_cards, size - wild)
+            .into_iter()
+            .any(|cards| {
+                get_joker_fills(&cards, wild, orders)
+                    .into_iter()
+                    .any(|fill| {
+                        let mut cards = cards.clone();
+                        cards.extend(fill);
+                        beats(cards)
+                    })
+            })
+    })
+}
+
+/// The ways `wild` jokers could complete a single, pair or prial started by
+/// `cards`.
+fn get_joker_fills(
+    cards: &[PlayedCard],
+    wild: usize,
+    orders: &OrderContext,
+) -> Vec<Vec<PlayedCard>> {
+    let ranks = match cards.first() {
+        Some(card) => vec![card.get_rank()],
+        None => orders.get_rank_order().to_vec(),
+    };
+    let suits = orders.get_suit_order();
+
+    ranks.into_iter()
+        .flat_map(|rank| {
+            (0..wild).fold(vec![vec![]], |fills: Vec<Vec<PlayedCard>>, _| {
+                fills.into_iter()
+                    .flat_map(|fill| suits.iter().map(move |&suit| {
+                        let mut fill = fill.clone();
+                        fill.push(PlayedCard::new(rank, suit, true));
+                        fill
+                    }))
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::scenario::parse_cards;
+
+    fn find(hand: &[Card], unseen: Vec<Card>) -> Vec<Vec<PlayedCard>> {
+        find_controls(
+            hand,
+            &CardTracker::new("a", unseen),
+            Ruleset::default(),
+            &OrderContext::standard(),
+        )
+    }
+
+    #[test]
+    fn the_highest_single_is_a_control() {
+        let hand = parse_cards("3C 2S").unwrap();
+        let unseen = parse_cards("KH 4D").unwrap();
+
+        assert_eq!(find(&hand, unseen), vec![
+            vec![PlayedCard::new(Rank::Two, Suit::Spades, false)],
+        ]);
+    }
+
+    #[test]
+    fn an_unseen_joker_can_beat_a_pair() {
+        let hand = parse_cards("KH KS").unwrap();
+        let pair = vec![
+            PlayedCard::new(Rank::King, Suit::Hearts, false),
+            PlayedCard::new(Rank::King, Suit::Spades, false),
+        ];
+
+        let controls = find(&hand, parse_cards("AC QD").unwrap());
+        assert_eq!(controls, vec![pair.clone()]);
+
+        let controls = find(&hand, parse_cards("AC JK").unwrap());
+        assert!(!controls.contains(&pair));
+    }
+}
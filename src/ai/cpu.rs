@@ -1,35 +1,61 @@
 use crate::game::{
+    FinalCardRule,
     Hand,
+    JokerUsage,
+    OrderContext,
     Player,
-    compare_hands,
+    Ruleset,
+    compare_hands_with_ruleset,
+    holds_two_or_joker,
     sort_unplayed_cards,
-    FlushPrecedence
 };
-use crate::cards::{Card, PlayedCard, Rank, Suit};
+use crate::cards::{Card, PlayedCard};
 use super::{find_pairs, get_sets_of_same_rank, find_fct};
+use crate::prelude::*;
 
+/// The move the rule-of-thumb cpu would make, built and compared under
+/// `ruleset`. It doesn't see the round, so house rules and the like can
+/// still turn it down; `CpuAgent` checks it before playing it.
 pub fn get_move(
     last_move: Option<Hand>,
     player_option: Option<Player>,
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13],
+    ruleset: Ruleset,
+    orders: &OrderContext,
 ) -> Option<Vec<PlayedCard>> {
-    let player = player_option.unwrap();
+    let cards_left = player_option.as_ref()?.get_card_count();
+    let cards = choose_move(last_move, player_option, ruleset, orders)?;
+
+    // going out on a two or a joker would be turned down, so hold on
+    if ruleset.final_card == FinalCardRule::Forbidden
+        && cards.len() == cards_left
+        && holds_two_or_joker(&cards) {
+        return get_pass();
+    }
+
+    Some(cards)
+}
+
+fn choose_move(
+    last_move: Option<Hand>,
+    player_option: Option<Player>,
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> Option<Vec<PlayedCard>> {
+    let player = player_option?;
     let unsorted_player_hand = player.get_hand();
     let mut sorted_player_hand = sort_unplayed_cards(
         &unsorted_player_hand,
-        suit_order,
-        rank_order
+        orders
     );
 
     sorted_player_hand.reverse();
     let player_hand = sorted_player_hand;
 
-    if last_move == None {
-        return Some(get_all_low_cards(&player_hand))
-    } 
+    let move_hand = match last_move {
+        Some(move_hand) => move_hand,
+        None => return Some(get_all_low_cards(&player_hand)),
+    };
 
-    let move_hand = last_move.unwrap();
     match move_hand {
         Hand::Pass => {
 
@@ -37,40 +63,32 @@ pub fn get_move(
             let num_jokers = get_jokers(&player_hand).len();
 
             if cards_left == num_jokers {
+                let finishing_allowed =
+                    ruleset.joker_usage != JokerUsage::NotOnFinishingHand;
                 let hand = match cards_left {
-                    1 | 2 | 3 | 5 => player_hand.clone(),
-                    _ => vec!(
-                            *player_hand.clone().first().unwrap(),
-                        )
+                    1 | 2 | 3 | 5 if finishing_allowed => player_hand.clone(),
+                    // the last joker can't be played, so hold on to it
+                    1 => return get_pass(),
+                    _ => player_hand[..1].to_vec(),
                 };
                 return Some(convert_to_played(
                     &hand,
-                    suit_order,
-                    rank_order
+                    orders
                 ));
             }
 
             let pairs = find_pairs(&player_hand);
-            let fct = find_fct(&player_hand);
+            let fct = find_fct(&player_hand, ruleset, orders);
 
-            let first_pair = if !pairs.is_empty() {
-                Some(pairs.first().unwrap().to_vec())
-            } else {
-                None
-            };
+            let first_pair = pairs.first().map(|pair| pair.to_vec());
 
-
-            let first_fct = if !fct.is_empty() {
-                Some(fct.first().unwrap().to_vec())
-            } else {
-                None
-            };
+            let first_fct = fct.first().map(|trick| trick.to_vec());
 
             let lowest_natural_card = get_lowest_natural_card(
                 &player_hand
             );
 
-            if first_fct != None {
+            if first_fct.is_some() {
                 let card_in_fct = first_fct.iter().any(|t| {
                     t.iter().any(|&p| {
                         p == lowest_natural_card[0]
@@ -81,7 +99,7 @@ pub fn get_move(
                 }
             }
 
-            if first_pair != None {
+            if first_pair.is_some() {
                 let card_in_pair = first_pair.iter().any(|p| {
                     p[0] == lowest_natural_card[0]
                 });
@@ -104,13 +122,13 @@ pub fn get_move(
 
             let played_single = 
                 get_lowest_natural_card_against_played(
-                    &single_cards.as_slice(),
+                    single_cards.as_slice(),
                     move_hand,
-                    suit_order,
-                    rank_order
+                    ruleset,
+                    orders
                 );
 
-            if played_single != None {
+            if played_single.is_some() {
                 return played_single;
             }
 
@@ -119,24 +137,33 @@ pub fn get_move(
                 get_lowest_natural_card_against_played(
                     &player_hand,
                     move_hand,
-                    suit_order,
-                    rank_order
+                    ruleset,
+                    orders
                 );
 
-            if played_single != None {
+            if played_single.is_some() {
                 return played_single;
             }
 
             let jokers = get_jokers(&player_hand);
-
-            if !jokers.is_empty() {
+            let may_finish_on_joker =
+                ruleset.joker_usage != JokerUsage::NotOnFinishingHand;
+
+            if !jokers.is_empty() && (player_hand.len() > 1 || may_finish_on_joker) {
+                // a player holding nothing but jokers can play one as a single
+                // whatever the joker usage
+                let ruleset = if jokers.len() == player_hand.len() {
+                    Ruleset { joker_usage: JokerUsage::Anywhere, ..ruleset }
+                } else {
+                    ruleset
+                };
                 let player_hand = get_winning_joker(
-                    suit_order,
-                    rank_order,
+                    ruleset,
+                    orders,
                     move_hand,
                 );
 
-                if player_hand != None {
+                if player_hand.is_some() {
                     return player_hand;
                 }
             }
@@ -145,11 +172,11 @@ pub fn get_move(
         },
         Hand::Pair(_, _) | Hand::Prial(_, _, _) => {
             let hand = get_beating_multiple_card_hand(
-                move_hand.to_cards().len(),
+                move_hand.get_card_count(),
                 &player_hand,
                 move_hand,
-                suit_order,
-                rank_order,
+                ruleset,
+                orders,
             );
 
             if hand.is_none() {
@@ -160,16 +187,20 @@ pub fn get_move(
 
         },
         Hand::FiveCardTrick(_) => {
-            for trick in find_fct(&player_hand) {
-                let built_hand = Hand::build(
-                    trick.to_vec()
-                ).unwrap();
-                if compare_hands(
+            for trick in find_fct(&player_hand, ruleset, orders) {
+                let built_hand = match Hand::try_build_in_order(
+                    trick.to_vec(),
+                    ruleset,
+                    orders
+                ) {
+                    Ok(hand) => hand,
+                    Err(_) => continue,
+                };
+                if compare_hands_with_ruleset(
                     move_hand,
                     built_hand,
-                    FlushPrecedence::Rank,
-                    suit_order,
-                    rank_order) {
+                    ruleset,
+                    orders) {
                     return Some(trick.to_vec());
                 }
             }
@@ -183,17 +214,19 @@ fn get_beating_multiple_card_hand(
     n: usize,
     player_hand: &[Card],
     move_hand: Hand,
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13], 
+    ruleset: Ruleset,
+    orders: &OrderContext,
 ) -> Option<Vec<PlayedCard>> {
     for hand in get_sets_of_same_rank(n, player_hand) {
-        let built_hand = Hand::build(hand.clone()).unwrap();
-        if compare_hands(
+        let built_hand = match Hand::try_build_in_order(hand.clone(), ruleset, orders) {
+            Ok(hand) => hand,
+            Err(_) => continue,
+        };
+        if compare_hands_with_ruleset(
             move_hand,
             built_hand,
-            FlushPrecedence::Rank,
-            suit_order,
-            rank_order) {
+            ruleset,
+            orders) {
             return Some(hand.clone());
         }
     }
@@ -205,79 +238,42 @@ fn get_pass() -> Option<Vec<PlayedCard>>{
     Some(vec!())
 }
 
+/// Every card of the lowest rank, up to a prial, since four cards make no
+/// hand on their own.
 fn get_all_low_cards(hand: &[Card]) -> Vec<PlayedCard> {
-    let natural_cards = get_natural_cards(hand);
-    let player_card = natural_cards.first();
-    let (_head, tail_cards) = natural_cards.split_at(1);
-
-    if player_card.is_some() {
-        let card = player_card.unwrap();
-        let mut all_low_cards = vec![
-            PlayedCard::new(
-                card.get_rank()
-                    .unwrap(),
-                card.get_suit()
-                    .unwrap(),
-                false)
-        ];
-
-        for c in tail_cards {
-            if c.get_rank() == card.get_rank() {
-                all_low_cards.push(
-                    PlayedCard::new(
-                        c.get_rank().unwrap(),
-                        c.get_suit().unwrap(),
-                        false
-                    )
-                );
-            }
-        }
-
-        return all_low_cards;
+    let natural_cards: Vec<PlayedCard> = hand.iter()
+        .filter_map(to_natural_card)
+        .collect();
+
+    match natural_cards.first() {
+        Some(&lowest) => natural_cards.into_iter()
+            .filter(|c| c.get_rank() == lowest.get_rank())
+            .take(3)
+            .collect(),
+        None => vec![],
     }
-
-    vec![]
 }
 
 fn get_lowest_natural_card(hand: &[Card]) -> Vec<PlayedCard>{
-    let natural_cards = get_natural_cards(hand);
-    let player_card = natural_cards.first();
-    match player_card {
-        Some(card)  =>  vec!(
-            PlayedCard::new(
-                card.get_rank()
-                    .unwrap(),
-                card.get_suit()
-                    .unwrap(),
-                false)
-        ),
-        None        => vec!()
-    }
+    hand.iter()
+        .filter_map(to_natural_card)
+        .take(1)
+        .collect()
 }
 
 fn get_lowest_natural_card_against_played(
     hand: &[Card],
     last_move: Hand,
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13]
+    ruleset: Ruleset,
+    orders: &OrderContext
 ) -> Option<Vec<PlayedCard>> {
-    let natural_cards = get_natural_cards(hand);
-    for player_card in natural_cards {
-        let player_hand = Hand::build(
-            vec!(PlayedCard::new(
-                player_card.get_rank()
-                    .unwrap(),
-                player_card.get_suit()
-                    .unwrap(),
-                false
-            ))
-        ).unwrap();
-        if compare_hands(
+    for player_card in hand.iter().filter_map(to_natural_card) {
+        let player_hand = Hand::Single(player_card);
+        if compare_hands_with_ruleset(
             last_move,
-            player_hand, 
-            FlushPrecedence::Rank,
-            suit_order,
-            rank_order
+            player_hand,
+            ruleset,
+            orders
         ) {
             return Some(player_hand.to_cards());
         }
@@ -286,24 +282,25 @@ fn get_lowest_natural_card_against_played(
 }
 
 fn get_winning_joker(
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13],
+    ruleset: Ruleset,
+    orders: &OrderContext,
     last_move: Hand,
 ) -> Option<Vec<PlayedCard>> {
-    let joker_single = Hand::build(
+    let joker_single = Hand::try_build_in_order(
         vec!(PlayedCard::new(
-            *rank_order.last().unwrap(),
-            *suit_order.last().unwrap(),
+            orders.highest_rank(),
+            orders.highest_suit(),
             true
-        ))
-    ).unwrap();
+        )),
+        ruleset,
+        orders
+    ).ok()?;
 
-    if compare_hands(
+    if compare_hands_with_ruleset(
         last_move,
-        joker_single, 
-        FlushPrecedence::Rank,
-        suit_order,
-        rank_order
+        joker_single,
+        ruleset,
+        orders
     ) {
         Some(joker_single.to_cards())
     } else {
@@ -312,24 +309,23 @@ fn get_winning_joker(
 
 }
 
-fn get_natural_cards(hand: &[Card]) -> Vec<Card> {
-    hand.iter().filter(|c| {
-        c.get_rank() != None
-    })
-    .cloned().collect::<Vec<Card>>()
+fn to_natural_card(card: &Card) -> Option<PlayedCard> {
+    match *card {
+        Card::Standard { rank, suit, .. } => Some(PlayedCard::new(rank, suit, false)),
+        Card::Joker { .. } => None,
+    }
 }
 
 fn get_jokers(hand: &[Card]) -> Vec<Card>{
     hand.iter().filter(|c| {
-        c.get_rank() == None
+        c.get_rank().is_none()
     })
     .cloned().collect::<Vec<Card>>()
 }
 
 fn convert_to_played(
     hand: &[Card],
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13]
+    orders: &OrderContext
 ) -> Vec<PlayedCard> {
     hand.iter().map(|&c| {
         match c {
@@ -339,8 +335,8 @@ fn convert_to_played(
                 PlayedCard::new(rank, suit, false)
             },
             Card::Joker{ .. } => PlayedCard::new(
-                rank_order[0],
-                suit_order[0],
+                orders.lowest_rank(),
+                orders.lowest_suit(),
                 true
             )
         }
@@ -353,25 +349,6 @@ mod tests {
     use crate::cards::*;
     use crate::game::{TrickType, Trick};
 
-    static DEFAULT_SUIT_ORDER: [Suit; 4] =
-        [Suit::Clubs, Suit::Hearts, Suit::Diamonds, Suit::Spades];
-
-    static DEFAULT_RANK_ORDER: [Rank; 13] = [
-        Rank::Three,
-        Rank::Four,
-        Rank::Five,
-        Rank::Six,
-        Rank::Seven,
-        Rank::Eight,
-        Rank::Nine,
-        Rank::Ten,
-        Rank::Jack,
-        Rank::Queen,
-        Rank::King,
-        Rank::Ace,
-        Rank::Two,
-    ];
-
     #[test]
     fn ai_plays_lowest_card_at_start_of_game() {
         let hand = vec!(
@@ -383,8 +360,8 @@ mod tests {
             get_move(
                 None,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -409,8 +386,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -433,8 +410,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -458,8 +435,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -484,8 +461,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -495,6 +472,160 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ai_doesnt_go_out_on_a_joker_when_the_ruleset_bars_it() {
+        let previous_move = Some(Hand::Single(
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+        ));
+        let hand = vec!(
+            Card::Joker{deck_id: 0},
+        );
+        let player = Player::new("cpu".to_string(), hand);
+        let ruleset = Ruleset {
+            joker_usage: JokerUsage::NotOnFinishingHand,
+            ..Ruleset::default()
+        };
+
+        assert_eq!(
+            get_move(
+                previous_move,
+                Some(player.clone()),
+                ruleset,
+                &OrderContext::standard(),
+            ),
+            Some(vec!())
+        );
+        assert_eq!(
+            get_move(
+                Some(Hand::Pass),
+                Some(player),
+                ruleset,
+                &OrderContext::standard(),
+            ),
+            Some(vec!())
+        );
+    }
+
+    #[test]
+    fn ai_keeps_a_joker_back_when_the_ruleset_bars_finishing_on_one() {
+        let hand = vec!(
+            Card::Joker{deck_id: 0},
+            Card::Joker{deck_id: 0},
+        );
+        let player = Player::new("cpu".to_string(), hand);
+        let ruleset = Ruleset {
+            joker_usage: JokerUsage::NotOnFinishingHand,
+            ..Ruleset::default()
+        };
+
+        assert_eq!(
+            get_move(
+                Some(Hand::Pass),
+                Some(player),
+                ruleset,
+                &OrderContext::standard(),
+            ),
+            Some(vec!(
+                PlayedCard::new(
+                    Rank::Three, Suit::Clubs, true
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn ai_doesnt_play_a_lone_joker_when_jokers_are_for_five_card_tricks() {
+        let previous_move = Some(Hand::Single(
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+        ));
+        let hand = vec!(
+            Card::Standard{deck_id: 0, rank: Rank::Three, suit: Suit::Clubs},
+            Card::Joker{deck_id: 0},
+        );
+        let player = Player::new("cpu".to_string(), hand);
+        let ruleset = Ruleset {
+            joker_usage: JokerUsage::FiveCardTricksOnly,
+            ..Ruleset::default()
+        };
+
+        assert_eq!(
+            get_move(
+                previous_move,
+                Some(player),
+                ruleset,
+                &OrderContext::standard(),
+            ),
+            Some(vec!())
+        );
+    }
+
+    #[test]
+    fn ai_plays_its_last_joker_when_jokers_are_for_five_card_tricks() {
+        let previous_move = Some(Hand::Single(
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+        ));
+        let hand = vec!(
+            Card::Joker{deck_id: 0},
+        );
+        let player = Player::new("cpu".to_string(), hand);
+        let ruleset = Ruleset {
+            joker_usage: JokerUsage::FiveCardTricksOnly,
+            ..Ruleset::default()
+        };
+
+        assert_eq!(
+            get_move(
+                previous_move,
+                Some(player),
+                ruleset,
+                &OrderContext::standard(),
+            ),
+            Some(vec!(
+                PlayedCard::new(
+                    Rank::Two, Suit::Spades, true
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn ai_doesnt_go_out_on_a_two_when_the_ruleset_forbids_it() {
+        let previous_move = Some(Hand::Single(
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+        ));
+        let hand = vec!(
+            Card::Standard{deck_id: 0, rank: Rank::Two, suit: Suit::Spades},
+        );
+        let player = Player::new("cpu".to_string(), hand);
+        let ruleset = Ruleset {
+            final_card: FinalCardRule::Forbidden,
+            ..Ruleset::default()
+        };
+
+        assert_eq!(
+            get_move(
+                previous_move,
+                Some(player.clone()),
+                Ruleset::default(),
+                &OrderContext::standard(),
+            ),
+            Some(vec!(
+                PlayedCard::new(
+                    Rank::Two, Suit::Spades, false
+                )
+            ))
+        );
+        assert_eq!(
+            get_move(
+                previous_move,
+                Some(player),
+                ruleset,
+                &OrderContext::standard(),
+            ),
+            Some(vec!())
+        );
+    }
+
     #[test]
     fn ai_cant_play_a_joker_if_it_doesnt_have_one() {
          let previous_move = Some(Hand::Single(
@@ -510,8 +641,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!())
         );
@@ -534,8 +665,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -564,8 +695,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!())
         );
@@ -587,8 +718,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -619,8 +750,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -654,8 +785,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!())
         );
@@ -683,8 +814,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -738,8 +869,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -788,8 +919,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -838,8 +969,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!())
         );
@@ -858,8 +989,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Three, Suit::Clubs, true)
@@ -880,8 +1011,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Three, Suit::Clubs, true),
@@ -905,9 +1036,9 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
+                Ruleset::default(),
 
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Three, Suit::Clubs, true),
@@ -929,8 +1060,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Three, Suit::Clubs, false),
@@ -953,8 +1084,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Three, Suit::Clubs, false),
@@ -977,8 +1108,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Jack, Suit::Clubs, false),
@@ -1005,8 +1136,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Queen, Suit::Clubs, false),
@@ -1030,8 +1161,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Three, Suit::Clubs, false),
@@ -1067,8 +1198,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(
@@ -1118,8 +1249,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!())
         );
@@ -1143,8 +1274,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Queen, Suit::Spades, false),
@@ -1169,8 +1300,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Three, Suit::Clubs, false),
@@ -1201,8 +1332,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                DEFAULT_SUIT_ORDER,
-                DEFAULT_RANK_ORDER,
+                Ruleset::default(),
+                &OrderContext::standard(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Two, Suit::Spades, true),
@@ -1248,8 +1379,8 @@ mod tests {
             get_move(
                 previous_move,
                 Some(player),
-                alternative_suit_order,
-                alternative_rank_order,
+                Ruleset::default(),
+                &OrderContext::new(&alternative_suit_order, alternative_rank_order).unwrap(),
             ),
             Some(vec!(
                 PlayedCard::new(Rank::Five, Suit::Clubs, false),
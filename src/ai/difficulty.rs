@@ -0,0 +1,245 @@
+use super::{evaluate, get_legal_moves, Agent, MctsAgent, MctsConfig};
+use crate::cards::PlayedCard;
+use crate::game::Round;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Preset strengths for the built-in bots, for single player games with
+/// graded opponents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn get_settings(self) -> DifficultySettings {
+        match self {
+            Difficulty::Easy => DifficultySettings {
+                randomness: 0.3,
+                search_iterations: 0,
+                bomb_threshold: 0,
+            },
+            Difficulty::Medium => DifficultySettings {
+                randomness: 0.1,
+                search_iterations: 0,
+                bomb_threshold: 4,
+            },
+            Difficulty::Hard => DifficultySettings {
+                randomness: 0.0,
+                search_iterations: 300,
+                bomb_threshold: 6,
+            },
+        }
+    }
+
+    fn get_name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+/// The knobs a difficulty turns.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DifficultySettings {
+    /// Chance, from 0 to 1, of playing any legal move at all instead of
+    /// the one the bot thinks best
+    pub randomness: f64,
+    /// Tree search iterations per move. With none, the bot plays whichever
+    /// move leaves it the best `evaluate` score.
+    pub search_iterations: usize,
+    /// Bombs are held back while every opponent has more cards than this,
+    /// unless there's nothing else legal to play. Zero never holds them
+    /// back.
+    pub bomb_threshold: usize,
+}
+
+/// A built-in bot playing at a set difficulty. Like the other agents,
+/// each move is chosen with a seed of its own so it can be replayed.
+#[derive(Debug, Clone)]
+pub struct DifficultyAgent {
+    difficulty: Option<Difficulty>,
+    settings: DifficultySettings,
+    next_seed: u64,
+}
+
+impl DifficultyAgent {
+    pub fn new(difficulty: Difficulty, seed: u64) -> DifficultyAgent {
+        DifficultyAgent {
+            difficulty: Some(difficulty),
+            settings: difficulty.get_settings(),
+            next_seed: seed,
+        }
+    }
+
+    /// A bot tuned by hand rather than from a preset.
+    pub fn with_settings(settings: DifficultySettings, seed: u64) -> DifficultyAgent {
+        DifficultyAgent { difficulty: None, settings, next_seed: seed }
+    }
+
+    pub fn get_settings(&self) -> DifficultySettings {
+        self.settings
+    }
+
+    fn choose(&self, round: &Round, player_id: &str, rng: &mut StdRng) -> Vec<PlayedCard> {
+        let legal_moves = get_legal_moves(round, player_id);
+        if rng.gen_bool(self.settings.randomness.clamp(0.0, 1.0)) {
+            return legal_moves.choose(rng).cloned().unwrap_or_default();
+        }
+
+        let candidates = self.hold_back_bombs(round, player_id, legal_moves);
+        if self.settings.search_iterations > 0 && candidates.len() > 1 {
+            let config = MctsConfig {
+                iterations: self.settings.search_iterations,
+                ..MctsConfig::default()
+            };
+            let searched = MctsAgent::new(config, rng.gen()).choose_move(round, player_id);
+            if candidates.contains(&searched) {
+                return searched;
+            }
+        }
+
+        get_best_evaluated(round, player_id, candidates)
+    }
+
+    /// The legal moves without the bombs this bot would rather keep, or
+    /// all of them if that leaves nothing legal. Going out on a bomb is
+    /// never held back.
+    fn hold_back_bombs(
+        &self,
+        round: &Round,
+        player_id: &str,
+        legal_moves: Vec<Vec<PlayedCard>>,
+    ) -> Vec<Vec<PlayedCard>> {
+        let threshold = self.settings.bomb_threshold;
+        let opponents_far_off = round.get_card_counts().into_iter()
+            .filter(|(id, count)| id != player_id && *count > 0)
+            .all(|(_, count)| count > threshold);
+        if threshold == 0 || !opponents_far_off {
+            return legal_moves;
+        }
+
+        let hand_size = round.get_player(player_id)
+            .map(|player| player.get_card_count())
+            .unwrap_or(0);
+        let kept: Vec<Vec<PlayedCard>> = legal_moves.iter()
+            .filter(|cards| {
+                let is_bomb = round.build_hand(cards)
+                    .map(|hand| hand.is_bomb())
+                    .unwrap_or(false);
+                !is_bomb || cards.len() == hand_size
+            })
+            .cloned()
+            .collect();
+
+        if kept.is_empty() {
+            legal_moves
+        } else {
+            kept
+        }
+    }
+}
+
+impl Agent for DifficultyAgent {
+    fn choose_move(&mut self, round: &Round, player_id: &str) -> Vec<PlayedCard> {
+        let mut rng = StdRng::seed_from_u64(self.next_seed);
+        let chosen = self.choose(round, player_id, &mut rng);
+        self.next_seed = rng.gen();
+
+        chosen
+    }
+
+    fn get_name(&self) -> String {
+        match self.difficulty {
+            Some(difficulty) => format!("{}_bot", difficulty.get_name()),
+            None => "custom_bot".to_string(),
+        }
+    }
+
+    fn get_seed(&self) -> Option<u64> {
+        Some(self.next_seed)
+    }
+}
+
+/// The move leaving `player_id` the best `evaluate` score, taking the
+/// first on a tie.
+fn get_best_evaluated(
+    round: &Round,
+    player_id: &str,
+    candidates: Vec<Vec<PlayedCard>>,
+) -> Vec<PlayedCard> {
+    let mut best: Option<(f32, Vec<PlayedCard>)> = None;
+    for cards in candidates {
+        let mut next = round.fork_for_simulation();
+        if next.submit_move_mut(player_id, cards.clone()).is_err() {
+            continue;
+        }
+        let score = evaluate(&next, player_id);
+        if best.as_ref().map(|(best_score, _)| score > *best_score).unwrap_or(true) {
+            best = Some((score, cards));
+        }
+    }
+
+    best.map(|(_, cards)| cards).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::Scenario;
+
+    #[test]
+    fn bots_only_play_legal_moves_and_replay_from_their_seed() {
+        let round = Scenario::new()
+            .player("a", "3C 5D 5H 9S JC")
+            .player("b", "4C 6D 7H")
+            .player("c", "8C 8D KS")
+            .last_move("c", "4D")
+            .next_player("a")
+            .build()
+            .unwrap();
+
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let mut agent = DifficultyAgent::new(difficulty, 11);
+            let mut replayed = DifficultyAgent::new(difficulty, 11);
+            for _ in 0..5 {
+                let chosen = agent.choose_move(&round, "a");
+                assert!(round.validate_move("a", &chosen).is_ok());
+                assert_eq!(replayed.choose_move(&round, "a"), chosen);
+            }
+            assert_eq!(agent.get_name(), format!("{}_bot", difficulty.get_name()));
+        }
+    }
+
+    #[test]
+    fn bombs_are_held_back_until_an_opponent_gets_close() {
+        let get_round = |opponent: &str| Scenario::new()
+            .player("a", "3D 6C 6D 6H 6S KC")
+            .player("b", opponent)
+            .last_move("b", "9C 9D 9H 4C 4D")
+            .next_player("a")
+            .build()
+            .unwrap();
+        let settings = DifficultySettings { randomness: 0.0, ..Difficulty::Hard.get_settings() };
+        let tuned = |search_iterations| DifficultyAgent::with_settings(
+            DifficultySettings { search_iterations, ..settings },
+            3,
+        );
+
+        let far_off = get_round("3S 4S 5S 7S 8S 10S JS QS");
+        assert_eq!(tuned(0).choose_move(&far_off, "a"), vec![]);
+        assert_eq!(tuned(50).choose_move(&far_off, "a"), vec![]);
+
+        let close = get_round("3S 4S");
+        let chosen = close.build_hand(&tuned(0).choose_move(&close, "a")).unwrap();
+        assert!(chosen.is_bomb());
+    }
+}
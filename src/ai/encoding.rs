@@ -0,0 +1,128 @@
+use crate::cards::{get_rank_array, get_suit_array, Card, Rank, Suit};
+use crate::game::{Hand, Round};
+use crate::prelude::*;
+
+/// One slot for every rank and suit, then one for jokers.
+pub const CARD_FEATURES: usize = 53;
+/// Card counts are encoded for this many seats. Seats past it are left
+/// out.
+pub const MAX_SEATS: usize = 8;
+/// The length of every encoding, laid out as:
+///
+/// - the viewer's hand, as a count of each card
+/// - every card played so far, as a count of each card
+/// - the hand on the table, with jokers counted as the card they were
+///   played as
+/// - how many cards each seat holds, starting with the viewer and going
+///   round in turn order
+/// - where each rank and each suit sits in the current orders, from 0 for
+///   the lowest to 1 for the highest
+/// - 1 if the viewer is next to play and can lead anything, otherwise 0
+pub const ENCODING_LEN: usize = CARD_FEATURES * 3 + MAX_SEATS + 13 + 4 + 1;
+
+/// A fixed-size numeric view of `round` from `viewer`'s seat, holding
+/// nothing they couldn't see at the table. `None` if `viewer` isn't
+/// seated, or if the round isn't played with the four standard suits the
+/// layout has room for.
+pub fn encode_round(round: &Round, viewer: &str) -> Option<Vec<f32>> {
+    if !round.get_order_context().has_standard_suits() {
+        return None;
+    }
+    let player_ids = round.get_player_ids();
+    let seat = player_ids.iter().position(|id| id == viewer)?;
+    let mut encoding = Vec::with_capacity(ENCODING_LEN);
+
+    let mut hand = [0.0; CARD_FEATURES];
+    for card in round.get_player(viewer)?.get_hand() {
+        hand[get_card_index(card)] += 1.0;
+    }
+    encoding.extend_from_slice(&hand);
+
+    let mut played = [0.0; CARD_FEATURES];
+    for card in round.get_discarded() {
+        played[get_card_index(card)] += 1.0;
+    }
+    encoding.extend_from_slice(&played);
+
+    let mut table = [0.0; CARD_FEATURES];
+    if let Some(last_move) = round.get_last_move() {
+        for card in last_move.to_cards() {
+            table[get_index(card.get_rank(), card.get_suit())] += 1.0;
+        }
+    }
+    encoding.extend_from_slice(&table);
+
+    let mut counts = [0.0; MAX_SEATS];
+    for (i, count) in counts.iter_mut().enumerate().take(player_ids.len()) {
+        let id = &player_ids[(seat + i) % player_ids.len()];
+        *count = round.get_player(id).map(|p| p.get_card_count()).unwrap_or(0) as f32;
+    }
+    encoding.extend_from_slice(&counts);
+
+    let rank_order = round.get_rank_order();
+    for rank in get_rank_array().iter() {
+        let position = rank_order.iter().position(|r| r == rank).unwrap_or(0);
+        encoding.push(position as f32 / 12.0);
+    }
+    let suit_order = round.get_suit_order();
+    for suit in get_suit_array().iter() {
+        let position = suit_order.iter().position(|s| s == suit).unwrap_or(0);
+        encoding.push(position as f32 / 3.0);
+    }
+
+    let leading = round.get_next_player().as_deref() == Some(viewer)
+        && matches!(round.get_last_move(), None | Some(Hand::Pass));
+    encoding.push(if leading { 1.0 } else { 0.0 });
+
+    Some(encoding)
+}
+
+fn get_card_index(card: Card) -> usize {
+    match card {
+        Card::Standard { rank, suit, .. } => get_index(rank, suit),
+        Card::Joker { .. } => CARD_FEATURES - 1,
+    }
+}
+
+fn get_index(rank: Rank, suit: Suit) -> usize {
+    let rank_index = get_rank_array().iter().position(|&r| r == rank).unwrap_or(0);
+    let suit_index = get_suit_array().iter().position(|&s| s == suit).unwrap_or(0);
+
+    rank_index * 4 + suit_index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::Scenario;
+
+    #[test]
+    fn rounds_are_encoded_from_the_viewers_seat() {
+        let round = Scenario::new()
+            .player("a", "3C 3D JK")
+            .player("b", "4C 5H")
+            .player("c", "9D")
+            .last_move("c", "JK:2S")
+            .next_player("a")
+            .build()
+            .unwrap();
+
+        let encoding = encode_round(&round, "b").unwrap();
+
+        assert_eq!(encoding.len(), ENCODING_LEN);
+        assert_eq!(encoding[4], 1.0);
+        assert_eq!(encoding[2 * 4 + 1], 1.0);
+        assert_eq!(encoding[..CARD_FEATURES].iter().sum::<f32>(), 2.0);
+
+        let table = &encoding[CARD_FEATURES * 2..CARD_FEATURES * 3];
+        assert_eq!(table[12 * 4 + 3], 1.0);
+        assert_eq!(table[CARD_FEATURES - 1], 0.0);
+
+        let counts = &encoding[CARD_FEATURES * 3..CARD_FEATURES * 3 + MAX_SEATS];
+        assert_eq!(counts[..4], [2.0, 1.0, 3.0, 0.0]);
+
+        assert_eq!(encoding[ENCODING_LEN - 1], 0.0);
+        assert_eq!(encode_round(&round, "a").unwrap()[ENCODING_LEN - 1], 0.0);
+        assert_eq!(encode_round(&round, "z"), None);
+    }
+}
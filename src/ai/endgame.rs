@@ -0,0 +1,193 @@
+use super::get_legal_moves;
+use crate::cards::PlayedCard;
+use crate::game::{Hand, RecordedMove, Round};
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EndgameConfig {
+    /// The solver only runs once no more than this many cards are left
+    /// across every hand
+    pub max_cards: usize,
+}
+
+impl Default for EndgameConfig {
+    fn default() -> EndgameConfig {
+        EndgameConfig { max_cards: 10 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndgameResult {
+    /// The player to move, who the game was solved for
+    pub player_id: String,
+    /// Whether they can go out before anyone else however the others play
+    pub can_force_win: bool,
+    /// A winning line through to them going out, against one defence.
+    /// Empty when there's no forced win.
+    pub line: Vec<RecordedMove>,
+}
+
+/// Searches every way the rest of the game could go, with every hand known,
+/// to find whether the player to move can force their way out first. A
+/// player leading a fresh trick is taken to play rather than pass.
+/// Returns `None` once the game is over or while too many cards are left.
+pub fn solve_endgame(round: &Round, config: EndgameConfig) -> Option<EndgameResult> {
+    let player_id = round.get_next_player()?;
+    let cards_left: usize = round.get_card_counts().iter()
+        .map(|(_, count)| count)
+        .sum();
+    if cards_left > config.max_cards {
+        return None;
+    }
+
+    let mut solver = Solver {
+        player_id: player_id.clone(),
+        contenders: round.get_card_counts().into_iter()
+            .filter(|&(_, count)| count > 0)
+            .map(|(id, _)| id)
+            .collect(),
+        memo: BTreeMap::new(),
+    };
+
+    let can_force_win = solver.wins(round);
+    let line = if can_force_win {
+        solver.get_line(round)
+    } else {
+        vec![]
+    };
+
+    Some(EndgameResult {
+        player_id,
+        can_force_win,
+        line,
+    })
+}
+
+struct Solver {
+    player_id: String,
+    /// Players who still held cards when the search started
+    contenders: Vec<String>,
+    memo: BTreeMap<String, bool>,
+}
+
+impl Solver {
+    /// `Some(true)` once the solving player is out, `Some(false)` once
+    /// someone else is.
+    fn get_outcome(&self, round: &Round) -> Option<bool> {
+        self.contenders.iter()
+            .find(|id| round.get_player(id).map(|p| p.get_card_count()) == Some(0))
+            .map(|id| *id == self.player_id)
+    }
+
+    fn wins(&mut self, round: &Round) -> bool {
+        if let Some(outcome) = self.get_outcome(round) {
+            return outcome;
+        }
+        let next_player = match round.get_next_player() {
+            Some(id) => id,
+            None => return false,
+        };
+
+        let key = serde_json::to_string(round).expect("rounds always serialize");
+        if let Some(&wins) = self.memo.get(&key) {
+            return wins;
+        }
+
+        let mut moves = get_moves(round, &next_player).into_iter()
+            .map(|cards| apply(round, &next_player, cards));
+        let wins = if next_player == self.player_id {
+            moves.any(|next| self.wins(&next))
+        } else {
+            moves.all(|next| self.wins(&next))
+        };
+
+        self.memo.insert(key, wins);
+        wins
+    }
+
+    /// Follows a winning move at each of the solving player's turns and the
+    /// first legal move at everyone else's.
+    fn get_line(&mut self, round: &Round) -> Vec<RecordedMove> {
+        let mut round = round.clone();
+        let mut line = vec![];
+
+        while self.get_outcome(&round).is_none() {
+            let next_player = match round.get_next_player() {
+                Some(id) => id,
+                None => break,
+            };
+
+            let cards = get_moves(&round, &next_player).into_iter()
+                .find(|cards| {
+                    next_player != self.player_id
+                        || self.wins(&apply(&round, &next_player, cards.clone()))
+                })
+                .expect("a winning line always has a move to follow");
+
+            round = apply(&round, &next_player, cards.clone());
+            line.push(RecordedMove {
+                player_id: next_player,
+                cards,
+                annotations: vec![],
+                signature: None,
+            });
+        }
+
+        line
+    }
+}
+
+/// The legal moves, except that a player leading a fresh trick always
+/// plays. Passing the lead round the table would never end the search.
+fn get_moves(round: &Round, player_id: &str) -> Vec<Vec<PlayedCard>> {
+    let leading = round.get_last_move() == Some(Hand::Pass);
+
+    get_legal_moves(round, player_id).into_iter()
+        .filter(|cards| !(leading && cards.is_empty()))
+        .collect()
+}
+
+fn apply(round: &Round, player_id: &str, cards: Vec<PlayedCard>) -> Round {
+    round.submit_move(player_id, cards)
+        .expect("legal moves can always be submitted")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::Scenario;
+
+    fn get_round(a_hand: &str) -> Round {
+        Scenario::new()
+            .player("a", a_hand)
+            .player("b", "5C")
+            .next_player("a")
+            .lead("b")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn a_pair_forces_the_win() {
+        let round = get_round("3C 3D");
+
+        let result = solve_endgame(&round, EndgameConfig::default()).unwrap();
+
+        assert!(result.can_force_win);
+        assert_eq!(result.line.len(), 1);
+        assert_eq!(result.line[0].player_id, "a");
+        assert_eq!(result.line[0].cards.len(), 2);
+    }
+
+    #[test]
+    fn two_low_singles_lose() {
+        let round = get_round("3C 4C");
+
+        let result = solve_endgame(&round, EndgameConfig::default()).unwrap();
+
+        assert!(!result.can_force_win);
+        assert!(result.line.is_empty());
+        assert_eq!(solve_endgame(&round, EndgameConfig { max_cards: 2 }), None);
+    }
+}
@@ -1,5 +1,7 @@
 use crate::cards::{Card, PlayedCard, Rank, Suit};
-use std::collections::HashMap;
+use crate::game::{Hand, OrderContext, Ruleset};
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
 
 pub fn find_pairs(hand: &[Card]) -> Vec<Vec<PlayedCard>> {
     get_sets_of_same_rank(2, hand)
@@ -9,9 +11,14 @@ pub fn find_prials(hand: &[Card]) -> Vec<Vec<PlayedCard>> {
     get_sets_of_same_rank(3, hand)
 }
 
-pub fn find_fct(hand: &[Card]) -> Vec<Vec<PlayedCard>> {
+/// Five card tricks in `hand` that `ruleset` allows under `orders`.
+pub fn find_fct(
+    hand: &[Card],
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> Vec<Vec<PlayedCard>> {
     let natural_cards = get_natural_cards(hand.to_vec());
-    let straights = get_straights(&natural_cards);
+    let straights = get_straights(&natural_cards, ruleset, orders);
     let flushes = get_flushes(&natural_cards);
     let full_houses = get_full_houses(&natural_cards);
     let four_of_a_kinds = get_four_of_a_kinds(&natural_cards);
@@ -22,55 +29,62 @@ pub fn find_fct(hand: &[Card]) -> Vec<Vec<PlayedCard>> {
     five_card_tricks.extend(full_houses);
     five_card_tricks.extend(four_of_a_kinds);
 
+    five_card_tricks.retain(|trick| {
+        Hand::try_build_in_order(trick.clone(), ruleset, orders).is_ok()
+    });
     five_card_tricks
 }
 
-fn get_straights(hand: &[Card]) -> Vec<Vec<PlayedCard>> {
+/// Runs of five cards climbing through the rank order, one starting from
+/// each card. Runs `ruleset` doesn't allow as a straight in that order,
+/// such as ones holding a two when twos are kept out, are left out.
+fn get_straights(
+    hand: &[Card],
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> Vec<Vec<PlayedCard>> {
     let mut straights = vec![];
     for card in hand {
-        let mut sequence = vec![
-            PlayedCard::new(
-                card.get_rank().unwrap(),
-                card.get_suit().unwrap(),
-                false
-            )
-        ];
-        let mut next_rank = get_next_rank(*card);
-        for c2 in hand {
-            if c2.get_rank() == next_rank {
-                sequence.push(PlayedCard::new(
-                    c2.get_rank().unwrap(),
-                    c2.get_suit().unwrap(),
-                    false
-                ));
-                next_rank = get_next_rank(*c2);
-            }
-            if sequence.len() == 5 {
-                break;
+        let mut sequence = vec![to_played_card(*card)];
+        let mut rank = card.get_rank().unwrap();
+        while sequence.len() < 5 {
+            let next_card = rank.next_in(orders.get_rank_order())
+                .and_then(|next| hand.iter().find(|c| c.get_rank() == Some(next)));
+            match next_card {
+                Some(next_card) => {
+                    sequence.push(to_played_card(*next_card));
+                    rank = next_card.get_rank().unwrap();
+                },
+                None => break,
             }
         }
 
-        if sequence.len() == 5 {
+        if sequence.len() == 5
+            && Hand::try_build_in_order(sequence.clone(), ruleset, orders).is_ok() {
             straights.push(sequence);
         }
     }
     straights
 }
 
+fn to_played_card(card: Card) -> PlayedCard {
+    PlayedCard::new(card.get_rank().unwrap(), card.get_suit().unwrap(), false)
+}
+
 fn get_flushes(hand: &[Card]) -> Vec<Vec<PlayedCard>> {
     let mut flushes = vec![];
     let counts = get_suit_counts(hand);
 
     for (r, count) in &counts {
         if *count >= 5 {
-            let flush_suit = Some(*r);
+            let flush_suit = *r;
             let mut flush = vec!();
             for card in hand {
-                if card.get_suit() == flush_suit {
+                if card.get_suit() == Some(flush_suit) {
                     flush.push(
                         PlayedCard::new(
                             card.get_rank().unwrap(),
-                            flush_suit.unwrap(),
+                            flush_suit,
                             false
                         )
                     );
@@ -90,8 +104,8 @@ fn get_flushes(hand: &[Card]) -> Vec<Vec<PlayedCard>> {
 
 fn get_full_houses(hand: &[Card]) -> Vec<Vec<PlayedCard>> {
     let mut full_houses = vec![];
-    let pairs = find_pairs(&hand.to_vec());
-    let prials = find_prials(&hand.to_vec());
+    let pairs = find_pairs(hand);
+    let prials = find_prials(hand);
    
     for prial in &prials {
         for pair in &pairs {
@@ -167,10 +181,10 @@ pub fn get_sets_of_same_rank(
     hands
 }
 
-fn get_counts(cards: Vec<Card>) -> HashMap<Rank, usize> {
+fn get_counts(cards: Vec<Card>) -> BTreeMap<Rank, usize> {
     cards.iter()
         .filter(|c| c.get_rank().is_some())
-        .fold(HashMap::new(), |mut acc, &card| {
+        .fold(BTreeMap::new(), |mut acc, &card| {
             *acc.entry(
                 card.get_rank().unwrap()
             ).or_insert(0) += 1;
@@ -178,10 +192,10 @@ fn get_counts(cards: Vec<Card>) -> HashMap<Rank, usize> {
         })
 }
 
-fn get_suit_counts(cards: &[Card]) -> HashMap<Suit, usize> {
+fn get_suit_counts(cards: &[Card]) -> BTreeMap<Suit, usize> {
     cards.iter()
         .filter(|c| c.get_rank().is_some())
-        .fold(HashMap::new(), |mut acc, &card| {
+        .fold(BTreeMap::new(), |mut acc, &card| {
             *acc.entry(
                 card.get_suit().unwrap()
             ).or_insert(0) += 1;
@@ -191,33 +205,16 @@ fn get_suit_counts(cards: &[Card]) -> HashMap<Suit, usize> {
 
 fn get_natural_cards(hand: Vec<Card>) -> Vec<Card> {
     hand.iter().filter(|c| {
-        c.get_rank() != None
+        c.get_rank().is_some()
     })
     .cloned().collect::<Vec<Card>>()
 }
 
-fn get_next_rank(card: Card) -> Option<Rank> {
-    match card.get_rank().unwrap_or(Rank::Two) {
-        Rank::Three => Some(Rank::Four),
-        Rank::Four => Some(Rank::Five),
-        Rank::Five => Some(Rank::Six),
-        Rank::Six => Some(Rank::Seven),
-        Rank::Seven => Some(Rank::Eight),
-        Rank::Eight => Some(Rank::Nine),
-        Rank::Nine => Some(Rank::Ten),
-        Rank::Ten => Some(Rank::Jack),
-        Rank::Jack => Some(Rank::Queen),
-        Rank::Queen => Some(Rank::King),
-        Rank::King => Some(Rank::Ace),
-        Rank::Ace => Some(Rank::Two),
-        Rank::Two => None
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::cards::*;
+    use crate::scenario::parse_cards;
 /*
     static DEFAULT_SUIT_ORDER: [Suit; 4] =
         [Suit::Clubs, Suit::Hearts, Suit::Diamonds, Suit::Spades];
@@ -292,7 +289,7 @@ mod tests {
             Card::Standard{deck_id: 0, rank: Rank::Seven, suit: Suit::Clubs},
         ];
 
-        assert_eq!(find_fct(&hand).len(), 1);
+        assert_eq!(find_fct(&hand, Ruleset::default(), &OrderContext::standard()).len(), 1);
     }
 
     #[test]
@@ -305,7 +302,7 @@ mod tests {
             Card::Standard{deck_id: 0, rank: Rank::Ten, suit: Suit::Clubs},
         ];
 
-        assert_eq!(find_fct(&hand).len(), 0);
+        assert_eq!(find_fct(&hand, Ruleset::default(), &OrderContext::standard()).len(), 0);
     }
 
     #[test]
@@ -318,7 +315,7 @@ mod tests {
             Card::Standard{deck_id: 0, rank: Rank::Ten, suit: Suit::Clubs},
         ];
 
-        assert_eq!(find_fct(&hand).len(), 1);
+        assert_eq!(find_fct(&hand, Ruleset::default(), &OrderContext::standard()).len(), 1);
     }
 
     #[test]
@@ -331,7 +328,7 @@ mod tests {
             Card::Standard{deck_id: 0, rank: Rank::Four, suit: Suit::Diamonds},
         ];
 
-        assert_eq!(find_fct(&hand).len(), 1);
+        assert_eq!(find_fct(&hand, Ruleset::default(), &OrderContext::standard()).len(), 1);
     }
 
     #[test]
@@ -343,6 +340,32 @@ mod tests {
             Card::Standard{deck_id: 0, rank: Rank::Three, suit: Suit::Clubs},
             Card::Standard{deck_id: 0, rank: Rank::Four, suit: Suit::Diamonds},
         ];
-        assert_eq!(find_fct(&hand).len(), 1);
+        assert_eq!(find_fct(&hand, Ruleset::default(), &OrderContext::standard()).len(), 1);
+    }
+
+    #[test]
+    fn straights_are_found_in_any_order_and_under_reversals() {
+        let hand = vec![
+            Card::Standard{deck_id: 0, rank: Rank::Seven, suit: Suit::Clubs},
+            Card::Standard{deck_id: 0, rank: Rank::Six, suit: Suit::Hearts},
+            Card::Standard{deck_id: 0, rank: Rank::Five, suit: Suit::Spades},
+            Card::Standard{deck_id: 0, rank: Rank::Four, suit: Suit::Clubs},
+            Card::Standard{deck_id: 0, rank: Rank::Three, suit: Suit::Clubs},
+        ];
+        let orders = OrderContext::standard();
+
+        assert_eq!(find_fct(&hand, Ruleset::default(), &orders).len(), 1);
+        assert_eq!(find_fct(&hand, Ruleset::default(), &orders.reverse()).len(), 1);
+    }
+
+    #[test]
+    fn straights_follow_a_custom_rank_order() {
+        let hand = parse_cards("2C 3H 4D 5S 6C").unwrap();
+        let mut rank_order = get_rank_array();
+        rank_order.rotate_right(1);
+        let twos_low = OrderContext::new(&get_suit_array(), rank_order).unwrap();
+
+        assert_eq!(find_fct(&hand, Ruleset::default(), &OrderContext::standard()).len(), 0);
+        assert_eq!(find_fct(&hand, Ruleset::default(), &twos_low).len(), 1);
     }
 }
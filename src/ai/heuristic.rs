@@ -0,0 +1,104 @@
+use super::{find_controls, plan_partition, CardTracker, PartitionGoal};
+use crate::cards::PlayedCard;
+use crate::game::{Hand, Round};
+use crate::prelude::*;
+
+/// How good the round looks for `player_id`, from 0 to 1, using only what
+/// they can see. Agents can compare positions with it and UIs can show it
+/// as a chance of winning.
+///
+/// The player's hand is split into the fewest plays and every play that
+/// no unseen card could beat counts as a control. The other plays each
+/// need someone else to hand over the lead. That is weighed against how
+/// close the nearest opponent is to going out, taking two cards a play.
+/// A player who is out scores 1, and an unknown player 0.
+pub fn evaluate(round: &Round, player_id: &str) -> f32 {
+    let player = match round.get_player(player_id) {
+        Some(player) => player,
+        None => return 0.0,
+    };
+    let hand = player.get_hand();
+    if hand.is_empty() {
+        return 1.0;
+    }
+
+    let orders = round.get_order_context();
+    let plan = plan_partition(
+        &hand,
+        PartitionGoal::FewestPlays,
+        round.get_ruleset(),
+        &orders,
+    );
+    let controls: Vec<Vec<PlayedCard>> = find_controls(
+        &hand,
+        &CardTracker::from_round(round, player_id),
+        round.get_ruleset(),
+        &orders,
+    )
+        .into_iter()
+        .map(sorted)
+        .collect();
+
+    let controlled = plan.plays.iter()
+        .filter(|play| controls.contains(&sorted(play.to_cards())))
+        .count();
+    let turns_needed = (plan.plays.len() - controlled) as f32;
+
+    let nearest_opponent = round.get_card_counts().into_iter()
+        .filter(|(id, count)| id != player_id && *count > 0)
+        .map(|(_, count)| count)
+        .min()
+        .unwrap_or(0);
+    let opponent_turns = nearest_opponent as f32 / 2.0;
+
+    let on_lead = round.get_next_player().as_deref() == Some(player_id)
+        && matches!(round.get_last_move(), None | Some(Hand::Pass));
+    let lead_bonus = if on_lead { 0.5 } else { 0.0 };
+
+    logistic(opponent_turns - turns_needed + lead_bonus)
+}
+
+fn sorted(mut cards: Vec<PlayedCard>) -> Vec<PlayedCard> {
+    cards.sort();
+    cards
+}
+
+fn logistic(x: f32) -> f32 {
+    1.0 / (1.0 + libm::expf(-x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Card;
+    use crate::game::{Player, RoundBuilder};
+    use crate::scenario::parse_cards;
+
+    fn get_round(a_hand: Vec<Card>) -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), a_hand))
+            .player(Player::new("b".to_string(), parse_cards("4C 6H 9D JS").unwrap()))
+            .next_player("b")
+            .table_cleared("a")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn controls_make_a_position_stronger() {
+        let strong = get_round(parse_cards("2S 2D").unwrap());
+        let weak = get_round(parse_cards("3C 5D 7C").unwrap());
+
+        assert!(evaluate(&strong, "a") > evaluate(&weak, "a"));
+        assert!(evaluate(&strong, "a") > 0.5);
+        assert!(evaluate(&weak, "a") < 0.5);
+    }
+
+    #[test]
+    fn finished_and_unknown_players_are_at_the_extremes() {
+        let round = get_round(vec![]);
+
+        assert_eq!(evaluate(&round, "a"), 1.0);
+        assert_eq!(evaluate(&round, "z"), 0.0);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::cards::{Card, PlayedCard};
+use crate::game::{compare_hands, FlushPrecedence, GameRecord, Hand, OrderContext, Round};
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+
+const BALANCING_ITERATIONS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InferenceConfig {
+    /// How much a pass on a single counts against holding each card that
+    /// would have beaten it. Players do hold cards back, so this is a
+    /// discount rather than a certainty.
+    pub single_pass_discount: f64,
+    /// The same for pairs and prials, where one card alone wasn't enough
+    /// to beat the hand and the evidence is weaker.
+    pub set_pass_discount: f64,
+}
+
+impl Default for InferenceConfig {
+    fn default() -> InferenceConfig {
+        InferenceConfig {
+            single_pass_discount: 0.3,
+            set_pass_discount: 0.7,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Pass {
+    player_id: String,
+    facing: Hand,
+    flush_precedence: FlushPrecedence,
+    orders: OrderContext,
+}
+
+/// Estimates who holds each card `viewer` cannot see. Every unseen card
+/// starts out equally likely to be in any opponent's hand, weighted by how
+/// many cards they hold; each pass on a single, pair or prial then counts
+/// against the cards that could have beaten it.
+#[derive(Debug, Clone)]
+pub struct CardInference {
+    viewer: String,
+    config: InferenceConfig,
+    passes: Vec<Pass>,
+}
+
+impl CardInference {
+    pub fn new(viewer: &str, config: InferenceConfig) -> CardInference {
+        CardInference {
+            viewer: viewer.to_string(),
+            config,
+            passes: vec![],
+        }
+    }
+
+    /// Replays a recorded game, observing every move.
+    pub fn from_record(
+        record: &GameRecord,
+        viewer: &str,
+        config: InferenceConfig,
+    ) -> CardInference {
+        let mut inference = CardInference::new(viewer, config);
+        let mut round = record.initial_round.clone();
+
+        for recorded in record.moves.iter() {
+            inference.observe(&round, &recorded.player_id, &recorded.cards);
+            if round.submit_move_mut(
+                &recorded.player_id, recorded.cards.clone()
+            ).is_err() {
+                break;
+            }
+        }
+
+        inference
+    }
+
+    /// Takes note of a move, given the round as it stood before it.
+    pub fn observe(&mut self, round: &Round, player_id: &str, cards: &[PlayedCard]) {
+        if !cards.is_empty() || player_id == self.viewer {
+            return;
+        }
+
+        match round.get_last_move() {
+            Some(Hand::Pass) | Some(Hand::FiveCardTrick(_)) | None => (),
+            Some(facing) => self.passes.push(Pass {
+                player_id: player_id.to_string(),
+                facing,
+                flush_precedence: round.get_ruleset().flush_precedence,
+                orders: round.get_order_context(),
+            }),
+        }
+    }
+
+    /// Probability that each opponent holds each unseen card. For every
+    /// card the chances across opponents add up to one, and for every
+    /// opponent they add up to the number of cards in their hand.
+    pub fn get_probabilities(&self, round: &Round) -> BTreeMap<String, BTreeMap<Card, f64>> {
+        let opponents: Vec<(String, usize)> = round.get_card_counts().into_iter()
+            .filter(|(id, count)| *id != self.viewer && *count > 0)
+            .collect();
+
+        let mut unseen: Vec<Card> = round.get_players().into_iter()
+            .filter(|p| p.get_id() != self.viewer)
+            .flat_map(|p| p.get_hand())
+            .collect();
+        unseen.sort();
+
+        let mut weights: Vec<Vec<f64>> = opponents.iter()
+            .map(|(id, _)| {
+                unseen.iter().map(|&card| self.get_weight(id, card)).collect()
+            })
+            .collect();
+
+        for _ in 0..BALANCING_ITERATIONS {
+            for card in 0..unseen.len() {
+                let total: f64 = weights.iter().map(|w| w[card]).sum();
+                if total > 0.0 {
+                    weights.iter_mut().for_each(|w| w[card] /= total);
+                }
+            }
+            for (w, (_, count)) in weights.iter_mut().zip(opponents.iter()) {
+                let total: f64 = w.iter().sum();
+                if total > 0.0 {
+                    let scale = *count as f64 / total;
+                    w.iter_mut().for_each(|p| *p *= scale);
+                }
+            }
+        }
+
+        opponents.into_iter()
+            .zip(weights)
+            .map(|((id, _), w)| {
+                (id, unseen.iter().cloned().zip(w).collect())
+            })
+            .collect()
+    }
+
+    pub fn get_probability(&self, round: &Round, player_id: &str, card: Card) -> f64 {
+        self.get_probabilities(round)
+            .get(player_id)
+            .and_then(|cards| cards.get(&card))
+            .cloned()
+            .unwrap_or(0.0)
+    }
+
+    fn get_weight(&self, player_id: &str, card: Card) -> f64 {
+        self.passes.iter()
+            .filter(|pass| pass.player_id == player_id)
+            .filter(|pass| pass.could_be_beaten_with(card))
+            .map(|pass| match pass.facing {
+                Hand::Single(_) => self.config.single_pass_discount,
+                _ => self.config.set_pass_discount,
+            })
+            .product()
+    }
+}
+
+impl Pass {
+    /// Whether `card` could be the top card of a hand that beats the one
+    /// that was passed on.
+    fn could_be_beaten_with(&self, card: Card) -> bool {
+        let played = match card {
+            Card::Standard { rank, suit, .. } => PlayedCard::new(rank, suit, false),
+            Card::Joker { .. } => return true,
+        };
+
+        self.facing.to_cards().into_iter().all(|top| {
+            compare_hands(
+                Hand::Single(top),
+                Hand::Single(played),
+                self.flush_precedence,
+                &self.orders,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Rank, Suit};
+    use crate::game::{Player, RoundBuilder};
+    use crate::scenario::parse_cards;
+
+    fn get_round() -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("KC KS 3C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("4C 5C").unwrap()))
+            .player(Player::new("c".to_string(), parse_cards("2H 2S").unwrap()))
+            .next_player("a")
+            .table_cleared("a")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn without_evidence_cards_are_spread_by_hand_size() {
+        let round = get_round();
+        let inference = CardInference::new("a", InferenceConfig::default());
+        let probabilities = inference.get_probabilities(&round);
+
+        assert_eq!(probabilities.len(), 2);
+        assert!((probabilities["b"][&"2H".parse::<Card>().unwrap()] - 0.5).abs() < 1e-9);
+        for cards in probabilities.values() {
+            assert!((cards.values().sum::<f64>() - 2.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn passing_on_a_pair_discounts_higher_cards() {
+        let round = get_round();
+        let kings = vec![
+            PlayedCard::new(Rank::King, Suit::Clubs, false),
+            PlayedCard::new(Rank::King, Suit::Spades, false),
+        ];
+        let mut inference = CardInference::new("a", InferenceConfig::default());
+
+        inference.observe(&round, "a", &kings);
+        let round = round.submit_move("a", kings).unwrap();
+        inference.observe(&round, "b", &[]);
+
+        let two = "2H".parse::<Card>().unwrap();
+        let b_two = inference.get_probability(&round, "b", two);
+        let c_two = inference.get_probability(&round, "c", two);
+
+        assert!(b_two < 0.5);
+        assert!((b_two + c_two - 1.0).abs() < 1e-6);
+        assert_eq!(inference.get_probability(&round, "a", two), 0.0);
+    }
+}
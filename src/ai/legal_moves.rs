@@ -0,0 +1,263 @@
+use crate::cards::{Card, PlayedCard};
+use crate::game::{sort_played_cards, Hand, OrderContext, Round, Ruleset};
+use crate::prelude::*;
+use alloc::collections::BTreeSet;
+
+const HAND_SIZES: [usize; 4] = [1, 2, 3, 5];
+
+/// Every move `player_id` could legally submit in the current round,
+/// including the pass where one is allowed. Moves are ordered from the
+/// lowest cards upwards.
+pub fn get_legal_moves(round: &Round, player_id: &str) -> Vec<Vec<PlayedCard>> {
+    let player = match round.get_player(player_id) {
+        Some(p) => p,
+        None => return vec![],
+    };
+
+    get_candidate_moves(
+        &player.get_hand(),
+        round.get_ruleset(),
+        &round.get_order_context(),
+    )
+        .into_iter()
+        .filter(|cards| round.validate_move(player_id, cards).is_ok())
+        .collect()
+}
+
+/// All valid hands that can be formed from `hand` under `ruleset` and
+/// `orders`, ignoring the state of the table, so runs that only a
+/// custom order makes are offered too. Jokers are only offered as the
+/// highest possible single.
+pub fn get_candidate_moves(
+    hand: &[Card],
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> Vec<Vec<PlayedCard>> {
+    let natural_cards: Vec<PlayedCard> = hand.iter()
+        .filter_map(|c| match *c {
+            Card::Standard { rank, suit, .. } => {
+                Some(PlayedCard::new(rank, suit, false))
+            },
+            _ => None,
+        })
+        .collect();
+    let mut natural_cards = sort_played_cards(
+        &natural_cards, orders
+    );
+    natural_cards.reverse();
+
+    let mut seen = BTreeSet::new();
+    let mut candidates = vec![vec![]];
+
+    for &size in HAND_SIZES.iter() {
+        for cards in get_combinations(&natural_cards, size) {
+            if Hand::try_build_in_order(cards.clone(), ruleset, orders).is_ok()
+                && seen.insert(cards.clone()) {
+                candidates.push(cards);
+            }
+        }
+    }
+
+    if hand.iter().any(|c| c.get_rank().is_none()) {
+        candidates.push(vec![PlayedCard::new(
+            orders.highest_rank(),
+            orders.highest_suit(),
+            true,
+        )]);
+    }
+
+    candidates
+}
+
+impl Round {
+    /// Up to `limit` of the cheapest hands `user_id` holds that beat the
+    /// hand on the table, lowest top card first and fewer cards on a tie.
+    /// Empty when there's nothing on the table to beat. It doesn't matter
+    /// whose turn it is, so hints can be worked out ahead of time.
+    pub fn lowest_beating_hands(&self, user_id: &str, limit: usize) -> Vec<Hand> {
+        let last_move = match self.get_last_move() {
+            None | Some(Hand::Pass) => return vec![],
+            Some(last_move) => last_move,
+        };
+        let player = match self.get_player(user_id) {
+            Some(player) => player,
+            None => return vec![],
+        };
+        let ruleset = self.get_ruleset();
+        let orders = self.get_order_context();
+
+        let mut beating: Vec<Vec<PlayedCard>> = get_candidate_moves(
+            &player.get_hand(), ruleset, &orders
+        )
+            .into_iter()
+            .filter(|cards| !cards.is_empty())
+            .filter(|cards| {
+                Hand::try_build_in_order(cards.clone(), ruleset, &orders)
+                    .map(|hand| hand.beats(&last_move, ruleset, &orders))
+                    .unwrap_or(false)
+            })
+            .collect();
+        beating.sort_by_key(|cards| get_move_cost(cards, &orders));
+
+        beating.into_iter()
+            .take(limit)
+            .filter_map(|cards| Hand::try_build_in_order(cards, ruleset, &orders).ok())
+            .collect()
+    }
+}
+
+/// Orders moves by their top card under the given orders, then by how
+/// many cards they use. Passes have no top card and come first.
+pub(crate) fn get_move_cost(
+    cards: &[PlayedCard],
+    orders: &OrderContext,
+) -> (Option<usize>, Option<usize>, usize) {
+    let top_card = match sort_played_cards(cards, orders).first() {
+        Some(&card) => card,
+        None => return (None, None, 0),
+    };
+    (
+        orders.rank_index(top_card.get_rank()),
+        orders.suit_index(top_card.get_suit()),
+        cards.len(),
+    )
+}
+
+pub(crate) fn get_combinations(
+    cards: &[PlayedCard],
+    size: usize,
+) -> Vec<Vec<PlayedCard>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+
+    if cards.len() < size {
+        return vec![];
+    }
+
+    let mut combinations = vec![];
+    for (i, &card) in cards.iter().enumerate() {
+        for mut rest in get_combinations(&cards[i + 1..], size - 1) {
+            rest.insert(0, card);
+            combinations.push(rest);
+        }
+    }
+
+    combinations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{Player, RoundBuilder};
+    use crate::scenario::parse_cards;
+
+    #[test]
+    fn opening_moves_must_contain_the_lowest_card() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C 3H 4C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("5C").unwrap()))
+            .build()
+            .unwrap();
+
+        let moves = get_legal_moves(&round, "a");
+
+        assert_eq!(moves, vec![
+            vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)],
+            vec![
+                PlayedCard::new(Rank::Three, Suit::Clubs, false),
+                PlayedCard::new(Rank::Three, Suit::Hearts, false),
+            ],
+        ]);
+    }
+
+    #[test]
+    fn pass_is_legal_when_following() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("4C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("5C 3H").unwrap()))
+            .next_player("b")
+            .last_move(
+                Hand::Single(PlayedCard::new(Rank::Four, Suit::Hearts, false)),
+                "a"
+            )
+            .build()
+            .unwrap();
+
+        let moves = get_legal_moves(&round, "b");
+
+        assert_eq!(moves, vec![
+            vec![],
+            vec![PlayedCard::new(Rank::Five, Suit::Clubs, false)],
+        ]);
+    }
+
+    #[test]
+    fn it_offers_no_moves_to_other_players() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("5C").unwrap()))
+            .build()
+            .unwrap();
+
+        assert!(get_legal_moves(&round, "b").is_empty());
+    }
+
+    #[test]
+    fn it_finds_five_card_tricks() {
+        let hand = parse_cards("3C 4C 5C 6C 7H").unwrap();
+
+        let candidates = get_candidate_moves(
+            &hand, Ruleset::default(), &OrderContext::standard()
+        );
+
+        assert!(candidates.iter().any(|c| c.len() == 5));
+    }
+
+    #[test]
+    fn runs_made_by_a_custom_rank_order_are_legal_moves() {
+        let mut twos_low = get_rank_array();
+        twos_low.rotate_right(1);
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("2C 3H 4D 5S 6C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("9C").unwrap()))
+            .rank_order(twos_low)
+            .build()
+            .unwrap();
+        let run: Vec<PlayedCard> = round.get_player("a").unwrap().get_hand().iter()
+            .map(|c| PlayedCard::new(c.get_rank().unwrap(), c.get_suit().unwrap(), false))
+            .collect();
+
+        assert!(round.validate_move("a", &run).is_ok());
+        let five_card_moves: Vec<Vec<PlayedCard>> = get_legal_moves(&round, "a").into_iter()
+            .filter(|cards| cards.len() == 5)
+            .collect();
+        assert_eq!(five_card_moves.len(), 1);
+        assert!(matches!(
+            round.validate_move("a", &five_card_moves[0]),
+            Ok(Hand::FiveCardTrick(_))
+        ));
+    }
+
+    #[test]
+    fn the_cheapest_beating_hands_come_first() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("4C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("3H 5C 9C 9S 2S").unwrap()))
+            .next_player("a")
+            .last_move(
+                Hand::Single(PlayedCard::new(Rank::Four, Suit::Hearts, false)),
+                "a"
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(round.lowest_beating_hands("b", 2), vec![
+            Hand::Single(PlayedCard::new(Rank::Five, Suit::Clubs, false)),
+            Hand::Single(PlayedCard::new(Rank::Nine, Suit::Clubs, false)),
+        ]);
+        assert_eq!(round.lowest_beating_hands("b", 10).len(), 4);
+        assert!(round.lowest_beating_hands("a", 10).is_empty());
+    }
+}
@@ -0,0 +1,538 @@
+use super::{get_legal_moves, Agent, CpuAgent, HiddenHands};
+use crate::cards::PlayedCard;
+use crate::game::Round;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+const MAX_ROLLOUT_MOVES: usize = 500;
+
+#[derive(Debug, Clone, Copy)]
+pub struct MctsConfig {
+    pub iterations: usize,
+    /// Wall-clock budget per decision. `Instant` is unavailable on
+    /// wasm32-unknown-unknown, so leave this unset in the browser.
+    pub time_limit: Option<Duration>,
+    pub exploration: f64,
+}
+
+impl Default for MctsConfig {
+    fn default() -> MctsConfig {
+        MctsConfig {
+            iterations: 200,
+            time_limit: None,
+            exploration: 0.7,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Node {
+    action: Vec<PlayedCard>,
+    player: String,
+    visits: u32,
+    availability: u32,
+    reward: f64,
+    children: Vec<usize>,
+}
+
+/// Monte Carlo tree search over determinized copies of the round: each
+/// iteration redeals the cards this seat cannot see, so the search never
+/// peeks at opponents' actual hands.
+#[derive(Debug, Clone)]
+pub struct MctsAgent {
+    config: MctsConfig,
+    rng: StdRng,
+    next_seed: u64,
+}
+
+impl MctsAgent {
+    pub fn new(config: MctsConfig, seed: u64) -> MctsAgent {
+        MctsAgent {
+            config,
+            rng: StdRng::seed_from_u64(seed),
+            next_seed: seed,
+        }
+    }
+
+    fn search(&mut self, round: &Round, player_id: &str) -> Vec<PlayedCard> {
+        let legal_moves = get_legal_moves(round, player_id);
+        if legal_moves.len() < 2 {
+            return legal_moves.into_iter().next().unwrap_or_default();
+        }
+
+        let visits = self.get_root_visits(round, player_id, &legal_moves);
+        get_most_visited(visits, legal_moves)
+    }
+
+    fn get_root_visits(
+        &mut self,
+        round: &Round,
+        player_id: &str,
+        legal_moves: &[Vec<PlayedCard>],
+    ) -> Vec<(Vec<PlayedCard>, u32)> {
+        let mut tree = new_tree();
+        // only read the clock when there's a limit, as it panics on
+        // wasm32-unknown-unknown
+        let started = self.config.time_limit.map(|limit| (Instant::now(), limit));
+
+        for _ in 0..self.config.iterations {
+            if let Some((started, limit)) = started {
+                if started.elapsed() >= limit {
+                    break;
+                }
+            }
+
+            let state = determinize(round, player_id, &mut self.rng).fork_for_simulation();
+            self.run_iteration(&mut tree, state);
+        }
+
+        get_visits(&tree, legal_moves)
+    }
+
+    fn run_iteration(&mut self, tree: &mut Vec<Node>, mut state: Round) {
+        let starting_players = count_players_still_in(&state);
+        let mut finished = vec![];
+        let mut path = vec![0];
+        let mut node = 0;
+
+        while let Some(current) = state.get_next_player() {
+            let legal_moves = get_legal_moves(&state, &current);
+            let available: Vec<usize> = tree[node].children.iter()
+                .cloned()
+                .filter(|&c| legal_moves.contains(&tree[c].action))
+                .collect();
+            for &child in &available {
+                tree[child].availability += 1;
+            }
+
+            let untried: Vec<&Vec<PlayedCard>> = legal_moves.iter()
+                .filter(|m| !available.iter().any(|&c| tree[c].action == **m))
+                .collect();
+
+            let (child, expanded) = match untried.choose(&mut self.rng) {
+                Some(&action) => {
+                    tree.push(Node {
+                        action: action.clone(),
+                        player: current.clone(),
+                        visits: 0,
+                        availability: 1,
+                        reward: 0.0,
+                        children: vec![],
+                    });
+                    let child = tree.len() - 1;
+                    tree[node].children.push(child);
+                    (child, true)
+                },
+                None => match self.select(tree, &available) {
+                    Some(child) => (child, false),
+                    None => break,
+                },
+            };
+
+            state = match apply(&state, &current, &tree[child].action, &mut finished) {
+                Some(s) => s,
+                None => break,
+            };
+            path.push(child);
+            node = child;
+
+            if expanded {
+                break;
+            }
+        }
+
+        let mut rollout_agent = CpuAgent;
+        let mut moves = 0;
+        while let Some(current) = state.get_next_player() {
+            if moves >= MAX_ROLLOUT_MOVES {
+                break;
+            }
+            let mut action = rollout_agent.choose_move(&state, &current);
+            if state.validate_move(&current, &action).is_err() {
+                action = get_legal_moves(&state, &current)
+                    .into_iter().next().unwrap_or_default();
+            }
+            state = match apply(&state, &current, &action, &mut finished) {
+                Some(s) => s,
+                None => break,
+            };
+            moves += 1;
+        }
+
+        for &i in path.iter().skip(1) {
+            let reward = get_reward(&tree[i].player, &finished, starting_players);
+            tree[i].visits += 1;
+            tree[i].reward += reward;
+        }
+        tree[0].visits += 1;
+    }
+
+    fn select(&self, tree: &[Node], available: &[usize]) -> Option<usize> {
+        let exploration = self.config.exploration;
+        available.iter()
+            .cloned()
+            .max_by(|&a, &b| {
+                let score = |n: &Node| {
+                    let visits = f64::from(n.visits.max(1));
+                    n.reward / visits + exploration
+                        * (f64::from(n.availability).ln() / visits).sqrt()
+                };
+                score(&tree[a]).partial_cmp(&score(&tree[b]))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+}
+
+/// Each search starts from a seed of its own, so a move can be replayed
+/// from its seed, as long as the config has no time limit to cut the
+/// search short.
+impl Agent for MctsAgent {
+    fn choose_move(&mut self, round: &Round, player_id: &str) -> Vec<PlayedCard> {
+        use rand::Rng;
+
+        self.rng = StdRng::seed_from_u64(self.next_seed);
+        let chosen = self.search(round, player_id);
+        self.next_seed = self.rng.gen();
+
+        chosen
+    }
+
+    fn get_name(&self) -> String {
+        "mcts".to_string()
+    }
+
+    fn get_seed(&self) -> Option<u64> {
+        Some(self.next_seed)
+    }
+}
+
+/// A tree search that runs a slice at a time and picks up where it left
+/// off, so a browser can spread it over several animation frames instead
+/// of freezing the UI thread. It's done after the config's iterations; the
+/// config's time limit isn't used.
+#[derive(Debug)]
+pub struct MctsSearch {
+    agent: MctsAgent,
+    round: Round,
+    player_id: String,
+    legal_moves: Vec<Vec<PlayedCard>>,
+    tree: Vec<Node>,
+    iterations: usize,
+}
+
+impl MctsSearch {
+    pub fn new(round: &Round, player_id: &str, config: MctsConfig, seed: u64) -> MctsSearch {
+        MctsSearch {
+            agent: MctsAgent::new(config, seed),
+            round: round.clone(),
+            player_id: player_id.to_string(),
+            legal_moves: get_legal_moves(round, player_id),
+            tree: new_tree(),
+            iterations: 0,
+        }
+    }
+
+    pub fn get_iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Searches for about `budget_ms` milliseconds. `Instant` is
+    /// unavailable on wasm32-unknown-unknown, so use `think_with_clock`
+    /// there.
+    pub fn think(&mut self, budget_ms: u64) -> Poll<Vec<PlayedCard>> {
+        let started = Instant::now();
+        self.think_with_clock(budget_ms as f64, || started.elapsed().as_secs_f64() * 1000.0)
+    }
+
+    /// Searches until `budget_ms` has passed on `now_ms`, a millisecond
+    /// clock such as JavaScript's `Date.now`. At least one iteration runs
+    /// on every call.
+    pub fn think_with_clock<F: Fn() -> f64>(
+        &mut self,
+        budget_ms: f64,
+        now_ms: F,
+    ) -> Poll<Vec<PlayedCard>> {
+        let deadline = now_ms() + budget_ms;
+
+        while !self.is_done() {
+            let state = determinize(&self.round, &self.player_id, &mut self.agent.rng)
+                .fork_for_simulation();
+            self.agent.run_iteration(&mut self.tree, state);
+            self.iterations += 1;
+
+            if now_ms() >= deadline {
+                break;
+            }
+        }
+
+        if self.is_done() {
+            Poll::Ready(self.get_best_move())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.legal_moves.len() < 2 || self.iterations >= self.agent.config.iterations
+    }
+
+    fn get_best_move(&self) -> Vec<PlayedCard> {
+        if self.legal_moves.is_empty() {
+            return vec![];
+        }
+
+        let visits = get_visits(&self.tree, &self.legal_moves);
+        get_most_visited(visits, self.legal_moves.clone())
+    }
+}
+
+/// Root parallel tree search: several independent `MctsAgent` searches run
+/// across rayon's thread pool and their visit counts are added up at the
+/// root. Each tree gets its own seed drawn from the agent's, so results are
+/// still reproducible.
+#[cfg(feature = "parallel")]
+#[derive(Debug, Clone)]
+pub struct ParallelMctsAgent {
+    config: MctsConfig,
+    trees: usize,
+    next_seed: u64,
+}
+
+#[cfg(feature = "parallel")]
+impl ParallelMctsAgent {
+    /// `config` applies to each of the `trees` searches.
+    pub fn new(config: MctsConfig, trees: usize, seed: u64) -> ParallelMctsAgent {
+        ParallelMctsAgent {
+            config,
+            trees: trees.max(1),
+            next_seed: seed,
+        }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl Agent for ParallelMctsAgent {
+    fn choose_move(&mut self, round: &Round, player_id: &str) -> Vec<PlayedCard> {
+        use rand::Rng;
+        use rayon::prelude::*;
+
+        let legal_moves = get_legal_moves(round, player_id);
+        if legal_moves.len() < 2 {
+            return legal_moves.into_iter().next().unwrap_or_default();
+        }
+
+        let config = self.config;
+        let mut rng = StdRng::seed_from_u64(self.next_seed);
+        let seeds: Vec<u64> = (0..self.trees).map(|_| rng.gen()).collect();
+        self.next_seed = rng.gen();
+        let totals = seeds.into_par_iter()
+            .map(|seed| {
+                let visits = MctsAgent::new(config, seed)
+                    .get_root_visits(round, player_id, &legal_moves);
+                legal_moves.iter()
+                    .map(|m| visits.iter().find(|(a, _)| a == m).map_or(0, |(_, n)| *n))
+                    .collect::<Vec<u32>>()
+            })
+            .reduce(
+                || vec![0; legal_moves.len()],
+                |a, b| a.iter().zip(b).map(|(x, y)| x + y).collect(),
+            );
+        let visits = legal_moves.iter().cloned().zip(totals).collect();
+
+        get_most_visited(visits, legal_moves)
+    }
+    fn get_name(&self) -> String {
+        "parallel_mcts".to_string()
+    }
+
+    fn get_seed(&self) -> Option<u64> {
+        Some(self.next_seed)
+    }
+}
+
+fn new_tree() -> Vec<Node> {
+    vec![Node {
+        action: vec![],
+        player: String::new(),
+        visits: 0,
+        availability: 0,
+        reward: 0.0,
+        children: vec![],
+    }]
+}
+
+/// How often the search tried each of the legal moves at the root.
+fn get_visits(tree: &[Node], legal_moves: &[Vec<PlayedCard>]) -> Vec<(Vec<PlayedCard>, u32)> {
+    tree[0].children.iter()
+        .map(|&i| &tree[i])
+        .filter(|node| legal_moves.contains(&node.action))
+        .map(|node| (node.action.clone(), node.visits))
+        .collect()
+}
+
+fn get_most_visited(
+    visits: Vec<(Vec<PlayedCard>, u32)>,
+    legal_moves: Vec<Vec<PlayedCard>>,
+) -> Vec<PlayedCard> {
+    visits.into_iter()
+        .max_by_key(|(_, count)| *count)
+        .map(|(action, _)| action)
+        .unwrap_or_else(|| legal_moves[0].clone())
+}
+
+/// Redeals every card `player_id` cannot see among the other players,
+/// keeping each player's card count.
+pub fn determinize<R: rand::Rng>(
+    round: &Round,
+    player_id: &str,
+    rng: &mut R,
+) -> Round {
+    HiddenHands::from_round(round, player_id).sample_round(round, rng)
+}
+
+fn apply(
+    state: &Round,
+    player_id: &str,
+    action: &[PlayedCard],
+    finished: &mut Vec<String>,
+) -> Option<Round> {
+    let next = state.submit_move(player_id, action.to_vec()).ok()?;
+    let out = next.get_player(player_id)
+        .map(|p| p.get_card_count() == 0)
+        .unwrap_or(false);
+    if out && !finished.iter().any(|f| f == player_id) {
+        finished.push(player_id.to_string());
+    }
+    Some(next)
+}
+
+fn count_players_still_in(round: &Round) -> usize {
+    round.get_player_ids().iter()
+        .filter_map(|id| round.get_player(id))
+        .filter(|p| p.get_card_count() > 0)
+        .count()
+}
+
+fn get_reward(player_id: &str, finished: &[String], players: usize) -> f64 {
+    if players < 2 {
+        return 0.0;
+    }
+    match finished.iter().position(|f| f == player_id) {
+        Some(position) => 1.0 - position as f64 / (players - 1) as f64,
+        None => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{Hand, Player, RoundBuilder};
+    use crate::scenario::parse_cards;
+
+    fn get_round() -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("4C KC KH").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("9C 9S").unwrap()))
+            .next_player("b")
+            .table_cleared("b")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn it_finds_the_move_that_goes_out() {
+        let mut agent = MctsAgent::new(MctsConfig::default(), 1);
+
+        assert_eq!(
+            agent.choose_move(&get_round(), "b"),
+            vec![
+                PlayedCard::new(Rank::Nine, Suit::Clubs, false),
+                PlayedCard::new(Rank::Nine, Suit::Spades, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn it_is_reproducible_from_a_seed() {
+        let config = MctsConfig { iterations: 30, ..MctsConfig::default() };
+        let round = get_round();
+
+        assert_eq!(
+            MctsAgent::new(config, 9).choose_move(&round, "a"),
+            MctsAgent::new(config, 9).choose_move(&round, "a")
+        );
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_search_finds_the_move_that_goes_out() {
+        let config = MctsConfig { iterations: 50, ..MctsConfig::default() };
+        let round = get_round();
+        let choose = || ParallelMctsAgent::new(config, 4, 9).choose_move(&round, "b");
+
+        assert_eq!(choose(), vec![
+            PlayedCard::new(Rank::Nine, Suit::Clubs, false),
+            PlayedCard::new(Rank::Nine, Suit::Spades, false),
+        ]);
+        assert_eq!(choose(), choose());
+    }
+
+    #[test]
+    fn searches_can_be_resumed_until_they_finish() {
+        let config = MctsConfig { iterations: 40, ..MctsConfig::default() };
+        let mut search = MctsSearch::new(&get_round(), "b", config, 1);
+        let mut calls = 0;
+
+        // a clock that jumps a millisecond every time it's read
+        let clock = std::cell::Cell::new(0.0);
+        let now = || {
+            clock.set(clock.get() + 1.0);
+            clock.get()
+        };
+
+        let best = loop {
+            calls += 1;
+            if let Poll::Ready(best) = search.think_with_clock(10.0, now) {
+                break best;
+            }
+        };
+
+        assert!(calls > 1);
+        assert_eq!(search.get_iterations(), 40);
+        assert_eq!(best, vec![
+            PlayedCard::new(Rank::Nine, Suit::Clubs, false),
+            PlayedCard::new(Rank::Nine, Suit::Spades, false),
+        ]);
+    }
+
+    #[test]
+    fn determinizing_keeps_own_hand_and_card_counts() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("4C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("9C 9S").unwrap()))
+            .player(Player::new("c".to_string(), parse_cards("3C 10S JS").unwrap()))
+            .next_player("a")
+            .last_move(
+                Hand::Single(PlayedCard::new(Rank::Three, Suit::Hearts, false)),
+                "c"
+            )
+            .build()
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+
+        let sampled = determinize(&round, "a", &mut rng);
+
+        assert_eq!(
+            sampled.get_player("a").unwrap().get_hand(),
+            round.get_player("a").unwrap().get_hand()
+        );
+        assert_eq!(sampled.get_player("b").unwrap().get_card_count(), 2);
+        assert_eq!(sampled.get_player("c").unwrap().get_card_count(), 3);
+        assert_eq!(sampled.get_next_player(), Some("a".to_string()));
+        assert_eq!(sampled.get_last_player(), Some("c".to_string()));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::cards::PlayedCard;
+use crate::game::{GameRecord, Hand, Round};
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+/// What everyone at the table has seen of one player's play.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpponentKnowledge {
+    /// Every card they've played, with jokers as the card they stood for
+    pub played: Vec<PlayedCard>,
+    /// The cards their jokers stood for, so the jokers they've given up
+    pub jokers_shown: Vec<PlayedCard>,
+    /// Hands on the table they passed on instead of beating
+    pub declined: Vec<Hand>,
+}
+
+/// Public knowledge about every player, kept alongside a `Round` and fed
+/// each move as it's made. Agents and coaching overlays can read it here
+/// instead of working it out again from the history.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OpponentModel {
+    players: BTreeMap<String, OpponentKnowledge>,
+}
+
+impl OpponentModel {
+    pub fn new(round: &Round) -> OpponentModel {
+        OpponentModel {
+            players: round.get_player_ids().into_iter()
+                .map(|id| (id, OpponentKnowledge::default()))
+                .collect(),
+        }
+    }
+
+    /// Replays a recorded game, observing every move.
+    pub fn from_record(record: &GameRecord) -> OpponentModel {
+        let mut model = OpponentModel::new(&record.initial_round);
+        let mut round = record.initial_round.clone();
+
+        for recorded in record.moves.iter() {
+            model.observe(&round, &recorded.player_id, &recorded.cards);
+            if round.submit_move_mut(
+                &recorded.player_id, recorded.cards.clone()
+            ).is_err() {
+                break;
+            }
+        }
+
+        model
+    }
+
+    /// Takes note of a move, given the round as it stood before it.
+    pub fn observe(&mut self, round: &Round, player_id: &str, cards: &[PlayedCard]) {
+        let knowledge = self.players.entry(player_id.to_string()).or_default();
+
+        if cards.is_empty() {
+            if let Some(facing) = round.get_last_move().filter(|&h| h != Hand::Pass) {
+                knowledge.declined.push(facing);
+            }
+            return;
+        }
+
+        knowledge.played.extend_from_slice(cards);
+        knowledge.jokers_shown.extend(cards.iter().filter(|c| c.get_is_joker()));
+    }
+
+    pub fn get_knowledge(&self, player_id: &str) -> Option<&OpponentKnowledge> {
+        self.players.get(player_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_played_cards, Scenario};
+
+    #[test]
+    fn jokers_and_declined_hands_are_remembered() {
+        let round = Scenario::new()
+            .player("a", "3C JK")
+            .player("b", "4C 5C")
+            .next_player("a")
+            .lead("b")
+            .build()
+            .unwrap();
+        let mut model = OpponentModel::new(&round);
+
+        let joker = parse_played_cards("JK:2S").unwrap();
+        model.observe(&round, "a", &joker);
+        let round = round.submit_move("a", joker.clone()).unwrap();
+        model.observe(&round, "b", &[]);
+
+        let a = model.get_knowledge("a").unwrap();
+        assert_eq!(a.played, joker);
+        assert_eq!(a.jokers_shown, joker);
+
+        let b = model.get_knowledge("b").unwrap();
+        assert!(b.played.is_empty());
+        assert_eq!(b.declined, vec![Hand::build(joker).unwrap()]);
+        assert_eq!(model.get_knowledge("z"), None);
+    }
+}
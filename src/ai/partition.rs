@@ -0,0 +1,208 @@
+use super::get_candidate_moves;
+use crate::cards::{Card, PlayedCard};
+use crate::game::{sort_played_cards, Hand, OrderContext, Ruleset};
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+
+/// What a hand plan optimises for. Ties on the goal are broken by the
+/// other measure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PartitionGoal {
+    /// Go out in as few plays as possible
+    #[default]
+    FewestPlays,
+    /// Keep as many four of a kinds and straight flushes as possible
+    KeepBombs,
+}
+
+/// A way of playing out a whole hand, with the plays that hold the lowest
+/// cards first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HandPlan {
+    pub plays: Vec<Hand>,
+}
+
+impl HandPlan {
+    /// Plays that are four of a kinds or better.
+    pub fn count_bombs(&self) -> usize {
+        self.plays.iter().filter(|hand| hand.is_bomb()).count()
+    }
+}
+
+/// Splits `hand` into the set of plays that best meets `goal`. The search
+/// is exact: every partition of the natural cards into singles, pairs,
+/// prials and five-card tricks `ruleset` allows is considered. Jokers are
+/// kept back as the highest possible singles.
+pub fn plan_partition(
+    hand: &[Card],
+    goal: PartitionGoal,
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> HandPlan {
+    let natural_hand: Vec<Card> = hand.iter()
+        .filter(|c| c.get_rank().is_some())
+        .cloned()
+        .collect();
+    let candidates: Vec<Hand> = get_candidate_moves(
+        &natural_hand,
+        ruleset,
+        orders,
+    )
+        .into_iter()
+        .filter_map(|cards| Hand::try_build_in_order(cards, ruleset, orders).ok())
+        .collect();
+
+    let cards: Vec<PlayedCard> = natural_hand.iter()
+        .map(|c| PlayedCard::new(c.get_rank().unwrap(), c.get_suit().unwrap(), false))
+        .collect();
+    let mut cards = sort_played_cards(&cards, orders);
+    cards.reverse();
+
+    let mut planner = Planner {
+        cards,
+        candidates,
+        goal,
+        memo: BTreeMap::new(),
+    };
+    let full = (1u64 << planner.cards.len()) - 1;
+    let mut plays = planner.plan(full).1;
+
+    let jokers = hand.len() - natural_hand.len();
+    for _ in 0..jokers {
+        plays.push(Hand::Single(PlayedCard::new(orders.highest_rank(), orders.highest_suit(), true)));
+    }
+
+    HandPlan { plays }
+}
+
+/// How many plays a plan takes and how many of them are bombs.
+type Tally = (usize, usize);
+
+struct Planner {
+    cards: Vec<PlayedCard>,
+    candidates: Vec<Hand>,
+    goal: PartitionGoal,
+    memo: BTreeMap<u64, (Tally, Vec<Hand>)>,
+}
+
+impl Planner {
+    fn plan(&mut self, remaining: u64) -> (Tally, Vec<Hand>) {
+        if remaining == 0 {
+            return ((0, 0), vec![]);
+        }
+        if let Some(planned) = self.memo.get(&remaining) {
+            return planned.clone();
+        }
+
+        // the lowest card left has to go in some play, so only plays
+        // holding it need trying
+        let lowest = self.cards[remaining.trailing_zeros() as usize];
+        let mut best: Option<(Tally, Vec<Hand>)> = None;
+
+        for i in 0..self.candidates.len() {
+            let candidate = self.candidates[i];
+            let cards = candidate.to_cards();
+            if !cards.contains(&lowest) {
+                continue;
+            }
+            let used = match self.take(remaining, &cards) {
+                Some(used) => used,
+                None => continue,
+            };
+
+            let ((plays, bombs), rest) = self.plan(remaining & !used);
+            let tally = (plays + 1, bombs + candidate.is_bomb() as usize);
+            let improves = best.as_ref()
+                .map(|&(best_tally, _)| self.is_better(tally, best_tally))
+                .unwrap_or(true);
+            if improves {
+                let mut plays = vec![candidate];
+                plays.extend(rest);
+                best = Some((tally, plays));
+            }
+        }
+
+        let planned = best.expect("a single can always be played");
+        self.memo.insert(remaining, planned.clone());
+        planned
+    }
+
+    /// The cards from `remaining` that make up `cards`, if they're all
+    /// there.
+    fn take(&self, remaining: u64, cards: &[PlayedCard]) -> Option<u64> {
+        let mut used = 0u64;
+        for card in cards {
+            let index = (0..self.cards.len()).find(|&i| {
+                let bit = 1u64 << i;
+                remaining & bit != 0 && used & bit == 0 && self.cards[i] == *card
+            })?;
+            used |= 1 << index;
+        }
+
+        Some(used)
+    }
+
+    fn is_better(&self, (plays, bombs): Tally, (best_plays, best_bombs): Tally) -> bool {
+        match self.goal {
+            PartitionGoal::FewestPlays => {
+                plays < best_plays || (plays == best_plays && bombs > best_bombs)
+            },
+            PartitionGoal::KeepBombs => {
+                bombs > best_bombs || (bombs == best_bombs && plays < best_plays)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::TrickType;
+    use crate::scenario::parse_cards;
+
+    fn plan(hand: &[Card], goal: PartitionGoal) -> HandPlan {
+        plan_partition(hand, goal, Ruleset::default(), &OrderContext::standard())
+    }
+
+    #[test]
+    fn a_hand_is_split_into_as_few_plays_as_possible() {
+        let hand = parse_cards("3C 4H 5C 6S 7C 9C 9S JK").unwrap();
+
+        let plan = plan(&hand, PartitionGoal::FewestPlays);
+
+        assert_eq!(plan.plays.len(), 3);
+        assert_eq!(plan.plays[0].get_trick_type(), Some(TrickType::Straight));
+        assert!(matches!(plan.plays[1], Hand::Pair(..)));
+        assert!(matches!(plan.plays[2], Hand::Single(card) if card.get_is_joker()));
+    }
+
+    #[test]
+    fn bombs_can_be_kept_at_the_cost_of_more_plays() {
+        let hand = parse_cards("8C 8H 8D 8S 4C 5H 6D 7S 9C").unwrap();
+
+        let fewest = plan(&hand, PartitionGoal::FewestPlays);
+        assert_eq!(fewest.plays.len(), 3);
+        assert_eq!(fewest.count_bombs(), 0);
+
+        let bombs = plan(&hand, PartitionGoal::KeepBombs);
+        assert_eq!(bombs.count_bombs(), 1);
+        assert_eq!(bombs.plays.len(), 5);
+    }
+
+    #[test]
+    fn plans_only_use_tricks_the_ruleset_allows() {
+        let hand = parse_cards("JC QH KD AS 2C").unwrap();
+        let ruleset = Ruleset { twos_in_straights: false, ..Ruleset::default() };
+
+        assert_eq!(plan(&hand, PartitionGoal::FewestPlays).plays.len(), 1);
+
+        let plan = plan_partition(
+            &hand,
+            PartitionGoal::FewestPlays,
+            ruleset,
+            &OrderContext::standard(),
+        );
+        assert_eq!(plan.plays.len(), 5);
+    }
+}
@@ -0,0 +1,155 @@
+use crate::cards::Card;
+use crate::game::Round;
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// What one seat knows about the hands it can't see: which cards are
+/// unaccounted for, how many each opponent holds and any cards an opponent
+/// is known to hold. Simulation agents sample complete deals from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HiddenHands {
+    viewer: String,
+    /// Unseen cards that haven't been revealed
+    unseen: Vec<Card>,
+    /// Opponents still holding cards, in seating order
+    card_counts: Vec<(String, usize)>,
+    revealed: BTreeMap<String, Vec<Card>>,
+}
+
+impl HiddenHands {
+    /// Everything in an opponent's hand starts out unseen.
+    pub fn from_round(round: &Round, viewer: &str) -> HiddenHands {
+        let opponents: Vec<_> = round.get_players().into_iter()
+            .filter(|p| p.get_id() != viewer && p.get_card_count() > 0)
+            .collect();
+        let mut unseen: Vec<Card> = opponents.iter()
+            .flat_map(|p| p.get_hand())
+            .collect();
+        unseen.sort();
+
+        HiddenHands {
+            viewer: viewer.to_string(),
+            unseen,
+            card_counts: opponents.iter()
+                .map(|p| (p.get_id().to_string(), p.get_card_count()))
+                .collect(),
+            revealed: BTreeMap::new(),
+        }
+    }
+
+    pub fn get_viewer(&self) -> String {
+        self.viewer.clone()
+    }
+
+    /// Notes that `player_id` is known to hold `card`, for instance after
+    /// it was shown during a swap. Returns false if the card isn't unseen
+    /// or they can't be holding any more known cards.
+    pub fn reveal(&mut self, player_id: &str, card: Card) -> bool {
+        let count = match self.card_counts.iter().find(|(id, _)| id == player_id) {
+            Some(&(_, count)) => count,
+            None => return false,
+        };
+        let known = self.revealed.get(player_id).map(Vec::len).unwrap_or(0);
+        let position = self.unseen.iter().position(|&c| c == card);
+
+        match position {
+            Some(i) if known < count => {
+                self.unseen.remove(i);
+                self.revealed.entry(player_id.to_string()).or_default().push(card);
+                true
+            },
+            _ => false,
+        }
+    }
+
+    /// Every card the opponents hold between them, known or not.
+    pub(crate) fn get_pool(&self) -> Vec<Card> {
+        let mut pool = self.unseen.clone();
+        pool.extend(self.revealed.values().flatten());
+        pool
+    }
+
+    /// Deals the unseen cards at random, keeping every opponent's card
+    /// count and the cards they're known to hold.
+    pub fn sample_hands<R: Rng>(&self, rng: &mut R) -> BTreeMap<String, Vec<Card>> {
+        let mut pool = self.unseen.clone();
+        pool.shuffle(rng);
+
+        self.card_counts.iter()
+            .map(|(id, count)| {
+                let mut hand = self.revealed.get(id).cloned().unwrap_or_default();
+                let drawn = count - hand.len();
+                hand.extend(pool.split_off(pool.len() - drawn));
+                (id.clone(), hand)
+            })
+            .collect()
+    }
+
+    /// A copy of `round` with the opponents' hands swapped for a sample.
+    pub fn sample_round<R: Rng>(&self, round: &Round, rng: &mut R) -> Round {
+        let mut sampled = round.clone();
+        for (id, hand) in self.sample_hands(rng) {
+            sampled.set_hand(&id, hand);
+        }
+
+        sampled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Player, RoundBuilder};
+    use crate::scenario::parse_cards;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn get_round() -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("4C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("9C 9S").unwrap()))
+            .player(Player::new("c".to_string(), parse_cards("3C 10S JS").unwrap()))
+            .next_player("a")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn samples_keep_card_counts_and_revealed_cards() {
+        let round = get_round();
+        let mut hidden = HiddenHands::from_round(&round, "a");
+        let jack = "JS".parse::<Card>().unwrap();
+
+        assert!(hidden.reveal("b", jack));
+        assert!(!hidden.reveal("c", jack));
+        assert!(!hidden.reveal("a", "4C".parse::<Card>().unwrap()));
+
+        let mut rng = StdRng::seed_from_u64(5);
+        for _ in 0..10 {
+            let sampled = hidden.sample_round(&round, &mut rng);
+
+            assert_eq!(
+                sampled.get_player("a").unwrap().get_hand(),
+                round.get_player("a").unwrap().get_hand()
+            );
+            assert!(sampled.get_player("b").unwrap().has_card(jack));
+            assert_eq!(sampled.get_player("b").unwrap().get_card_count(), 2);
+            assert_eq!(sampled.get_player("c").unwrap().get_card_count(), 3);
+            assert_eq!(sampled.verify_card_conservation(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn fully_revealed_hands_are_fixed() {
+        let mut hidden = HiddenHands::from_round(&get_round(), "a");
+        hidden.reveal("b", "9C".parse::<Card>().unwrap());
+        hidden.reveal("b", "9S".parse::<Card>().unwrap());
+
+        assert!(!hidden.reveal("b", "3C".parse::<Card>().unwrap()));
+
+        let hands = hidden.sample_hands(&mut StdRng::seed_from_u64(1));
+        assert_eq!(hands["b"], parse_cards("9C 9S").unwrap());
+    }
+}
@@ -0,0 +1,442 @@
+use super::{choose_audited_move, Agent, DecisionAudit};
+use crate::cards::{get_suit_array, Deck, PlayedCard};
+use crate::game::{
+    Game,
+    GameResult,
+    Hand,
+    PlayerResult,
+    Round,
+    Ruleset,
+    SetupError,
+    SubmitError,
+    Trick,
+    TrickType,
+};
+use crate::prelude::*;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use alloc::collections::BTreeMap;
+#[cfg(feature = "std")]
+use std::io;
+
+#[derive(Debug, Clone)]
+pub struct SelfPlayConfig {
+    pub games: usize,
+    pub num_decks: u8,
+    pub num_jokers: u8,
+    /// Games take turns using each of these, starting from the first
+    pub rulesets: Vec<Ruleset>,
+    /// Every deal is drawn from this, so the same seed and agents give
+    /// the same games
+    pub seed: u64,
+}
+
+impl Default for SelfPlayConfig {
+    fn default() -> SelfPlayConfig {
+        SelfPlayConfig {
+            games: 1,
+            num_decks: 1,
+            num_jokers: 0,
+            rulesets: vec![Ruleset::default()],
+            seed: 0,
+        }
+    }
+}
+
+/// The position a move was chosen in and the cards chosen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfPlayMove {
+    pub round: Round,
+    pub player_id: String,
+    pub cards: Vec<PlayedCard>,
+    #[serde(default)]
+    pub audit: DecisionAudit,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelfPlayGame {
+    /// The seed the deck was shuffled with
+    pub seed: u64,
+    pub ruleset: Ruleset,
+    pub moves: Vec<SelfPlayMove>,
+    pub result: GameResult,
+}
+
+impl SelfPlayGame {
+    /// A sample for every move, labelled with how the player who made it
+    /// finished.
+    pub fn get_samples(&self) -> Vec<TrainingSample> {
+        self.moves.iter()
+            .map(|played| TrainingSample {
+                state: played.round.clone(),
+                player_id: played.player_id.clone(),
+                action: played.cards.clone(),
+                outcome: self.result.players.iter()
+                    .find(|p| p.player_id == played.player_id)
+                    .cloned()
+                    .expect("every player who moved has a result"),
+            })
+            .collect()
+    }
+}
+
+/// A state, the action taken from it and the outcome for the player who
+/// took it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingSample {
+    pub state: Round,
+    pub player_id: String,
+    pub action: Vec<PlayedCard>,
+    pub outcome: PlayerResult,
+}
+
+/// Writes a `TrainingSample` per move as newline-delimited JSON, one game
+/// at a time, so games can be written out as they're played.
+#[cfg(feature = "std")]
+pub fn write_ndjson<'a, W, I>(writer: &mut W, games: I) -> io::Result<()>
+where
+    W: io::Write,
+    I: IntoIterator<Item = &'a SelfPlayGame>,
+{
+    for game in games {
+        for sample in game.get_samples() {
+            serde_json::to_writer(&mut *writer, &sample)?;
+            writer.write_all(b"\n")?;
+        }
+    }
+
+    Ok(())
+}
+
+/// The shape of a hand, without its cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayKind {
+    Single,
+    Pair,
+    Prial,
+    FiveCardTrick,
+}
+
+impl PlayKind {
+    /// `None` for a pass.
+    pub fn from_hand(hand: Hand) -> Option<PlayKind> {
+        match hand {
+            Hand::Pass => None,
+            Hand::Single(_) => Some(PlayKind::Single),
+            Hand::Pair(..) => Some(PlayKind::Pair),
+            Hand::Prial(..) => Some(PlayKind::Prial),
+            Hand::FiveCardTrick(_) => Some(PlayKind::FiveCardTrick),
+        }
+    }
+}
+
+/// How one seat played across a batch of self-play games.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SeatReport {
+    pub player_id: String,
+    /// The agent's name, as given by `Agent::get_name`
+    pub agent: String,
+    /// Hands played onto a clear table, by kind
+    pub leads: BTreeMap<PlayKind, usize>,
+    /// Every hand played, leads included, by kind
+    pub plays: BTreeMap<PlayKind, usize>,
+    pub five_card_tricks: BTreeMap<TrickType, usize>,
+    pub passes: usize,
+    pub reversals_triggered: usize,
+}
+
+impl SeatReport {
+    /// The fraction of this seat's leads that were `kind`, from 0 to 1.
+    pub fn get_lead_share(&self, kind: PlayKind) -> f64 {
+        let leads: usize = self.leads.values().sum();
+        if leads == 0 {
+            return 0.0;
+        }
+        self.leads.get(&kind).copied().unwrap_or(0) as f64 / leads as f64
+    }
+}
+
+/// How a batch of self-play games went, for seeing how a rule change
+/// shifts the way agents play.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StrategyReport {
+    pub games: usize,
+    pub tricks: usize,
+    /// Hands played, passes aside, across every trick
+    pub hands_played: usize,
+    pub reversals: usize,
+    /// In seating order
+    pub seats: Vec<SeatReport>,
+}
+
+impl StrategyReport {
+    pub fn from_games(games: &[SelfPlayGame]) -> StrategyReport {
+        let mut report = StrategyReport { games: games.len(), ..StrategyReport::default() };
+
+        for game in games {
+            for (index, played) in game.moves.iter().enumerate() {
+                // moves that don't follow from the round, as in a file
+                // that's been edited by hand, are left out of the report
+                let hand = match played.round.build_hand(&played.cards) {
+                    Some(hand) => hand,
+                    None => continue,
+                };
+                let after = match game.moves.get(index + 1) {
+                    Some(next) => next.round.clone(),
+                    None => match played.round.submit_move(&played.player_id, played.cards.clone()) {
+                        Ok(after) => after,
+                        Err(_) => continue,
+                    },
+                };
+
+                let seat = report.get_seat(played);
+                let kind = match PlayKind::from_hand(hand) {
+                    Some(kind) => kind,
+                    None => {
+                        seat.passes += 1;
+                        continue;
+                    },
+                };
+
+                let leading = matches!(played.round.get_last_move(), None | Some(Hand::Pass));
+                if leading {
+                    *seat.leads.entry(kind).or_insert(0) += 1;
+                }
+                *seat.plays.entry(kind).or_insert(0) += 1;
+                if let Hand::FiveCardTrick(Trick { trick_type, .. }) = hand {
+                    *seat.five_card_tricks.entry(trick_type).or_insert(0) += 1;
+                }
+
+                let reversed = after.get_suit_order() != played.round.get_suit_order();
+                if reversed {
+                    seat.reversals_triggered += 1;
+                    report.reversals += 1;
+                }
+                if leading {
+                    report.tricks += 1;
+                }
+                report.hands_played += 1;
+            }
+        }
+
+        if let Some(first) = games.iter().flat_map(|game| game.moves.first()).next() {
+            let seated = first.round.get_player_ids();
+            report.seats.sort_by_key(|seat| seated.iter().position(|id| *id == seat.player_id));
+        }
+
+        report
+    }
+
+    /// Hands played per trick, leads included.
+    pub fn average_trick_length(&self) -> f64 {
+        if self.tricks == 0 {
+            return 0.0;
+        }
+        self.hands_played as f64 / self.tricks as f64
+    }
+
+    fn get_seat(&mut self, played: &SelfPlayMove) -> &mut SeatReport {
+        let index = match self.seats.iter().position(|s| s.player_id == played.player_id) {
+            Some(index) => index,
+            None => {
+                self.seats.push(SeatReport {
+                    player_id: played.player_id.clone(),
+                    agent: played.audit.agent.clone(),
+                    ..SeatReport::default()
+                });
+                self.seats.len() - 1
+            },
+        };
+
+        &mut self.seats[index]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelfPlayError {
+    NoRulesets,
+    Setup(SetupError),
+    /// An agent chose a move the game wouldn't accept
+    IllegalMove {
+        game: usize,
+        player_id: String,
+        error: SubmitError,
+    },
+}
+
+impl From<SetupError> for SelfPlayError {
+    fn from(err: SetupError) -> SelfPlayError {
+        SelfPlayError::Setup(err)
+    }
+}
+
+/// Plays `config.games` games between `agents`, recording every position
+/// a move was made from along with how each game ended. The agents are
+/// seated as `"p1"`, `"p2"` and so on, in order, and keep their state
+/// from one game to the next.
+pub fn run_selfplay(
+    config: &SelfPlayConfig,
+    agents: &mut [Box<dyn Agent>],
+) -> Result<Vec<SelfPlayGame>, SelfPlayError> {
+    if config.rulesets.is_empty() {
+        return Err(SelfPlayError::NoRulesets);
+    }
+
+    let player_ids: Vec<String> = (1..=agents.len())
+        .map(|seat| format!("p{}", seat))
+        .collect();
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut games = vec![];
+
+    for index in 0..config.games {
+        let seed: u64 = rng.gen();
+        let ruleset = config.rulesets[index % config.rulesets.len()];
+
+        let mut cards = Deck::new(config.num_decks, config.num_jokers).to_vec();
+        cards.shuffle(&mut StdRng::seed_from_u64(seed));
+        let deck = Deck::from_cards(cards);
+
+        let mut game = Game::from_deck(
+            &deck,
+            config.num_decks,
+            config.num_jokers,
+            &player_ids,
+            &get_suit_array(),
+            ruleset,
+        )?;
+        let mut moves = vec![];
+
+        while let Some(player_id) = game.get_next_player() {
+            let seat = player_ids.iter()
+                .position(|id| *id == player_id)
+                .expect("only seated players are asked to move");
+            let round = game.get_round().clone();
+            let (cards, audit) = choose_audited_move(agents[seat].as_mut(), &round, &player_id);
+
+            game.play_move(&player_id, cards.clone())
+                .map_err(|error| SelfPlayError::IllegalMove {
+                    game: index,
+                    player_id: player_id.clone(),
+                    error,
+                })?;
+            moves.push(SelfPlayMove { round, player_id, cards, audit });
+        }
+
+        games.push(SelfPlayGame {
+            seed,
+            ruleset,
+            moves,
+            result: game.get_result(),
+        });
+    }
+
+    Ok(games)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{LowestCardAgent, RandomAgent};
+
+    fn get_agents() -> Vec<Box<dyn Agent>> {
+        vec![
+            Box::new(RandomAgent::new(1)),
+            Box::new(LowestCardAgent),
+            Box::new(RandomAgent::new(2)),
+        ]
+    }
+
+    #[test]
+    fn every_game_is_played_to_the_end() {
+        let config = SelfPlayConfig { games: 3, ..SelfPlayConfig::default() };
+
+        let games = run_selfplay(&config, &mut get_agents()).unwrap();
+
+        assert_eq!(games.len(), 3);
+        for game in games.iter() {
+            let dealt: usize = game.moves[0].round.get_players().iter()
+                .map(|p| p.get_card_count())
+                .sum();
+            assert_eq!(dealt, 52);
+            assert_eq!(game.result.players.len(), 3);
+            assert!(game.result.players.iter().any(|p| p.finishing_position == Some(1)));
+        }
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_games() {
+        let config = SelfPlayConfig { games: 2, seed: 9, ..SelfPlayConfig::default() };
+        let played = |config: &SelfPlayConfig| -> Vec<Vec<Vec<PlayedCard>>> {
+            run_selfplay(config, &mut get_agents()).unwrap()
+                .into_iter()
+                .map(|game| game.moves.into_iter().map(|m| m.cards).collect())
+                .collect()
+        };
+
+        assert_eq!(played(&config), played(&config));
+        assert_ne!(played(&config), played(&SelfPlayConfig { seed: 10, ..config.clone() }));
+    }
+
+    #[test]
+    fn reports_count_how_each_seat_led() {
+        let config = SelfPlayConfig { games: 4, seed: 3, ..SelfPlayConfig::default() };
+        let games = run_selfplay(&config, &mut get_agents()).unwrap();
+        let report = StrategyReport::from_games(&games);
+
+        assert_eq!(report.games, 4);
+        assert_eq!(
+            report.seats.iter().map(|s| s.agent.as_str()).collect::<Vec<_>>(),
+            vec!["random", "lowest_card", "random"]
+        );
+
+        let moves: usize = games.iter().map(|g| g.moves.len()).sum();
+        let passes: usize = report.seats.iter().map(|s| s.passes).sum();
+        assert_eq!(report.hands_played + passes, moves);
+
+        let leads: usize = report.seats.iter().flat_map(|s| s.leads.values()).sum();
+        assert_eq!(leads, report.tricks);
+        assert!(report.average_trick_length() >= 1.0);
+
+        // the lowest card agent only leads with its lowest card
+        let lowest = &report.seats[1];
+        assert_eq!(lowest.get_lead_share(PlayKind::Single), 1.0);
+        assert_eq!(lowest.get_lead_share(PlayKind::FiveCardTrick), 0.0);
+    }
+
+    #[test]
+    fn reports_leave_out_moves_that_were_tampered_with() {
+        let config = SelfPlayConfig { games: 2, seed: 3, ..SelfPlayConfig::default() };
+        let mut games = run_selfplay(&config, &mut get_agents()).unwrap();
+        let report = StrategyReport::from_games(&games);
+
+        let last = games[0].moves.last_mut().unwrap();
+        last.player_id = "nobody".to_string();
+        let first = &mut games[1].moves[0];
+        first.cards = vec![first.cards[0]; 4];
+        let tampered = StrategyReport::from_games(&games);
+
+        assert_eq!(tampered.hands_played, report.hands_played - 2);
+        assert!(tampered.seats.iter().all(|seat| seat.player_id != "nobody"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn samples_are_written_a_line_each() {
+        let config = SelfPlayConfig { games: 2, ..SelfPlayConfig::default() };
+        let games = run_selfplay(&config, &mut get_agents()).unwrap();
+        let mut output = vec![];
+
+        write_ndjson(&mut output, &games).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), games[0].moves.len() + games[1].moves.len());
+
+        let sample: TrainingSample = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(sample.player_id, games[0].moves[0].player_id);
+        assert_eq!(sample.outcome.player_id, sample.player_id);
+    }
+}
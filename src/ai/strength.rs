@@ -0,0 +1,86 @@
+use super::CardTracker;
+use crate::cards::{Card, PlayedCard};
+use crate::game::{enumerate_tricks, Hand, OrderContext, Round, Ruleset};
+use crate::prelude::*;
+
+/// The share of hands with the same number of cards as `hand` that could
+/// still be made from `unseen` and that `hand` beats, from 0 to 1. It's 1
+/// when no such hand can be made. Every five card trick is listed to
+/// work this out, so it slows down with lots of unseen cards and jokers.
+pub fn get_hand_percentile(
+    hand: Hand,
+    unseen: &[Card],
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> f64 {
+    let size = hand.get_card_count();
+    let others: Vec<Hand> = match size {
+        0 => vec![],
+        1 => unseen.iter()
+            .map(|card| match *card {
+                Card::Standard { rank, suit, .. } => PlayedCard::new(rank, suit, false),
+                Card::Joker { .. } => PlayedCard::new(orders.highest_rank(), orders.highest_suit(), true),
+            })
+            .map(Hand::Single)
+            .collect(),
+        _ => enumerate_tricks(unseen, size < 5, ruleset).into_iter()
+            .filter(|other| other.get_card_count() == size)
+            .collect(),
+    };
+
+    if others.is_empty() {
+        return 1.0;
+    }
+
+    let beaten = others.iter()
+        .filter(|other| hand.beats(other, ruleset, orders))
+        .count();
+
+    beaten as f64 / others.len() as f64
+}
+
+/// `get_hand_percentile` against the cards `viewer` hasn't seen, under the
+/// round's rules and orders.
+pub fn get_hand_strength(round: &Round, viewer: &str, hand: Hand) -> f64 {
+    get_hand_percentile(
+        hand,
+        &CardTracker::from_round(round, viewer).get_unseen(),
+        round.get_ruleset(),
+        &round.get_order_context(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_cards, parse_played_cards, Scenario};
+
+    fn percentile(hand: &str, unseen: &str) -> f64 {
+        get_hand_percentile(
+            Hand::build(parse_played_cards(hand).unwrap()).unwrap(),
+            &parse_cards(unseen).unwrap(),
+            Ruleset::default(),
+            &OrderContext::standard(),
+        )
+    }
+
+    #[test]
+    fn hands_are_compared_with_others_of_the_same_size() {
+        assert_eq!(percentile("9C", "3C 4C 10C 2S"), 0.5);
+        assert_eq!(percentile("9C 9H", "3C 3H 10C 10H 10S"), 0.25);
+        assert_eq!(percentile("9C 9H", "3C 4H"), 1.0);
+        assert_eq!(percentile("3C 4C 5C 6C 7C", "8H 9H 10H JH QH KD"), 0.5);
+    }
+
+    #[test]
+    fn strength_uses_what_the_viewer_cant_see() {
+        let round = Scenario::new()
+            .player("a", "9C AC")
+            .player("b", "4C 2S")
+            .build()
+            .unwrap();
+        let hand = Hand::build(parse_played_cards("AC").unwrap()).unwrap();
+
+        assert_eq!(get_hand_strength(&round, "a", hand), 0.5);
+    }
+}
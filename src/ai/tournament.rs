@@ -0,0 +1,303 @@
+use super::{run_selfplay, Agent, SelfPlayConfig, SelfPlayError};
+use crate::game::Ruleset;
+use crate::prelude::*;
+use alloc::collections::BTreeSet;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// z for a 95% confidence interval
+const Z_95: f64 = 1.96;
+
+/// An agent entered into a tournament. A fresh agent is made for every
+/// match, so nothing an agent learns carries over between opponents.
+pub struct TournamentEntrant {
+    pub name: String,
+    pub make_agent: Box<dyn Fn() -> Box<dyn Agent>>,
+}
+
+impl TournamentEntrant {
+    pub fn new<F>(name: &str, make_agent: F) -> TournamentEntrant
+    where
+        F: Fn() -> Box<dyn Agent> + 'static,
+    {
+        TournamentEntrant {
+            name: name.to_string(),
+            make_agent: Box::new(make_agent),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TournamentFormat {
+    /// Every entrant plays every other entrant once
+    RoundRobin,
+    /// Entrants with similar numbers of wins are paired, preferring
+    /// opponents they haven't played yet. With an odd number of entrants
+    /// the lowest placed entrant sits each round out.
+    Swiss { rounds: usize },
+}
+
+#[derive(Debug, Clone)]
+pub struct TournamentConfig {
+    pub format: TournamentFormat,
+    /// Deals per match. Each deal is played twice, with the entrants
+    /// swapping seats, so luck of the deal evens out.
+    pub deals_per_match: usize,
+    pub num_decks: u8,
+    pub num_jokers: u8,
+    pub ruleset: Ruleset,
+    /// Every match's deals are drawn from this, so the same seed and
+    /// entrants give the same results
+    pub seed: u64,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> TournamentConfig {
+        TournamentConfig {
+            format: TournamentFormat::RoundRobin,
+            deals_per_match: 10,
+            num_decks: 1,
+            num_jokers: 0,
+            ruleset: Ruleset::default(),
+            seed: 0,
+        }
+    }
+}
+
+/// How a match between two entrants went. Entrants are indexes into the
+/// list the tournament was run with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchResult {
+    pub first: usize,
+    pub second: usize,
+    pub first_wins: usize,
+    pub second_wins: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentStanding {
+    pub name: String,
+    pub games: usize,
+    pub wins: usize,
+    pub win_rate: f64,
+    /// The 95% Wilson score interval for the win rate
+    pub confidence_interval: (f64, f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TournamentResult {
+    /// Entrants from the highest win rate to the lowest
+    pub standings: Vec<TournamentStanding>,
+    pub matches: Vec<MatchResult>,
+}
+
+/// Plays `entrants` against each other in two player games and reports
+/// each entrant's win rate.
+pub fn run_tournament(
+    config: &TournamentConfig,
+    entrants: &[TournamentEntrant],
+) -> Result<TournamentResult, SelfPlayError> {
+    let mut rng = StdRng::seed_from_u64(config.seed);
+    let mut matches = vec![];
+
+    match config.format {
+        TournamentFormat::RoundRobin => {
+            for first in 0..entrants.len() {
+                for second in first + 1..entrants.len() {
+                    let seed = rng.gen();
+                    matches.push(play_match(config, entrants, first, second, seed)?);
+                }
+            }
+        },
+        TournamentFormat::Swiss { rounds } => {
+            for _ in 0..rounds {
+                for (first, second) in get_swiss_pairings(entrants.len(), &matches) {
+                    let seed = rng.gen();
+                    matches.push(play_match(config, entrants, first, second, seed)?);
+                }
+            }
+        },
+    }
+
+    let mut standings: Vec<TournamentStanding> = entrants.iter()
+        .enumerate()
+        .map(|(index, entrant)| {
+            let (wins, games) = get_record(index, &matches);
+            let win_rate = if games == 0 { 0.0 } else { wins as f64 / games as f64 };
+
+            TournamentStanding {
+                name: entrant.name.clone(),
+                games,
+                wins,
+                win_rate,
+                confidence_interval: get_wilson_interval(wins, games),
+            }
+        })
+        .collect();
+    standings.sort_by(|a, b| b.win_rate.total_cmp(&a.win_rate));
+
+    Ok(TournamentResult { standings, matches })
+}
+
+fn play_match(
+    config: &TournamentConfig,
+    entrants: &[TournamentEntrant],
+    first: usize,
+    second: usize,
+    seed: u64,
+) -> Result<MatchResult, SelfPlayError> {
+    let selfplay = SelfPlayConfig {
+        games: config.deals_per_match,
+        num_decks: config.num_decks,
+        num_jokers: config.num_jokers,
+        rulesets: vec![config.ruleset],
+        seed,
+    };
+    let mut result = MatchResult { first, second, first_wins: 0, second_wins: 0 };
+
+    for swapped in [false, true] {
+        let mut agents = vec![
+            (entrants[first].make_agent)(),
+            (entrants[second].make_agent)(),
+        ];
+        if swapped {
+            agents.reverse();
+        }
+
+        for game in run_selfplay(&selfplay, &mut agents)? {
+            let winner = game.result.players.iter()
+                .find(|p| p.finishing_position == Some(1))
+                .map(|p| p.player_id.as_str());
+            // seats are "p1" then "p2"
+            match (winner, swapped) {
+                (Some("p1"), false) | (Some("p2"), true) => result.first_wins += 1,
+                (Some(_), _) => result.second_wins += 1,
+                (None, _) => (),
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Pairs entrants from most wins to fewest, each with the next entrant
+/// down they haven't played yet, or the next one down if they've played
+/// everyone.
+fn get_swiss_pairings(entrant_count: usize, matches: &[MatchResult]) -> Vec<(usize, usize)> {
+    let mut order: Vec<usize> = (0..entrant_count).collect();
+    order.sort_by_key(|&index| core::cmp::Reverse(get_record(index, matches).0));
+
+    let played: BTreeSet<(usize, usize)> = matches.iter()
+        .map(|m| (m.first.min(m.second), m.first.max(m.second)))
+        .collect();
+    let mut pairings = vec![];
+
+    while order.len() > 1 {
+        let first = order.remove(0);
+        let opponent = order.iter()
+            .position(|&other| !played.contains(&(first.min(other), first.max(other))))
+            .unwrap_or(0);
+        let second = order.remove(opponent);
+        pairings.push((first, second));
+    }
+
+    pairings
+}
+
+/// Wins and games played by the entrant at `index`.
+fn get_record(index: usize, matches: &[MatchResult]) -> (usize, usize) {
+    matches.iter().fold((0, 0), |(wins, games), m| {
+        let played = m.first_wins + m.second_wins;
+        if m.first == index {
+            (wins + m.first_wins, games + played)
+        } else if m.second == index {
+            (wins + m.second_wins, games + played)
+        } else {
+            (wins, games)
+        }
+    })
+}
+
+/// The 95% Wilson score interval for `wins` out of `games`. Unlike the
+/// normal approximation it stays within 0 and 1 for small samples and
+/// lopsided results.
+pub fn get_wilson_interval(wins: usize, games: usize) -> (f64, f64) {
+    if games == 0 {
+        return (0.0, 1.0);
+    }
+
+    let n = games as f64;
+    let p = wins as f64 / n;
+    let z2 = Z_95 * Z_95;
+    let centre = p + z2 / (2.0 * n);
+    let spread = Z_95 * libm::sqrt(p * (1.0 - p) / n + z2 / (4.0 * n * n));
+    let denominator = 1.0 + z2 / n;
+
+    (
+        ((centre - spread) / denominator).max(0.0),
+        ((centre + spread) / denominator).min(1.0),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{LowestCardAgent, RandomAgent};
+
+    fn get_entrants() -> Vec<TournamentEntrant> {
+        vec![
+            TournamentEntrant::new("random", || Box::new(RandomAgent::new(1))),
+            TournamentEntrant::new("lowest", || Box::new(LowestCardAgent)),
+            TournamentEntrant::new("random2", || Box::new(RandomAgent::new(2))),
+        ]
+    }
+
+    #[test]
+    fn every_entrant_plays_every_other_in_a_round_robin() {
+        let config = TournamentConfig { deals_per_match: 2, ..TournamentConfig::default() };
+
+        let result = run_tournament(&config, &get_entrants()).unwrap();
+
+        assert_eq!(result.matches.len(), 3);
+        for standing in result.standings.iter() {
+            assert_eq!(standing.games, 8);
+            let (low, high) = standing.confidence_interval;
+            assert!(low <= standing.win_rate && standing.win_rate <= high);
+        }
+        assert!(result.standings.windows(2).all(|s| s[0].win_rate >= s[1].win_rate));
+
+        let again = run_tournament(&config, &get_entrants()).unwrap();
+        assert_eq!(result, again);
+    }
+
+    #[test]
+    fn swiss_rounds_avoid_rematches() {
+        let config = TournamentConfig {
+            format: TournamentFormat::Swiss { rounds: 3 },
+            deals_per_match: 1,
+            ..TournamentConfig::default()
+        };
+        let mut entrants = get_entrants();
+        entrants.push(TournamentEntrant::new("random3", || Box::new(RandomAgent::new(3))));
+
+        let result = run_tournament(&config, &entrants).unwrap();
+
+        assert_eq!(result.matches.len(), 6);
+        let pairs: BTreeSet<(usize, usize)> = result.matches.iter()
+            .map(|m| (m.first.min(m.second), m.first.max(m.second)))
+            .collect();
+        assert_eq!(pairs.len(), 6);
+    }
+
+    #[test]
+    fn wilson_intervals_narrow_with_more_games() {
+        let (low, high) = get_wilson_interval(5, 10);
+        let (wide_low, wide_high) = (low, high);
+        assert!(((low + high) / 2.0 - 0.5).abs() < 1e-9);
+
+        let (low, high) = get_wilson_interval(500, 1000);
+        assert!(high - low < wide_high - wide_low);
+        assert_eq!(get_wilson_interval(0, 0), (0.0, 1.0));
+        assert!(get_wilson_interval(0, 10).0 == 0.0);
+    }
+}
@@ -0,0 +1,89 @@
+use super::HiddenHands;
+use crate::cards::Card;
+use crate::game::{enumerate_tricks, Hand, Round, Ruleset, TrickType};
+use alloc::collections::{BTreeMap, BTreeSet};
+use rand::Rng;
+
+const TRICK_TYPES: [TrickType; 7] = [
+    TrickType::Straight,
+    TrickType::Flush,
+    TrickType::FullHouse,
+    TrickType::FourOfAKind,
+    TrickType::StraightFlush,
+    TrickType::FiveOfAKind,
+    TrickType::RoyalFlush,
+];
+
+/// For every kind of five card trick the ruleset allows, the chance that
+/// at least one of `viewer`'s opponents holds the cards to make it, from 0
+/// to 1. It's estimated by dealing the cards `viewer` can't see out
+/// `samples` times, keeping each opponent's card count. Kinds that can't
+/// be made from the unseen cards at all are 0 without sampling, so "a
+/// straight flush is still possible" is exact either way.
+pub fn get_trick_type_odds<R: Rng>(
+    round: &Round,
+    viewer: &str,
+    samples: usize,
+    rng: &mut R,
+) -> BTreeMap<TrickType, f64> {
+    let ruleset = round.get_ruleset();
+    let hidden = HiddenHands::from_round(round, viewer);
+    let possible = get_trick_types(&hidden.get_pool(), ruleset);
+
+    let mut found: BTreeMap<TrickType, usize> = BTreeMap::new();
+    if !possible.is_empty() {
+        for _ in 0..samples {
+            let held: BTreeSet<TrickType> = hidden.sample_hands(rng).values()
+                .flat_map(|hand| get_trick_types(hand, ruleset))
+                .collect();
+            for trick_type in held {
+                *found.entry(trick_type).or_insert(0) += 1;
+            }
+        }
+    }
+
+    TRICK_TYPES.iter()
+        .filter(|&&t| t != TrickType::RoyalFlush || ruleset.royal_flush_tier)
+        .filter(|&&t| ruleset.trick_ranking.get_tier(t).is_some())
+        .map(|&t| {
+            let odds = match found.get(&t) {
+                Some(&count) if samples > 0 => count as f64 / samples as f64,
+                _ => 0.0,
+            };
+            (t, odds)
+        })
+        .collect()
+}
+
+fn get_trick_types(cards: &[Card], ruleset: Ruleset) -> BTreeSet<TrickType> {
+    enumerate_tricks(cards, false, ruleset).iter()
+        .filter_map(Hand::get_trick_type)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::Scenario;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn odds_cover_the_unseen_cards() {
+        let round = Scenario::new()
+            .player("a", "3C 4C")
+            .player("b", "5H 6H 7H 8H 9C 10D")
+            .player("c", "KC KD")
+            .build()
+            .unwrap();
+        let mut rng = StdRng::seed_from_u64(4);
+
+        let odds = get_trick_type_odds(&round, "a", 50, &mut rng);
+
+        assert_eq!(odds.len(), 6);
+        assert_eq!(odds[&TrickType::FourOfAKind], 0.0);
+        assert_eq!(odds[&TrickType::StraightFlush], 0.0);
+        assert!(odds[&TrickType::Straight] > 0.0 && odds[&TrickType::Straight] < 1.0);
+        assert_eq!(odds[&TrickType::Flush], 0.0);
+    }
+}
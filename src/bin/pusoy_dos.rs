@@ -0,0 +1,87 @@
+//! Play a game in the terminal against the built-in agents.
+//!
+//! Usage: `pusoy_dos [players] [humans]` (defaults to 4 players, 1 human).
+//! Enter moves as space-separated cards such as `3C 3H`, a joker as
+//! `JK:2S`, or `pass`.
+
+use pusoy_dos2::ai::{Agent, CpuAgent};
+use pusoy_dos2::cards::{get_suit_array, PlayedCard};
+use pusoy_dos2::game::{Game, Ruleset};
+use pusoy_dos2::render::{played_cards_symbols, render_text};
+use std::env;
+use std::io::{self, BufRead, Write};
+
+fn parse_move(line: &str) -> Option<Vec<PlayedCard>> {
+    let line = line.trim();
+    if line.eq_ignore_ascii_case("pass") {
+        return Some(vec![]);
+    }
+
+    line.split_whitespace().map(|c| c.parse().ok()).collect()
+}
+
+fn main() {
+    let args: Vec<usize> = env::args()
+        .skip(1)
+        .filter_map(|a| a.parse().ok())
+        .collect();
+    let num_players = args.first().cloned().unwrap_or(4).clamp(2, 8);
+    let num_humans = args.get(1).cloned().unwrap_or(1).min(num_players);
+
+    let ids: Vec<String> = (0..num_players)
+        .map(|i| if i < num_humans {
+            format!("you{}", i + 1)
+        } else {
+            format!("cpu{}", i + 1 - num_humans)
+        })
+        .collect();
+    let num_decks = if num_players > 4 { 2 } else { 1 };
+
+    let mut game = Game::new(
+        num_decks, 0, &ids, &get_suit_array(), Ruleset::default()
+    ).expect("player ids are numbered");
+    let mut agent = CpuAgent;
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    while let Some(player_id) = game.get_next_player() {
+        if player_id.starts_with("you") {
+            println!("{}", render_text(game.get_round(), &player_id));
+            print!("{}> ", player_id);
+            io::stdout().flush().expect("unable to flush stdout");
+
+            let line = match lines.next() {
+                Some(Ok(line)) => line,
+                _ => return,
+            };
+            let cards = match parse_move(&line) {
+                Some(cards) => cards,
+                None => {
+                    println!("could not read that move, try e.g. `3C 3H` or `pass`");
+                    continue;
+                },
+            };
+            if let Err(err) = game.play_move(&player_id, cards) {
+                println!("illegal move: {:?}", err);
+            }
+        } else {
+            let cards = agent.choose_move(game.get_round(), &player_id);
+            let description = if cards.is_empty() {
+                "passes".to_string()
+            } else {
+                format!("plays {}", played_cards_symbols(&cards))
+            };
+
+            if game.play_move(&player_id, cards).is_ok() {
+                println!("{} {}", player_id, description);
+            } else if game.play_move(&player_id, vec![]).is_ok() {
+                println!("{} passes", player_id);
+            } else {
+                println!("{} is stuck, ending the game", player_id);
+                return;
+            }
+        }
+    }
+
+    println!("Game over! Finishing order: {}", game.get_winners().join(", "));
+}
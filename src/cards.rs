@@ -1,7 +1,13 @@
+mod commitment;
 mod core;
 mod deck;
+mod mask;
+mod notation;
 mod types;
 
+pub use self::commitment::*;
 pub use self::core::*;
 pub use self::deck::*;
+pub use self::mask::*;
+pub use self::notation::*;
 pub use self::types::*;
@@ -0,0 +1,147 @@
+use super::{Card, Deck};
+use crate::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// A hash of a shuffled deck published before the deal. Revealing the deck
+/// and salt after the game lets every player check the deal used the order
+/// that was committed to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeckCommitment {
+    pub hash: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeckReveal {
+    pub deck: Deck,
+    pub salt: String,
+}
+
+impl DeckCommitment {
+    pub fn verify(&self, reveal: &DeckReveal) -> bool {
+        reveal.deck.commit(&reveal.salt) == *self
+    }
+}
+
+impl Deck {
+    /// The stable encoding of the deck order that commitments are taken
+    /// over: `deck_id:card` for every card, comma separated, e.g.
+    /// `0:3C,0:4C,0:JK`.
+    pub fn serialize_order(&self) -> String {
+        self.to_vec().iter()
+            .map(|card| {
+                let deck_id = match card {
+                    Card::Standard { deck_id, .. } => deck_id,
+                    Card::Joker { deck_id } => deck_id,
+                };
+                format!("{}:{}", deck_id, card)
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    /// SHA-256 of the salt and the deck order, hex encoded.
+    pub fn commit(&self, salt: &str) -> DeckCommitment {
+        let mut hasher = Sha256::new();
+        hasher.update(salt.as_bytes());
+        hasher.update(b"|");
+        hasher.update(self.serialize_order().as_bytes());
+
+        DeckCommitment {
+            hash: to_hex(&hasher.finalize()),
+        }
+    }
+}
+
+/// 16 random bytes, hex encoded, to stop a committed order being guessed.
+pub fn generate_salt<R: Rng>(rng: &mut R) -> String {
+    let bytes: Vec<u8> = (0..16).map(|_| rng.gen()).collect();
+    to_hex(&bytes)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Rank, Suit};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn deck_order_is_serialized_stably() {
+        let deck = Deck::new(2, 1);
+        let order = deck.serialize_order();
+
+        assert!(order.starts_with("0:JK,0:3C,0:4C,"));
+        assert!(order.ends_with(",1:AS,1:2S"));
+    }
+
+    #[test]
+    fn commitments_use_sha256_of_salt_and_order() {
+        let deck = Deck::from_cards(vec![
+            Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+            Card::Joker { deck_id: 0 },
+        ]);
+
+        assert_eq!(
+            deck.commit("salt").hash,
+            "60ee0105c68ab4e08ebc9cf75e7ed59d80b5c1cd1babc138e5937555e8419b70"
+        );
+    }
+
+    #[test]
+    fn a_revealed_deck_matches_its_commitment() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let mut deck = Deck::new(1, 0);
+        deck.shuffle();
+        let salt = generate_salt(&mut rng);
+        let commitment = deck.commit(&salt);
+
+        assert_eq!(commitment.hash.len(), 64);
+        assert!(commitment.verify(&DeckReveal { deck, salt }));
+    }
+
+    #[test]
+    fn a_committed_deck_can_be_dealt() {
+        let mut deck = Deck::new(1, 0);
+        deck.shuffle();
+        let commitment = deck.commit("salt");
+        let ids = vec!["a".to_string(), "b".to_string()];
+
+        let game = crate::game::Game::from_deck(
+            &deck,
+            1,
+            0,
+            &ids,
+            &crate::cards::get_suit_array(),
+            crate::game::Ruleset::default()
+        ).unwrap();
+
+        assert_eq!(game.get_round().get_dealt_cards().len(), 52);
+        assert!(commitment.verify(&DeckReveal {
+            deck,
+            salt: "salt".to_string(),
+        }));
+    }
+
+    #[test]
+    fn a_different_order_or_salt_fails_verification() {
+        let deck = Deck::new(1, 0);
+        let commitment = deck.commit("salt");
+        let mut cards = deck.to_vec();
+        cards.swap(0, 1);
+
+        assert!(!commitment.verify(&DeckReveal {
+            deck: Deck::from_cards(cards),
+            salt: "salt".to_string(),
+        }));
+        assert!(!commitment.verify(&DeckReveal {
+            deck,
+            salt: "pepper".to_string(),
+        }));
+    }
+}
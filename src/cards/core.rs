@@ -1,7 +1,10 @@
 use super::{Rank, Suit};
+use crate::game::OrderContext;
 use serde::{Deserialize, Serialize};
+use core::cmp::Ordering;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 #[serde(tag = "type")]
 #[serde(rename_all = "lowercase")]
 pub enum Card {
@@ -23,10 +26,32 @@ impl Card {
             _ => None,
         }
     }
-}
 
+    /// Orders two cards by rank and then suit in `orders`, with jokers
+    /// above every standard card and the deck breaking any tie left, so
+    /// `Greater` means this card plays higher. Unlike going through the
+    /// rank and suit indexes this never panics: a rank or suit missing
+    /// from a malformed order sorts below the rest.
+    pub fn cmp_with(&self, other: &Card, orders: &OrderContext) -> Ordering {
+        let key = |card: &Card| match *card {
+            Card::Joker { deck_id } => (
+                Some(orders.get_rank_order().len()),
+                Some(orders.get_suit_order().len()),
+                deck_id,
+            ),
+            Card::Standard { deck_id, rank, suit } => (
+                orders.rank_index(rank),
+                orders.suit_index(suit),
+                deck_id,
+            ),
+        };
+
+        key(self).cmp(&key(other))
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 #[serde(rename_all = "lowercase")]
 pub struct PlayedCard {
     rank: Rank,
@@ -71,6 +96,7 @@ impl PlayedCard {
 #[cfg(test)]
 mod tests {
     use super::super::*;
+    use super::{OrderContext, Ordering};
 
     #[test]
     fn card_has_rank_and_suit() {
@@ -92,6 +118,39 @@ mod tests {
         assert_eq!(joker_ace_of_spades.get_suit(), Suit::Spades);
     }
 
+    #[test]
+    fn cards_compare_under_any_order_without_panicking() {
+        let card = |rank, suit| Card::Standard { deck_id: 0, rank, suit };
+        let orders = OrderContext::standard();
+        let three = card(Rank::Three, Suit::Spades);
+        let two = card(Rank::Two, Suit::Clubs);
+        let joker = Card::Joker { deck_id: 0 };
+
+        assert_eq!(three.cmp_with(&two, &orders), Ordering::Less);
+        assert_eq!(joker.cmp_with(&two, &orders), Ordering::Greater);
+        assert_eq!(
+            card(Rank::Two, Suit::Spades).cmp_with(&two, &orders),
+            Ordering::Greater
+        );
+        assert_eq!(
+            Card::Standard { deck_id: 1, rank: Rank::Two, suit: Suit::Clubs }
+                .cmp_with(&two, &orders),
+            Ordering::Greater
+        );
+
+        assert_eq!(three.cmp_with(&two, &orders.reverse()), Ordering::Greater);
+
+        // an order with the two missing still gives an answer
+        let mut rank_order = get_rank_array();
+        rank_order[12] = Rank::Three;
+        let malformed: OrderContext = serde_json::from_value(serde_json::json!({
+            "suit_order": get_suit_array(),
+            "rank_order": rank_order,
+            "reversed": false,
+        })).unwrap();
+        assert_eq!(two.cmp_with(&three, &malformed), Ordering::Less);
+    }
+
     #[test]
     fn played_card_to_card() {
         let ace_of_spades = Card::Standard {
@@ -1,14 +1,54 @@
 use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-use super::{get_rank_array, get_suit_array, Card};
+use super::{get_rank_array, get_short_deck_ranks, get_suit_array, Card, Rank, Suit};
+use crate::prelude::*;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Deck(Vec<Card>);
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DeckError {
+    /// A suit was listed more than once
+    DuplicateSuit(Suit),
+}
+
 impl Deck {
     pub fn new(number_of_decks: u8, number_of_jokers: u8) -> Deck {
-        let ranks = get_rank_array();
-        let suits = get_suit_array();
+        Deck::build(number_of_decks, number_of_jokers, &get_suit_array(), &get_rank_array())
+    }
+
+    /// A short deck holding every rank from `lowest_rank` up to the ace,
+    /// as played sevens or nines up. Sevens up is 32 cards a deck and
+    /// nines up 24, before any jokers.
+    pub fn short(number_of_decks: u8, number_of_jokers: u8, lowest_rank: Rank) -> Deck {
+        Deck::build(
+            number_of_decks,
+            number_of_jokers,
+            &get_suit_array(),
+            &get_short_deck_ranks(lowest_rank),
+        )
+    }
+
+    /// A deck made of the given suits, each with every rank, for games
+    /// played with fewer suits than the standard four or with eagles as a
+    /// fifth. Flushes and dealing work the same whatever the suits are.
+    pub fn with_suits(
+        number_of_decks: u8,
+        number_of_jokers: u8,
+        suits: &[Suit]
+    ) -> Result<Deck, DeckError> {
+        for (i, suit) in suits.iter().enumerate() {
+            if suits[..i].contains(suit) {
+                return Err(DeckError::DuplicateSuit(*suit));
+            }
+        }
+
+        Ok(Deck::build(number_of_decks, number_of_jokers, suits, &get_rank_array()))
+    }
+
+    fn build(number_of_decks: u8, number_of_jokers: u8, suits: &[Suit], ranks: &[Rank]) -> Deck {
         let mut cards = vec![];
 
         while cards.len() < number_of_jokers as usize {
@@ -18,8 +58,8 @@ impl Deck {
         let mut deck_count = 0;
 
         while deck_count < number_of_decks {
-            for suit in &suits {
-                for rank in &ranks {
+            for suit in suits {
+                for rank in ranks {
                     let card = Card::Standard {
                         deck_id: deck_count,
                         rank: *rank,
@@ -34,11 +74,41 @@ impl Deck {
         Deck(cards)
     }
 
+    /// A deck in exactly the given order, e.g. one revealed after a game.
+    pub fn from_cards(cards: Vec<Card>) -> Deck {
+        Deck(cards)
+    }
+
+    /// Takes every copy of a card out of the deck.
+    pub fn strip(&mut self, rank: Rank, suit: Suit) {
+        self.0.retain(|&c| c.get_rank() != Some(rank) || c.get_suit() != Some(suit));
+    }
+
+    /// Takes `count` cards off the bottom of the deck, the last to be
+    /// dealt, and returns them.
+    pub fn set_aside(&mut self, count: usize) -> Vec<Card> {
+        self.0.drain(..count.min(self.0.len())).collect()
+    }
+
+    /// Takes out every card a short deck played `lowest_rank` up leaves
+    /// out, the twos included, so a full deck becomes `Deck::short`.
+    pub fn strip_below(&mut self, lowest_rank: Rank) {
+        let ranks = get_short_deck_ranks(lowest_rank);
+        self.0.retain(|&c| c.get_rank().is_none_or(|rank| ranks.contains(&rank)));
+    }
+
+    #[cfg(feature = "std")]
     pub fn shuffle(&mut self) {
         let mut rng = rand::thread_rng();
         self.0.shuffle(&mut rng);
     }
 
+    /// Shuffles with the given random source, such as a seeded one, so
+    /// the same seed always gives the same deal.
+    pub fn shuffle_with<R: Rng>(&mut self, rng: &mut R) {
+        self.0.shuffle(rng);
+    }
+
     pub fn deal(&self, players: u8) -> Vec<Vec<Card>> {
         let mut index = 0;
         let mut deck_stack = self.0.clone();
@@ -53,6 +123,24 @@ impl Deck {
         dealt_stacks
     }
 
+    /// Deals `cards_per_player` cards to each player, the same way `deal`
+    /// would, and returns the undealt cards as a kitty. Players get an
+    /// equal share of a deck too small to deal that many.
+    pub fn deal_with_kitty(
+        &self,
+        players: u8,
+        cards_per_player: usize,
+    ) -> (Vec<Vec<Card>>, Deck) {
+        let per_player = match players {
+            0 => 0,
+            n => cards_per_player.min(self.0.len() / n as usize),
+        };
+        let kitty_size = self.0.len() - per_player * players as usize;
+
+        let dealt = Deck(self.0[kitty_size..].to_vec()).deal(players);
+        (dealt, Deck(self.0[..kitty_size].to_vec()))
+    }
+
     pub fn count(&self) -> usize {
         self.0.len()
     }
@@ -107,6 +195,28 @@ mod tests {
         assert_eq!(deck.count(), 105);
     }
 
+    #[test]
+    fn it_can_leave_out_suits() {
+        let deck = Deck::with_suits(2, 1, &[Suit::Clubs, Suit::Hearts, Suit::Diamonds]).unwrap();
+
+        assert_eq!(deck.count(), 79);
+        assert!(deck.to_vec().iter().all(|c| c.get_suit() != Some(Suit::Spades)));
+        assert_eq!(
+            Deck::with_suits(1, 0, &[Suit::Clubs, Suit::Hearts, Suit::Clubs]),
+            Err(DeckError::DuplicateSuit(Suit::Clubs))
+        );
+    }
+
+    #[test]
+    fn it_can_add_a_fifth_suit() {
+        let mut suits = get_suit_array().to_vec();
+        suits.push(Suit::Eagles);
+        let deck = Deck::with_suits(1, 1, &suits).unwrap();
+
+        assert_eq!(deck.count(), 66);
+        assert_eq!(deck.to_vec().iter().filter(|c| c.get_suit() == Some(Suit::Eagles)).count(), 13);
+    }
+
     #[test]
     fn it_can_shuffle() {
         let mut deck = Deck::new(1, 0);
@@ -120,7 +230,7 @@ mod tests {
         let not_deep_equal = original_order
             .iter()
             .zip(new_order)
-            .any(|(a, b)| a.clone() != b.clone());
+            .any(|(a, b)| *a != b);
         assert!(not_deep_equal);
     }
 
@@ -132,4 +242,32 @@ mod tests {
         assert_eq!(dealt.len(), 4);
         assert_eq!(dealt[0].len(), 13);
     }
+
+    #[test]
+    fn it_can_deal_a_set_number_of_cards_and_keep_a_kitty() {
+        let deck = Deck::new(1, 0);
+
+        let (dealt, kitty) = deck.deal_with_kitty(4, 10);
+        assert_eq!(dealt.len(), 4);
+        assert!(dealt.iter().all(|hand| hand.len() == 10));
+        assert_eq!(kitty.count(), 12);
+        assert_eq!(kitty.to_vec(), deck.to_vec()[..12].to_vec());
+
+        let (dealt, kitty) = deck.deal_with_kitty(5, 20);
+        assert!(dealt.iter().all(|hand| hand.len() == 10));
+        assert_eq!(kitty.count(), 2);
+    }
+
+    #[test]
+    fn it_can_strip_down_to_a_short_deck() {
+        let mut deck = Deck::new(1, 1);
+
+        deck.strip_below(Rank::Seven);
+        assert_eq!(deck.count(), 33);
+        assert_eq!(deck, Deck::short(1, 1, Rank::Seven));
+        assert_eq!(Deck::short(1, 0, Rank::Seven).count(), 32);
+        assert_eq!(Deck::short(2, 0, Rank::Nine).count(), 48);
+        assert!(deck.to_vec().iter()
+            .all(|c| !matches!(c.get_rank(), Some(Rank::Two) | Some(Rank::Six))));
+    }
 }
@@ -0,0 +1,167 @@
+use super::{get_rank_array, get_suit_array, Card};
+use crate::prelude::*;
+use core::fmt;
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+
+const CARDS_PER_DECK: u32 = 52;
+
+/// A set of cards as one integer, a bit per card. Each deck's cards take
+/// 52 bits, from the three of clubs up to the two of spades, and the bits
+/// above them each hold a joker. One deck fits a `u64` with room for 12
+/// jokers and two fit a `u128` with room for 24. Masks cross the wire as
+/// hex strings, since javascript numbers can't hold 64 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardMask {
+    OneDeck(u64),
+    TwoDecks(u128),
+}
+
+impl CardMask {
+    /// The mask for `cards` dealt from `num_decks` decks, or `None` if
+    /// that needs more than two decks or more jokers than there are bits
+    /// for. Masks only cover the four standard suits, so eagles have no
+    /// bits either.
+    pub fn from_cards(cards: &[Card], num_decks: u8) -> Option<CardMask> {
+        let width = match num_decks {
+            0 | 1 => 64,
+            2 => 128,
+            _ => return None,
+        };
+        let joker_start = CARDS_PER_DECK * num_decks.max(1) as u32;
+        let mut mask: u128 = 0;
+        let mut jokers = 0;
+
+        for card in cards {
+            let bit = match *card {
+                Card::Standard { deck_id, rank, suit } if deck_id < num_decks.max(1) => {
+                    let rank = get_rank_array().iter().position(|&r| r == rank)? as u32;
+                    let suit = get_suit_array().iter().position(|&s| s == suit)? as u32;
+                    CARDS_PER_DECK * deck_id as u32 + rank * 4 + suit
+                },
+                Card::Standard { .. } => return None,
+                Card::Joker { .. } => {
+                    jokers += 1;
+                    joker_start + jokers - 1
+                },
+            };
+            if bit >= width || mask & (1 << bit) != 0 {
+                return None;
+            }
+            mask |= 1 << bit;
+        }
+
+        Some(match width {
+            64 => CardMask::OneDeck(mask as u64),
+            _ => CardMask::TwoDecks(mask),
+        })
+    }
+
+    /// The cards in the mask, in deck order with jokers last. Jokers all
+    /// come back with deck id 0, as `Deck` deals them.
+    pub fn to_cards(self) -> Vec<Card> {
+        let (mask, decks) = match self {
+            CardMask::OneDeck(mask) => (mask as u128, 1),
+            CardMask::TwoDecks(mask) => (mask, 2),
+        };
+        let joker_start = CARDS_PER_DECK * decks;
+
+        (0..128u32)
+            .filter(|&bit| mask & (1 << bit) != 0)
+            .map(|bit| {
+                if bit >= joker_start {
+                    return Card::Joker { deck_id: 0 };
+                }
+                let index = bit % CARDS_PER_DECK;
+                Card::Standard {
+                    deck_id: (bit / CARDS_PER_DECK) as u8,
+                    rank: get_rank_array()[(index / 4) as usize],
+                    suit: get_suit_array()[(index % 4) as usize],
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for CardMask {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CardMask::OneDeck(mask) => write!(f, "{:016x}", mask),
+            CardMask::TwoDecks(mask) => write!(f, "{:032x}", mask),
+        }
+    }
+}
+
+/// 16 hex digits for one deck, 32 for two.
+impl core::str::FromStr for CardMask {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<CardMask, String> {
+        let invalid = || format!("invalid card mask: {}", s);
+        match s.len() {
+            16 => u64::from_str_radix(s, 16).map(CardMask::OneDeck).map_err(|_| invalid()),
+            32 => u128::from_str_radix(s, 16).map(CardMask::TwoDecks).map_err(|_| invalid()),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl Serialize for CardMask {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CardMask {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<CardMask, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Deck, Rank, Suit};
+
+    #[test]
+    fn hands_round_trip_through_masks() {
+        let deck = Deck::new(1, 2).to_vec();
+        let mask = CardMask::from_cards(&deck, 1).unwrap();
+        assert_eq!(mask, CardMask::OneDeck((1 << 54) - 1));
+
+        let mut cards = mask.to_cards();
+        let mut expected = deck;
+        cards.sort();
+        expected.sort();
+        assert_eq!(cards, expected);
+
+        let hand = vec![
+            Card::Standard { deck_id: 1, rank: Rank::Two, suit: Suit::Spades },
+            Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+        ];
+        let mask = CardMask::from_cards(&hand, 2).unwrap();
+        assert_eq!(mask, CardMask::TwoDecks(1 | 1 << 103));
+        assert_eq!(mask.to_cards(), vec![hand[1], hand[0]]);
+    }
+
+    #[test]
+    fn cards_that_dont_fit_have_no_mask() {
+        let second_deck = Card::Standard { deck_id: 1, rank: Rank::Two, suit: Suit::Spades };
+
+        assert_eq!(CardMask::from_cards(&[second_deck], 1), None);
+        assert_eq!(CardMask::from_cards(&[second_deck, second_deck], 2), None);
+        assert_eq!(CardMask::from_cards(&[], 3), None);
+        assert_eq!(CardMask::from_cards(&[Card::Joker { deck_id: 0 }; 13], 1), None);
+    }
+
+    #[test]
+    fn masks_are_sent_as_hex() {
+        let mask = CardMask::OneDeck(0x1f);
+
+        let json = serde_json::to_string(&mask).unwrap();
+        assert_eq!(json, r#""000000000000001f""#);
+        assert_eq!(serde_json::from_str::<CardMask>(&json).unwrap(), mask);
+        assert!(serde_json::from_str::<CardMask>(r#""1f""#).is_err());
+    }
+}
@@ -0,0 +1,283 @@
+use super::{Card, PlayedCard, Rank, Suit};
+use crate::prelude::*;
+use core::fmt;
+use core::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseCardError {
+    InvalidRank(String),
+    InvalidSuit(String),
+    InvalidCard(String),
+}
+
+impl fmt::Display for ParseCardError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCardError::InvalidRank(s) => write!(f, "invalid rank: {}", s),
+            ParseCardError::InvalidSuit(s) => write!(f, "invalid suit: {}", s),
+            ParseCardError::InvalidCard(s) => write!(f, "invalid card: {}", s),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseCardError {}
+
+impl Rank {
+    /// Short notation for the rank, e.g. `3`, `10`, `J`
+    pub fn notation(self) -> &'static str {
+        match self {
+            Rank::Three => "3",
+            Rank::Four => "4",
+            Rank::Five => "5",
+            Rank::Six => "6",
+            Rank::Seven => "7",
+            Rank::Eight => "8",
+            Rank::Nine => "9",
+            Rank::Ten => "10",
+            Rank::Jack => "J",
+            Rank::Queen => "Q",
+            Rank::King => "K",
+            Rank::Ace => "A",
+            Rank::Two => "2",
+        }
+    }
+}
+
+impl Suit {
+    /// Single letter notation for the suit, e.g. `C`
+    pub fn letter(self) -> char {
+        match self {
+            Suit::Clubs => 'C',
+            Suit::Hearts => 'H',
+            Suit::Diamonds => 'D',
+            Suit::Spades => 'S',
+            Suit::Eagles => 'E',
+        }
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.notation())
+    }
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Rank, ParseCardError> {
+        match s.trim().to_uppercase().as_str() {
+            "3" => Ok(Rank::Three),
+            "4" => Ok(Rank::Four),
+            "5" => Ok(Rank::Five),
+            "6" => Ok(Rank::Six),
+            "7" => Ok(Rank::Seven),
+            "8" => Ok(Rank::Eight),
+            "9" => Ok(Rank::Nine),
+            "10" | "T" => Ok(Rank::Ten),
+            "J" => Ok(Rank::Jack),
+            "Q" => Ok(Rank::Queen),
+            "K" => Ok(Rank::King),
+            "A" => Ok(Rank::Ace),
+            "2" => Ok(Rank::Two),
+            _ => Err(ParseCardError::InvalidRank(s.to_string())),
+        }
+    }
+}
+
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Suit, ParseCardError> {
+        match s.trim().to_uppercase().as_str() {
+            "C" | "♣" | "CLUBS" => Ok(Suit::Clubs),
+            "H" | "♥" | "HEARTS" => Ok(Suit::Hearts),
+            "D" | "♦" | "DIAMONDS" => Ok(Suit::Diamonds),
+            "S" | "♠" | "SPADES" => Ok(Suit::Spades),
+            "E" | "🦅" | "EAGLES" => Ok(Suit::Eagles),
+            _ => Err(ParseCardError::InvalidSuit(s.to_string())),
+        }
+    }
+}
+
+fn parse_rank_and_suit(s: &str) -> Result<(Rank, Suit), ParseCardError> {
+    let s = s.trim();
+    let suit_start = match s.char_indices().last() {
+        Some((i, _)) if i > 0 => i,
+        _ => return Err(ParseCardError::InvalidCard(s.to_string())),
+    };
+    let (rank, suit) = s.split_at(suit_start);
+
+    Ok((rank.parse()?, suit.parse()?))
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Card::Standard { rank, suit, .. } => write!(f, "{}{}", rank, suit),
+            Card::Joker { .. } => write!(f, "JK"),
+        }
+    }
+}
+
+/// Parses `3C`, `10♥` or `JK`. The deck id is always 0.
+impl FromStr for Card {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Card, ParseCardError> {
+        let upper = s.trim().to_uppercase();
+        if upper == "JK" || upper == "JOKER" {
+            return Ok(Card::Joker { deck_id: 0 });
+        }
+
+        let (rank, suit) = parse_rank_and_suit(s)?;
+        Ok(Card::Standard { deck_id: 0, rank, suit })
+    }
+}
+
+impl Card {
+    /// A stable name for the card's artwork, shared by every frontend:
+    /// `3C`, `10H` or `JOKER_1`. Cards from different decks share artwork,
+    /// but jokers are numbered by deck from 1 so each can have its own.
+    pub fn asset_key(self) -> String {
+        match self {
+            Card::Standard { rank, suit, .. } => format!("{}{}", rank.notation(), suit.letter()),
+            Card::Joker { deck_id } => format!("JOKER_{}", deck_id as u16 + 1),
+        }
+    }
+
+    /// The card an asset key names, from the first deck for standard
+    /// cards. Unlike parsing, only the exact keys `asset_key` gives are
+    /// accepted.
+    pub fn from_asset_key(key: &str) -> Result<Card, ParseCardError> {
+        let invalid = || ParseCardError::InvalidCard(key.to_string());
+
+        if let Some(number) = key.strip_prefix("JOKER_") {
+            let deck_id = number.parse::<u16>().ok()
+                .filter(|n| *n >= 1 && *n <= 256 && !number.starts_with('0'))
+                .ok_or_else(invalid)?;
+            return Ok(Card::Joker { deck_id: (deck_id - 1) as u8 });
+        }
+
+        let (rank, suit) = parse_rank_and_suit(key)?;
+        let card = Card::Standard { deck_id: 0, rank, suit };
+        if card.asset_key() == key {
+            Ok(card)
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+impl fmt::Display for PlayedCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.get_is_joker() {
+            write!(f, "JK:")?;
+        }
+        write!(f, "{}{}", self.get_rank(), self.get_suit())
+    }
+}
+
+/// Parses `3C`, or `JK:2S` for a joker played as the two of spades.
+impl FromStr for PlayedCard {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<PlayedCard, ParseCardError> {
+        let s = s.trim();
+        let (is_joker, card) = match s.to_uppercase().strip_prefix("JK:") {
+            Some(card) => (true, card.to_string()),
+            None => (false, s.to_string()),
+        };
+
+        let (rank, suit) = parse_rank_and_suit(&card)?;
+        Ok(PlayedCard::new(rank, suit, is_joker))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranks_and_suits_round_trip() {
+        for &rank in super::super::get_rank_array().iter() {
+            assert_eq!(rank.to_string().parse::<Rank>(), Ok(rank));
+        }
+        for &suit in super::super::get_suit_array().iter() {
+            assert_eq!(suit.to_string().parse::<Suit>(), Ok(suit));
+        }
+    }
+
+    #[test]
+    fn suits_can_be_parsed_from_symbols() {
+        assert_eq!("♣".parse::<Suit>(), Ok(Suit::Clubs));
+        assert_eq!("h".parse::<Suit>(), Ok(Suit::Hearts));
+        assert_eq!("🦅".parse::<Suit>(), Ok(Suit::Eagles));
+        assert_eq!(Suit::Eagles.to_string().parse::<Suit>(), Ok(Suit::Eagles));
+        assert_eq!(
+            "X".parse::<Suit>(),
+            Err(ParseCardError::InvalidSuit("X".to_string()))
+        );
+    }
+
+    #[test]
+    fn cards_can_be_parsed_and_formatted() {
+        let ten_hearts = Card::Standard {
+            deck_id: 0,
+            rank: Rank::Ten,
+            suit: Suit::Hearts,
+        };
+
+        assert_eq!("10♥".parse::<Card>(), Ok(ten_hearts));
+        assert_eq!("th".parse::<Card>(), Ok(ten_hearts));
+        assert_eq!(ten_hearts.to_string(), "10H");
+        assert_eq!("JK".parse::<Card>(), Ok(Card::Joker { deck_id: 0 }));
+        assert_eq!(
+            "1C".parse::<Card>(),
+            Err(ParseCardError::InvalidRank("1".to_string()))
+        );
+        assert_eq!(
+            "C".parse::<Card>(),
+            Err(ParseCardError::InvalidCard("C".to_string()))
+        );
+    }
+
+    #[test]
+    fn played_jokers_carry_their_substitute() {
+        let joker = PlayedCard::new(Rank::Two, Suit::Spades, true);
+
+        assert_eq!(joker.to_string(), "JK:2S");
+        assert_eq!("jk:2s".parse::<PlayedCard>(), Ok(joker));
+        assert_eq!(
+            "3C".parse::<PlayedCard>(),
+            Ok(PlayedCard::new(Rank::Three, Suit::Clubs, false))
+        );
+    }
+
+    #[test]
+    fn asset_keys_round_trip() {
+        let ten_hearts = Card::Standard { deck_id: 1, rank: Rank::Ten, suit: Suit::Hearts };
+
+        assert_eq!(ten_hearts.asset_key(), "10H");
+        assert_eq!(Card::Joker { deck_id: 0 }.asset_key(), "JOKER_1");
+        assert_eq!(
+            Card::from_asset_key("10H"),
+            Ok(Card::Standard { deck_id: 0, rank: Rank::Ten, suit: Suit::Hearts })
+        );
+        assert_eq!(Card::from_asset_key("JOKER_2"), Ok(Card::Joker { deck_id: 1 }));
+
+        for key in ["th", "10♥", "JK", "JOKER_0", "JOKER_01"] {
+            assert!(Card::from_asset_key(key).is_err(), "{}", key);
+        }
+        for card in super::super::Deck::new(1, 1).to_vec() {
+            assert_eq!(Card::from_asset_key(&card.asset_key()), Ok(card));
+        }
+    }
+}
@@ -1,4 +1,6 @@
+use crate::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -6,16 +8,21 @@ use wasm_bindgen::prelude::*;
 pub enum Colour {
     Red,
     Black,
+    Green,
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 #[serde(rename_all = "lowercase")]
 pub enum Suit {
     Clubs,
     Hearts,
     Diamonds,
     Spades,
+    /// The fifth suit of the old five-suit decks, for tables too big to
+    /// deal from four. It isn't in the standard suit order.
+    Eagles,
 }
 
 impl Suit {
@@ -23,12 +30,14 @@ impl Suit {
         match self {
             Suit::Clubs | Suit::Spades => Colour::Black,
             Suit::Hearts | Suit::Diamonds => Colour::Red,
+            Suit::Eagles => Colour::Green,
         }
     }
 }
 
-#[wasm_bindgen]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 #[serde(rename_all = "lowercase")]
 pub enum Rank {
     Three,
@@ -46,6 +55,20 @@ pub enum Rank {
     Two,
 }
 
+impl Rank {
+    /// The rank above this one in `rank_order`, or `None` at the top.
+    pub fn next_in(self, rank_order: &[Rank; 13]) -> Option<Rank> {
+        let position = rank_order.iter().position(|&r| r == self)?;
+        rank_order.get(position + 1).copied()
+    }
+
+    /// The rank below this one in `rank_order`, or `None` at the bottom.
+    pub fn previous_in(self, rank_order: &[Rank; 13]) -> Option<Rank> {
+        let position = rank_order.iter().position(|&r| r == self)?;
+        position.checked_sub(1).map(|i| rank_order[i])
+    }
+}
+
 pub fn get_suit_array() -> [Suit; 4] {
     [Suit::Clubs, Suit::Hearts, Suit::Diamonds, Suit::Spades]
 }
@@ -68,14 +91,45 @@ pub fn get_rank_array() -> [Rank; 13] {
     ]
 }
 
+/// The ranks left in a short deck played `lowest_rank` up, from that rank
+/// to the ace in the standard order. Twos go with the other low ranks.
+pub fn get_short_deck_ranks(lowest_rank: Rank) -> Vec<Rank> {
+    get_rank_array().iter()
+        .copied()
+        .filter(|&rank| rank >= lowest_rank && rank != Rank::Two)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
     #[test]
     fn colour_can_be_derived_from_suit() {
         assert_eq!(Suit::Clubs.colour(), Colour::Black);
         assert_eq!(Suit::Hearts.colour(), Colour::Red);
         assert_eq!(Suit::Diamonds.colour(), Colour::Red);
         assert_eq!(Suit::Spades.colour(), Colour::Black);
+        assert_eq!(Suit::Eagles.colour(), Colour::Green);
+    }
+
+    #[test]
+    fn next_and_previous_ranks_follow_the_order() {
+        let mut reversed = get_rank_array();
+        reversed.reverse();
+
+        assert_eq!(Rank::Ace.next_in(&get_rank_array()), Some(Rank::Two));
+        assert_eq!(Rank::Two.next_in(&get_rank_array()), None);
+        assert_eq!(Rank::Three.previous_in(&get_rank_array()), None);
+        assert_eq!(Rank::Ace.next_in(&reversed), Some(Rank::King));
+        assert_eq!(Rank::Ace.previous_in(&reversed), Some(Rank::Two));
+    }
+
+    #[test]
+    fn short_decks_run_up_to_the_ace() {
+        assert_eq!(get_short_deck_ranks(Rank::Seven).len(), 8);
+        assert_eq!(get_short_deck_ranks(Rank::Nine), vec![
+            Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
+        ]);
     }
 }
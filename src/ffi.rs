@@ -0,0 +1,240 @@
+//! A C ABI for embedding the engine in native apps. Games are opaque
+//! pointers owned by the caller, cards go in as space separated notation
+//! (`3C 3D`, `JK:2S`) and state comes back as the game's JSON.
+//!
+//! Build a library for linking with
+//! `cargo rustc --release --lib --features ffi --crate-type staticlib` (or
+//! `cdylib`).
+
+use crate::cards::{get_suit_array, PlayedCard};
+use crate::game::{Game, Ruleset, SubmitError};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PusoyStatus {
+    Ok = 0,
+    /// A null pointer, or a string that isn't valid UTF-8
+    InvalidArgument,
+    /// A card code that couldn't be parsed
+    InvalidCard,
+    FirstRoundPass,
+    FirstHandMustContainLowestCard,
+    HandNotHighEnough,
+    NotCurrentPlayer,
+    InvalidHand,
+    PlayerDoesntHaveCard,
+    JokerOutsideFiveCardTrick,
+    CannotFinishWithJoker,
+    IllegalFinalCard,
+    CannotFinishWithBomb,
+    CannotFinishWithReversal,
+    DeadGame,
+    LockedOutOfTrick,
+    HouseRule,
+    KittyPending,
+    GamePaused,
+    AmbiguousCardCopy,
+    PlayerAlreadyFinished,
+}
+
+impl From<SubmitError> for PusoyStatus {
+    fn from(err: SubmitError) -> PusoyStatus {
+        match err {
+            SubmitError::FirstRoundPass => PusoyStatus::FirstRoundPass,
+            SubmitError::FirstHandMustContainLowestCard => {
+                PusoyStatus::FirstHandMustContainLowestCard
+            },
+            SubmitError::HandNotHighEnough => PusoyStatus::HandNotHighEnough,
+            SubmitError::NotCurrentPlayer => PusoyStatus::NotCurrentPlayer,
+            SubmitError::InvalidHand => PusoyStatus::InvalidHand,
+            SubmitError::PlayerDoesntHaveCard => PusoyStatus::PlayerDoesntHaveCard,
+            SubmitError::JokerOutsideFiveCardTrick => {
+                PusoyStatus::JokerOutsideFiveCardTrick
+            },
+            SubmitError::CannotFinishWithJoker => PusoyStatus::CannotFinishWithJoker,
+            SubmitError::IllegalFinalCard => PusoyStatus::IllegalFinalCard,
+            SubmitError::CannotFinishWithBomb => PusoyStatus::CannotFinishWithBomb,
+            SubmitError::CannotFinishWithReversal => {
+                PusoyStatus::CannotFinishWithReversal
+            },
+            SubmitError::DeadGame => PusoyStatus::DeadGame,
+            SubmitError::LockedOutOfTrick => PusoyStatus::LockedOutOfTrick,
+            SubmitError::HouseRule(_) => PusoyStatus::HouseRule,
+            SubmitError::KittyPending => PusoyStatus::KittyPending,
+            SubmitError::GamePaused => PusoyStatus::GamePaused,
+            SubmitError::AmbiguousCardCopy => PusoyStatus::AmbiguousCardCopy,
+            SubmitError::PlayerAlreadyFinished => PusoyStatus::PlayerAlreadyFinished,
+        }
+    }
+}
+
+/// Deals a new game under the default rules to `num_players` players.
+/// Returns null if any id is null or not UTF-8. Free the game with
+/// `pusoy_game_free`.
+///
+/// # Safety
+///
+/// `player_ids` must point to `num_players` valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn pusoy_game_new(
+    num_decks: u8,
+    num_jokers: u8,
+    player_ids: *const *const c_char,
+    num_players: usize,
+) -> *mut Game {
+    if player_ids.is_null() {
+        return ptr::null_mut();
+    }
+
+    let ids: Option<Vec<String>> = std::slice::from_raw_parts(player_ids, num_players)
+        .iter()
+        .map(|&id| to_str(id).map(String::from))
+        .collect();
+
+    let game = ids.and_then(|ids| Game::new(
+        num_decks,
+        num_jokers,
+        &ids,
+        &get_suit_array(),
+        Ruleset::default(),
+    ).ok());
+
+    match game {
+        Some(game) => Box::into_raw(Box::new(game)),
+        None => ptr::null_mut(),
+    }
+}
+
+/// # Safety
+///
+/// `game` must have come from `pusoy_game_new` and not been freed
+/// already. Null is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn pusoy_game_free(game: *mut Game) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+/// Plays `cards` for `player_id`. An empty string passes.
+///
+/// # Safety
+///
+/// `game` must be a live game from `pusoy_game_new`, and `player_id` and
+/// `cards` valid C strings.
+#[no_mangle]
+pub unsafe extern "C" fn pusoy_game_play_move(
+    game: *mut Game,
+    player_id: *const c_char,
+    cards: *const c_char,
+) -> PusoyStatus {
+    let (game, player_id, cards) = match (game.as_mut(), to_str(player_id), to_str(cards)) {
+        (Some(game), Some(player_id), Some(cards)) => (game, player_id, cards),
+        _ => return PusoyStatus::InvalidArgument,
+    };
+
+    let cards: Result<Vec<PlayedCard>, _> = cards.split_whitespace()
+        .map(|c| c.parse())
+        .collect();
+
+    match cards {
+        Ok(cards) => match game.play_move(player_id, cards) {
+            Ok(()) => PusoyStatus::Ok,
+            Err(err) => err.into(),
+        },
+        Err(_) => PusoyStatus::InvalidCard,
+    }
+}
+
+/// The whole game as JSON, every hand included, or null if `game` is
+/// null. Free the string with `pusoy_string_free`.
+///
+/// # Safety
+///
+/// `game` must be null or a live game from `pusoy_game_new`.
+#[no_mangle]
+pub unsafe extern "C" fn pusoy_game_state_json(game: *const Game) -> *mut c_char {
+    let game = match game.as_ref() {
+        Some(game) => game,
+        None => return ptr::null_mut(),
+    };
+
+    let json = serde_json::to_string(game).expect("games always serialize");
+    CString::new(json)
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// # Safety
+///
+/// `s` must have come from this library and not been freed already. Null
+/// is ignored.
+#[no_mangle]
+pub unsafe extern "C" fn pusoy_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+unsafe fn to_str<'a>(s: *const c_char) -> Option<&'a str> {
+    if s.is_null() {
+        return None;
+    }
+
+    CStr::from_ptr(s).to_str().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_game(ids: &[&str]) -> *mut Game {
+        let ids: Vec<CString> = ids.iter().map(|&id| CString::new(id).unwrap()).collect();
+        let ptrs: Vec<*const c_char> = ids.iter().map(|id| id.as_ptr()).collect();
+
+        unsafe { pusoy_game_new(1, 0, ptrs.as_ptr(), ptrs.len()) }
+    }
+
+    fn play(game: *mut Game, player_id: &str, cards: &str) -> PusoyStatus {
+        let player_id = CString::new(player_id).unwrap();
+        let cards = CString::new(cards).unwrap();
+
+        unsafe { pusoy_game_play_move(game, player_id.as_ptr(), cards.as_ptr()) }
+    }
+
+    #[test]
+    fn a_game_can_be_played_through_the_c_api() {
+        let game = new_game(&["a", "b"]);
+        let next = unsafe { (*game).get_next_player().unwrap() };
+
+        assert_eq!(play(game, &next, "3C"), PusoyStatus::Ok);
+        assert_eq!(play(game, &next, ""), PusoyStatus::NotCurrentPlayer);
+        assert_eq!(play(game, &next, "3X"), PusoyStatus::InvalidCard);
+
+        let json = unsafe { pusoy_game_state_json(game) };
+        let state: Game = serde_json::from_str(
+            unsafe { CStr::from_ptr(json) }.to_str().unwrap()
+        ).unwrap();
+        assert!(state.get_last_move().is_some());
+
+        unsafe {
+            pusoy_string_free(json);
+            pusoy_game_free(game);
+        }
+    }
+
+    #[test]
+    fn null_arguments_are_rejected() {
+        unsafe {
+            assert!(pusoy_game_new(1, 0, ptr::null(), 2).is_null());
+            assert!(pusoy_game_state_json(ptr::null()).is_null());
+            assert_eq!(
+                pusoy_game_play_move(ptr::null_mut(), ptr::null(), ptr::null()),
+                PusoyStatus::InvalidArgument
+            );
+        }
+    }
+}
@@ -2,13 +2,102 @@ mod game_container;
 mod player;
 #[macro_use]
 mod hands;
+mod invariants;
+mod claim;
+mod clock;
 mod comparisons;
+mod concede;
+mod conservation;
+mod dealer;
+mod enumeration;
+mod evaluation;
+mod events;
+mod explain;
+#[cfg(feature = "std")]
+mod guests;
+mod handicap;
+#[cfg(feature = "std")]
+mod lobby;
+mod match_play;
+mod move_rules;
+mod narration;
+mod observer;
+mod order_context;
+mod persistence;
+mod perspective;
+mod puzzle;
+mod rating;
+mod record;
+mod redeal;
+mod rejoin;
+mod replay;
 mod round;
+mod round_builder;
+mod round_delta;
+mod rule_description;
 mod rulesets;
+mod scoring;
+mod share_code;
+#[cfg(feature = "crypto")]
+mod signing;
+mod sorting;
+mod spectator;
+mod state_hash;
+mod stats;
+mod summary;
+mod table_config;
+mod telemetry;
+#[cfg(feature = "simd")]
+mod trick_mask;
 
+pub use self::claim::*;
+pub use self::clock::*;
 pub use self::comparisons::*;
+pub use self::concede::*;
+pub use self::conservation::*;
+pub use self::dealer::*;
+pub use self::enumeration::*;
+pub use self::evaluation::*;
+pub use self::events::*;
+pub use self::explain::*;
+#[cfg(feature = "std")]
+pub use self::guests::*;
+pub use self::handicap::*;
 pub use self::game_container::*;
 pub use self::hands::*;
+pub use self::invariants::*;
+#[cfg(feature = "std")]
+pub use self::lobby::*;
+pub use self::match_play::*;
+pub use self::move_rules::*;
+pub use self::narration::*;
+pub use self::observer::*;
+pub use self::order_context::*;
+pub use self::persistence::*;
+pub use self::perspective::*;
+pub use self::puzzle::*;
 pub use self::player::*;
+pub use self::rating::*;
+pub use self::record::*;
+pub use self::redeal::*;
+pub use self::rejoin::*;
+pub use self::replay::*;
 pub use self::round::*;
+pub use self::round_builder::*;
+pub use self::round_delta::*;
+pub use self::rule_description::*;
 pub use self::rulesets::*;
+pub use self::scoring::*;
+pub use self::share_code::*;
+#[cfg(feature = "crypto")]
+pub use self::signing::*;
+pub use self::sorting::*;
+pub use self::spectator::*;
+pub use self::stats::*;
+pub use self::summary::*;
+pub use self::table_config::*;
+#[cfg(feature = "telemetry")]
+pub use self::telemetry::Telemetry;
+pub(crate) use self::telemetry::TelemetryHook;
+#[cfg(feature = "simd")]
+pub use self::trick_mask::*;
@@ -0,0 +1,142 @@
+use super::Round;
+use crate::ai::get_legal_moves;
+use crate::cards::PlayedCard;
+use crate::prelude::*;
+use alloc::collections::BTreeSet;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClaimError {
+    UnknownPlayer,
+    GameOver,
+    /// Claims are made on the claimer's turn
+    NotCurrentPlayer,
+    GamePaused,
+    /// The kitty is still to be picked up or discarded
+    KittyPending,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClaimOutcome {
+    /// Nobody could have beaten the hand. It has been played out and the
+    /// game is over.
+    Upheld,
+    /// The hand could be beaten and the ruleset's false claim penalty has
+    /// been applied
+    Rejected,
+}
+
+impl Round {
+    /// The moves that take `player_id` out with every other player only
+    /// able to pass in between, if there are any. Every order their hand
+    /// can be played in is tried against the cards the others actually
+    /// hold, so a line found here can't be beaten. `None` when it isn't
+    /// their turn.
+    pub fn find_winning_line(&self, player_id: &str) -> Option<Vec<Vec<PlayedCard>>> {
+        if self.get_next_player().as_deref() != Some(player_id) {
+            return None;
+        }
+
+        find_winning_line(self, player_id, &mut BTreeSet::new())
+    }
+}
+
+fn find_winning_line(
+    round: &Round,
+    player_id: &str,
+    beaten: &mut BTreeSet<u64>,
+) -> Option<Vec<Vec<PlayedCard>>> {
+    let hash = round.state_hash();
+    if beaten.contains(&hash) {
+        return None;
+    }
+
+    // the highest hands are the likeliest to go unanswered
+    let moves = get_legal_moves(round, player_id).into_iter()
+        .rev()
+        .filter(|cards| !cards.is_empty());
+    for cards in moves {
+        let after = match play_unanswered(round, player_id, &cards) {
+            Some(after) => after,
+            None => continue,
+        };
+        if is_out(&after, player_id) {
+            return Some(vec![cards]);
+        }
+        if let Some(mut line) = find_winning_line(&after, player_id, beaten) {
+            line.insert(0, cards);
+            return Some(line);
+        }
+    }
+
+    beaten.insert(hash);
+    None
+}
+
+/// Plays `cards` and then a pass for each player after, as long as a pass
+/// is all they can play, until the lead comes back round. `None` if
+/// someone could answer the move.
+fn play_unanswered(round: &Round, player_id: &str, cards: &[PlayedCard]) -> Option<Round> {
+    let mut round = round.submit_move(player_id, cards.to_vec()).ok()?;
+    if is_out(&round, player_id) {
+        return Some(round);
+    }
+    round.declare_last_card_mut(player_id).ok();
+
+    while let Some(next) = round.get_next_player() {
+        if next == player_id {
+            return Some(round);
+        }
+        if get_legal_moves(&round, &next).iter().any(|cards| !cards.is_empty()) {
+            return None;
+        }
+        round = round.submit_move(&next, vec![]).ok()?;
+    }
+
+    None
+}
+
+fn is_out(round: &Round, player_id: &str) -> bool {
+    round.get_player(player_id).is_some_and(|p| p.get_card_count() == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::Scenario;
+
+    #[test]
+    fn a_hand_nobody_can_answer_has_a_winning_line() {
+        let round = Scenario::new()
+            .player("a", "2S 2H AS")
+            .player("b", "3C 4D 9H")
+            .player("c", "5C KD")
+            .lead("a")
+            .next_player("a")
+            .build()
+            .unwrap();
+
+        let line = round.find_winning_line("a").unwrap();
+
+        assert_eq!(line.iter().map(Vec::len).sum::<usize>(), 3);
+        // whatever's left for the last move goes out without an answer
+        let last_card = Scenario::new()
+            .player("a", "2S KH")
+            .player("b", "AC")
+            .lead("a")
+            .next_player("a")
+            .build()
+            .unwrap();
+        assert_eq!(last_card.find_winning_line("a").map(|line| line.len()), Some(2));
+        assert_eq!(round.find_winning_line("b"), None);
+
+        let beatable = Scenario::new()
+            .player("a", "QS KH")
+            .player("b", "AC")
+            .lead("a")
+            .next_player("a")
+            .build()
+            .unwrap();
+        assert_eq!(beatable.find_winning_line("a"), None);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+/// What happens to a player who runs out of time on their turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimeoutAction {
+    /// They pass. When passing isn't allowed they forfeit instead.
+    #[default]
+    AutoPass,
+    /// They resign from the game
+    Forfeit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ClockConfig {
+    /// Every player's starting time bank, in milliseconds
+    pub initial_ms: u64,
+    /// Added to a player's bank after each of their moves
+    pub increment_ms: u64,
+    pub on_timeout: TimeoutAction,
+}
+
+impl Default for ClockConfig {
+    fn default() -> ClockConfig {
+        ClockConfig {
+            initial_ms: 5 * 60 * 1000,
+            increment_ms: 5 * 1000,
+            on_timeout: TimeoutAction::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ClockStatus {
+    #[default]
+    Running,
+    /// Nobody's time runs down, for a lobby wide break or while a
+    /// disconnected player has a grace period to come back
+    Paused,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClockError {
+    /// The game has no clock to pause
+    NoClock,
+    AlreadyPaused,
+    NotPaused,
+}
+
+/// A chess clock with a time bank per player. The clock never reads the
+/// time itself: callers pass in how long the current turn has taken.
+///
+/// Pausing keeps the time the turn had taken so far, and after resuming
+/// callers start timing the turn from zero again. The clock adds the two
+/// together.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameClock {
+    config: ClockConfig,
+    remaining: BTreeMap<String, u64>,
+    #[serde(default)]
+    status: ClockStatus,
+    /// Time the current turn took before it was last paused
+    #[serde(default)]
+    turn_used_ms: u64,
+}
+
+impl GameClock {
+    pub fn new(player_ids: &[String], config: ClockConfig) -> GameClock {
+        GameClock {
+            config,
+            remaining: player_ids.iter()
+                .map(|id| (id.clone(), config.initial_ms))
+                .collect(),
+            status: ClockStatus::Running,
+            turn_used_ms: 0,
+        }
+    }
+
+    pub fn get_config(&self) -> ClockConfig {
+        self.config
+    }
+
+    /// Milliseconds left in a player's bank.
+    pub fn get_remaining(&self, player_id: &str) -> Option<u64> {
+        self.remaining.get(player_id).cloned()
+    }
+
+    pub fn get_status(&self) -> ClockStatus {
+        self.status
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.status == ClockStatus::Paused
+    }
+
+    /// Milliseconds the player has left on a turn that has taken
+    /// `elapsed_ms` since the clock was last started, so a server can set
+    /// its turn deadline. While paused this is the time left when the
+    /// clock stopped.
+    pub fn get_time_left(&self, player_id: &str, elapsed_ms: u64) -> Option<u64> {
+        let used = match self.status {
+            ClockStatus::Running => self.turn_used_ms + elapsed_ms,
+            ClockStatus::Paused => self.turn_used_ms,
+        };
+        self.get_remaining(player_id).map(|remaining| remaining.saturating_sub(used))
+    }
+
+    /// Whether a turn that has taken `elapsed_ms` so far has used up the
+    /// player's bank. Nobody runs out while the clock is paused.
+    pub fn has_run_out(&self, player_id: &str, elapsed_ms: u64) -> bool {
+        !self.is_paused() && self.get_time_left(player_id, elapsed_ms) == Some(0)
+    }
+
+    /// Takes a finished turn out of the player's bank, then adds the
+    /// increment. A turn that ran out of time empties the bank first.
+    pub fn charge(&mut self, player_id: &str, elapsed_ms: u64) {
+        let used = self.turn_used_ms + elapsed_ms;
+        if let Some(remaining) = self.remaining.get_mut(player_id) {
+            *remaining = remaining.saturating_sub(used) + self.config.increment_ms;
+            self.turn_used_ms = 0;
+        }
+    }
+
+    /// Stops the clock on a turn that has taken `elapsed_ms` since the
+    /// clock was last started.
+    pub fn pause(&mut self, elapsed_ms: u64) -> Result<(), ClockError> {
+        if self.is_paused() {
+            return Err(ClockError::AlreadyPaused);
+        }
+        self.status = ClockStatus::Paused;
+        self.turn_used_ms += elapsed_ms;
+        Ok(())
+    }
+
+    /// Starts the clock again. Callers time the rest of the turn from
+    /// zero.
+    pub fn resume(&mut self) -> Result<(), ClockError> {
+        if !self.is_paused() {
+            return Err(ClockError::NotPaused);
+        }
+        self.status = ClockStatus::Running;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns_are_charged_and_then_incremented() {
+        let config = ClockConfig {
+            initial_ms: 1000,
+            increment_ms: 100,
+            on_timeout: TimeoutAction::Forfeit,
+        };
+        let mut clock = GameClock::new(&["a".to_string(), "b".to_string()], config);
+
+        clock.charge("a", 400);
+        assert_eq!(clock.get_remaining("a"), Some(700));
+        assert_eq!(clock.get_remaining("b"), Some(1000));
+
+        assert!(!clock.has_run_out("a", 699));
+        assert!(clock.has_run_out("a", 700));
+
+        clock.charge("a", 5000);
+        assert_eq!(clock.get_remaining("a"), Some(100));
+    }
+
+    #[test]
+    fn paused_clocks_keep_the_time_the_turn_has_taken() {
+        let config = ClockConfig {
+            initial_ms: 1000,
+            increment_ms: 100,
+            on_timeout: TimeoutAction::Forfeit,
+        };
+        let mut clock = GameClock::new(&["a".to_string()], config);
+
+        assert_eq!(clock.pause(300), Ok(()));
+        assert_eq!(clock.pause(0), Err(ClockError::AlreadyPaused));
+        assert_eq!(clock.get_time_left("a", 5000), Some(700));
+        assert!(!clock.has_run_out("a", 5000));
+
+        let json = serde_json::to_string(&clock).unwrap();
+        let mut clock: GameClock = serde_json::from_str(&json).unwrap();
+        assert_eq!(clock.get_status(), ClockStatus::Paused);
+
+        assert_eq!(clock.resume(), Ok(()));
+        assert_eq!(clock.resume(), Err(ClockError::NotPaused));
+        assert_eq!(clock.get_time_left("a", 200), Some(500));
+        assert!(clock.has_run_out("a", 700));
+
+        clock.charge("a", 200);
+        assert_eq!(clock.get_remaining("a"), Some(600));
+        assert_eq!(clock.get_time_left("a", 0), Some(600));
+    }
+}
@@ -1,13 +1,40 @@
-use super::{Hand, TrickType, FlushPrecedence};
-use crate::cards::{Card, PlayedCard, Rank, Suit};
-use std::cmp::Ordering;
+use super::{Hand, TrickType, FlushPrecedence, OrderContext, Ruleset, StraightComparison};
+use crate::cards::{Card, PlayedCard};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use core::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// What decided a comparison between two hands
+pub enum ComparisonReason {
+    /// Hands with different numbers of cards can't be compared
+    DifferentCardCount,
+    /// A pass can't be beaten or beat anything
+    Pass,
+    /// Five card tricks of different types
+    TrickType,
+    /// The deciding cards differ in rank
+    Rank,
+    /// The deciding cards share a rank and differ in suit
+    Suit,
+    /// Flushes compared on suit first, as set by `FlushPrecedence::Suit`
+    FlushSuit,
+    /// Nothing separates the hands
+    Equal,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HandComparison {
+    /// How the new hand relates to the last move; `Greater` means it beats it
+    pub ordering: Ordering,
+    pub reason: ComparisonReason,
+}
 
 pub fn compare_hands(
     last_move: Hand,
     new_hand: Hand,
     flush_precedence: FlushPrecedence,
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13],
+    orders: &OrderContext,
 ) -> bool {
     let last_cards = last_move.to_cards();
     let new_cards = new_hand.to_cards();
@@ -21,46 +48,230 @@ pub fn compare_hands(
         | Hand::Pair(_, _)
         | Hand::Prial(_, _, _) => {
             let last_card = get_top_card(
-                last_cards, suit_order, rank_order
+                last_cards, orders
             );
             let new_card = get_top_card(
-                new_cards, suit_order, rank_order
+                new_cards, orders
             );
             compare_single(
-                last_card, new_card, suit_order, rank_order
+                last_card, new_card, orders
             ) == Ordering::Greater
         }
         Hand::FiveCardTrick(_) => compare_five_cards(
             last_move,
             new_hand,
-            suit_order,
-            rank_order,
+            orders,
             flush_precedence
         ),
         _ => false,
     }
 }
 
+/// Whether `new_hand` beats `last_move` under every comparison rule in
+/// `ruleset`.
+pub fn compare_hands_with_ruleset(
+    last_move: Hand,
+    new_hand: Hand,
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> bool {
+    if ruleset.cross_count_bombs.can_beat(new_hand, last_move) {
+        return true;
+    }
+
+    let (last_move, new_hand) = match ruleset.deal_mode.get_low_ace_rank(orders) {
+        Some(low_ace) => (
+            last_move.with_low_ace(low_ace, orders),
+            new_hand.with_low_ace(low_ace, orders),
+        ),
+        None => (last_move, new_hand),
+    };
+
+    if let (Hand::FiveCardTrick(last_trick), Hand::FiveCardTrick(new_trick)) = (last_move, new_hand) {
+        let last_tier = ruleset.trick_ranking.get_tier(last_trick.trick_type);
+        let new_tier = ruleset.trick_ranking.get_tier(new_trick.trick_type);
+        if new_tier.is_none() {
+            return false;
+        }
+        if last_tier != new_tier {
+            return new_tier > last_tier;
+        }
+
+        let both_straights = last_trick.trick_type == TrickType::Straight
+            && new_trick.trick_type == TrickType::Straight;
+        if both_straights && ruleset.straight_comparison == StraightComparison::RankOnly {
+            let last_card = get_top_card(last_move.to_cards(), orders);
+            let new_card = get_top_card(new_hand.to_cards(), orders);
+            return compare_rank(last_card, new_card, orders) == Ordering::Greater;
+        }
+    }
+
+    compare_hands(
+        last_move,
+        new_hand,
+        ruleset.flush_precedence,
+        orders,
+    )
+}
+
+impl Hand {
+    /// Whether this hand can be played on top of `other`. It's the check
+    /// rounds make for every move, so clients can predict the result of a
+    /// move before sending it.
+    pub fn beats(
+        &self,
+        other: &Hand,
+        ruleset: Ruleset,
+        orders: &OrderContext,
+    ) -> bool {
+        compare_hands_with_ruleset(*other, *self, ruleset, orders)
+    }
+}
+
+/// Same decision as `compare_hands`, but reporting the ordering of the new
+/// hand against the last move and which rule decided it.
+pub fn compare_hands_explained(
+    last_move: Hand,
+    new_hand: Hand,
+    flush_precedence: FlushPrecedence,
+    orders: &OrderContext,
+) -> HandComparison {
+    let last_cards = last_move.to_cards();
+    let new_cards = new_hand.to_cards();
+
+    if last_cards.len() != new_cards.len() {
+        return HandComparison {
+            ordering: Ordering::Less,
+            reason: ComparisonReason::DifferentCardCount,
+        };
+    }
+
+    match (last_move, new_hand) {
+        (Hand::FiveCardTrick(last_trick), Hand::FiveCardTrick(new_trick)) => {
+            if last_trick.trick_type != new_trick.trick_type {
+                return HandComparison {
+                    ordering: new_trick.trick_type.cmp(&last_trick.trick_type),
+                    reason: ComparisonReason::TrickType,
+                };
+            }
+
+            let (last_card, new_card) = match last_trick.trick_type {
+                TrickType::FullHouse => (
+                    get_top_of_n(last_cards, 3, orders),
+                    get_top_of_n(new_cards, 3, orders),
+                ),
+                TrickType::FourOfAKind => (
+                    get_top_of_n(last_cards, 4, orders),
+                    get_top_of_n(new_cards, 4, orders),
+                ),
+                _ => (
+                    get_top_card(last_cards, orders),
+                    get_top_card(new_cards, orders),
+                ),
+            };
+
+            let is_flush = last_trick.trick_type == TrickType::Flush
+                || last_trick.trick_type == TrickType::StraightFlush
+                || last_trick.trick_type == TrickType::RoyalFlush;
+            if is_flush && flush_precedence == FlushPrecedence::Suit {
+                let suit_comparison = compare_suits(
+                    last_card, new_card, orders
+                );
+                if suit_comparison != Ordering::Equal {
+                    return HandComparison {
+                        ordering: suit_comparison,
+                        reason: ComparisonReason::FlushSuit,
+                    };
+                }
+            }
+
+            if is_flush && flush_precedence == FlushPrecedence::AllCards {
+                return explain_card_by_card(
+                    &last_trick.cards, &new_trick.cards, orders
+                );
+            }
+
+            explain_single(last_card, new_card, orders)
+        },
+        (Hand::Pass, _) | (_, Hand::Pass) => HandComparison {
+            ordering: Ordering::Equal,
+            reason: ComparisonReason::Pass,
+        },
+        _ => explain_single(
+            get_top_card(last_cards, orders),
+            get_top_card(new_cards, orders),
+            orders,
+        ),
+    }
+}
+
+fn explain_card_by_card(
+    last_cards: &[PlayedCard],
+    new_cards: &[PlayedCard],
+    orders: &OrderContext,
+) -> HandComparison {
+    let last_cards = sort_played_cards(last_cards, orders);
+    let new_cards = sort_played_cards(new_cards, orders);
+
+    let rank_comparison = last_cards.iter()
+        .zip(new_cards.iter())
+        .map(|(&last, &new)| compare_rank(last, new, orders))
+        .find(|&ordering| ordering != Ordering::Equal);
+
+    match rank_comparison {
+        Some(ordering) => HandComparison {
+            ordering,
+            reason: ComparisonReason::Rank,
+        },
+        None => explain_single(
+            last_cards[0], new_cards[0], orders
+        ),
+    }
+}
+
+fn explain_single(
+    last_card: PlayedCard,
+    new_card: PlayedCard,
+    orders: &OrderContext,
+) -> HandComparison {
+    let rank_comparison = compare_rank(last_card, new_card, orders);
+    if rank_comparison != Ordering::Equal {
+        return HandComparison {
+            ordering: rank_comparison,
+            reason: ComparisonReason::Rank,
+        };
+    }
+
+    match compare_suits(last_card, new_card, orders) {
+        Ordering::Equal => HandComparison {
+            ordering: Ordering::Equal,
+            reason: ComparisonReason::Equal,
+        },
+        ordering => HandComparison {
+            ordering,
+            reason: ComparisonReason::Suit,
+        },
+    }
+}
+
 pub fn sort_played_cards(
     hand: &[PlayedCard],
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13]
+    orders: &OrderContext,
 ) -> Vec<PlayedCard> {
     let mut sortable_cards = hand.to_owned();
     sortable_cards.sort_by(
-        |&a, &b| compare_single(a, b, suit_order, rank_order)
+        |&a, &b| compare_single(a, b, orders)
     );
     sortable_cards
 }
 
 pub fn sort_unplayed_cards(
     hand: &[Card],
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13]
+    orders: &OrderContext,
 ) -> Vec<Card> {
     let mut sortable_cards = hand.to_owned();
     sortable_cards.sort_by(
-        |&a, &b| compare_single_unplayed(a, b, suit_order, rank_order)
+        |&a, &b| compare_single_unplayed(a, b, orders)
     );
     sortable_cards
 }
@@ -68,13 +279,12 @@ pub fn sort_unplayed_cards(
 fn compare_single(
     last_card: PlayedCard,
     new_card: PlayedCard,
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13],
+    orders: &OrderContext,
 ) -> Ordering {
-    let rank_comparison = compare_rank(last_card, new_card, rank_order);
+    let rank_comparison = compare_rank(last_card, new_card, orders);
 
     match rank_comparison {
-        Ordering::Equal => compare_suits(last_card, new_card, suit_order),
+        Ordering::Equal => compare_suits(last_card, new_card, orders),
         x => x,
     }
 }
@@ -82,23 +292,15 @@ fn compare_single(
 fn compare_single_unplayed(
     last_card: Card,
     new_card: Card,
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13],
+    orders: &OrderContext,
 ) -> Ordering {
-    let rank_comparison = compare_rank_unplayed(last_card, new_card, rank_order);
-
-    match rank_comparison {
-        Ordering::Equal => compare_suits_unplayed(last_card, new_card, suit_order),
-        x => x,
-    }
-
+    new_card.cmp_with(&last_card, orders)
 }
 
 pub fn compare_five_cards(
     last_move: Hand,
     new_hand: Hand,
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13],
+    orders: &OrderContext,
     flush_precedence: FlushPrecedence,
 ) -> bool {
     let last_trick = match last_move {
@@ -125,39 +327,44 @@ pub fn compare_five_cards(
         TrickType::Straight
         | TrickType::FiveOfAKind => {
             let last_card = get_top_card(
-                last_cards, suit_order, rank_order
+                last_cards, orders
             );
             let new_card = get_top_card(
-                new_cards, suit_order, rank_order
+                new_cards, orders
             );
             compare_single(
-                last_card, new_card, suit_order, rank_order
+                last_card, new_card, orders
             )
         },
         TrickType::Flush
-        | TrickType::StraightFlush => {
+        | TrickType::StraightFlush
+        | TrickType::RoyalFlush => {
             let last_card = get_top_card(
-                last_cards, suit_order, rank_order
+                last_cards, orders
             );
             let new_card = get_top_card(
-                new_cards, suit_order, rank_order
+                new_cards, orders
             );
 
-            if flush_precedence == FlushPrecedence::Suit {
-                let rank_comparison = compare_suits(
-                    last_card, new_card, suit_order
-                );
-
-                match rank_comparison {
-                    Ordering::Equal => compare_rank(
-                        last_card, new_card, rank_order
-                    ),
-                    x => x,
-                }
-            } else {
-                compare_single(
-                    last_card, new_card, suit_order, rank_order
-                )
+            match flush_precedence {
+                FlushPrecedence::Suit => {
+                    let rank_comparison = compare_suits(
+                        last_card, new_card, orders
+                    );
+
+                    match rank_comparison {
+                        Ordering::Equal => compare_rank(
+                            last_card, new_card, orders
+                        ),
+                        x => x,
+                    }
+                },
+                FlushPrecedence::Rank => compare_single(
+                    last_card, new_card, orders
+                ),
+                FlushPrecedence::AllCards => explain_card_by_card(
+                    &last_trick.cards, &new_trick.cards, orders
+                ).ordering,
             }
 
         },
@@ -169,14 +376,14 @@ pub fn compare_five_cards(
             };
 
             let last_card = get_top_of_n(
-                last_cards, set_count, suit_order, rank_order
+                last_cards, set_count, orders
             );
             let new_card = get_top_of_n(
-                new_cards, set_count, suit_order, rank_order
+                new_cards, set_count, orders
             );
 
             compare_single(
-                last_card, new_card, suit_order, rank_order
+                last_card, new_card, orders
             )
         }
     };
@@ -186,24 +393,21 @@ pub fn compare_five_cards(
 
 fn get_top_card(
     cards: Vec<PlayedCard>,
-    suit_order: [Suit; 4],
-    rank_order: [Rank; 13],
+    orders: &OrderContext,
 ) -> PlayedCard {
     *sort_played_cards(
         &cards,
-        suit_order,
-        rank_order
+        orders
     ).first().expect("no cards found")
 }
 
 fn get_top_of_n(
     cards: Vec<PlayedCard>,
     n: usize,
-    suits_order: [Suit; 4],
-    rank_order: [Rank; 13],
+    orders: &OrderContext,
 ) -> PlayedCard {
     let counts = Hand::get_counts(cards.clone());
-    let mut top_rank = *rank_order.first().unwrap();
+    let mut top_rank = orders.lowest_rank();
 
     for (rank, count) in &counts {
         if *count == n {
@@ -217,91 +421,30 @@ fn get_top_of_n(
         .cloned()
         .collect();
 
-    get_top_card(valid_cards, suits_order, rank_order)
+    get_top_card(valid_cards, orders)
 }
 
-fn compare_suits(card1: PlayedCard, card2: PlayedCard, suit_order: [Suit; 4]) -> Ordering {
-    let c1_i = get_suit_index(card1, suit_order);
-    let c2_i = get_suit_index(card2, suit_order);
+fn compare_suits(card1: PlayedCard, card2: PlayedCard, orders: &OrderContext) -> Ordering {
+    let c1_i = orders.suit_index(card1.get_suit());
+    let c2_i = orders.suit_index(card2.get_suit());
 
     c2_i.cmp(&c1_i)
 }
 
-fn compare_rank(card1: PlayedCard, card2: PlayedCard, rank_order: [Rank; 13]) -> Ordering {
-    let c1_i = get_rank_index(card1, rank_order);
-    let c2_i = get_rank_index(card2, rank_order);
+fn compare_rank(card1: PlayedCard, card2: PlayedCard, orders: &OrderContext) -> Ordering {
+    let c1_i = orders.rank_index(card1.get_rank());
+    let c2_i = orders.rank_index(card2.get_rank());
 
     c2_i.cmp(&c1_i)
 }
 
-fn get_suit_index(card: PlayedCard, suit_order: [Suit; 4]) -> Option<usize> {
-    suit_order.iter().position(|&x| x == card.get_suit())
-}
-
-fn get_rank_index(card: PlayedCard, rank_order: [Rank; 13]) -> Option<usize> {
-    rank_order.iter().position(|&x| x == card.get_rank())
-}
-
-fn compare_suits_unplayed(card1: Card, card2: Card, suit_order: [Suit; 4]) -> Ordering {
-    let c1_i = get_suit_index_unplayed(card1, suit_order);
-    let c2_i = get_suit_index_unplayed(card2, suit_order);
-
-    c2_i.cmp(&c1_i)
-}
-
-fn compare_rank_unplayed(card1: Card, card2: Card, rank_order: [Rank; 13]) -> Ordering {
-    let mut c1_i:u8 = get_rank_index_unplayed(card1, rank_order)
-        .expect("unable to find rank index") as u8;
-    let mut c2_i:u8 = get_rank_index_unplayed(card2, rank_order)
-        .expect("unable to find rank index") as u8;
-
-    if let Card::Joker{ .. } = card1 {
-        c1_i += 1;
-    }
-
-    if let Card::Joker{ .. } = card2 {
-        c2_i += 1;
-    }
-
-    c2_i.cmp(&c1_i)
-}
-
-fn get_suit_index_unplayed(card: Card, suit_order: [Suit; 4]) -> Option<usize> {
-    suit_order.iter().position(|&x| 
-        x == card.get_suit().unwrap_or(suit_order[3])
-    )
-}
-
-fn get_rank_index_unplayed(card: Card, rank_order: [Rank; 13]) -> Option<usize> {
-    rank_order.iter().position(|&x|
-        x == card.get_rank().unwrap_or(rank_order[12])
-    )
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cards::{PlayedCard, Rank, Suit};
+    use crate::cards::{get_rank_array, PlayedCard, Rank, Suit};
     use crate::game::hands::*;
-
-    static DEFAULT_SUIT_ORDER: [Suit; 4] =
-        [Suit::Clubs, Suit::Hearts, Suit::Diamonds, Suit::Spades];
-
-    static DEFAULT_RANK_ORDER: [Rank; 13] = [
-        Rank::Three,
-        Rank::Four,
-        Rank::Five,
-        Rank::Six,
-        Rank::Seven,
-        Rank::Eight,
-        Rank::Nine,
-        Rank::Ten,
-        Rank::Jack,
-        Rank::Queen,
-        Rank::King,
-        Rank::Ace,
-        Rank::Two,
-    ];
+    use crate::scenario::parse_played_cards;
+    use crate::game::{CrossCountBombs, TrickRanking};
 
     #[test]
     fn it_can_compare_singles() {
@@ -312,8 +455,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -326,8 +468,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -340,8 +481,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -354,8 +494,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -368,8 +507,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -388,8 +526,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -408,8 +545,7 @@ mod tests {
             hand2,
             hand1,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -428,8 +564,7 @@ mod tests {
             hand2,
             hand1,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -450,8 +585,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -472,8 +606,7 @@ mod tests {
             hand2,
             hand1,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -494,8 +627,7 @@ mod tests {
             hand2,
             hand1,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -523,16 +655,14 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
 
         assert!(!compare_hands(
             hand2,
             hand1,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -560,8 +690,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -589,8 +718,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -618,8 +746,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -647,8 +774,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -676,8 +802,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -705,8 +830,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -734,8 +858,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -763,8 +886,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -792,8 +914,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -812,8 +933,7 @@ mod tests {
 
         let sorted_hand = sort_played_cards(
             &hand,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         );
 
         assert_eq!(
@@ -849,8 +969,7 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Rank,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
@@ -878,9 +997,255 @@ mod tests {
             hand1,
             hand2,
             FlushPrecedence::Suit,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            &OrderContext::standard(),
         ));
     }
 
+    #[test]
+    fn explained_comparison_reports_the_deciding_rule() {
+        let three_clubs = Hand::Single(PlayedCard::new(Rank::Three, Suit::Clubs, false));
+        let three_spades = Hand::Single(PlayedCard::new(Rank::Three, Suit::Spades, false));
+        let four_clubs = Hand::Single(PlayedCard::new(Rank::Four, Suit::Clubs, false));
+
+        let by_rank = compare_hands_explained(
+            four_clubs, three_spades, FlushPrecedence::Rank,
+            &OrderContext::standard(),
+        );
+        let by_suit = compare_hands_explained(
+            three_clubs, three_spades, FlushPrecedence::Rank,
+            &OrderContext::standard(),
+        );
+
+        assert_eq!(by_rank, HandComparison {
+            ordering: Ordering::Less,
+            reason: ComparisonReason::Rank,
+        });
+        assert_eq!(by_suit, HandComparison {
+            ordering: Ordering::Greater,
+            reason: ComparisonReason::Suit,
+        });
+    }
+
+    #[test]
+    fn explained_comparison_reports_trick_type_and_card_count() {
+        let straight = Hand::build(vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Four, Suit::Hearts, false),
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+            PlayedCard::new(Rank::Seven, Suit::Clubs, false),
+        ]).unwrap();
+        let flush = Hand::build(vec![
+            PlayedCard::new(Rank::Three, Suit::Hearts, false),
+            PlayedCard::new(Rank::Four, Suit::Hearts, false),
+            PlayedCard::new(Rank::Five, Suit::Hearts, false),
+            PlayedCard::new(Rank::Six, Suit::Hearts, false),
+            PlayedCard::new(Rank::Nine, Suit::Hearts, false),
+        ]).unwrap();
+        let single = Hand::Single(PlayedCard::new(Rank::Two, Suit::Spades, false));
+
+        assert_eq!(
+            compare_hands_explained(
+                straight, flush, FlushPrecedence::Rank,
+                &OrderContext::standard(),
+            ).reason,
+            ComparisonReason::TrickType
+        );
+        assert_eq!(
+            compare_hands_explained(
+                straight, single, FlushPrecedence::Rank,
+                &OrderContext::standard(),
+            ),
+            HandComparison {
+                ordering: Ordering::Less,
+                reason: ComparisonReason::DifferentCardCount,
+            }
+        );
+    }
+
+    #[test]
+    fn explained_comparison_reports_flush_suit_precedence() {
+        let low_spades = Hand::build(vec![
+            PlayedCard::new(Rank::Three, Suit::Spades, false),
+            PlayedCard::new(Rank::Four, Suit::Spades, false),
+            PlayedCard::new(Rank::Five, Suit::Spades, false),
+            PlayedCard::new(Rank::Six, Suit::Spades, false),
+            PlayedCard::new(Rank::Nine, Suit::Spades, false),
+        ]).unwrap();
+        let high_clubs = Hand::build(vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+            PlayedCard::new(Rank::Ace, Suit::Clubs, false),
+        ]).unwrap();
+
+        let comparison = compare_hands_explained(
+            high_clubs, low_spades, FlushPrecedence::Suit,
+            &OrderContext::standard(),
+        );
+
+        assert_eq!(comparison, HandComparison {
+            ordering: Ordering::Greater,
+            reason: ComparisonReason::FlushSuit,
+        });
+        assert!(compare_hands(
+            high_clubs, low_spades, FlushPrecedence::Suit,
+            &OrderContext::standard(),
+        ));
+    }
+
+    #[test]
+    fn flushes_can_be_compared_card_by_card() {
+        let flush = |suit, ranks: [Rank; 5]| Hand::build(
+            ranks.iter().map(|&r| PlayedCard::new(r, suit, false)).collect()
+        ).unwrap();
+        let low_kicker = flush(
+            Suit::Spades,
+            [Rank::Three, Rank::Five, Rank::Six, Rank::Seven, Rank::Ace]
+        );
+        let high_kicker = flush(
+            Suit::Clubs,
+            [Rank::Three, Rank::Five, Rank::Six, Rank::Nine, Rank::Ace]
+        );
+
+        assert!(!compare_hands(
+            low_kicker, high_kicker, FlushPrecedence::Rank,
+            &OrderContext::standard(),
+        ));
+        assert_eq!(
+            compare_hands_explained(
+                low_kicker, high_kicker, FlushPrecedence::AllCards,
+                &OrderContext::standard(),
+            ),
+            HandComparison {
+                ordering: Ordering::Greater,
+                reason: ComparisonReason::Rank,
+            }
+        );
+        assert!(compare_hands(
+            low_kicker, high_kicker, FlushPrecedence::AllCards,
+            &OrderContext::standard(),
+        ));
+    }
+
+    #[test]
+    fn eagles_flushes_follow_a_five_suit_order() {
+        let flush = |cards| Hand::build(parse_played_cards(cards).unwrap()).unwrap();
+        let eagles = flush("3E 5E 6E 7E 9E");
+        let spades = flush("3S 5S 6S 7S 9S");
+        let five_suits = OrderContext::new(
+            &[Suit::Clubs, Suit::Hearts, Suit::Diamonds, Suit::Spades, Suit::Eagles],
+            get_rank_array(),
+        ).unwrap();
+
+        assert_eq!(eagles.get_trick_type(), Some(TrickType::Flush));
+        assert!(compare_hands(spades, eagles, FlushPrecedence::Suit, &five_suits));
+        assert!(!compare_hands(eagles, spades, FlushPrecedence::Suit, &five_suits));
+        assert!(compare_hands(eagles, spades, FlushPrecedence::Suit, &five_suits.reverse()));
+    }
+
+    #[test]
+    fn straights_can_ignore_the_suit_of_the_top_card() {
+        let straight = |top_suit| Hand::build(vec![
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+            PlayedCard::new(Rank::Five, Suit::Hearts, false),
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+            PlayedCard::new(Rank::Seven, Suit::Diamonds, false),
+            PlayedCard::new(Rank::Eight, top_suit, false),
+        ]).unwrap();
+        let higher_straight = Hand::build(vec![
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Six, Suit::Hearts, false),
+            PlayedCard::new(Rank::Seven, Suit::Clubs, false),
+            PlayedCard::new(Rank::Eight, Suit::Diamonds, false),
+            PlayedCard::new(Rank::Nine, Suit::Clubs, false),
+        ]).unwrap();
+        let mut ruleset = Ruleset::default();
+
+        assert!(compare_hands_with_ruleset(
+            straight(Suit::Clubs), straight(Suit::Spades), ruleset,
+            &OrderContext::standard(),
+        ));
+
+        ruleset.straight_comparison = StraightComparison::RankOnly;
+
+        assert!(!compare_hands_with_ruleset(
+            straight(Suit::Clubs), straight(Suit::Spades), ruleset,
+            &OrderContext::standard(),
+        ));
+        assert!(compare_hands_with_ruleset(
+            straight(Suit::Spades), higher_straight, ruleset,
+            &OrderContext::standard(),
+        ));
+    }
+
+    #[test]
+    fn hands_beat_lower_hands_of_the_same_size() {
+        let three = Hand::Single(PlayedCard::new(Rank::Three, Suit::Clubs, false));
+        let four = Hand::Single(PlayedCard::new(Rank::Four, Suit::Clubs, false));
+        let pair = Hand::Pair(
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Five, Suit::Hearts, false),
+        );
+        let ruleset = Ruleset::default();
+
+        assert!(four.beats(&three, ruleset, &OrderContext::standard()));
+        assert!(!three.beats(&four, ruleset, &OrderContext::standard()));
+        assert!(!pair.beats(&three, ruleset, &OrderContext::standard()));
+
+        assert!(three.beats(&four, ruleset, &OrderContext::standard().reverse()));
+    }
+
+    #[test]
+    fn the_ruleset_can_reorder_trick_types() {
+        let flush = Hand::build(vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Seven, Suit::Clubs, false),
+            PlayedCard::new(Rank::Nine, Suit::Clubs, false),
+            PlayedCard::new(Rank::Jack, Suit::Clubs, false),
+        ]).unwrap();
+        let full_house = Hand::build(vec![
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+            PlayedCard::new(Rank::Four, Suit::Hearts, false),
+            PlayedCard::new(Rank::Four, Suit::Spades, false),
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+            PlayedCard::new(Rank::Six, Suit::Hearts, false),
+        ]).unwrap();
+        let mut ruleset = Ruleset::default();
+
+        assert!(full_house.beats(&flush, ruleset, &OrderContext::standard()));
+
+        ruleset.trick_ranking = TrickRanking::from_order(&[
+            TrickType::Straight,
+            TrickType::FullHouse,
+            TrickType::Flush,
+            TrickType::FourOfAKind,
+            TrickType::StraightFlush,
+        ]);
+
+        assert!(flush.beats(&full_house, ruleset, &OrderContext::standard()));
+        assert!(!full_house.beats(&flush, ruleset, &OrderContext::standard()));
+    }
+
+    #[test]
+    fn designated_bombs_can_beat_smaller_hands() {
+        let four_of_a_kind = Hand::build(vec![
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Five, Suit::Hearts, false),
+            PlayedCard::new(Rank::Five, Suit::Diamonds, false),
+            PlayedCard::new(Rank::Five, Suit::Spades, false),
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+        ]).unwrap();
+        let two = Hand::Single(PlayedCard::new(Rank::Two, Suit::Spades, false));
+        let mut ruleset = Ruleset::default();
+
+        assert!(!four_of_a_kind.beats(&two, ruleset, &OrderContext::standard()));
+
+        ruleset.cross_count_bombs = CrossCountBombs { four_of_a_kind: true, ..CrossCountBombs::none() };
+
+        assert!(four_of_a_kind.beats(&two, ruleset, &OrderContext::standard()));
+        assert!(!two.beats(&four_of_a_kind, ruleset, &OrderContext::standard()));
+    }
 }
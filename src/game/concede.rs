@@ -0,0 +1,78 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConcedeError {
+    UnknownPlayer,
+    /// Only players still holding cards take part in a vote
+    NotVoting,
+    GameOver,
+    /// Only one vote can be open at a time
+    VoteInProgress,
+    NoVote,
+    AlreadyVoted,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ConcedeOutcome {
+    /// Some players still have to vote
+    Pending,
+    /// Everyone agreed and the game has ended
+    Accepted,
+    /// Someone voted against and the vote was dropped
+    Rejected,
+}
+
+/// A vote to end the game early and score the hands as they stand. It
+/// passes only if every player still holding cards agrees.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConcedeVote {
+    proposer: String,
+    voters: Vec<String>,
+    agreed: Vec<String>,
+}
+
+impl ConcedeVote {
+    pub(crate) fn new(proposer: &str, voters: Vec<String>) -> ConcedeVote {
+        ConcedeVote {
+            proposer: proposer.to_string(),
+            voters,
+            agreed: vec![proposer.to_string()],
+        }
+    }
+
+    pub fn get_proposer(&self) -> &str {
+        &self.proposer
+    }
+
+    /// Players who haven't voted yet.
+    pub fn get_waiting_on(&self) -> Vec<String> {
+        self.voters.iter()
+            .filter(|id| !self.agreed.contains(id))
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn vote(&mut self, player_id: &str, agree: bool) -> Result<ConcedeOutcome, ConcedeError> {
+        if !self.voters.iter().any(|id| id == player_id) {
+            return Err(ConcedeError::NotVoting);
+        }
+        if self.agreed.iter().any(|id| id == player_id) {
+            return Err(ConcedeError::AlreadyVoted);
+        }
+        if !agree {
+            return Ok(ConcedeOutcome::Rejected);
+        }
+
+        self.agreed.push(player_id.to_string());
+        Ok(self.get_outcome())
+    }
+
+    pub(crate) fn get_outcome(&self) -> ConcedeOutcome {
+        if self.get_waiting_on().is_empty() {
+            ConcedeOutcome::Accepted
+        } else {
+            ConcedeOutcome::Pending
+        }
+    }
+}
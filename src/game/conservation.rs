@@ -0,0 +1,121 @@
+use super::Round;
+use crate::cards::Card;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use alloc::collections::BTreeMap;
+
+/// A card whose dealt count doesn't match the number held plus the number
+/// discarded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct CardMismatch {
+    pub card: Card,
+    pub dealt: usize,
+    pub in_hands: usize,
+    pub discarded: usize,
+}
+
+impl Round {
+    /// Checks that every dealt card is either in a hand or on the discard
+    /// pile, and that nothing else is. Any mismatch points at corrupted
+    /// state.
+    pub fn verify_card_conservation(&self) -> Result<(), Vec<CardMismatch>> {
+        let mut counts: BTreeMap<Card, (usize, usize, usize)> = BTreeMap::new();
+
+        for card in self.get_dealt_cards() {
+            counts.entry(card).or_default().0 += 1;
+        }
+        for player in self.get_player_ids() {
+            let hand = self.get_player(&player)
+                .map(|p| p.get_hand())
+                .unwrap_or_default();
+            for card in hand {
+                counts.entry(card).or_default().1 += 1;
+            }
+        }
+        for card in self.get_discarded() {
+            counts.entry(card).or_default().2 += 1;
+        }
+
+        let mismatches: Vec<CardMismatch> = counts.into_iter()
+            .filter(|(_, (dealt, in_hands, discarded))| {
+                *dealt != in_hands + discarded
+            })
+            .map(|(card, (dealt, in_hands, discarded))| CardMismatch {
+                card,
+                dealt,
+                in_hands,
+                discarded,
+            })
+            .collect();
+
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{Agent, LowestCardAgent};
+    use crate::cards::{get_suit_array, PlayedCard, Rank, Suit};
+    use crate::game::{Game, Player, RoundBuilder, Ruleset};
+    use crate::scenario::parse_cards;
+
+    #[test]
+    fn cards_are_conserved_through_a_game() {
+        let ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let mut game = Game::new(1, 2, &ids, &get_suit_array(), Ruleset::default()).unwrap();
+
+        let mut agent = LowestCardAgent;
+
+        for _ in 0..20 {
+            let next = match game.get_next_player() {
+                Some(next) => next,
+                None => break,
+            };
+            let cards = agent.choose_move(game.get_round(), &next);
+            game.play_move(&next, cards).unwrap();
+
+            assert_eq!(game.get_round().verify_card_conservation(), Ok(()));
+        }
+
+        assert!(!game.get_round().get_discarded().is_empty());
+    }
+
+    #[test]
+    fn discarded_cards_from_the_builder_count_as_dealt() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("4C").unwrap()))
+            .discarded(parse_cards("3C").unwrap())
+            .build()
+            .unwrap();
+
+        assert_eq!(round.verify_card_conservation(), Ok(()));
+        assert_eq!(round.get_dealt_cards().len(), 2);
+    }
+
+    #[test]
+    fn mismatches_are_reported_per_card() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C 4C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("5C").unwrap()))
+            .build()
+            .unwrap();
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+        let round = round.submit_move("a", vec![three]).unwrap();
+        let mut tampered = round.clone();
+        tampered.set_discarded(vec![]);
+
+        assert_eq!(round.verify_card_conservation(), Ok(()));
+        assert_eq!(tampered.verify_card_conservation(), Err(vec![CardMismatch {
+            card: "3C".parse::<Card>().unwrap(),
+            dealt: 1,
+            in_hands: 0,
+            discarded: 0,
+        }]));
+    }
+}
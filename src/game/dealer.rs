@@ -0,0 +1,217 @@
+use super::check_player_ids;
+#[cfg(feature = "std")]
+use super::{Game, Handicap, Ruleset, SetupError};
+use crate::cards::Deck;
+#[cfg(feature = "std")]
+use crate::cards::Suit;
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use alloc::collections::BTreeMap;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Tracks who deals across the games of a match. The deal goes to the
+/// dealer's left, so the dealer is always dealt to last, and passes to
+/// the next seat after every game.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Dealer {
+    player_ids: Vec<String>,
+    dealer: usize,
+}
+
+impl Dealer {
+    /// `player_ids` is the seating order. Returns `None` if `dealer_id`
+    /// isn't seated or an id is repeated.
+    pub fn new(player_ids: &[String], dealer_id: &str) -> Option<Dealer> {
+        check_player_ids(player_ids).ok()?;
+        let dealer = player_ids.iter().position(|id| id == dealer_id)?;
+
+        Some(Dealer {
+            player_ids: player_ids.to_vec(),
+            dealer,
+        })
+    }
+
+    /// Every player cuts a card from a shuffled deck and the highest card
+    /// deals first.
+    pub fn cut_for_deal<R: Rng>(player_ids: &[String], rng: &mut R) -> Dealer {
+        let mut cards = Deck::new(1, 0).to_vec();
+        cards.shuffle(rng);
+
+        let dealer = cards.iter()
+            .take(player_ids.len())
+            .enumerate()
+            .max_by_key(|(_, card)| (card.get_rank(), card.get_suit()))
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+
+        Dealer {
+            player_ids: player_ids.to_vec(),
+            dealer,
+        }
+    }
+
+    pub fn get_dealer(&self) -> &str {
+        &self.player_ids[self.dealer]
+    }
+
+    /// The players in the order they are dealt to, starting on the
+    /// dealer's left and ending with the dealer.
+    pub fn get_deal_order(&self) -> Vec<String> {
+        let seats = self.player_ids.len();
+
+        (1..=seats)
+            .map(|offset| self.player_ids[(self.dealer + offset) % seats].clone())
+            .collect()
+    }
+
+    /// The players in seating order.
+    pub fn get_player_ids(&self) -> Vec<String> {
+        self.player_ids.clone()
+    }
+
+    /// Seats `player_id` on the dealer's right, so they're the last to
+    /// deal. Returns `false` if they're already seated.
+    pub fn add_player(&mut self, player_id: &str) -> bool {
+        if self.player_ids.iter().any(|id| id == player_id) {
+            return false;
+        }
+
+        self.player_ids.insert(self.dealer, player_id.to_string());
+        self.dealer += 1;
+        true
+    }
+
+    /// Takes `player_id` out of the seating. If they were due to deal,
+    /// the deal passes to their left. Returns `false` if they aren't
+    /// seated or are the last player left.
+    pub fn remove_player(&mut self, player_id: &str) -> bool {
+        let seat = match self.player_ids.iter().position(|id| id == player_id) {
+            Some(seat) if self.player_ids.len() > 1 => seat,
+            _ => return false,
+        };
+
+        self.player_ids.remove(seat);
+        if seat < self.dealer {
+            self.dealer -= 1;
+        }
+        self.dealer %= self.player_ids.len();
+        true
+    }
+
+    pub fn rotate(&mut self) {
+        self.dealer = (self.dealer + 1) % self.player_ids.len();
+    }
+
+    /// Deals a new game in the current deal order and passes the deal on.
+    #[cfg(feature = "std")]
+    pub fn deal_game(
+        &mut self,
+        num_decks: u8,
+        num_jokers: u8,
+        suit_order: &[Suit],
+        ruleset: Ruleset
+    ) -> Result<Game, SetupError> {
+        self.deal_game_with_handicaps(num_decks, num_jokers, suit_order, ruleset, &BTreeMap::new())
+    }
+
+    /// Deals a new game as `deal_game` does, then applies the handicaps
+    /// of the players at the table.
+    #[cfg(feature = "std")]
+    pub fn deal_game_with_handicaps(
+        &mut self,
+        num_decks: u8,
+        num_jokers: u8,
+        suit_order: &[Suit],
+        ruleset: Ruleset,
+        handicaps: &BTreeMap<String, Handicap>,
+    ) -> Result<Game, SetupError> {
+        let mut game = Game::new(
+            num_decks,
+            num_jokers,
+            &self.get_deal_order(),
+            suit_order,
+            ruleset
+        )?;
+        let seated = handicaps.iter()
+            .filter(|(id, _)| self.player_ids.contains(id))
+            .map(|(id, &handicap)| (id.clone(), handicap))
+            .collect();
+        game.apply_handicaps(&seated)
+            .expect("only seated players' handicaps are applied to a new game");
+        self.rotate();
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::get_suit_array;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn ids() -> Vec<String> {
+        vec!["a", "b", "c", "d"].into_iter().map(String::from).collect()
+    }
+
+    #[test]
+    fn the_dealer_is_dealt_to_last() {
+        let dealer = Dealer::new(&ids(), "b").unwrap();
+
+        assert_eq!(dealer.get_deal_order(), vec!["c", "d", "a", "b"]);
+    }
+
+    #[test]
+    fn players_can_join_and_leave_the_table() {
+        let mut dealer = Dealer::new(&ids(), "c").unwrap();
+
+        assert!(dealer.add_player("e"));
+        assert!(!dealer.add_player("a"));
+        assert_eq!(dealer.get_deal_order(), vec!["d", "a", "b", "e", "c"]);
+
+        assert!(dealer.remove_player("c"));
+        assert_eq!(dealer.get_dealer(), "d");
+        assert!(dealer.remove_player("a"));
+        assert_eq!(dealer.get_deal_order(), vec!["b", "e", "d"]);
+        assert!(!dealer.remove_player("a"));
+    }
+
+    #[test]
+    fn the_deal_rotates_around_the_table() {
+        let mut dealer = Dealer::new(&ids(), "c").unwrap();
+
+        dealer.rotate();
+        assert_eq!(dealer.get_dealer(), "d");
+        dealer.rotate();
+        assert_eq!(dealer.get_dealer(), "a");
+    }
+
+    #[test]
+    fn an_unseated_dealer_is_rejected() {
+        assert_eq!(Dealer::new(&ids(), "e"), None);
+        assert_eq!(Dealer::new(&["a".to_string(), "a".to_string()], "a"), None);
+    }
+
+    #[test]
+    fn cutting_for_deal_picks_a_seated_player() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let dealer = Dealer::cut_for_deal(&ids(), &mut rng);
+
+        assert!(ids().contains(&dealer.get_dealer().to_string()));
+    }
+
+    #[test]
+    fn dealt_games_use_the_deal_order_and_pass_the_deal() {
+        let mut dealer = Dealer::new(&ids(), "a").unwrap();
+        let game = dealer.deal_game(1, 0, &get_suit_array(), Ruleset::default()).unwrap();
+
+        assert_eq!(
+            game.get_round().get_player_ids(),
+            vec!["b", "c", "d", "a"]
+        );
+        assert_eq!(dealer.get_dealer(), "b");
+    }
+}
@@ -0,0 +1,245 @@
+use super::{Hand, OrderContext, Ruleset, Trick};
+use crate::cards::{get_rank_array, Card, PlayedCard};
+use crate::prelude::*;
+use alloc::collections::BTreeSet;
+
+/// Every five card trick that can be made from `hand`, along with every
+/// pair and prial when `include_sets` is set. Jokers stand in for any
+/// card, so the same natural cards can appear in several hands with the
+/// jokers played as different cards. Hands are built under `ruleset`, so
+/// jokers are left out of sets it doesn't allow them in. Sets come before
+/// five card tricks and no hand is listed twice.
+pub fn enumerate_tricks(hand: &[Card], include_sets: bool, ruleset: Ruleset) -> Vec<Hand> {
+    let sizes: &[usize] = if include_sets { &[2, 3, 5] } else { &[5] };
+
+    enumerate_sizes(hand, sizes, ruleset, &OrderContext::standard())
+}
+
+/// Every distinct hand that can be made from `hand`: singles, pairs,
+/// prials and five card tricks, smallest first, with jokers standing in
+/// as `enumerate_tricks` plays them. Given a hand to beat, only the hands
+/// that beat it under `ruleset` and the orders are kept.
+pub fn enumerate_hands(
+    hand: &[Card],
+    beating: Option<Hand>,
+    orders: &OrderContext,
+    ruleset: Ruleset,
+) -> Vec<Hand> {
+    let hands = enumerate_sizes(hand, &[1, 2, 3, 5], ruleset, orders);
+
+    match beating {
+        Some(last_move) => hands.into_iter()
+            .filter(|hand| hand.beats(&last_move, ruleset, orders))
+            .collect(),
+        None => hands,
+    }
+}
+
+fn enumerate_sizes(
+    hand: &[Card],
+    sizes: &[usize],
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> Vec<Hand> {
+    let natural_cards: Vec<PlayedCard> = hand.iter()
+        .filter_map(|c| match *c {
+            Card::Standard { rank, suit, .. } => Some(PlayedCard::new(rank, suit, false)),
+            Card::Joker { .. } => None,
+        })
+        .collect();
+    let jokers = hand.len() - natural_cards.len();
+    let substitutes: Vec<PlayedCard> = get_rank_array().iter()
+        .flat_map(|&rank| {
+            orders.get_suit_order().iter()
+                .map(move |&suit| PlayedCard::new(rank, suit, true))
+                .collect::<Vec<PlayedCard>>()
+        })
+        .collect();
+
+    let mut seen = BTreeSet::new();
+    let mut hands = vec![];
+
+    for &size in sizes {
+        for jokers_used in 0..=jokers.min(size) {
+            let naturals = get_combinations(&natural_cards, size - jokers_used);
+            let stand_ins = get_combinations_with_repeats(&substitutes, jokers_used);
+
+            for natural in naturals.iter() {
+                for stand_in in stand_ins.iter() {
+                    let mut cards = natural.clone();
+                    cards.extend(stand_in);
+                    cards.sort();
+
+                    if seen.contains(&cards) {
+                        continue;
+                    }
+                    if let Ok(hand) = Hand::try_build_in_order(cards.clone(), ruleset, orders) {
+                        hands.push(hand);
+                    }
+                    seen.insert(cards);
+                }
+            }
+        }
+    }
+
+    hands
+}
+
+/// The strongest five card trick that can be made from `hand`, ranked as
+/// `Hand::beats` ranks them under `ruleset` and the orders, or `None` when
+/// no five cards make one. Jokers are played as whatever makes the trick
+/// best.
+pub fn best_five_card_trick(
+    hand: &[Card],
+    orders: &OrderContext,
+    ruleset: Ruleset,
+) -> Option<Trick> {
+    enumerate_tricks(hand, false, ruleset).into_iter()
+        .filter_map(|hand| match hand {
+            Hand::FiveCardTrick(trick) => Some(trick),
+            _ => None,
+        })
+        .fold(None, |best, trick| match best {
+            Some(best) if !Hand::FiveCardTrick(trick).beats(
+                &Hand::FiveCardTrick(best),
+                ruleset,
+                orders,
+            ) => Some(best),
+            _ => Some(trick),
+        })
+}
+
+fn get_combinations(cards: &[PlayedCard], size: usize) -> Vec<Vec<PlayedCard>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+
+    let mut combinations = vec![];
+    for (i, &card) in cards.iter().enumerate() {
+        for mut rest in get_combinations(&cards[i + 1..], size - 1) {
+            rest.insert(0, card);
+            combinations.push(rest);
+        }
+    }
+
+    combinations
+}
+
+/// Like `get_combinations`, but a card can be picked more than once. Two
+/// jokers can both stand in for the same card.
+fn get_combinations_with_repeats(cards: &[PlayedCard], size: usize) -> Vec<Vec<PlayedCard>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+
+    let mut combinations = vec![];
+    for (i, &card) in cards.iter().enumerate() {
+        for mut rest in get_combinations_with_repeats(&cards[i..], size - 1) {
+            rest.insert(0, card);
+            combinations.push(rest);
+        }
+    }
+
+    combinations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Rank, Suit};
+    use crate::game::{JokerUsage, TrickType};
+    use crate::scenario::parse_cards;
+
+    fn get_trick_types(hands: &[Hand]) -> Vec<TrickType> {
+        hands.iter().filter_map(|hand| hand.get_trick_type()).collect()
+    }
+
+    #[test]
+    fn natural_tricks_are_found() {
+        let hand = parse_cards("3C 4C 5C 6C 7C 7H").unwrap();
+
+        let hands = enumerate_tricks(&hand, false, Ruleset::default());
+
+        assert_eq!(get_trick_types(&hands), vec![
+            TrickType::StraightFlush,
+            TrickType::Straight,
+        ]);
+    }
+
+    #[test]
+    fn jokers_complete_tricks_and_sets() {
+        let hand = parse_cards("9C 9H 9D JK 3S").unwrap();
+
+        let hands = enumerate_tricks(&hand, true, Ruleset::default());
+        let tricks = get_trick_types(&hands);
+
+        assert!(tricks.contains(&TrickType::FourOfAKind));
+        assert!(tricks.contains(&TrickType::FullHouse));
+
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, true);
+        assert!(hands.iter().any(|hand| {
+            matches!(hand, Hand::Pair(..)) && hand.to_cards().contains(&three)
+        }));
+
+        let ruleset = Ruleset {
+            joker_usage: JokerUsage::FiveCardTricksOnly,
+            ..Ruleset::default()
+        };
+        let natural_sets = enumerate_tricks(&hand, true, ruleset).into_iter()
+            .filter(|hand| !matches!(hand, Hand::FiveCardTrick(_)))
+            .count();
+
+        // three pairs and a prial of nines
+        assert_eq!(natural_sets, 4);
+    }
+
+    #[test]
+    fn the_best_trick_is_picked_under_the_orders() {
+        let best = |cards: &str, orders: &OrderContext| {
+            let hand = parse_cards(cards).unwrap();
+            best_five_card_trick(&hand, orders, Ruleset::default())
+                .map(|trick| (trick.trick_type, Hand::FiveCardTrick(trick).to_cards()))
+        };
+        let standard = OrderContext::standard();
+        let reversed = standard.clone().reverse();
+
+        let (trick_type, cards) = best("3C 4H 5D 6S 7C 8D 9C 9H", &standard).unwrap();
+        assert_eq!(trick_type, TrickType::Straight);
+        assert!(cards.contains(&PlayedCard::new(Rank::Nine, Suit::Hearts, false)));
+
+        let (_, cards) = best("3C 4H 5D 6S 7C 8D 9C 9H", &reversed).unwrap();
+        assert!(cards.contains(&PlayedCard::new(Rank::Three, Suit::Clubs, false)));
+
+        let (trick_type, _) = best("4C 5C 6C 7C 8C 9H 9D 9S 4H", &standard).unwrap();
+        assert_eq!(trick_type, TrickType::StraightFlush);
+
+        let (trick_type, cards) = best("9C 9H 9D 9S JK 2C", &standard).unwrap();
+        assert_eq!(trick_type, TrickType::FiveOfAKind);
+        assert!(cards.iter().any(|card| card.get_is_joker()));
+
+        assert_eq!(best("3C 4H 5D 6S 2C", &standard).map(|(t, _)| t), None);
+    }
+
+    #[test]
+    fn every_hand_is_listed_once_and_can_be_filtered() {
+        let hand = parse_cards("3C 3D 9H 9S").unwrap();
+        let all = |beating| {
+            enumerate_hands(&hand, beating, &OrderContext::standard(), Ruleset::default())
+        };
+
+        let hands = all(None);
+        assert_eq!(hands.len(), 6);
+        assert!(matches!(hands[0], Hand::Single(_)));
+
+        let five = Hand::Single(PlayedCard::new(Rank::Five, Suit::Hearts, false));
+        assert_eq!(all(Some(five)).len(), 2);
+
+        let threes = Hand::Pair(
+            PlayedCard::new(Rank::Three, Suit::Hearts, false),
+            PlayedCard::new(Rank::Three, Suit::Spades, false),
+        );
+        let pairs = all(Some(threes));
+        assert_eq!(pairs.len(), 1);
+        assert!(pairs[0].to_cards().contains(&PlayedCard::new(Rank::Nine, Suit::Spades, false)));
+    }
+}
@@ -0,0 +1,128 @@
+use super::{compare_hands_with_ruleset, Hand, OrderContext, Ruleset};
+use crate::cards::PlayedCard;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use core::cmp::Ordering;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HandEvaluation {
+    /// The hand the cards form, or `None` if they are not a valid hand
+    pub hand: Option<Hand>,
+    /// Position of the hand among the other valid candidates with the same
+    /// number of cards, starting at 0 for the weakest. Hands that cannot
+    /// beat each other share a strength.
+    pub strength: Option<usize>,
+}
+
+/// Classifies every candidate and ranks it against the others of the same
+/// size in a single pass, so callers scoring many options don't have to
+/// compare them pairwise themselves.
+pub fn evaluate_hands(
+    candidates: &[Vec<PlayedCard>],
+    ruleset: Ruleset,
+    orders: &OrderContext,
+) -> Vec<HandEvaluation> {
+    let hands: Vec<Option<Hand>> = candidates.iter()
+        .map(|cards| Hand::try_build_in_order(cards.clone(), ruleset, orders).ok())
+        .collect();
+    let mut strengths = vec![None; hands.len()];
+
+    let compare = |a: Hand, b: Hand| {
+        if compare_hands_with_ruleset(a, b, ruleset, orders) {
+            Ordering::Less
+        } else if compare_hands_with_ruleset(b, a, ruleset, orders) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    };
+
+    for &size in [1, 2, 3, 5].iter() {
+        let mut indexes: Vec<usize> = hands.iter()
+            .enumerate()
+            .filter(|(_, h)| h.map(|h| h.get_card_count()) == Some(size))
+            .map(|(i, _)| i)
+            .collect();
+
+        indexes.sort_by(|&a, &b| compare(hands[a].unwrap(), hands[b].unwrap()));
+
+        let mut strength = 0;
+        for (position, &index) in indexes.iter().enumerate() {
+            if position > 0 {
+                let previous = hands[indexes[position - 1]].unwrap();
+                if compare(previous, hands[index].unwrap()) == Ordering::Less {
+                    strength += 1;
+                }
+            }
+            strengths[index] = Some(strength);
+        }
+    }
+
+    hands.into_iter()
+        .zip(strengths)
+        .map(|(hand, strength)| HandEvaluation { hand, strength })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+
+    #[test]
+    fn it_ranks_hands_of_the_same_size() {
+        let candidates = vec![
+            vec![PlayedCard::new(Rank::Five, Suit::Clubs, false)],
+            vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)],
+            vec![PlayedCard::new(Rank::Five, Suit::Spades, false)],
+            vec![
+                PlayedCard::new(Rank::Four, Suit::Clubs, false),
+                PlayedCard::new(Rank::Four, Suit::Hearts, false),
+            ],
+        ];
+
+        let strengths: Vec<Option<usize>> = evaluate_hands(
+            &candidates,
+            Ruleset::default(),
+            &OrderContext::standard(),
+        ).iter().map(|e| e.strength).collect();
+
+        assert_eq!(strengths, vec![Some(1), Some(0), Some(2), Some(0)]);
+    }
+
+    #[test]
+    fn invalid_hands_are_not_ranked() {
+        let candidates = vec![vec![
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+            PlayedCard::new(Rank::Five, Suit::Hearts, false),
+        ]];
+
+        let evaluations = evaluate_hands(
+            &candidates,
+            Ruleset::default(),
+            &OrderContext::standard(),
+        );
+
+        assert_eq!(evaluations[0], HandEvaluation {
+            hand: None,
+            strength: None,
+        });
+    }
+
+    #[test]
+    fn equal_hands_share_a_strength() {
+        let candidates = vec![
+            vec![PlayedCard::new(Rank::Five, Suit::Clubs, false)],
+            vec![PlayedCard::new(Rank::Five, Suit::Clubs, false)],
+        ];
+
+        let evaluations = evaluate_hands(
+            &candidates,
+            Ruleset::default(),
+            &OrderContext::standard(),
+        );
+
+        assert_eq!(evaluations[0].strength, Some(0));
+        assert_eq!(evaluations[1].strength, Some(0));
+    }
+}
@@ -0,0 +1,293 @@
+use super::{Hand, OrderContext, Round, SubmitError};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Something that happened in a game, in the order it happened. A move
+/// gives rise to one or more of these, which makes them a feed for logs,
+/// replays and notifications.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameEvent {
+    CardsPlayed { player_id: String, hand: Hand },
+    Passed { player_id: String },
+    TrickCleared { winner_id: String },
+    /// The new orders, which say whether they're reversed from those the
+    /// game started with rather than flipped back
+    OrderReversed { orders: OrderContext },
+    /// `position` counts from 1
+    PlayerFinished { player_id: String, position: usize },
+    GameOver { winners: Vec<String> },
+}
+
+/// The events `player_id` playing `hand` caused, given the rounds before
+/// and after the move and the finishing order after it.
+pub fn get_move_events(
+    old_round: &Round,
+    round: &Round,
+    player_id: &str,
+    hand: Hand,
+    winners: &[String],
+) -> Vec<GameEvent> {
+    let player_id = player_id.to_string();
+    let mut events = vec![];
+
+    if hand == Hand::Pass {
+        events.push(GameEvent::Passed { player_id: player_id.clone() });
+    } else {
+        events.push(GameEvent::CardsPlayed { player_id: player_id.clone(), hand });
+    }
+
+    let table_cleared = round.get_last_move() == Some(Hand::Pass)
+        && (old_round.get_last_move() != Some(Hand::Pass)
+            || hand != Hand::Pass);
+    if table_cleared {
+        if let Some(winner_id) = round.get_last_player() {
+            events.push(GameEvent::TrickCleared { winner_id });
+        }
+    }
+
+    if round.get_suit_order() != old_round.get_suit_order() {
+        events.push(GameEvent::OrderReversed { orders: round.get_order_context() });
+    }
+
+    let went_out = old_round.get_player(&player_id).map(|p| p.get_card_count() > 0)
+        == Some(true)
+        && round.get_player(&player_id).map(|p| p.get_card_count()) == Some(0);
+    if went_out {
+        if let Some(position) = winners.iter().position(|id| *id == player_id) {
+            events.push(GameEvent::PlayerFinished { player_id, position: position + 1 });
+        }
+    }
+
+    if round.is_game_over() {
+        events.push(GameEvent::GameOver { winners: winners.to_vec() });
+    }
+
+    events
+}
+
+/// Why a stream of events couldn't be replayed into a round. `index` is
+/// the position in the stream the problem was found at.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum EventReplayError {
+    /// A play or pass the round wouldn't accept
+    IllegalMove { index: usize, error: SubmitError },
+    /// An event other than a play or pass where the next move should be
+    UnexpectedEvent { index: usize, event: GameEvent },
+    /// The move before gave rise to `expected`, but the stream has
+    /// something else here, or ends
+    MissingEvent { index: usize, expected: GameEvent },
+}
+
+impl Round {
+    /// Rebuilds a round from the one that was dealt and every event since,
+    /// so a server can store the event log alone and still load games.
+    /// Plays and passes are replayed, and every other event has to be
+    /// exactly what the move before it gave rise to, so a tampered or
+    /// truncated log is refused rather than loaded into a different game.
+    /// The result is the same round that playing the moves directly gives.
+    pub fn from_events(initial: &Round, events: &[GameEvent]) -> Result<Round, EventReplayError> {
+        let mut round = initial.clone();
+        let mut winners: Vec<String> = vec![];
+        let mut index = 0;
+
+        while let Some(event) = events.get(index) {
+            let (player_id, hand) = match event {
+                GameEvent::CardsPlayed { player_id, hand } => (player_id, *hand),
+                GameEvent::Passed { player_id } => (player_id, Hand::Pass),
+                _ => return Err(EventReplayError::UnexpectedEvent { index, event: event.clone() }),
+            };
+
+            let old_round = round.clone();
+            round.submit_move_mut(player_id, hand.to_cards())
+                .map_err(|error| EventReplayError::IllegalMove { index, error })?;
+
+            let went_out = round.get_player(player_id)
+                .map(|p| p.get_card_count() == 0)
+                .unwrap_or(false);
+            if went_out && !winners.contains(player_id) {
+                winners.push(player_id.clone());
+            }
+
+            let expected = get_move_events(&old_round, &round, player_id, hand, &winners);
+            let count = expected.len();
+            for (offset, expected) in expected.into_iter().enumerate().skip(1) {
+                if events.get(index + offset) != Some(&expected) {
+                    return Err(EventReplayError::MissingEvent { index: index + offset, expected });
+                }
+            }
+            index += count;
+        }
+
+        Ok(round)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_played_cards, Scenario};
+
+    #[test]
+    fn moves_are_described_as_events() {
+        let round = Scenario::new()
+            .player("a", "3C 9D")
+            .player("b", "4C")
+            .last_move("a", "3C")
+            .next_player("b")
+            .build()
+            .unwrap();
+        let cards = parse_played_cards("4C").unwrap();
+        let after = round.submit_move("b", cards.clone()).unwrap();
+        let winners = vec!["b".to_string()];
+
+        assert_eq!(
+            get_move_events(&round, &after, "b", Hand::build(cards).unwrap(), &winners),
+            vec![
+                GameEvent::CardsPlayed {
+                    player_id: "b".to_string(),
+                    hand: Hand::build(parse_played_cards("4C").unwrap()).unwrap(),
+                },
+                GameEvent::PlayerFinished { player_id: "b".to_string(), position: 1 },
+                GameEvent::GameOver { winners },
+            ]
+        );
+    }
+
+    fn play_out(round: &Round) -> (Round, Vec<GameEvent>) {
+        use crate::ai::{Agent, RandomAgent};
+
+        let mut round = round.clone();
+        let mut agent = RandomAgent::new(5);
+        let mut winners: Vec<String> = vec![];
+        let mut events = vec![];
+        while let Some(player_id) = round.get_next_player() {
+            let cards = agent.choose_move(&round, &player_id);
+            let old_round = round.clone();
+            round.submit_move_mut(&player_id, cards.clone()).unwrap();
+            if round.get_player(&player_id).unwrap().get_card_count() == 0 {
+                winners.push(player_id.clone());
+            }
+            let hand = old_round.build_hand(&cards).unwrap();
+            events.extend(get_move_events(&old_round, &round, &player_id, hand, &winners));
+        }
+
+        (round, events)
+    }
+
+    #[test]
+    fn rounds_rebuilt_from_events_match_the_played_round() {
+        let ids: Vec<String> = ["a", "b", "c", "d"].iter().map(|id| id.to_string()).collect();
+        let game = crate::game::Game::new(
+            1,
+            0,
+            &ids,
+            &crate::cards::get_suit_array(),
+            crate::game::Ruleset::default(),
+        ).unwrap();
+        let initial = game.get_round().clone();
+        let (played, events) = play_out(&initial);
+        assert!(events.iter().any(|e| matches!(e, GameEvent::TrickCleared { .. })));
+
+        let rebuilt = Round::from_events(&initial, &events).unwrap();
+        assert_eq!(
+            serde_json::to_value(&rebuilt).unwrap(),
+            serde_json::to_value(&played).unwrap()
+        );
+        assert_eq!(rebuilt.state_hash(), played.state_hash());
+    }
+
+    #[test]
+    fn rounds_with_custom_orders_are_rebuilt_from_events() {
+        use crate::cards::{get_rank_array, Card, Rank, Suit};
+
+        let card = |rank, suit| Card::Standard { deck_id: 0, rank, suit };
+        let hands = vec![
+            ("a".to_string(), vec![
+                card(Rank::Two, Suit::Clubs),
+                card(Rank::Three, Suit::Hearts),
+                card(Rank::Four, Suit::Diamonds),
+                card(Rank::Five, Suit::Spades),
+                card(Rank::Six, Suit::Clubs),
+                card(Rank::King, Suit::Clubs),
+                card(Rank::Nine, Suit::Hearts),
+            ]),
+            ("b".to_string(), vec![
+                card(Rank::Seven, Suit::Clubs),
+                card(Rank::Eight, Suit::Hearts),
+                card(Rank::Nine, Suit::Diamonds),
+                card(Rank::Ten, Suit::Spades),
+                card(Rank::Jack, Suit::Clubs),
+                card(Rank::Ace, Suit::Spades),
+            ]),
+        ];
+        let suit_order = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+        let mut twos_low = get_rank_array();
+        twos_low.rotate_right(1);
+        let game = crate::game::Game::from_hands(
+            &hands,
+            1,
+            0,
+            OrderContext::new(&suit_order, twos_low).unwrap(),
+            crate::game::Ruleset::default(),
+        ).unwrap();
+        let initial = game.get_round().clone();
+
+        let run = parse_played_cards("2C 3H 4D 5S 6C").unwrap();
+        let after_run = initial.submit_move("a", run.clone()).unwrap();
+        let mut events = get_move_events(
+            &initial,
+            &after_run,
+            "a",
+            initial.build_hand(&run).unwrap(),
+            &[],
+        );
+        assert!(matches!(events[0], GameEvent::CardsPlayed { hand: Hand::FiveCardTrick(_), .. }));
+        let (played, rest) = play_out(&after_run);
+        events.extend(rest);
+
+        let rebuilt = Round::from_events(&initial, &events).unwrap();
+        assert_eq!(
+            serde_json::to_value(&rebuilt).unwrap(),
+            serde_json::to_value(&played).unwrap()
+        );
+        assert_eq!(rebuilt.state_hash(), played.state_hash());
+    }
+
+    #[test]
+    fn tampered_event_logs_are_refused() {
+        let round = Scenario::new()
+            .player("a", "3C 9D")
+            .player("b", "4C 5C")
+            .build()
+            .unwrap();
+        let (_, events) = play_out(&round);
+        let cleared = events.iter()
+            .position(|e| matches!(e, GameEvent::TrickCleared { .. }))
+            .unwrap();
+
+        let mut dropped = events.clone();
+        dropped.remove(cleared);
+        assert_eq!(
+            Round::from_events(&round, &dropped).unwrap_err(),
+            EventReplayError::MissingEvent { index: cleared, expected: events[cleared].clone() }
+        );
+        assert_eq!(
+            Round::from_events(&round, &events[cleared..]).unwrap_err(),
+            EventReplayError::UnexpectedEvent { index: 0, event: events[cleared].clone() }
+        );
+        assert_eq!(
+            Round::from_events(&round, &[GameEvent::Passed { player_id: "a".to_string() }]).unwrap_err(),
+            EventReplayError::IllegalMove { index: 0, error: SubmitError::FirstRoundPass }
+        );
+    }
+
+    #[test]
+    fn events_are_tagged_by_type() {
+        let event = GameEvent::Passed { player_id: "a".to_string() };
+        let json = serde_json::to_string(&event).unwrap();
+
+        assert_eq!(json, r#"{"type":"passed","player_id":"a"}"#);
+        assert_eq!(serde_json::from_str::<GameEvent>(&json).unwrap(), event);
+    }
+}
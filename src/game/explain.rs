@@ -0,0 +1,153 @@
+use super::{Hand, Round, SubmitError};
+use crate::ai::{get_legal_moves, get_move_cost};
+use crate::cards::PlayedCard;
+use crate::i18n::{hand_name, played_card_name, Locale};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Why a move was turned down, in words a tutorial or screen reader can
+/// use as they are.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MoveExplanation {
+    pub error: SubmitError,
+    /// What the rule that was broken asks for
+    pub requirement: String,
+    /// The cheapest move the player could make instead. Empty cards mean
+    /// passing, and `None` means they can't move at all right now.
+    pub alternative: Option<Vec<PlayedCard>>,
+    /// The hand the alternative makes under the round's orders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alternative_hand: Option<Hand>,
+}
+
+impl MoveExplanation {
+    /// The requirement followed by the alternative, e.g. "Your hand has to
+    /// beat the Pair of Fives on the table. You could play Pair of Nines."
+    pub fn get_text(&self) -> String {
+        if self.alternative.is_none() {
+            return format!("{}.", self.requirement);
+        }
+
+        match self.alternative_hand.unwrap_or(Hand::Pass) {
+            Hand::Pass => format!("{}. You could pass.", self.requirement),
+            hand => format!(
+                "{}. You could play {}.",
+                self.requirement,
+                hand_name(hand, Locale::English)
+            ),
+        }
+    }
+}
+
+/// Explains why `user_id` can't play `cards`, or `None` if they can.
+pub fn explain_move(round: &Round, user_id: &str, cards: &[PlayedCard]) -> Option<MoveExplanation> {
+    let error = round.validate_move(user_id, cards).err()?;
+    let describe = |hand: Hand| hand_name(hand, Locale::English);
+
+    let requirement = match &error {
+        SubmitError::FirstRoundPass => "The opening move has to be a hand, not a pass".to_string(),
+        SubmitError::FirstHandMustContainLowestCard => match round.get_lowest_card() {
+            Some(card) => format!(
+                "The opening hand has to include the {}",
+                played_card_name(card, Locale::English)
+            ),
+            None => "The opening hand has to include the lowest card".to_string(),
+        },
+        SubmitError::HandNotHighEnough => match round.get_last_move() {
+            Some(last_move) => format!(
+                "Your hand has to beat the {} on the table",
+                describe(last_move)
+            ),
+            None => "Your hand has to beat the one on the table".to_string(),
+        },
+        SubmitError::NotCurrentPlayer => match round.get_next_player() {
+            Some(next_player) => format!("It's {}'s turn, so you have to wait", next_player),
+            None => "Nobody can move because the game is over".to_string(),
+        },
+        SubmitError::InvalidHand => {
+            "Cards have to make a single, pair, prial or five card trick".to_string()
+        },
+        SubmitError::PlayerDoesntHaveCard => "You can only play cards in your hand".to_string(),
+        SubmitError::JokerOutsideFiveCardTrick => {
+            "Jokers can only be played in five card tricks".to_string()
+        },
+        SubmitError::CannotFinishWithJoker => "Your last hand can't include a joker".to_string(),
+        SubmitError::IllegalFinalCard => {
+            "Your last hand can't include a two or a joker".to_string()
+        },
+        SubmitError::CannotFinishWithBomb => "Your last hand can't be a bomb".to_string(),
+        SubmitError::CannotFinishWithReversal => {
+            "Your last hand can't be one that reverses the suit and rank orders".to_string()
+        },
+        SubmitError::DeadGame => "This game can't carry on".to_string(),
+        SubmitError::LockedOutOfTrick => {
+            "You passed earlier in this trick, so you have to pass until it clears".to_string()
+        },
+        SubmitError::HouseRule(reason) => reason.clone(),
+        SubmitError::KittyPending => {
+            "The winner of the first trick has to take the kitty and discard first".to_string()
+        },
+        SubmitError::GamePaused => "The game is paused until it's resumed".to_string(),
+        SubmitError::AmbiguousCardCopy => {
+            "You hold more than one copy of a card you played, so say which one".to_string()
+        },
+        SubmitError::PlayerAlreadyFinished => {
+            "You've already gone out, so there's nothing left for you to play".to_string()
+        },
+    };
+
+    let orders = round.get_order_context();
+    let legal_moves = get_legal_moves(round, user_id);
+    let alternative = legal_moves.iter()
+        .filter(|cards| !cards.is_empty())
+        .min_by_key(|cards| get_move_cost(cards, &orders))
+        .or_else(|| legal_moves.first())
+        .cloned();
+    let alternative_hand = alternative.as_ref()
+        .and_then(|cards| round.build_hand(cards));
+
+    Some(MoveExplanation {
+        error,
+        requirement,
+        alternative,
+        alternative_hand,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_played_cards, Scenario};
+
+    fn get_round() -> Round {
+        Scenario::new()
+            .player("a", "4C 4H 9C 9S")
+            .player("b", "3C 6D")
+            .last_move("b", "5C 5S")
+            .next_player("a")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn illegal_moves_are_explained_with_an_alternative() {
+        let round = get_round();
+        let explanation = explain_move(&round, "a", &parse_played_cards("4C 4H").unwrap()).unwrap();
+
+        assert_eq!(explanation.error, SubmitError::HandNotHighEnough);
+        assert_eq!(
+            explanation.get_text(),
+            "Your hand has to beat the Pair of Fives on the table. You could play Pair of Nines."
+        );
+        assert_eq!(explain_move(&round, "a", &parse_played_cards("9C 9S").unwrap()), None);
+    }
+
+    #[test]
+    fn players_waiting_for_their_turn_have_no_alternative() {
+        let round = get_round();
+        let explanation = explain_move(&round, "b", &parse_played_cards("6D").unwrap()).unwrap();
+
+        assert_eq!(explanation.alternative, None);
+        assert_eq!(explanation.get_text(), "It's a's turn, so you have to wait.");
+    }
+}
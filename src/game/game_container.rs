@@ -2,24 +2,150 @@ use super::{
     Player,
     Round,
     SubmitError,
+    ResignError,
+    LastCardError,
+    KittyError,
     Hand,
-    sort_unplayed_cards,
+    sort_hand,
     Ruleset,
-    compare_hands,
-    FlushPrecedence
+    DealMode,
+    compare_hands_with_ruleset,
+    GameObserver,
+    Observers,
+    GameResult,
+    get_game_result,
+    ClockConfig,
+    ClockError,
+    GameClock,
+    TimeoutAction,
+    ConcedeError,
+    ConcedeOutcome,
+    ConcedeVote,
+    RedealError,
+    RedealOutcome,
+    ClaimError,
+    ClaimOutcome,
+    FalseClaimPenalty,
+    KittyStage,
+    Handicap,
+    HandicapError,
+    MoveRule,
+    TelemetryHook,
+    GameEvent,
+    get_move_events,
+    OrderContext,
 };
 use crate::cards::{
     get_rank_array,
+    Card,
     Deck,
     PlayedCard,
     Suit,
     Rank,
 };
-use crate::ai::get_move;
+use crate::ai::{Agent, CpuAgent};
+#[cfg(feature = "telemetry")]
+use super::Telemetry;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 use serde::{Deserialize, Serialize};
 
-#[wasm_bindgen]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MisdealError {
+    UnknownPlayer,
+    /// Misdeals can only be called before the first move
+    AlreadyStarted,
+    /// The player's hand doesn't meet any of the ruleset's misdeal
+    /// conditions
+    NoMisdeal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SetupError {
+    NoPlayers,
+    /// The same id was given for more than one seat
+    DuplicatePlayer(String),
+    /// The suit order is empty, names a suit twice or leaves out a suit
+    /// the deck holds
+    InvalidSuitOrder,
+    /// The rank order doesn't list every rank exactly once
+    InvalidRankOrder,
+    /// The seat order doesn't list every seated player exactly once
+    InvalidSeatOrder,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CustomDealError {
+    Setup(SetupError),
+    /// The hands hold a card the deck doesn't have, or more copies of it
+    /// than the deck has
+    CardNotInDeck(Card),
+}
+
+impl From<SetupError> for CustomDealError {
+    fn from(err: SetupError) -> CustomDealError {
+        CustomDealError::Setup(err)
+    }
+}
+
+/// Checks a list of player ids can be seated: there has to be at least one
+/// and no id can be repeated.
+pub fn check_player_ids(player_ids: &[String]) -> Result<(), SetupError> {
+    if player_ids.is_empty() {
+        return Err(SetupError::NoPlayers);
+    }
+
+    for (i, id) in player_ids.iter().enumerate() {
+        if player_ids[..i].contains(id) {
+            return Err(SetupError::DuplicatePlayer(id.clone()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that a custom suit order names each of its suits once, and
+/// that a custom rank order is a true permutation of the standard one.
+pub fn check_orders(suit_order: &[Suit], rank_order: [Rank; 13]) -> Result<(), SetupError> {
+    let repeated = suit_order.iter()
+        .enumerate()
+        .any(|(i, suit)| suit_order[..i].contains(suit));
+    if suit_order.is_empty() || repeated {
+        return Err(SetupError::InvalidSuitOrder);
+    }
+    if !is_permutation(&rank_order, &get_rank_array()) {
+        return Err(SetupError::InvalidRankOrder);
+    }
+
+    Ok(())
+}
+
+/// A fresh deck made of the suits in `orders`. They're laid out in the
+/// standard suit order whatever order they rank in, so a seeded shuffle
+/// of the four standard suits deals what it would from `Deck::new`.
+fn new_deck(num_decks: u8, num_jokers: u8, orders: &OrderContext) -> Deck {
+    let mut suits = orders.get_suit_order().to_vec();
+    suits.sort();
+    suits.dedup();
+
+    Deck::with_suits(num_decks, num_jokers, &suits)
+        .expect("the suits were deduplicated")
+}
+
+fn is_permutation<T: Ord + Copy>(order: &[T], all: &[T]) -> bool {
+    let mut sorted = order.to_vec();
+    sorted.sort();
+    let mut expected = all.to_vec();
+    expected.sort();
+
+    sorted == expected
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Game {
     num_decks: u8,
@@ -27,45 +153,209 @@ pub struct Game {
     round: Round,
     winners: Vec<String>,
     ruleset: Ruleset,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    clock: Option<GameClock>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    concede_vote: Option<ConcedeVote>,
+    /// Players asking for a redeal before the first move
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    redeal_votes: Vec<String>,
+    /// Cards set aside face down when not all of the deck is dealt
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    kitty: Vec<Card>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    handicaps: BTreeMap<String, Handicap>,
+    /// Cards each handicapped player may still set aside
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    handicap_discards: BTreeMap<String, usize>,
+    #[serde(skip)]
+    observers: Observers,
+    #[serde(skip)]
+    telemetry: TelemetryHook,
 }
 
 impl Game {
+    #[cfg(feature = "std")]
     pub fn new(
         num_decks: u8,
         num_jokers: u8,
         player_ids: &[String],
-        suit_order: [Suit; 4],
+        suit_order: &[Suit],
         ruleset: Ruleset
-    ) -> Game {
-        let rank_order = get_rank_array();
+    ) -> Result<Game, SetupError> {
+        Game::with_orders(
+            num_decks,
+            num_jokers,
+            player_ids,
+            OrderContext::new(suit_order, get_rank_array())?,
+            ruleset
+        )
+    }
 
-        let mut deck = Deck::new(num_decks, num_jokers);
+    /// Starts a game with a non-standard rank order as well as suit order,
+    /// such as a variant where twos are low. The opening lead goes to
+    /// whoever holds the lowest card under these orders.
+    #[cfg(feature = "std")]
+    pub fn with_orders(
+        num_decks: u8,
+        num_jokers: u8,
+        player_ids: &[String],
+        orders: OrderContext,
+        ruleset: Ruleset
+    ) -> Result<Game, SetupError> {
+        let mut deck = new_deck(num_decks, num_jokers, &orders);
         deck.shuffle();
-        let cards = deck.deal(player_ids.len() as u8);
 
-        let players: Vec<Player> = cards
-            .iter()
-            .zip(player_ids)
-            .map(|(c, id)| {
-                let mut player_hand = sort_unplayed_cards(
-                    &c, suit_order, rank_order
-                );
-                player_hand.reverse();
+        Game::from_deck_with_orders(
+            &deck,
+            num_decks,
+            num_jokers,
+            player_ids,
+            orders,
+            ruleset
+        )
+    }
+
+    /// Deals `deck` without shuffling it, so a deck order that was committed
+    /// to up front can be revealed and checked after the game.
+    pub fn from_deck(
+        deck: &Deck,
+        num_decks: u8,
+        num_jokers: u8,
+        player_ids: &[String],
+        suit_order: &[Suit],
+        ruleset: Ruleset
+    ) -> Result<Game, SetupError> {
+        Game::from_deck_with_orders(
+            deck,
+            num_decks,
+            num_jokers,
+            player_ids,
+            OrderContext::new(suit_order, get_rank_array())?,
+            ruleset
+        )
+    }
+
+    pub fn from_deck_with_orders(
+        deck: &Deck,
+        num_decks: u8,
+        num_jokers: u8,
+        player_ids: &[String],
+        orders: OrderContext,
+        ruleset: Ruleset
+    ) -> Result<Game, SetupError> {
+        check_player_ids(player_ids)?;
+        let unordered_suit = deck.to_vec().iter().any(|card| match card {
+            Card::Standard { suit, .. } => orders.suit_index(*suit).is_none(),
+            Card::Joker { .. } => false,
+        });
+        if unordered_suit {
+            return Err(SetupError::InvalidSuitOrder);
+        }
+
+        let mut deck = deck.clone();
+        let players = player_ids.len() as u8;
+        let mut blind_kitty = vec![];
+        let (cards, kitty) = match ruleset.deal_mode {
+            DealMode::StrippedForThree { rank, suit } => {
+                if players == 3 {
+                    deck.strip(rank, suit);
+                }
+                (deck.deal(players), vec![])
+            },
+            DealMode::Limited { cards_per_player } => {
+                let (cards, kitty) = deck.deal_with_kitty(players, cards_per_player as usize);
+                (cards, kitty.to_vec())
+            },
+            DealMode::ShortDeck { lowest_rank } => {
+                deck.strip_below(lowest_rank);
+                (deck.deal(players), vec![])
+            },
+            DealMode::BlindKitty { cards } => {
+                blind_kitty = deck.set_aside(cards as usize);
+                (deck.deal(players), vec![])
+            },
+            DealMode::FullDeck => (deck.deal(players), vec![]),
+        };
+        let hands: Vec<(String, Vec<Card>)> = player_ids.iter()
+            .cloned()
+            .zip(cards)
+            .collect();
+
+        let mut game = Game::deal_hands(
+            &hands,
+            num_decks,
+            num_jokers,
+            orders,
+            ruleset
+        );
+        game.kitty = kitty;
+        game.round.set_kitty(blind_kitty);
+
+        Ok(game)
+    }
+
+    /// Starts a game with each player holding exactly the cards given,
+    /// for puzzles, tutorials and reproducing reported games. The hands
+    /// don't have to use the whole deck, but every card in them has to
+    /// come from a deck of `num_decks` decks and `num_jokers` jokers.
+    pub fn from_hands(
+        hands: &[(String, Vec<Card>)],
+        num_decks: u8,
+        num_jokers: u8,
+        orders: OrderContext,
+        ruleset: Ruleset
+    ) -> Result<Game, CustomDealError> {
+        let player_ids: Vec<String> = hands.iter().map(|(id, _)| id.clone()).collect();
+        check_player_ids(&player_ids)?;
+
+        let mut deck = new_deck(num_decks, num_jokers, &orders);
+        if let DealMode::ShortDeck { lowest_rank } = ruleset.deal_mode {
+            deck.strip_below(lowest_rank);
+        }
+        let mut available: BTreeMap<Card, usize> = BTreeMap::new();
+        for card in deck.to_vec() {
+            *available.entry(card).or_default() += 1;
+        }
+        for card in hands.iter().flat_map(|(_, hand)| hand) {
+            match available.get_mut(card) {
+                Some(count) if *count > 0 => *count -= 1,
+                _ => return Err(CustomDealError::CardNotInDeck(*card)),
+            }
+        }
+
+        Ok(Game::deal_hands(
+            hands,
+            num_decks,
+            num_jokers,
+            orders,
+            ruleset
+        ))
+    }
 
+    fn deal_hands(
+        hands: &[(String, Vec<Card>)],
+        num_decks: u8,
+        num_jokers: u8,
+        orders: OrderContext,
+        ruleset: Ruleset
+    ) -> Game {
+        let players: Vec<Player> = hands
+            .iter()
+            .map(|(id, c)| {
                 Player::new(
                     id.to_string(),
-                    player_hand
+                    sort_hand(c, &orders)
                 )
             })
             .collect();
 
         let round = Round::new(
-            players.clone(),
+            players,
             None,
             None,
             None,
-            suit_order,
-            rank_order,
+            orders,
             ruleset
         );
 
@@ -74,31 +364,570 @@ impl Game {
             num_jokers,
             round,
             winners: vec!(),
-            ruleset
+            ruleset,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
         }
     }
 
+    pub fn add_observer(&mut self, observer: Box<dyn GameObserver>) {
+        self.observers.add(observer);
+    }
+
+    /// Reports every move this game is asked to play, and how long it
+    /// took, to `telemetry`, replacing any earlier hook.
+    #[cfg(feature = "telemetry")]
+    pub fn set_telemetry(&mut self, telemetry: Box<dyn Telemetry>) {
+        self.telemetry.set(telemetry);
+    }
+
+    /// Adds a house rule every later move has to pass, on top of the
+    /// ruleset's own.
+    pub fn add_move_rule(&mut self, rule: Box<dyn MoveRule>) {
+        self.round.add_move_rule(rule);
+    }
+
     pub fn play_move(
         &mut self,
         player_id: &str,
         player_move: Vec<PlayedCard>,
     ) -> Result<(), SubmitError> {
-        match self.round.submit_move(player_id, player_move) {
+        self.play_move_with_copies(player_id, player_move, None)
+    }
+
+    /// As `play_move`, naming the exact copy of each card played. See
+    /// `Round::submit_exact_move`.
+    pub fn play_exact_move(
+        &mut self,
+        player_id: &str,
+        player_move: Vec<PlayedCard>,
+        copies: Vec<Card>,
+    ) -> Result<(), SubmitError> {
+        self.play_move_with_copies(player_id, player_move, Some(copies))
+    }
+
+    /// Turns down plain moves that leave open which copy of a card was
+    /// played, for servers that track exact cards in multi-deck games.
+    pub fn set_strict_card_identity(&mut self, strict: bool) {
+        self.round.set_strict_card_identity(strict);
+    }
+
+    fn play_move_with_copies(
+        &mut self,
+        player_id: &str,
+        player_move: Vec<PlayedCard>,
+        copies: Option<Vec<Card>>,
+    ) -> Result<(), SubmitError> {
+        if self.is_paused() {
+            return Err(SubmitError::GamePaused);
+        }
+
+        let round = &self.round;
+        let submitted = self.telemetry.time_submit(player_id, &player_move, || match &copies {
+            Some(copies) => round.submit_exact_move(player_id, player_move.clone(), copies.clone()),
+            None => round.submit_move(player_id, player_move.clone()),
+        });
+        match submitted {
             Ok(new_round) => {
                 let player = new_round.get_player(player_id)
                     .unwrap();
-                if player.get_hand().is_empty()
+                let finished = player.get_hand().is_empty()
                     && !self.winners
-                            .contains(&player_id.to_string()) {
+                            .contains(&player_id.to_string());
+                if finished {
                     self.winners.push(player_id.to_string());
+                    self.concede_vote = None;
                 }
-                self.round = new_round;
+                let old_round = core::mem::replace(
+                    &mut self.round, new_round
+                );
+                self.notify_observers(&old_round, player_id, player_move);
                 Ok(())
             },
             Err(x) => Err(x),
         }
     }
 
+    /// Starts a clock for every seated player. Only moves played with
+    /// `play_timed_move` are charged to it.
+    pub fn set_clock(&mut self, config: ClockConfig) {
+        self.clock = Some(GameClock::new(&self.round.get_player_ids(), config));
+    }
+
+    pub fn get_clock(&self) -> Option<&GameClock> {
+        self.clock.as_ref()
+    }
+
+    /// Stops the clock, with the current turn having taken `elapsed_ms`,
+    /// and holds every move until `resume`.
+    pub fn pause(&mut self, elapsed_ms: u64) -> Result<(), ClockError> {
+        self.clock.as_mut().ok_or(ClockError::NoClock)?.pause(elapsed_ms)
+    }
+
+    /// Starts the clock again. Time the rest of the turn from zero.
+    pub fn resume(&mut self) -> Result<(), ClockError> {
+        self.clock.as_mut().ok_or(ClockError::NoClock)?.resume()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.clock.as_ref().map(|clock| clock.is_paused()).unwrap_or(false)
+    }
+
+    /// Plays a move that took `elapsed_ms` since the player's turn began.
+    /// If that used up their time bank the move is thrown away and the
+    /// clock's timeout action is taken instead, which is returned. Without
+    /// a clock this is just `play_move`.
+    pub fn play_timed_move(
+        &mut self,
+        player_id: &str,
+        player_move: Vec<PlayedCard>,
+        elapsed_ms: u64,
+    ) -> Result<Option<TimeoutAction>, SubmitError> {
+        if self.get_next_player().as_deref() != Some(player_id) {
+            return Err(SubmitError::NotCurrentPlayer);
+        }
+
+        let timed_out = self.clock.as_ref()
+            .map(|clock| clock.has_run_out(player_id, elapsed_ms))
+            .unwrap_or(false);
+        if timed_out {
+            return Ok(self.claim_timeout(elapsed_ms));
+        }
+
+        self.play_move(player_id, player_move)?;
+        if let Some(clock) = self.clock.as_mut() {
+            clock.charge(player_id, elapsed_ms);
+        }
+
+        Ok(None)
+    }
+
+    /// Takes the timeout action against the player whose turn it is, if
+    /// the `elapsed_ms` their turn has taken has used up their time bank.
+    /// Returns the action taken.
+    pub fn claim_timeout(&mut self, elapsed_ms: u64) -> Option<TimeoutAction> {
+        let player_id = self.get_next_player()?;
+        let clock = self.clock.as_mut()?;
+        if !clock.has_run_out(&player_id, elapsed_ms) {
+            return None;
+        }
+        clock.charge(&player_id, elapsed_ms);
+
+        let on_timeout = clock.get_config().on_timeout;
+        if on_timeout == TimeoutAction::AutoPass
+            && self.play_move(&player_id, vec![]).is_ok() {
+            return Some(TimeoutAction::AutoPass);
+        }
+
+        self.resign(&player_id).ok()?;
+        Some(TimeoutAction::Forfeit)
+    }
+
+    /// Resigns `player_id` from the game. Resigned players never join the
+    /// winners list.
+    pub fn resign(&mut self, player_id: &str) -> Result<(), ResignError> {
+        self.round.resign_mut(player_id)?;
+        self.concede_vote = None;
+
+        if self.round.is_game_over() {
+            let winners = self.winners.clone();
+            self.observers.emit(&GameEvent::GameOver { winners });
+        }
+
+        Ok(())
+    }
+
+    /// Opens a vote to end the game early, with the proposer voting for
+    /// it. If they're the only player left holding cards the game ends
+    /// straight away. An open vote is dropped if anyone goes out or
+    /// resigns before it's decided.
+    pub fn propose_concede(&mut self, player_id: &str) -> Result<ConcedeOutcome, ConcedeError> {
+        if self.concede_vote.is_some() {
+            return Err(ConcedeError::VoteInProgress);
+        }
+
+        let voters = self.get_concede_voters(player_id)?;
+        self.concede_vote = Some(ConcedeVote::new(player_id, voters));
+        let outcome = self.concede_vote.as_ref()
+            .map(|vote| vote.get_outcome())
+            .unwrap_or(ConcedeOutcome::Pending);
+        self.resolve_concede(outcome);
+
+        Ok(outcome)
+    }
+
+    pub fn vote_concede(
+        &mut self,
+        player_id: &str,
+        agree: bool,
+    ) -> Result<ConcedeOutcome, ConcedeError> {
+        self.get_concede_voters(player_id)?;
+        let outcome = self.concede_vote.as_mut()
+            .ok_or(ConcedeError::NoVote)?
+            .vote(player_id, agree)?;
+        self.resolve_concede(outcome);
+
+        Ok(outcome)
+    }
+
+    pub fn get_concede_vote(&self) -> Option<&ConcedeVote> {
+        self.concede_vote.as_ref()
+    }
+
+    fn get_concede_voters(&self, player_id: &str) -> Result<Vec<String>, ConcedeError> {
+        let player = self.round.get_player(player_id)
+            .ok_or(ConcedeError::UnknownPlayer)?;
+
+        if self.round.is_game_over() {
+            return Err(ConcedeError::GameOver);
+        }
+        if player.get_card_count() == 0 {
+            return Err(ConcedeError::NotVoting);
+        }
+
+        Ok(self.round.get_players().into_iter()
+            .filter(|p| p.get_card_count() > 0)
+            .map(|p| p.get_id().to_string())
+            .collect())
+    }
+
+    fn resolve_concede(&mut self, outcome: ConcedeOutcome) {
+        match outcome {
+            ConcedeOutcome::Pending => {},
+            ConcedeOutcome::Rejected => self.concede_vote = None,
+            ConcedeOutcome::Accepted => {
+                self.concede_vote = None;
+                self.round.concede_mut();
+                let winners = self.winners.clone();
+                self.observers.emit(&GameEvent::GameOver { winners });
+            },
+        }
+    }
+
+    /// `player_id` lays down their hand and claims the rest of the game. If
+    /// nobody could beat it, whatever order it's played in, it's played
+    /// out with everyone else passing and the game ends with the others
+    /// placed on the cards they hold. Otherwise the claimer pays the
+    /// ruleset's false claim penalty.
+    pub fn claim_win(&mut self, player_id: &str) -> Result<ClaimOutcome, ClaimError> {
+        self.round.get_player(player_id)
+            .ok_or(ClaimError::UnknownPlayer)?;
+        if self.round.is_game_over() {
+            return Err(ClaimError::GameOver);
+        }
+        if self.get_next_player().as_deref() != Some(player_id) {
+            return Err(ClaimError::NotCurrentPlayer);
+        }
+        if self.is_paused() {
+            return Err(ClaimError::GamePaused);
+        }
+        let kitty_pending = match self.round.get_kitty_stage() {
+            KittyStage::Closed => !self.round.get_kitty().is_empty(),
+            KittyStage::PickUp(_) | KittyStage::Discard { .. } => true,
+            KittyStage::Done => false,
+        };
+        if kitty_pending {
+            return Err(ClaimError::KittyPending);
+        }
+
+        let line = match self.round.find_winning_line(player_id) {
+            Some(line) => line,
+            None => {
+                match self.ruleset.false_claim {
+                    FalseClaimPenalty::Resign => self.resign(player_id)
+                        .expect("the claimer is still in the game"),
+                    FalseClaimPenalty::Points(_) => self.round.record_false_claim(player_id),
+                }
+                return Ok(ClaimOutcome::Rejected);
+            },
+        };
+
+        for cards in line {
+            self.play_move(player_id, cards).expect("the line was checked");
+            if self.round.get_player(player_id).is_some_and(|p| p.get_card_count() == 0) {
+                break;
+            }
+            self.declare_last_card(player_id).ok();
+            while let Some(next) = self.get_next_player().filter(|id| id != player_id) {
+                self.play_move(&next, vec![]).expect("only a pass was left to play");
+            }
+        }
+        if !self.round.is_game_over() {
+            self.concede_vote = None;
+            self.round.concede_mut();
+            let winners = self.winners.clone();
+            self.observers.emit(&GameEvent::GameOver { winners });
+        }
+
+        Ok(ClaimOutcome::Upheld)
+    }
+
+    /// Calls last card for `player_id` under the ruleset's last card rule.
+    pub fn declare_last_card(&mut self, player_id: &str) -> Result<(), LastCardError> {
+        self.round.declare_last_card_mut(player_id)
+    }
+
+    /// Re-sorts every hand under the current orders. Hands are dealt
+    /// sorted, but a reversal or a pick up leaves them out of order.
+    pub fn sort_hands(&mut self) {
+        self.round.sort_hands_mut();
+    }
+
+    /// Keeps every hand sorted under the current orders for the rest of
+    /// the game.
+    pub fn set_keep_hands_sorted(&mut self, keep_sorted: bool) {
+        self.round.set_keep_hands_sorted(keep_sorted);
+    }
+
+    /// Passes play round the players in `player_ids` order instead of
+    /// seat order, for tables that don't sit in the order they joined.
+    pub fn set_seat_order(&mut self, player_ids: Vec<String>) -> Result<(), SetupError> {
+        self.round.set_seat_order(player_ids)
+    }
+
+    /// Picks up the blind kitty for the winner of the first trick.
+    pub fn pick_up_kitty(&mut self, player_id: &str) -> Result<(), KittyError> {
+        self.round.pick_up_kitty_mut(player_id)
+    }
+
+    /// Discards back down after picking up the blind kitty.
+    pub fn discard_to_kitty(
+        &mut self,
+        player_id: &str,
+        cards: Vec<Card>,
+    ) -> Result<(), KittyError> {
+        self.round.discard_to_kitty_mut(player_id, cards)
+    }
+
+    /// Players whose dealt hand lets them call a misdeal. Always empty once
+    /// play has started.
+    pub fn get_misdeal_claims(&self) -> Vec<String> {
+        if self.has_started() {
+            return vec![];
+        }
+
+        let conditions = self.ruleset.misdeal;
+        let orders = self.round.get_order_context();
+        self.round.get_players().into_iter()
+            .filter(|p| conditions.is_misdeal(&p.get_hand(), &orders))
+            .map(|p| p.get_id().to_string())
+            .collect()
+    }
+
+    /// Calls a misdeal on behalf of `player_id` and redeals a freshly
+    /// shuffled deck to the same seats.
+    #[cfg(feature = "std")]
+    pub fn declare_misdeal(&mut self, player_id: &str) -> Result<(), MisdealError> {
+        let player = self.round.get_player(player_id)
+            .ok_or(MisdealError::UnknownPlayer)?;
+
+        if self.has_started() {
+            return Err(MisdealError::AlreadyStarted);
+        }
+
+        if !self.ruleset.misdeal.is_misdeal(&player.get_hand(), &self.round.get_order_context()) {
+            return Err(MisdealError::NoMisdeal);
+        }
+
+        let mut deck = new_deck(self.num_decks, self.num_jokers, &self.round.get_order_context());
+        deck.shuffle();
+        self.deal_again(&deck);
+        self.observers.notify(|o| o.on_misdeal(player_id));
+
+        Ok(())
+    }
+
+    /// Records `player_id` asking for the cards to be redealt. Once as
+    /// many players have asked as the ruleset's `redeal_vote` needs, the
+    /// vote is agreed and whoever runs the game calls `redeal`.
+    pub fn vote_redeal(&mut self, player_id: &str) -> Result<RedealOutcome, RedealError> {
+        let needed = self.get_redeal_votes_needed()?;
+        self.round.get_player(player_id)
+            .ok_or(RedealError::UnknownPlayer)?;
+        if self.has_started() {
+            return Err(RedealError::AlreadyStarted);
+        }
+        if self.redeal_votes.iter().any(|id| id == player_id) {
+            return Err(RedealError::AlreadyVoted);
+        }
+
+        self.redeal_votes.push(player_id.to_string());
+        let votes = self.redeal_votes.len();
+
+        Ok(if votes >= needed {
+            RedealOutcome::Agreed
+        } else {
+            RedealOutcome::Pending { votes, needed }
+        })
+    }
+
+    pub fn get_redeal_votes(&self) -> Vec<String> {
+        self.redeal_votes.clone()
+    }
+
+    /// Deals a new deck, shuffled from `seed`, to the same seats once a
+    /// redeal has been agreed. The seed should be fresh for every redeal,
+    /// or the table would be dealt the hands it just voted away.
+    pub fn redeal(&mut self, seed: u64) -> Result<(), RedealError> {
+        let needed = self.get_redeal_votes_needed()?;
+        if self.has_started() {
+            return Err(RedealError::AlreadyStarted);
+        }
+        if self.redeal_votes.len() < needed {
+            return Err(RedealError::NotAgreed);
+        }
+
+        let mut deck = new_deck(self.num_decks, self.num_jokers, &self.round.get_order_context());
+        deck.shuffle_with(&mut StdRng::seed_from_u64(seed));
+        self.deal_again(&deck);
+        self.observers.notify(|o| o.on_redeal());
+
+        Ok(())
+    }
+
+    fn get_redeal_votes_needed(&self) -> Result<usize, RedealError> {
+        self.ruleset.redeal_vote
+            .get_votes_needed(self.round.get_player_ids().len())
+            .ok_or(RedealError::Disabled)
+    }
+
+    /// Deals `deck` to the same seats with the same orders, starting the
+    /// game over.
+    fn deal_again(&mut self, deck: &Deck) {
+        let redealt = Game::from_deck_with_orders(
+            deck,
+            self.num_decks,
+            self.num_jokers,
+            &self.round.get_player_ids(),
+            self.round.get_order_context(),
+            self.ruleset
+        ).expect("the players were checked when the game was dealt");
+        let mut round = redealt.round;
+        round.copy_move_rules(&self.round);
+        round.copy_table_settings(&self.round);
+        self.round = round;
+        self.kitty = redealt.kitty;
+        self.winners = vec![];
+        self.redeal_votes = vec![];
+        self.deal_handicaps();
+    }
+
+    /// Applies handicaps to the hands just dealt. Players with extra cards
+    /// take the lowest card from each of the players without that handicap
+    /// in turn, starting on their left, and players with a discard can set
+    /// cards aside with `discard_for_handicap` until the first move. The
+    /// handicaps are applied again if the game is redealt.
+    pub fn apply_handicaps(
+        &mut self,
+        handicaps: &BTreeMap<String, Handicap>,
+    ) -> Result<(), HandicapError> {
+        if handicaps.keys().any(|id| self.round.get_player(id).is_none()) {
+            return Err(HandicapError::UnknownPlayer);
+        }
+        if self.has_started() {
+            return Err(HandicapError::AlreadyStarted);
+        }
+
+        self.handicaps = handicaps.clone();
+        self.deal_handicaps();
+        Ok(())
+    }
+
+    fn deal_handicaps(&mut self) {
+        let orders = self.round.get_order_context();
+        let player_ids = self.round.get_player_ids();
+        let mut hands: Vec<Vec<Card>> = player_ids.iter()
+            .map(|id| self.round.get_player(id).map(|p| p.get_hand()).unwrap_or_default())
+            .collect();
+        let handicaps = &self.handicaps;
+        let has_extra_cards = |id: &String| {
+            matches!(handicaps.get(id), Some(Handicap::ExtraCards(_)))
+        };
+
+        self.handicap_discards = BTreeMap::new();
+        for (seat, id) in player_ids.iter().enumerate() {
+            let count = match handicaps.get(id) {
+                Some(&Handicap::ExtraCards(count)) => count as usize,
+                Some(&Handicap::Discard(count)) => {
+                    self.handicap_discards.insert(id.clone(), count as usize);
+                    continue;
+                },
+                None => continue,
+            };
+            let donors: Vec<usize> = (1..player_ids.len())
+                .map(|offset| (seat + offset) % player_ids.len())
+                .filter(|&donor| !has_extra_cards(&player_ids[donor]))
+                .collect();
+
+            let mut taken = 0;
+            for &donor in donors.iter().cycle() {
+                if taken == count || donors.iter().all(|&d| hands[d].len() <= 1) {
+                    break;
+                }
+                // nobody is left without cards to play
+                if hands[donor].len() <= 1 {
+                    continue;
+                }
+                let lowest = (0..hands[donor].len())
+                    .min_by(|&a, &b| {
+                        hands[donor][a].cmp_with(&hands[donor][b], &orders)
+                    })
+                    .expect("the donor holds cards");
+                let card = hands[donor].remove(lowest);
+                hands[seat].push(card);
+                taken += 1;
+            }
+        }
+
+        for (id, hand) in player_ids.iter().zip(hands) {
+            self.round.set_hand(id, sort_hand(&hand, &orders));
+        }
+    }
+
+    /// Sets aside the cards a `Handicap::Discard` lets `player_id` drop.
+    /// They go face down into the kitty, out of play.
+    pub fn discard_for_handicap(
+        &mut self,
+        player_id: &str,
+        cards: Vec<Card>,
+    ) -> Result<(), HandicapError> {
+        let player = self.round.get_player(player_id)
+            .ok_or(HandicapError::UnknownPlayer)?;
+        if self.has_started() {
+            return Err(HandicapError::AlreadyStarted);
+        }
+        let count = *self.handicap_discards.get(player_id)
+            .ok_or(HandicapError::NoDiscardOwed)?;
+        if cards.len() != count {
+            return Err(HandicapError::WrongDiscardCount(count));
+        }
+        if cards.len() >= player.get_card_count() {
+            return Err(HandicapError::NotEnoughCards);
+        }
+
+        self.round.set_aside_mut(player_id, &cards)
+            .map_err(HandicapError::PlayerDoesntHaveCard)?;
+        self.handicap_discards.remove(player_id);
+        self.kitty.extend(cards);
+        Ok(())
+    }
+
+    /// How many cards `player_id` may still set aside for their handicap.
+    pub fn get_handicap_discards(&self, player_id: &str) -> usize {
+        self.handicap_discards.get(player_id).copied().unwrap_or(0)
+    }
+
+    fn has_started(&self) -> bool {
+        self.round.get_last_move().is_some()
+            || !self.round.get_resigned_players().is_empty()
+    }
+
     pub fn get_player(&self, id: &str) -> Option<Player> {
         self.round.get_player(id)
     }
@@ -111,67 +940,55 @@ impl Game {
         self.round.get_last_move()
     }
 
+    /// What the cpu would play for `id`, or `None` if it isn't their turn.
     pub fn suggest_move(&self, id: &str) -> Option<Vec<PlayedCard>> {
-        get_move(
-            self.get_last_move(),
-            self.get_player(&id),
-            self.round.get_suit_order(),
-            self.round.get_rank_order(),
-        )
-            
+        if self.get_next_player().as_deref() != Some(id) {
+            return None;
+        }
+
+        Some(CpuAgent.choose_move(&self.round, id))
     }
 
     pub fn get_winners(&self) -> Vec<String> {
         self.winners.clone()
     }
 
-    pub fn check_move(
-        &self,
-        hand: Vec<PlayedCard>) -> bool {
-
-        let new_hand_option = Hand::build(hand.clone());
-        let last_move_option = self.round.get_last_move();
-
-        if new_hand_option.is_none() {
-            return false;
-        }
+    /// Placements for every player and team, aggregated per team when the
+    /// ruleset plays in partnerships.
+    pub fn get_result(&self) -> GameResult {
+        get_game_result(&self.round, &self.winners)
+    }
 
-        if last_move_option.is_none() {
+    /// The cards left undealt, face down, when the ruleset only deals
+    /// some of the deck.
+    pub fn get_kitty(&self) -> Vec<Card> {
+        self.kitty.clone()
+    }
 
-            let lowest_card = PlayedCard::new(
-                self.round.get_rank_order()[0],
-                self.round.get_suit_order()[0],
-                false
-            );
+    pub fn get_round(&self) -> &Round {
+        &self.round
+    }
 
-            return hand.contains(&lowest_card);
+    /// Whether the next player could play `hand` right now. It goes
+    /// through the same checks as `submit_move`, so the two never disagree.
+    pub fn check_move(&self, hand: Vec<PlayedCard>) -> bool {
+        match self.round.get_next_player() {
+            Some(id) => self.round.validate_move(&id, &hand).is_ok(),
+            None => false,
         }
-
-        let new_hand = new_hand_option.expect("invalid hand");
-        let last_move = last_move_option.expect("no last move");
-
-        if last_move == Hand::Pass {
-            return true;
-        }
-
-        compare_hands(
-            last_move,
-            new_hand,
-            self.ruleset.flush_precedence,
-            self.round.get_suit_order(),
-            self.round.get_rank_order()
-        )
     }
 
+    /// Whether `hand` could follow `last_move_option`, or open the round
+    /// if there's no last move, under `orders` and `ruleset`. Unlike
+    /// `check_move` it doesn't know who holds which cards.
     pub fn check_move_m(
         hand: Vec<PlayedCard>,
         last_move_option: Option<Hand>,
-        suit_order: [Suit; 4],
-        rank_order: [Rank; 13],
-        flush_precedence: FlushPrecedence,
+        orders: &OrderContext,
+        ruleset: Ruleset,
     ) -> bool {
 
-        let new_hand_option = Hand::build(hand.clone());
+        let new_hand_option = Hand::try_build_in_order(hand.clone(), ruleset, orders).ok();
 
         if new_hand_option.is_none() {
             return false;
@@ -180,12 +997,12 @@ impl Game {
         if last_move_option.is_none() {
 
             let lowest_card = PlayedCard::new(
-                rank_order[0],
-                suit_order[0],
+                orders.lowest_rank(),
+                orders.lowest_suit(),
                 false
             );
 
-            return hand.contains(&lowest_card);
+            return !ruleset.opening_requires_lowest_card || hand.contains(&lowest_card);
         }
 
         let new_hand = new_hand_option.expect("invalid hand");
@@ -195,16 +1012,15 @@ impl Game {
             return true;
         }
 
-        compare_hands(
+        compare_hands_with_ruleset(
             last_move,
             new_hand,
-            flush_precedence,
-            suit_order,
-            rank_order
+            ruleset,
+            orders
         )
     }
 
-    pub fn get_suit_order(&self) -> [Suit; 4] {
+    pub fn get_suit_order(&self) -> Vec<Suit> {
         self.round.get_suit_order()
     }
 
@@ -212,59 +1028,245 @@ impl Game {
         self.round.get_rank_order()
     }
 
+    fn notify_observers(
+        &mut self,
+        old_round: &Round,
+        player_id: &str,
+        player_move: Vec<PlayedCard>,
+    ) {
+        if self.observers.is_empty() {
+            return;
+        }
+
+        let hand = old_round.build_hand(&player_move)
+            .expect("accepted moves make a hand");
+        let events = get_move_events(old_round, &self.round, player_id, hand, &self.winners);
+        for event in events.iter() {
+            self.observers.emit(event);
+        }
+    }
+
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::cards::*;
-    use crate::game::FlushPrecedence;
+    use crate::game::{
+        CrossCountBombs,
+        DealMode,
+        GameStatus,
+        KittyStage,
+        FalseClaimPenalty,
+        FinalCardRule,
+        FlushPrecedence,
+        JokerUsage,
+        LastCardRule,
+        MisdealConditions,
+        RedealVote,
+        ReversalDuration,
+        ReversalTriggers,
+        StraightComparison,
+        TableClearMode,
+        TrickRanking,
+        TrickType,
+        TurnOrder,
+    };
+    use crate::scenario::{parse_cards, parse_played_cards, Scenario};
 
     const DEFAULT_RULESET: Ruleset = Ruleset{
+        preset: None,
         reversals_enabled: true,
+        reversal_triggers: ReversalTriggers::classic(),
+        reversal_duration: ReversalDuration::RestOfGame,
         flush_precedence: FlushPrecedence::Rank,
+        straight_comparison: StraightComparison::RankThenSuit,
+        partnership: false,
+        opening_requires_lowest_card: true,
+        table_clear: TableClearMode::ReturnToLastPlayer,
+        joker_usage: JokerUsage::Anywhere,
+        royal_flush_tier: false,
+        misdeal: MisdealConditions::none(),
+        redeal_vote: RedealVote::Off,
+        deal_mode: DealMode::FullDeck,
+        last_card: LastCardRule::Off,
+        final_card: FinalCardRule::Any,
+        false_claim: FalseClaimPenalty::Resign,
+        trick_ranking: TrickRanking::standard(),
+        twos_in_straights: true,
+        pass_locks_out: false,
+        cross_count_bombs: CrossCountBombs::none(),
+        strict_finish: false,
+        turn_order: TurnOrder::Clockwise,
     };
 
 
-    #[test]
-    fn it_allows_retrieving_a_player_by_id() {
-        let ids = [
-            String::from("a"),
-            String::from("b"),
-            String::from("c")
-        ];
-        let game = Game::new(
-            1, 0, &ids, get_suit_array(), DEFAULT_RULESET
+    #[test]
+    fn it_allows_retrieving_a_player_by_id() {
+        let ids = [
+            String::from("a"),
+            String::from("b"),
+            String::from("c")
+        ];
+        let game = Game::new(
+            1, 0, &ids, &get_suit_array(), DEFAULT_RULESET
+        ).unwrap();
+        let player_a = game.get_player("a").unwrap();
+
+        assert_eq!(player_a.get_card_count(), 18);
+    }
+
+    #[test]
+    fn when_game_hasnt_started_player_with_lowest_card_starts() {
+        let ids = [String::from("a"), String::from("b")];
+        let game = Game::new(
+            1, 0, &ids, &get_suit_array(), DEFAULT_RULESET
+        ).unwrap();
+
+        let next_player = game.get_next_player().unwrap();
+        let three_clubs = Card::Standard {
+            deck_id: 0,
+            rank: Rank::Three,
+            suit: Suit::Clubs,
+        };
+
+        assert!(game.get_player(&next_player).unwrap()
+            .has_card(three_clubs));
+    }
+
+    #[test]
+    fn games_need_distinct_players() {
+        let ids = [String::from("a"), String::from("b"), String::from("a")];
+
+        assert_eq!(
+            Game::new(1, 0, &ids, &get_suit_array(), DEFAULT_RULESET).err(),
+            Some(SetupError::DuplicatePlayer("a".to_string()))
+        );
+        assert_eq!(
+            Game::new(1, 0, &[], &get_suit_array(), DEFAULT_RULESET).err(),
+            Some(SetupError::NoPlayers)
+        );
+    }
+
+    #[test]
+    fn games_end_early_when_everyone_agrees_to_concede() {
+        let ids = [String::from("a"), String::from("b"), String::from("c")];
+        let mut game = Game::new(1, 0, &ids, &get_suit_array(), DEFAULT_RULESET).unwrap();
+
+        assert_eq!(game.vote_concede("a", true), Err(ConcedeError::NoVote));
+        assert_eq!(game.propose_concede("a"), Ok(ConcedeOutcome::Pending));
+        assert_eq!(game.propose_concede("b"), Err(ConcedeError::VoteInProgress));
+        assert_eq!(game.vote_concede("a", true), Err(ConcedeError::AlreadyVoted));
+        assert_eq!(game.vote_concede("b", true), Ok(ConcedeOutcome::Pending));
+        assert_eq!(game.vote_concede("c", false), Ok(ConcedeOutcome::Rejected));
+        assert!(game.get_concede_vote().is_none());
+        assert!(game.get_next_player().is_some());
+
+        game.propose_concede("c").unwrap();
+        assert_eq!(
+            game.get_concede_vote().unwrap().get_waiting_on(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+        game.vote_concede("b", true).unwrap();
+        assert_eq!(game.vote_concede("a", true), Ok(ConcedeOutcome::Accepted));
+
+        assert_eq!(game.get_next_player(), None);
+        assert!(game.get_round().is_conceded());
+        assert!(matches!(game.get_round().get_status(), GameStatus::Finished { .. }));
+        assert!(game.get_result().players.iter().all(|p| p.cards_left > 0));
+        assert_eq!(game.propose_concede("a"), Err(ConcedeError::GameOver));
+    }
+
+    #[test]
+    fn games_can_start_from_given_hands() {
+        let card = |deck_id, rank, suit| Card::Standard { deck_id, rank, suit };
+        let hands = vec![
+            ("a".to_string(), vec![card(0, Rank::Four, Suit::Clubs), Card::Joker { deck_id: 0 }]),
+            ("b".to_string(), vec![card(0, Rank::Three, Suit::Clubs)]),
+        ];
+        let start = |hands: &[(String, Vec<Card>)], num_jokers| Game::from_hands(
+            hands, 1, num_jokers, OrderContext::standard(), DEFAULT_RULESET
+        );
+
+        let game = start(&hands, 1).unwrap();
+        assert_eq!(game.get_player("a").unwrap().get_card_count(), 2);
+        assert_eq!(game.get_next_player(), Some("b".to_string()));
+
+        assert_eq!(
+            start(&hands, 0).err(),
+            Some(CustomDealError::CardNotInDeck(Card::Joker { deck_id: 0 }))
+        );
+
+        let mut doubled = hands.clone();
+        doubled[1].1.push(card(0, Rank::Four, Suit::Clubs));
+        assert_eq!(
+            start(&doubled, 1).err(),
+            Some(CustomDealError::CardNotInDeck(card(0, Rank::Four, Suit::Clubs)))
+        );
+
+        let second_deck = vec![("a".to_string(), vec![card(1, Rank::Four, Suit::Clubs)])];
+        assert_eq!(
+            start(&second_deck, 0).err(),
+            Some(CustomDealError::CardNotInDeck(card(1, Rank::Four, Suit::Clubs)))
         );
-        let player_a = game.get_player("a").unwrap();
+    }
 
-        assert_eq!(player_a.get_card_count(), 18);
+    #[test]
+    fn custom_orders_pick_the_opening_lead() {
+        let ids = [String::from("a"), String::from("b")];
+        let mut twos_low = get_rank_array();
+        twos_low.rotate_right(1);
+        let orders = OrderContext::new(&get_suit_array(), twos_low).unwrap();
+        let game = Game::with_orders(1, 0, &ids, orders, DEFAULT_RULESET).unwrap();
+        let two_clubs = PlayedCard::new(Rank::Two, Suit::Clubs, false);
+
+        assert_eq!(game.get_round().get_rank_order()[0], Rank::Two);
+        assert_eq!(game.get_round().get_lowest_card(), Some(two_clubs));
+        assert!(game.get_player(&game.get_next_player().unwrap()).unwrap()
+            .has_card(two_clubs.to_card()));
     }
 
     #[test]
-    fn when_game_hasnt_started_player_with_lowest_card_starts() {
+    fn custom_orders_must_be_permutations() {
         let ids = [String::from("a"), String::from("b")];
-        let game = Game::new(
-            1, 0, &ids, get_suit_array(), DEFAULT_RULESET
-        );
+        let mut rank_order = get_rank_array();
+        rank_order[1] = Rank::Three;
 
-        let next_player = game.get_next_player().unwrap();
-        let three_clubs = Card::Standard {
-            deck_id: 0,
-            rank: Rank::Three,
-            suit: Suit::Clubs,
-        };
+        assert_eq!(
+            Game::new(1, 0, &ids, &[Suit::Clubs; 4], DEFAULT_RULESET).err(),
+            Some(SetupError::InvalidSuitOrder)
+        );
+        assert_eq!(
+            OrderContext::new(&get_suit_array(), rank_order).err(),
+            Some(SetupError::InvalidRankOrder)
+        );
+    }
 
-        assert!(game.get_player(&next_player).unwrap()
-            .has_card(three_clubs));
+    #[test]
+    fn games_can_be_dealt_with_five_suits() {
+        let ids = [String::from("a"), String::from("b"), String::from("c")];
+        let mut five_suits = vec![Suit::Eagles];
+        five_suits.extend(get_suit_array());
+        let game = Game::new(1, 0, &ids, &five_suits, DEFAULT_RULESET).unwrap();
+
+        assert_eq!(game.get_round().get_dealt_cards().len(), 65);
+        // the eagles rank lowest, so the three of eagles leads
+        let leader = game.get_next_player().unwrap();
+        assert!(game.get_player(&leader).unwrap().get_hand().contains(&"3E".parse().unwrap()));
+
+        let deck = Deck::with_suits(1, 0, &five_suits).unwrap();
+        assert_eq!(
+            Game::from_deck(&deck, 1, 0, &ids, &get_suit_array(), DEFAULT_RULESET).err(),
+            Some(SetupError::InvalidSuitOrder)
+        );
     }
 
     #[test]
     fn player_loses_cards_that_it_plays() {
         let ids = ["a".to_string(), "b".to_string()];
         let mut game = Game::new(
-            1,0, &ids, get_suit_array(), DEFAULT_RULESET
-        );
+            1,0, &ids, &get_suit_array(), DEFAULT_RULESET
+        ).unwrap();
 
         let next_player = game.get_next_player()
             .expect("unable to get next player").to_owned();
@@ -320,8 +1322,7 @@ mod tests {
             Some("b".to_string()),
             Some(Hand::Pass),
             Some("a".to_string()),
-            get_suit_array(),
-            get_rank_array(),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
 
@@ -330,7 +1331,15 @@ mod tests {
             num_jokers: 1,
             round,
             winners: vec!(),
-            ruleset: DEFAULT_RULESET 
+            ruleset: DEFAULT_RULESET,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
         };
 
         let hand = vec![
@@ -385,8 +1394,7 @@ mod tests {
             Some("b".to_string()),
             Some(Hand::Pass),
             Some("a".to_string()),
-            get_suit_array(),
-            get_rank_array(),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
 
@@ -395,7 +1403,15 @@ mod tests {
             num_jokers: 1,
             round,
             winners: vec!(),
-            ruleset: DEFAULT_RULESET
+            ruleset: DEFAULT_RULESET,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
         };
 
         let hand = vec![
@@ -408,7 +1424,7 @@ mod tests {
 
         let _ = game.play_move("b", hand);
 
-        assert!(game.get_winners().first().is_none());
+        assert!(game.get_winners().is_empty());
     }
 
     #[test]
@@ -444,8 +1460,7 @@ mod tests {
             Some("c".to_string()),
             Some(Hand::Pass),
             Some("a".to_string()),
-            get_suit_array(),
-            get_rank_array(),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
 
@@ -454,7 +1469,15 @@ mod tests {
             num_jokers: 1,
             round,
             winners: vec!["c".to_string()],
-            ruleset: DEFAULT_RULESET
+            ruleset: DEFAULT_RULESET,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
         };
 
         let hand = vec![];
@@ -497,8 +1520,7 @@ mod tests {
             Some("b".to_string()),
             Some(Hand::Pass),
             Some("a".to_string()),
-            get_suit_array(),
-            get_rank_array(),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
 
@@ -507,7 +1529,15 @@ mod tests {
             num_jokers: 1,
             round,
             winners: vec!["c".to_string()],
-            ruleset: DEFAULT_RULESET
+            ruleset: DEFAULT_RULESET,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
         };
 
         let hand = vec![
@@ -530,8 +1560,8 @@ mod tests {
     fn check_move_returns_false_when_unable_to_play() {
         let ids = ["a".to_string(), "b".to_string()];
         let game = Game::new(
-            1,0, &ids, get_suit_array(), DEFAULT_RULESET
-        );
+            1,0, &ids, &get_suit_array(), DEFAULT_RULESET
+        ).unwrap();
 
         let hand = vec![
             PlayedCard::new(
@@ -550,8 +1580,8 @@ mod tests {
     fn check_move_returns_ok_when_able_to_play() {
         let ids = ["a".to_string(), "b".to_string()];
         let game = Game::new(
-            1,0, &ids, get_suit_array(), DEFAULT_RULESET
-        );
+            1,0, &ids, &get_suit_array(), DEFAULT_RULESET
+        ).unwrap();
 
         let hand = vec![
             PlayedCard::new(
@@ -570,8 +1600,8 @@ mod tests {
     fn check_move_returns_false_when_hand_is_invalid() {
         let ids = ["a".to_string(), "b".to_string()];
         let game = Game::new(
-            1,0, &ids, get_suit_array(), DEFAULT_RULESET
-        );
+            1,0, &ids, &get_suit_array(), DEFAULT_RULESET
+        ).unwrap();
 
         let hand = vec![
             PlayedCard::new(
@@ -608,7 +1638,7 @@ mod tests {
         let b_cards = vec![
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Three,
+                rank: Rank::Four,
                 suit: Suit::Clubs,
             },
         ];
@@ -624,8 +1654,7 @@ mod tests {
             Some("b".to_string()),
             Some(Hand::Pass),
             Some("a".to_string()),
-            get_suit_array(),
-            get_rank_array(),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
 
@@ -634,7 +1663,15 @@ mod tests {
             num_jokers: 1,
             round,
             winners: vec!["c".to_string()],
-            ruleset: DEFAULT_RULESET
+            ruleset: DEFAULT_RULESET,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
         };
 
         let hand = vec![
@@ -667,7 +1704,7 @@ mod tests {
         let b_cards = vec![
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Three,
+                rank: Rank::Four,
                 suit: Suit::Clubs,
             },
         ];
@@ -687,8 +1724,7 @@ mod tests {
                 false,
             ))),
             Some("a".to_string()),
-            get_suit_array(),
-            get_rank_array(),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
 
@@ -697,7 +1733,15 @@ mod tests {
             num_jokers: 1,
             round,
             winners: vec!["c".to_string()],
-            ruleset: DEFAULT_RULESET
+            ruleset: DEFAULT_RULESET,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
         };
 
         let hand = vec![
@@ -750,8 +1794,7 @@ mod tests {
                 false,
             ))),
             Some("a".to_string()),
-            get_suit_array(),
-            get_rank_array(),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
 
@@ -760,7 +1803,15 @@ mod tests {
             num_jokers: 1,
             round,
             winners: vec!["c".to_string()],
-            ruleset: DEFAULT_RULESET
+            ruleset: DEFAULT_RULESET,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
         };
 
         let hand = vec![
@@ -776,4 +1827,661 @@ mod tests {
         assert!(!result);
     }
 
+    #[test]
+    fn check_move_follows_the_opening_rule() {
+        let hands = vec![
+            ("a".to_string(), parse_cards("3C 5D").unwrap()),
+            ("b".to_string(), parse_cards("4C 6D").unwrap()),
+        ];
+        let start = |opening_requires_lowest_card| Game::from_hands(
+            &hands, 1, 0, OrderContext::standard(),
+            Ruleset { opening_requires_lowest_card, ..DEFAULT_RULESET }
+        ).unwrap();
+        let five_diamonds = parse_played_cards("5D").unwrap();
+
+        assert!(!start(true).check_move(five_diamonds.clone()));
+        let mut game = start(false);
+        assert!(game.check_move(five_diamonds.clone()));
+        assert!(game.play_move("a", five_diamonds).is_ok());
+    }
+
+    #[test]
+    fn suggested_moves_dont_go_out_on_a_forbidden_final_card() {
+        let hands = vec![
+            ("a".to_string(), parse_cards("3C 5D").unwrap()),
+            ("b".to_string(), parse_cards("2S").unwrap()),
+        ];
+        let ruleset = Ruleset { final_card: FinalCardRule::Forbidden, ..DEFAULT_RULESET };
+        let mut game = Game::from_hands(&hands, 1, 0, OrderContext::standard(), ruleset).unwrap();
+        game.play_move("a", parse_played_cards("3C").unwrap()).unwrap();
+
+        let suggestion = game.suggest_move("b").unwrap();
+        assert!(game.check_move(suggestion.clone()));
+        assert!(game.play_move("b", suggestion).is_ok());
+    }
+
+    #[test]
+    fn suggested_moves_fall_back_to_a_legal_move() {
+        let hands = vec![
+            ("a".to_string(), parse_cards("3C 5C").unwrap()),
+            ("b".to_string(), parse_cards("4C 6C").unwrap()),
+            ("c".to_string(), parse_cards("4D 7D").unwrap()),
+        ];
+        let ruleset = Ruleset { pass_locks_out: true, ..DEFAULT_RULESET };
+        let mut game = Game::from_hands(&hands, 1, 0, OrderContext::standard(), ruleset).unwrap();
+        game.play_move("a", parse_played_cards("3C").unwrap()).unwrap();
+        game.play_move("b", vec![]).unwrap();
+        game.play_move("c", parse_played_cards("4D").unwrap()).unwrap();
+        game.play_move("a", parse_played_cards("5C").unwrap()).unwrap();
+
+        assert_eq!(game.suggest_move("a"), None);
+        assert_eq!(game.suggest_move("b"), Some(vec![]));
+    }
+
+    #[test]
+    fn check_move_builds_hands_under_the_ruleset() {
+        let hands = vec![
+            ("a".to_string(), parse_cards("3C JD QD KS AH 2C").unwrap()),
+            ("b".to_string(), parse_cards("4C").unwrap()),
+        ];
+        let ruleset = Ruleset { twos_in_straights: false, ..DEFAULT_RULESET };
+        let mut game = Game::from_hands(&hands, 1, 0, OrderContext::standard(), ruleset).unwrap();
+        game.play_move("a", parse_played_cards("3C").unwrap()).unwrap();
+        game.play_move("b", vec![]).unwrap();
+        let up_to_two = parse_played_cards("JD QD KS AH 2C").unwrap();
+
+        assert!(!game.check_move(up_to_two.clone()));
+        assert_eq!(game.play_move("a", up_to_two.clone()), Err(SubmitError::InvalidHand));
+        assert!(!Game::check_move_m(
+            up_to_two.clone(), Some(Hand::Pass), &OrderContext::standard(), ruleset
+        ));
+        assert!(Game::check_move_m(
+            up_to_two, Some(Hand::Pass), &OrderContext::standard(), DEFAULT_RULESET
+        ));
+    }
+
+    #[test]
+    fn check_move_runs_straights_along_the_round_order() {
+        let mut twos_low = get_rank_array();
+        twos_low.rotate_right(1);
+        let orders = OrderContext::new(&get_suit_array(), twos_low).unwrap();
+        let hands = vec![
+            ("a".to_string(), parse_cards("2C 3H 4D 5S 6C JC QH KD AS").unwrap()),
+            ("b".to_string(), parse_cards("7C").unwrap()),
+        ];
+        let game = Game::from_hands(&hands, 1, 0, orders.clone(), DEFAULT_RULESET).unwrap();
+        let twos_up = parse_played_cards("2C 3H 4D 5S 6C").unwrap();
+
+        assert!(game.check_move(twos_up.clone()));
+        assert!(!game.check_move(parse_played_cards("JC QH KD AS 2C").unwrap()));
+        assert!(Game::check_move_m(twos_up.clone(), Some(Hand::Pass), &orders, DEFAULT_RULESET));
+        assert!(!Game::check_move_m(
+            twos_up, Some(Hand::Pass), &OrderContext::standard(), DEFAULT_RULESET
+        ));
+    }
+
+    struct RecordingObserver(std::rc::Rc<std::cell::RefCell<Vec<String>>>);
+
+    impl GameObserver for RecordingObserver {
+        fn on_move_played(&mut self, player_id: &str, hand: Hand) {
+            let event = match hand {
+                Hand::Pass => format!("pass:{}", player_id),
+                hand => format!("move:{}:{}", player_id, hand.get_card_count()),
+            };
+            self.0.borrow_mut().push(event);
+        }
+
+        fn on_trick_cleared(&mut self, winner_id: &str) {
+            self.0.borrow_mut().push(format!("cleared:{}", winner_id));
+        }
+
+        fn on_player_finished(&mut self, player_id: &str, position: usize) {
+            self.0.borrow_mut().push(
+                format!("finished:{}:{}", player_id, position)
+            );
+        }
+
+        fn on_game_over(&mut self, winners: &[String]) {
+            self.0.borrow_mut().push(format!("over:{}", winners.join(",")));
+        }
+    }
+
+    #[test]
+    fn observers_are_notified_of_game_events() {
+        let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Four,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+        ];
+
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
+
+        let players = vec![player_a, player_b];
+        let round = Round::new(
+            players,
+            Some("b".to_string()),
+            Some(Hand::Pass),
+            Some("a".to_string()),
+            OrderContext::standard(),
+            DEFAULT_RULESET
+        );
+
+        let mut game = Game{
+            num_decks: 1,
+            num_jokers: 0,
+            round,
+            winners: vec!(),
+            ruleset: DEFAULT_RULESET,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
+        };
+
+        let events = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        game.add_observer(Box::new(RecordingObserver(events.clone())));
+
+        let hand = vec![
+            PlayedCard::new(
+                Rank::Three,
+                Suit::Clubs,
+                false,
+            )
+        ];
+
+        let _ = game.play_move("b", hand);
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                "move:b:1".to_string(),
+                "finished:b:1".to_string(),
+                "over:b".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn observers_see_the_hand_the_round_s_orders_make() {
+        let card = |rank, suit| Card::Standard { deck_id: 0, rank, suit };
+        let hands = vec![
+            ("a".to_string(), vec![
+                card(Rank::Two, Suit::Clubs),
+                card(Rank::Three, Suit::Hearts),
+                card(Rank::Four, Suit::Diamonds),
+                card(Rank::Five, Suit::Spades),
+                card(Rank::Six, Suit::Clubs),
+                card(Rank::King, Suit::Clubs),
+            ]),
+            ("b".to_string(), vec![card(Rank::Seven, Suit::Clubs)]),
+        ];
+        let mut twos_low = get_rank_array();
+        twos_low.rotate_right(1);
+        let orders = OrderContext::new(&get_suit_array(), twos_low).unwrap();
+        let mut game = Game::from_hands(&hands, 1, 0, orders, DEFAULT_RULESET).unwrap();
+
+        let events = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        game.add_observer(Box::new(RecordingObserver(events.clone())));
+
+        let run = [
+            (Rank::Two, Suit::Clubs),
+            (Rank::Three, Suit::Hearts),
+            (Rank::Four, Suit::Diamonds),
+            (Rank::Five, Suit::Spades),
+            (Rank::Six, Suit::Clubs),
+        ].iter().map(|&(rank, suit)| PlayedCard::new(rank, suit, false)).collect();
+        game.play_move("a", run).unwrap();
+
+        assert_eq!(*events.borrow(), vec!["move:a:5".to_string()]);
+    }
+
+    #[test]
+    fn a_misdeal_redeals_the_game() {
+        let card = |rank, suit| Card::Standard { deck_id: 0, rank, suit };
+        let player_a = Player::new("a".to_string(), vec![
+            card(Rank::Four, Suit::Clubs),
+            card(Rank::Five, Suit::Clubs),
+        ]);
+        let player_b = Player::new("b".to_string(), vec![
+            card(Rank::Three, Suit::Clubs),
+            card(Rank::King, Suit::Spades),
+        ]);
+        let ruleset = Ruleset {
+            misdeal: MisdealConditions {
+                no_face_cards: true,
+                ..MisdealConditions::none()
+            },
+            ..DEFAULT_RULESET
+        };
+        let round = Round::new(
+            vec![player_a, player_b],
+            None,
+            None,
+            None,
+            OrderContext::standard(),
+            ruleset
+        );
+        let mut game = Game{
+            num_decks: 1,
+            num_jokers: 0,
+            round,
+            winners: vec!(),
+            ruleset,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
+        };
+
+        assert_eq!(game.get_misdeal_claims(), vec!["a".to_string()]);
+        assert_eq!(game.declare_misdeal("b"), Err(MisdealError::NoMisdeal));
+        assert_eq!(game.declare_misdeal("c"), Err(MisdealError::UnknownPlayer));
+
+        game.declare_misdeal("a").unwrap();
+
+        assert_eq!(game.get_player("a").unwrap().get_card_count(), 26);
+        assert_eq!(game.get_player("b").unwrap().get_card_count(), 26);
+    }
+
+    #[test]
+    fn the_table_can_vote_for_a_redeal() {
+        let ids = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let start = |redeal_vote| {
+            let mut deck = Deck::new(1, 0);
+            deck.shuffle_with(&mut StdRng::seed_from_u64(1));
+            let ruleset = Ruleset { redeal_vote, ..DEFAULT_RULESET };
+            Game::from_deck(&deck, 1, 0, &ids, &get_suit_array(), ruleset).unwrap()
+        };
+        let hands = |game: &Game| -> Vec<Vec<Card>> {
+            game.get_round().get_players().iter().map(|p| p.get_hand()).collect()
+        };
+
+        let mut game = start(RedealVote::Off);
+        assert_eq!(game.vote_redeal("a"), Err(RedealError::Disabled));
+
+        let mut game = start(RedealVote::Majority);
+        let dealt = hands(&game);
+        assert_eq!(game.vote_redeal("a"), Ok(RedealOutcome::Pending { votes: 1, needed: 2 }));
+        assert_eq!(game.vote_redeal("a"), Err(RedealError::AlreadyVoted));
+        assert_eq!(game.vote_redeal("d"), Err(RedealError::UnknownPlayer));
+        assert_eq!(game.redeal(2), Err(RedealError::NotAgreed));
+        assert_eq!(game.vote_redeal("c"), Ok(RedealOutcome::Agreed));
+        game.redeal(2).unwrap();
+
+        let mut same_seed = start(RedealVote::Majority);
+        same_seed.vote_redeal("a").unwrap();
+        same_seed.vote_redeal("b").unwrap();
+        same_seed.redeal(2).unwrap();
+
+        assert_ne!(hands(&game), dealt);
+        assert_eq!(hands(&game), hands(&same_seed));
+        assert!(game.get_redeal_votes().is_empty());
+        assert_eq!(hands(&game).iter().map(Vec::len).sum::<usize>(), 52);
+
+        let lead = game.get_next_player().unwrap();
+        let cards = game.suggest_move(&lead).unwrap();
+        game.play_move(&lead, cards).unwrap();
+        assert_eq!(game.vote_redeal("b"), Err(RedealError::AlreadyStarted));
+    }
+
+    #[test]
+    fn a_stripped_deck_deals_three_even_hands() {
+        let ids = [
+            String::from("a"),
+            String::from("b"),
+            String::from("c")
+        ];
+        let ruleset = Ruleset {
+            deal_mode: DealMode::StrippedForThree {
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            ..DEFAULT_RULESET
+        };
+        let game = Game::new(1, 0, &ids, &get_suit_array(), ruleset).unwrap();
+        let three_hearts = PlayedCard::new(Rank::Three, Suit::Hearts, false);
+
+        for id in ids.iter() {
+            assert_eq!(game.get_player(id).unwrap().get_card_count(), 17);
+        }
+        assert_eq!(game.get_round().get_lowest_card(), Some(three_hearts));
+        assert!(game.get_player(&game.get_next_player().unwrap()).unwrap()
+            .has_card(three_hearts.to_card()));
+        assert!(game.check_move(vec![three_hearts]));
+    }
+
+    fn get_clocked_game(on_timeout: TimeoutAction) -> Game {
+        let card = |rank, suit| Card::Standard { deck_id: 0, rank, suit };
+        let players = vec![
+            Player::new("a".to_string(), vec![
+                card(Rank::Four, Suit::Clubs),
+                card(Rank::Five, Suit::Clubs),
+            ]),
+            Player::new("b".to_string(), vec![
+                card(Rank::Three, Suit::Clubs),
+                card(Rank::Six, Suit::Clubs),
+            ]),
+        ];
+        let round = Round::new(
+            players,
+            Some("b".to_string()),
+            Some(Hand::Single(PlayedCard::new(Rank::Three, Suit::Diamonds, false))),
+            Some("a".to_string()),
+            OrderContext::standard(),
+            DEFAULT_RULESET
+        );
+
+        let mut game = Game{
+            num_decks: 1,
+            num_jokers: 0,
+            round,
+            winners: vec!(),
+            ruleset: DEFAULT_RULESET,
+            clock: None,
+            concede_vote: None,
+            redeal_votes: vec![],
+            kitty: vec![],
+            handicaps: BTreeMap::new(),
+            handicap_discards: BTreeMap::new(),
+            observers: Observers::default(),
+            telemetry: TelemetryHook::default(),
+        };
+        game.set_clock(ClockConfig {
+            initial_ms: 1000,
+            increment_ms: 100,
+            on_timeout,
+        });
+
+        game
+    }
+
+    #[test]
+    fn running_out_of_time_passes_instead_of_playing() {
+        let mut game = get_clocked_game(TimeoutAction::AutoPass);
+        let six = vec![PlayedCard::new(Rank::Six, Suit::Clubs, false)];
+
+        assert_eq!(
+            game.play_timed_move("b", six, 1500),
+            Ok(Some(TimeoutAction::AutoPass))
+        );
+        assert_eq!(game.get_next_player(), Some("a".to_string()));
+        assert_eq!(game.get_player("b").unwrap().get_hand().len(), 2);
+        assert_eq!(game.get_clock().unwrap().get_remaining("b"), Some(100));
+    }
+
+    #[test]
+    fn the_clock_is_saved_with_the_game() {
+        let mut game = get_clocked_game(TimeoutAction::Forfeit);
+        let six = vec![PlayedCard::new(Rank::Six, Suit::Clubs, false)];
+
+        assert_eq!(game.play_timed_move("b", six, 200), Ok(None));
+
+        let json = serde_json::to_string(&game).unwrap();
+        let mut game: Game = serde_json::from_str(&json).unwrap();
+        assert_eq!(game.get_clock().unwrap().get_remaining("b"), Some(900));
+
+        assert_eq!(game.claim_timeout(999), None);
+        assert_eq!(game.claim_timeout(1000), Some(TimeoutAction::Forfeit));
+        assert_eq!(game.get_round().get_resigned_players(), vec!["a".to_string()]);
+    }
+
+    #[test]
+    fn a_paused_game_holds_moves_and_the_clock() {
+        let mut game = get_clocked_game(TimeoutAction::Forfeit);
+        let six = vec![PlayedCard::new(Rank::Six, Suit::Clubs, false)];
+
+        assert_eq!(game.pause(600), Ok(()));
+        assert_eq!(game.play_timed_move("b", six.clone(), 0), Err(SubmitError::GamePaused));
+        assert_eq!(game.claim_timeout(5000), None);
+
+        let json = serde_json::to_string(&game).unwrap();
+        let mut game: Game = serde_json::from_str(&json).unwrap();
+        assert!(game.is_paused());
+
+        assert_eq!(game.resume(), Ok(()));
+        assert_eq!(game.get_clock().unwrap().get_time_left("b", 300), Some(100));
+        assert_eq!(game.play_timed_move("b", six, 300), Ok(None));
+        assert_eq!(game.get_clock().unwrap().get_remaining("b"), Some(200));
+
+        game.clock = None;
+        assert_eq!(game.pause(0), Err(ClockError::NoClock));
+    }
+
+    #[test]
+    fn a_limited_deal_sets_the_rest_aside() {
+        let ids = [String::from("a"), String::from("b"), String::from("c")];
+        let ruleset = Ruleset {
+            deal_mode: DealMode::Limited { cards_per_player: 10 },
+            ..DEFAULT_RULESET
+        };
+        let game = Game::new(1, 0, &ids, &get_suit_array(), ruleset).unwrap();
+
+        for id in ids.iter() {
+            assert_eq!(game.get_player(id).unwrap().get_card_count(), 10);
+        }
+        assert_eq!(game.get_kitty().len(), 22);
+        assert_eq!(game.get_round().get_dealt_cards().len(), 30);
+
+        let lowest = game.get_round().get_lowest_card().unwrap();
+        assert!(game.get_player(&game.get_next_player().unwrap()).unwrap()
+            .has_card(lowest.to_card()));
+    }
+
+    #[test]
+    fn a_short_deck_opens_on_its_lowest_card() {
+        let ids: Vec<String> = ["a", "b", "c", "d"].iter().map(|id| id.to_string()).collect();
+        let ruleset = Ruleset {
+            deal_mode: DealMode::ShortDeck { lowest_rank: Rank::Seven },
+            ..DEFAULT_RULESET
+        };
+        let mut game = Game::new(1, 0, &ids, &get_suit_array(), ruleset).unwrap();
+        let seven_clubs = PlayedCard::new(Rank::Seven, Suit::Clubs, false);
+
+        for id in ids.iter() {
+            assert_eq!(game.get_player(id).unwrap().get_card_count(), 8);
+        }
+        assert_eq!(game.get_round().get_lowest_card(), Some(seven_clubs));
+
+        let opener = game.get_next_player().unwrap();
+        assert!(game.get_player(&opener).unwrap().has_card(seven_clubs.to_card()));
+        game.play_move(&opener, vec![seven_clubs]).unwrap();
+    }
+
+    #[test]
+    fn a_short_deck_ranks_the_ace_low_straight_lowest() {
+        let ruleset = Ruleset {
+            deal_mode: DealMode::ShortDeck { lowest_rank: Rank::Seven },
+            ..DEFAULT_RULESET
+        };
+        let hands = vec![
+            ("a".to_string(), parse_cards("AS 7C 8H 9D 10S KH").unwrap()),
+            ("b".to_string(), parse_cards("7S 8C 9H 10D JS QC").unwrap()),
+            ("c".to_string(), parse_cards("2C").unwrap()),
+        ];
+
+        assert_eq!(
+            Game::from_hands(&hands, 1, 0, OrderContext::standard(), ruleset).err(),
+            Some(CustomDealError::CardNotInDeck(parse_cards("2C").unwrap()[0]))
+        );
+
+        let mut game = Game::from_hands(
+            &hands[..2], 1, 0, OrderContext::standard(), ruleset
+        ).unwrap();
+        let wrapped = parse_played_cards("AS 7C 8H 9D 10S").unwrap();
+        let jack_high = parse_played_cards("7S 8C 9H 10D JS").unwrap();
+
+        assert_eq!(game.get_next_player(), Some("a".to_string()));
+        game.play_move("a", wrapped.clone()).unwrap();
+        assert_eq!(
+            game.get_round().get_last_move().and_then(|hand| hand.get_trick_type()),
+            Some(TrickType::Straight)
+        );
+        game.play_move("b", jack_high.clone()).unwrap();
+
+        let round = Scenario::new()
+            .player("a", "AS 7C 8H 9D 10S")
+            .player("b", "QC")
+            .last_move("b", "7S 8C 9H 10D JS")
+            .next_player("a")
+            .ruleset(ruleset)
+            .build()
+            .unwrap();
+        assert_eq!(round.validate_move("a", &wrapped), Err(SubmitError::HandNotHighEnough));
+    }
+
+    #[test]
+    fn a_blind_kitty_is_dealt_face_down() {
+        let ids = [String::from("a"), String::from("b"), String::from("c")];
+        let ruleset = Ruleset {
+            deal_mode: DealMode::BlindKitty { cards: 4 },
+            ..DEFAULT_RULESET
+        };
+        let game = Game::new(1, 0, &ids, &get_suit_array(), ruleset).unwrap();
+
+        for id in ids.iter() {
+            assert_eq!(game.get_player(id).unwrap().get_card_count(), 16);
+        }
+        assert_eq!(game.get_round().get_kitty().len(), 4);
+        assert_eq!(game.get_round().get_kitty_stage(), KittyStage::Closed);
+        assert!(game.get_kitty().is_empty());
+    }
+
+    #[test]
+    fn handicapped_players_take_extra_cards_or_discard() {
+        let cards = |cards| crate::scenario::parse_cards(cards).unwrap();
+        let hands = vec![
+            ("a".to_string(), cards("3C 9H KS AS")),
+            ("b".to_string(), cards("4C 5D 10H")),
+            ("c".to_string(), cards("6S 7S 8D JD")),
+        ];
+        let mut game = Game::from_hands(
+            &hands, 1, 0, OrderContext::standard(), DEFAULT_RULESET
+        ).unwrap();
+        let mut handicaps = BTreeMap::new();
+        handicaps.insert("d".to_string(), Handicap::Discard(1));
+        assert_eq!(game.apply_handicaps(&handicaps), Err(HandicapError::UnknownPlayer));
+
+        let mut handicaps = BTreeMap::new();
+        handicaps.insert("a".to_string(), Handicap::ExtraCards(2));
+        handicaps.insert("c".to_string(), Handicap::Discard(1));
+        game.apply_handicaps(&handicaps).unwrap();
+
+        let mut held = game.get_player("a").unwrap().get_hand();
+        held.sort();
+        let mut expected = cards("3C 4C 6S 9H KS AS");
+        expected.sort();
+        assert_eq!(held, expected);
+        assert_eq!(game.get_player("b").unwrap().get_card_count(), 2);
+        assert_eq!(game.get_handicap_discards("c"), 1);
+
+        assert_eq!(game.discard_for_handicap("b", cards("5D")), Err(HandicapError::NoDiscardOwed));
+        assert_eq!(
+            game.discard_for_handicap("c", vec![]),
+            Err(HandicapError::WrongDiscardCount(1))
+        );
+        game.discard_for_handicap("c", cards("JD")).unwrap();
+        assert_eq!(game.get_player("c").unwrap().get_card_count(), 2);
+        assert_eq!(game.get_kitty(), cards("JD"));
+        assert_eq!(game.get_handicap_discards("c"), 0);
+        assert_eq!(game.get_round().verify_card_conservation(), Ok(()));
+
+        game.play_move("a", vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)]).unwrap();
+        assert_eq!(game.apply_handicaps(&handicaps), Err(HandicapError::AlreadyStarted));
+    }
+
+    #[test]
+    fn claims_are_upheld_or_penalised() {
+        let start = |a_hand, false_claim| {
+            let cards = |cards| crate::scenario::parse_cards(cards).unwrap();
+            let hands = vec![
+                ("a".to_string(), cards(a_hand)),
+                ("b".to_string(), cards("4D 9H")),
+                ("c".to_string(), cards("5C KD")),
+            ];
+            let ruleset = Ruleset { false_claim, ..DEFAULT_RULESET };
+            Game::from_hands(&hands, 1, 0, OrderContext::standard(), ruleset).unwrap()
+        };
+
+        let mut game = start("3C 3S 2S", FalseClaimPenalty::Resign);
+        assert_eq!(game.claim_win("b"), Err(ClaimError::NotCurrentPlayer));
+        assert_eq!(game.claim_win("a"), Ok(ClaimOutcome::Upheld));
+        assert_eq!(game.get_winners(), vec!["a".to_string()]);
+        assert!(game.get_round().is_game_over());
+        assert_eq!(game.claim_win("a"), Err(ClaimError::GameOver));
+
+        // the three of clubs has to open and the four of diamonds beats it
+        let mut game = start("3C 2S", FalseClaimPenalty::Points(3));
+        assert_eq!(game.claim_win("a"), Ok(ClaimOutcome::Rejected));
+        assert_eq!(game.get_player("a").unwrap().get_card_count(), 2);
+        assert_eq!(game.get_result().players[0].penalty_points, 3);
+
+        let mut game = start("3C 2S", FalseClaimPenalty::Resign);
+        assert_eq!(game.claim_win("a"), Ok(ClaimOutcome::Rejected));
+        assert_eq!(game.get_round().get_resigned_players(), vec!["a".to_string()]);
+    }
+
+    #[cfg(feature = "telemetry")]
+    #[test]
+    fn telemetry_sees_every_submitted_move() {
+        use alloc::rc::Rc;
+        use core::cell::RefCell;
+        use std::time::Duration;
+
+        #[derive(Default)]
+        struct Counts {
+            submitted: Vec<String>,
+            failed: Vec<SubmitError>,
+        }
+        struct Recorder(Rc<RefCell<Counts>>);
+        impl Telemetry for Recorder {
+            fn on_submit(&mut self, player_id: &str, _cards: &[PlayedCard], _elapsed: Duration) {
+                self.0.borrow_mut().submitted.push(player_id.to_string());
+            }
+
+            fn on_validation_fail(
+                &mut self,
+                _player_id: &str,
+                error: &SubmitError,
+                _elapsed: Duration,
+            ) {
+                self.0.borrow_mut().failed.push(error.clone());
+            }
+        }
+
+        let cards = |cards| crate::scenario::parse_cards(cards).unwrap();
+        let hands = vec![
+            ("a".to_string(), cards("3C 9H")),
+            ("b".to_string(), cards("4C 5C")),
+        ];
+        let mut game = Game::from_hands(
+            &hands, 1, 0, OrderContext::standard(), DEFAULT_RULESET
+        ).unwrap();
+        let counts = Rc::new(RefCell::new(Counts::default()));
+        game.set_telemetry(Box::new(Recorder(counts.clone())));
+
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+        let _ = game.play_move("b", vec![three]);
+        game.play_move("a", vec![three]).unwrap();
+
+        assert_eq!(counts.borrow().submitted, vec!["a".to_string()]);
+        assert_eq!(counts.borrow().failed, vec![SubmitError::NotCurrentPlayer]);
+    }
 }
@@ -0,0 +1,182 @@
+use super::PlayerMetadata;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fmt;
+
+const GUEST_ID_PREFIX: &str = "guest-";
+const GUEST_ID_LENGTH: usize = 12;
+/// Longest display name, in characters, a guest can pick
+pub const MAX_DISPLAY_NAME_LENGTH: usize = 24;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GuestError {
+    /// The name is empty once trimmed, too long or has control characters
+    InvalidDisplayName,
+    UnknownGuest,
+}
+
+/// An id made up by the server for someone playing without an account.
+/// It serializes as the plain string the engine uses as a player id.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GuestId(String);
+
+impl GuestId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether `id` has the shape of a generated guest id, so servers can
+    /// keep users from picking one as a name of their own.
+    pub fn is_guest_id(id: &str) -> bool {
+        id.strip_prefix(GUEST_ID_PREFIX)
+            .map(|rest| {
+                rest.len() == GUEST_ID_LENGTH && rest.chars().all(|c| c.is_ascii_alphanumeric())
+            })
+            .unwrap_or(false)
+    }
+}
+
+impl fmt::Display for GuestId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<GuestId> for String {
+    fn from(id: GuestId) -> String {
+        id.0
+    }
+}
+
+impl Borrow<str> for GuestId {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Guests known to a server and the names they're shown by. Player ids
+/// are generated here rather than taken from what users type, so two
+/// guests picking the same name still get different seats, and a name can
+/// change without the game losing track of who's who.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GuestRegistry {
+    guests: BTreeMap<GuestId, String>,
+}
+
+impl GuestRegistry {
+    pub fn new() -> GuestRegistry {
+        GuestRegistry::default()
+    }
+
+    /// Makes up an id for a new guest shown as `display_name`.
+    pub fn register(&mut self, display_name: &str) -> Result<GuestId, GuestError> {
+        self.register_with_rng(display_name, &mut rand::thread_rng())
+    }
+
+    /// As `register`, drawing the id from `rng`. Ids already handed out
+    /// are never repeated.
+    pub fn register_with_rng<R: Rng>(
+        &mut self,
+        display_name: &str,
+        rng: &mut R,
+    ) -> Result<GuestId, GuestError> {
+        let display_name = check_display_name(display_name)?;
+        let id = loop {
+            let suffix: String = (0..GUEST_ID_LENGTH).map(|_| rng.sample(Alphanumeric)).collect();
+            let id = GuestId(format!("{}{}", GUEST_ID_PREFIX, suffix));
+            if !self.guests.contains_key(&id) {
+                break id;
+            }
+        };
+
+        self.guests.insert(id.clone(), display_name);
+        Ok(id)
+    }
+
+    pub fn rename(&mut self, id: &str, display_name: &str) -> Result<(), GuestError> {
+        let display_name = check_display_name(display_name)?;
+        let name = self.guests.get_mut(id).ok_or(GuestError::UnknownGuest)?;
+        *name = display_name;
+
+        Ok(())
+    }
+
+    /// Forgets a guest, returning the name they had.
+    pub fn remove(&mut self, id: &str) -> Option<String> {
+        self.guests.remove(id)
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.guests.contains_key(id)
+    }
+
+    pub fn get_display_name(&self, id: &str) -> Option<&str> {
+        self.guests.get(id).map(String::as_str)
+    }
+
+    /// Metadata for seating a guest, with their display name filled in.
+    pub fn get_metadata(&self, id: &str) -> Option<PlayerMetadata> {
+        self.get_display_name(id).map(|name| PlayerMetadata {
+            display_name: Some(name.to_string()),
+            ..PlayerMetadata::default()
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.guests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.guests.is_empty()
+    }
+}
+
+fn check_display_name(display_name: &str) -> Result<String, GuestError> {
+    let display_name = display_name.trim();
+    let valid = !display_name.is_empty()
+        && display_name.chars().count() <= MAX_DISPLAY_NAME_LENGTH
+        && !display_name.chars().any(char::is_control);
+
+    if valid {
+        Ok(display_name.to_string())
+    } else {
+        Err(GuestError::InvalidDisplayName)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn guests_with_the_same_name_get_different_ids() {
+        let mut registry = GuestRegistry::new();
+        let mut rng = StdRng::seed_from_u64(7);
+        let first = registry.register_with_rng("Ben", &mut rng).unwrap();
+        let second = registry.register_with_rng(" Ben ", &mut rng).unwrap();
+
+        assert_ne!(first, second);
+        assert!(GuestId::is_guest_id(first.as_str()));
+        assert!(!GuestId::is_guest_id("Ben"));
+        assert_eq!(registry.get_display_name(second.as_str()), Some("Ben"));
+
+        registry.rename(first.as_str(), "Benny").unwrap();
+        assert_eq!(
+            registry.get_metadata(first.as_str()).unwrap().display_name,
+            Some("Benny".to_string())
+        );
+        assert_eq!(registry.rename(first.as_str(), "  "), Err(GuestError::InvalidDisplayName));
+        assert_eq!(registry.rename("nobody", "Ann"), Err(GuestError::UnknownGuest));
+
+        let json = serde_json::to_string(&registry).unwrap();
+        assert!(json.contains(&format!("\"{}\":\"Benny\"", first)));
+        let loaded: GuestRegistry = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, registry);
+    }
+}
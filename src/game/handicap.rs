@@ -0,0 +1,25 @@
+use crate::cards::Card;
+use serde::{Deserialize, Serialize};
+
+/// A per-player handicap applied when the cards are dealt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Handicap {
+    /// The player is dealt this many extra cards, taken from the lowest
+    /// cards of the players without the handicap
+    ExtraCards(u8),
+    /// The player may set aside this many cards before the first move
+    Discard(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HandicapError {
+    UnknownPlayer,
+    /// Handicaps only apply before the first move
+    AlreadyStarted,
+    /// The player has no discard to make
+    NoDiscardOwed,
+    WrongDiscardCount(usize),
+    /// A discard can't leave the player without cards
+    NotEnoughCards,
+    PlayerDoesntHaveCard(Card),
+}
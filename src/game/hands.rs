@@ -1,6 +1,10 @@
+use super::{JokerUsage, OrderContext, Ruleset};
 use crate::cards::{PlayedCard, Rank};
+use crate::i18n::{hand_name, trick_type_name, Locale};
+use crate::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use alloc::collections::BTreeMap;
+use core::fmt;
 
 #[macro_export]
 macro_rules! build_fct {
@@ -13,6 +17,7 @@ macro_rules! build_fct {
 }
 
 #[derive(Clone, Debug, PartialEq, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 /// Type of hand that can be played
 #[serde(tag = "type", content = "cards")]
 #[serde(rename_all = "lowercase")]
@@ -29,16 +34,102 @@ pub enum Hand {
     FiveCardTrick(Trick),
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+/// Reason a set of cards does not form a hand
+pub enum HandError {
+    /// Hands are made of 0, 1, 2, 3 or 5 cards
+    InvalidCardCount(usize),
+    /// Both cards of a pair must share a rank
+    PairRanksDiffer,
+    /// All three cards of a prial must share a rank
+    PrialRanksDiffer,
+    /// Five different ranks that are neither in sequence nor one suit
+    BrokenStraight,
+    /// Five cards that don't make up any five card trick
+    NoFiveCardTrick,
+    /// The ruleset only allows jokers in five card tricks
+    JokerOutsideFiveCardTrick,
+    /// The ruleset's trick ranking leaves this kind of trick out
+    TrickTypeNotAllowed(TrickType),
+}
+
 impl Hand {
     pub fn build(cards: Vec<PlayedCard>) -> Option<Hand> {
+        Self::try_build(cards).ok()
+    }
+
+    pub fn try_build(cards: Vec<PlayedCard>) -> Result<Hand, HandError> {
+        Self::build_hand(cards, true, &OrderContext::standard(), None)
+    }
+
+    fn build_hand(
+        cards: Vec<PlayedCard>,
+        twos_in_straights: bool,
+        orders: &OrderContext,
+        low_ace: Option<Rank>,
+    ) -> Result<Hand, HandError> {
         match cards.len() {
-            0 => Some(Hand::Pass),
-            1 => Some(Hand::Single(cards[0])),
-            2 => Self::check_valid_pair(cards),
-            3 => Self::check_valid_prial(cards),
-            5 => Self::check_valid_fct(cards),
-            _ => None,
+            0 => Ok(Hand::Pass),
+            1 => Ok(Hand::Single(cards[0])),
+            2 => Self::check_valid_pair(cards)
+                .ok_or(HandError::PairRanksDiffer),
+            3 => Self::check_valid_prial(cards)
+                .ok_or(HandError::PrialRanksDiffer),
+            5 => {
+                let distinct_ranks = Self::get_counts(cards.clone()).len();
+                Self::check_valid_fct(cards, twos_in_straights, orders, low_ace).ok_or(
+                    if distinct_ranks == 5 {
+                        HandError::BrokenStraight
+                    } else {
+                        HandError::NoFiveCardTrick
+                    }
+                )
+            },
+            n => Err(HandError::InvalidCardCount(n)),
+        }
+    }
+
+    /// Builds a hand under a ruleset, checking whether twos may be used in
+    /// straights, where jokers may be played and which tricks may be
+    /// played, letting the ace run below a short deck and splitting out
+    /// royal flushes when they rank as their own tier.
+    pub fn try_build_with(
+        cards: Vec<PlayedCard>,
+        ruleset: Ruleset,
+    ) -> Result<Hand, HandError> {
+        Self::try_build_in_order(cards, ruleset, &OrderContext::standard())
+    }
+
+    /// As `try_build_with`, with straights running along the rank order in
+    /// `orders` rather than the standard one, so a round with its own order
+    /// accepts the runs that order makes. A reversed order makes the same
+    /// runs as the order it reverses.
+    pub fn try_build_in_order(
+        cards: Vec<PlayedCard>,
+        ruleset: Ruleset,
+        orders: &OrderContext,
+    ) -> Result<Hand, HandError> {
+        let low_ace = ruleset.deal_mode.get_low_ace_rank(orders);
+        let mut hand = Self::build_hand(cards, ruleset.twos_in_straights, orders, low_ace)?;
+
+        let has_joker = hand.to_cards().iter().any(|c| c.get_is_joker());
+        let five_card_trick = matches!(hand, Hand::FiveCardTrick(_));
+        if ruleset.joker_usage == JokerUsage::FiveCardTricksOnly
+            && has_joker
+            && !five_card_trick {
+            return Err(HandError::JokerOutsideFiveCardTrick);
+        }
+
+        if let Hand::FiveCardTrick(ref mut trick) = hand {
+            if ruleset.royal_flush_tier && trick.is_royal_flush() {
+                trick.trick_type = TrickType::RoyalFlush;
+            }
+            if ruleset.trick_ranking.get_tier(trick.trick_type).is_none() {
+                return Err(HandError::TrickTypeNotAllowed(trick.trick_type));
+            }
         }
+
+        Ok(hand)
     }
 
     pub fn to_cards(&self) -> Vec<PlayedCard> {
@@ -51,6 +142,75 @@ impl Hand {
         }
     }
 
+    /// The same hand with its cards in a fixed order, lowest first by rank
+    /// and then suit in `orders`, with jokers after the card they
+    /// stand in for. Hands holding the same cards are equal once
+    /// canonicalized, however the cards were played.
+    pub fn canonicalize(self, orders: &OrderContext) -> Hand {
+        let mut cards = self.to_cards();
+        cards.sort_by_key(|card| (
+            orders.rank_index(card.get_rank()),
+            orders.suit_index(card.get_suit()),
+            card.get_is_joker(),
+        ));
+
+        match self {
+            Hand::Pass | Hand::Single(_) => self,
+            Hand::Pair(_, _) => Hand::Pair(cards[0], cards[1]),
+            Hand::Prial(_, _, _) => Hand::Prial(cards[0], cards[1], cards[2]),
+            Hand::FiveCardTrick(trick) => Hand::FiveCardTrick(Trick {
+                trick_type: trick.trick_type,
+                cards: [cards[0], cards[1], cards[2], cards[3], cards[4]],
+            }),
+        }
+    }
+
+    /// How many cards the hand is made of, 0 for a pass.
+    pub fn get_card_count(&self) -> usize {
+        match *self {
+            Hand::Pass => 0,
+            Hand::Single(_) => 1,
+            Hand::Pair(_, _) => 2,
+            Hand::Prial(_, _, _) => 3,
+            Hand::FiveCardTrick(_) => 5,
+        }
+    }
+
+    /// The kind of five card trick, for hands that are one.
+    pub fn get_trick_type(&self) -> Option<TrickType> {
+        match *self {
+            Hand::FiveCardTrick(trick) => Some(trick.trick_type),
+            _ => None,
+        }
+    }
+
+    /// The same hand with its ace played as `low_ace`, when it only makes
+    /// a straight running below the lowest rank of a short deck, so it
+    /// compares as the lowest straight there is. Other hands are unchanged.
+    pub(crate) fn with_low_ace(self, low_ace: Rank, orders: &OrderContext) -> Hand {
+        match self {
+            Hand::FiveCardTrick(trick) if matches!(
+                trick.trick_type,
+                TrickType::Straight | TrickType::StraightFlush
+            ) && !Self::is_straight(&trick.cards, orders) => {
+                let cards = lower_aces(&trick.cards, low_ace);
+                Hand::FiveCardTrick(Trick {
+                    trick_type: trick.trick_type,
+                    cards: [cards[0], cards[1], cards[2], cards[3], cards[4]],
+                })
+            },
+            _ => self,
+        }
+    }
+
+    /// Four of a kinds and anything that beats them.
+    pub fn is_bomb(&self) -> bool {
+        match self {
+            Hand::FiveCardTrick(trick) => trick.trick_type >= TrickType::FourOfAKind,
+            _ => false,
+        }
+    }
+
     fn check_valid_pair(cards: Vec<PlayedCard>) -> Option<Hand> {
         if Self::get_counts(cards.clone()).len() == 1 {
             Some(Hand::Pair(cards[0], cards[1]))
@@ -67,7 +227,14 @@ impl Hand {
         }
     }
 
-    fn check_valid_fct(c: Vec<PlayedCard>) -> Option<Hand> {
+    /// Twos only count towards a straight when `twos_in_straights` is set,
+    /// and an ace can stand in for `low_ace` at the bottom of one.
+    fn check_valid_fct(
+        c: Vec<PlayedCard>,
+        twos_in_straights: bool,
+        orders: &OrderContext,
+        low_ace: Option<Rank>,
+    ) -> Option<Hand> {
         let cards = Self::sort_cards(c);
         let rank_count = Self::get_counts(cards.clone());
         match rank_count.len() {
@@ -78,8 +245,13 @@ impl Hand {
                 _ => None,
             },
             _ => {
+                let has_two = cards.iter().any(|c| c.get_rank() == Rank::Two);
+                let is_straight = Self::is_straight(&cards, orders)
+                    || low_ace.is_some_and(|low_ace| {
+                        Self::is_straight(&lower_aces(&cards, low_ace), orders)
+                    });
                 let fct_type = (
-                    Self::is_straight(cards.clone()),
+                    is_straight && (twos_in_straights || !has_two),
                     Self::is_flush(cards.clone()),
                 );
                 match fct_type {
@@ -92,20 +264,25 @@ impl Hand {
         }
     }
 
-    fn is_straight(c: Vec<PlayedCard>) -> bool {
-        c.iter().enumerate().all(|(i, &card)| {
-            i == 0
-                || Self::get_previous_rank(card).is_some()
-                    && c[i - 1].get_rank() == Self::get_previous_rank(card).unwrap()
-        })
+    /// Whether the cards' ranks are consecutive in the rank order, in
+    /// either direction, so reversing the order doesn't change which
+    /// cards make a run.
+    fn is_straight(c: &[PlayedCard], orders: &OrderContext) -> bool {
+        let mut positions: Vec<usize> = c.iter()
+            .filter_map(|card| orders.rank_index(card.get_rank()))
+            .collect();
+        positions.sort_unstable();
+
+        positions.len() == c.len()
+            && positions.windows(2).all(|pair| pair[1] == pair[0] + 1)
     }
 
     fn is_flush(c: Vec<PlayedCard>) -> bool {
         c.iter().all(|&card| card.get_suit() == c[0].get_suit())
     }
 
-    pub fn get_counts(cards: Vec<PlayedCard>) -> HashMap<Rank, usize> {
-        cards.iter().fold(HashMap::new(), |mut acc, &card| {
+    pub fn get_counts(cards: Vec<PlayedCard>) -> BTreeMap<Rank, usize> {
+        cards.iter().fold(BTreeMap::new(), |mut acc, &card| {
             *acc.entry(card.get_rank()).or_insert(0) += 1;
             acc
         })
@@ -116,27 +293,16 @@ impl Hand {
         c.sort();
         c
     }
+}
 
-    fn get_previous_rank(card: PlayedCard) -> Option<Rank> {
-        match card.get_rank() {
-            Rank::Three => None,
-            Rank::Four => Some(Rank::Three),
-            Rank::Five => Some(Rank::Four),
-            Rank::Six => Some(Rank::Five),
-            Rank::Seven => Some(Rank::Six),
-            Rank::Eight => Some(Rank::Seven),
-            Rank::Nine => Some(Rank::Eight),
-            Rank::Ten => Some(Rank::Nine),
-            Rank::Jack => Some(Rank::Ten),
-            Rank::Queen => Some(Rank::Jack),
-            Rank::King => Some(Rank::Queen),
-            Rank::Ace => Some(Rank::King),
-            Rank::Two => Some(Rank::Ace),
-        }
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hand_name(*self, Locale::English))
     }
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Eq, Ord, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 /// Type of 5 card trick
 #[serde(rename_all = "lowercase")]
 pub enum TrickType {
@@ -152,14 +318,46 @@ pub enum TrickType {
     StraightFlush,
     /// 5 of same
     FiveOfAKind,
+    /// 10 to ace of the same suit, only when the ruleset ranks it above
+    /// five of a kind
+    RoyalFlush,
+}
+
+impl fmt::Display for TrickType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", trick_type_name(*self, Locale::English))
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 pub struct Trick {
     pub trick_type: TrickType,
     pub cards: [PlayedCard; 5],
 }
 
+impl Trick {
+    /// A straight flush running from 10 up to the ace.
+    pub fn is_royal_flush(&self) -> bool {
+        let royal = [Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace];
+
+        matches!(self.trick_type, TrickType::StraightFlush | TrickType::RoyalFlush)
+            && royal.iter().all(|&rank| {
+                self.cards.iter().any(|c| c.get_rank() == rank)
+            })
+    }
+}
+
+fn lower_aces(cards: &[PlayedCard], low_ace: Rank) -> Vec<PlayedCard> {
+    cards.iter()
+        .map(|&card| if card.get_rank() == Rank::Ace {
+            PlayedCard::new(low_ace, card.get_suit(), card.get_is_joker())
+        } else {
+            card
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::*;
@@ -416,4 +614,191 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_build_explains_invalid_card_counts() {
+        let card = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+
+        assert_eq!(
+            Hand::try_build(vec![card, card, card, card]),
+            Err(HandError::InvalidCardCount(4))
+        );
+    }
+
+    #[test]
+    fn try_build_explains_mismatched_sets() {
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+        let four = PlayedCard::new(Rank::Four, Suit::Clubs, false);
+
+        assert_eq!(
+            Hand::try_build(vec![three, four]),
+            Err(HandError::PairRanksDiffer)
+        );
+        assert_eq!(
+            Hand::try_build(vec![three, three, four]),
+            Err(HandError::PrialRanksDiffer)
+        );
+    }
+
+    #[test]
+    fn try_build_explains_invalid_five_card_hands() {
+        let broken_straight = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Four, Suit::Hearts, false),
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+            PlayedCard::new(Rank::Eight, Suit::Clubs, false),
+        ];
+        let two_pair = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Hearts, false),
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Five, Suit::Hearts, false),
+            PlayedCard::new(Rank::Eight, Suit::Clubs, false),
+        ];
+
+        assert_eq!(
+            Hand::try_build(broken_straight),
+            Err(HandError::BrokenStraight)
+        );
+        assert_eq!(
+            Hand::try_build(two_pair),
+            Err(HandError::NoFiveCardTrick)
+        );
+    }
+
+    #[test]
+    fn hands_report_their_size_and_trick_type() {
+        let flush = Hand::build(vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+            PlayedCard::new(Rank::Eight, Suit::Clubs, false),
+        ]).unwrap();
+        let single = Hand::Single(PlayedCard::new(Rank::Three, Suit::Clubs, false));
+
+        assert_eq!(flush.get_card_count(), 5);
+        assert_eq!(flush.get_trick_type(), Some(TrickType::Flush));
+        assert_eq!(single.get_card_count(), 1);
+        assert_eq!(single.get_trick_type(), None);
+        assert_eq!(Hand::Pass.get_card_count(), 0);
+    }
+
+    #[test]
+    fn tricks_left_out_of_the_ranking_cant_be_built() {
+        let five_nines = vec![
+            PlayedCard::new(Rank::Nine, Suit::Clubs, false),
+            PlayedCard::new(Rank::Nine, Suit::Hearts, false),
+            PlayedCard::new(Rank::Nine, Suit::Diamonds, false),
+            PlayedCard::new(Rank::Nine, Suit::Spades, false),
+            PlayedCard::new(Rank::Nine, Suit::Spades, true),
+        ];
+        let ruleset = Ruleset {
+            trick_ranking: TrickRanking {
+                five_of_a_kind: None,
+                ..TrickRanking::standard()
+            },
+            ..Ruleset::default()
+        };
+
+        assert!(Hand::try_build_with(five_nines.clone(), Ruleset::default()).is_ok());
+        assert_eq!(
+            Hand::try_build_with(five_nines, ruleset),
+            Err(HandError::TrickTypeNotAllowed(TrickType::FiveOfAKind))
+        );
+    }
+
+    #[test]
+    fn twos_can_be_kept_out_of_straights() {
+        let to_two = vec![
+            PlayedCard::new(Rank::Jack, Suit::Clubs, false),
+            PlayedCard::new(Rank::Queen, Suit::Clubs, false),
+            PlayedCard::new(Rank::King, Suit::Clubs, false),
+            PlayedCard::new(Rank::Ace, Suit::Clubs, false),
+            PlayedCard::new(Rank::Two, Suit::Clubs, false),
+        ];
+        let mut offsuit = to_two.clone();
+        offsuit[0] = PlayedCard::new(Rank::Jack, Suit::Hearts, false);
+        let ruleset = Ruleset { twos_in_straights: false, ..Ruleset::default() };
+
+        assert_eq!(
+            Hand::try_build_with(to_two.clone(), Ruleset::default())
+                .unwrap()
+                .get_trick_type(),
+            Some(TrickType::StraightFlush)
+        );
+        assert_eq!(
+            Hand::try_build_with(to_two, ruleset).unwrap().get_trick_type(),
+            Some(TrickType::Flush)
+        );
+        assert_eq!(
+            Hand::try_build_with(offsuit, ruleset),
+            Err(HandError::BrokenStraight)
+        );
+    }
+
+    #[test]
+    fn canonical_hands_ignore_the_order_cards_were_played_in() {
+        let orders = OrderContext::standard();
+        let canonical = |cards: &str| {
+            Hand::build(crate::scenario::parse_played_cards(cards).unwrap())
+                .unwrap()
+                .canonicalize(&orders)
+        };
+
+        assert_eq!(canonical("9S 9C"), canonical("9C 9S"));
+        assert_eq!(canonical("JK:9C 9C 9H"), canonical("9H 9C JK:9C"));
+        assert_eq!(canonical("8C 8H 8D 10C 10H"), canonical("10H 8D 10C 8H 8C"));
+        assert_eq!(canonical("9S 9C").to_cards(), canonical("9C 9S").to_cards());
+        assert_ne!(canonical("9S 9C"), canonical("9C 9H"));
+
+        assert_eq!(
+            canonical("5H 3C 7C 4D 6S").canonicalize(&orders.reverse()).to_cards(),
+            crate::scenario::parse_played_cards("7C 6S 5H 4D 3C").unwrap()
+        );
+    }
+
+    #[test]
+    fn short_deck_straights_let_the_ace_run_low() {
+        let ruleset = Ruleset {
+            deal_mode: DealMode::ShortDeck { lowest_rank: Rank::Seven },
+            ..Ruleset::default()
+        };
+        let standard = OrderContext::standard();
+        let reversed = standard.clone().reverse();
+        let trick_type = |cards: &str, ruleset: Ruleset, orders: &OrderContext| {
+            let cards = crate::scenario::parse_played_cards(cards).unwrap();
+            Hand::try_build_in_order(cards, ruleset, orders)
+                .ok()
+                .and_then(|hand| hand.get_trick_type())
+        };
+
+        assert_eq!(trick_type("AS 7C 8H 9D 10S", ruleset, &standard), Some(TrickType::Straight));
+        assert_eq!(trick_type("AS 7C 8H 9D 10S", ruleset, &reversed), Some(TrickType::Straight));
+        assert_eq!(trick_type("AC 7C 8C 9C 10C", ruleset, &standard), Some(TrickType::StraightFlush));
+        assert_eq!(trick_type("10S JC QH KD AS", ruleset, &standard), Some(TrickType::Straight));
+        assert_eq!(trick_type("JC QH KD AS 7C", ruleset, &standard), None);
+        assert_eq!(trick_type("AS 7C 8H 9D 10S", Ruleset::default(), &standard), None);
+    }
+
+    #[test]
+    fn straights_run_along_the_given_rank_order() {
+        let trick_type = |cards: &str, orders: &OrderContext| {
+            let cards = crate::scenario::parse_played_cards(cards).unwrap();
+            Hand::try_build_in_order(cards, Ruleset::default(), orders)
+                .ok()
+                .and_then(|hand| hand.get_trick_type())
+        };
+        let reversed = OrderContext::standard().reverse();
+        // the two ranks lowest, below the three
+        let mut rank_order = get_rank_array();
+        rank_order.rotate_right(1);
+        let twos_low = OrderContext::new(&get_suit_array(), rank_order).unwrap();
+
+        assert_eq!(trick_type("3C 4H 5D 6S 7C", &reversed), Some(TrickType::Straight));
+        assert_eq!(trick_type("JC QH KD AS 2C", &reversed), Some(TrickType::Straight));
+        assert_eq!(trick_type("2C 3C 4C 5C 6C", &twos_low), Some(TrickType::StraightFlush));
+        assert_eq!(trick_type("2C 3H 4D 5S 6C", &OrderContext::standard()), None);
+        assert_eq!(trick_type("JC QH KD AS 2C", &twos_low), None);
+    }
 }
@@ -0,0 +1,140 @@
+use super::{check_orders, CardMismatch, Hand, ReversalDuration, Round, SetupError};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum InvariantViolation {
+    CardsNotConserved(Vec<CardMismatch>),
+    DuplicatePlayer(String),
+    /// The next player isn't seated in the round
+    UnknownNextPlayer(String),
+    UnknownLastPlayer(String),
+    /// A hand is on the table but nobody played it
+    LastMoveWithoutPlayer,
+    /// A last player is set before any move has been made
+    LastPlayerWithoutMove,
+    InvalidSuitOrder,
+    InvalidRankOrder,
+    /// The round is marked as reversed until the trick clears, but its
+    /// ruleset never reverses for just a trick
+    UnexpectedTemporaryReversal,
+}
+
+impl Round {
+    /// Checks the round is internally consistent: cards are conserved,
+    /// player ids are unique, the next and last players are seated and the
+    /// last move and last player agree with each other, the orders list
+    /// every suit and rank once and a temporary reversal is one the
+    /// ruleset allows.
+    pub fn check_invariants(&self) -> Result<(), Vec<InvariantViolation>> {
+        let mut violations = vec![];
+
+        if let Err(mismatches) = self.verify_card_conservation() {
+            violations.push(InvariantViolation::CardsNotConserved(mismatches));
+        }
+
+        let ids = self.get_player_ids();
+        for (i, id) in ids.iter().enumerate() {
+            let duplicate = InvariantViolation::DuplicatePlayer(id.clone());
+            if ids[..i].contains(id) && !violations.contains(&duplicate) {
+                violations.push(duplicate);
+            }
+        }
+
+        if let Some(next_player) = self.get_next_player() {
+            if !ids.contains(&next_player) {
+                violations.push(InvariantViolation::UnknownNextPlayer(next_player));
+            }
+        }
+
+        match (self.get_last_move(), self.get_last_player()) {
+            (Some(Hand::Pass), _) => {},
+            (Some(_), None) => violations.push(InvariantViolation::LastMoveWithoutPlayer),
+            (None, Some(_)) => violations.push(InvariantViolation::LastPlayerWithoutMove),
+            _ => {},
+        }
+        if let Some(last_player) = self.get_last_player() {
+            if !ids.contains(&last_player) {
+                violations.push(InvariantViolation::UnknownLastPlayer(last_player));
+            }
+        }
+
+        match check_orders(&self.get_suit_order(), self.get_rank_order()) {
+            Err(SetupError::InvalidSuitOrder) => violations.push(InvariantViolation::InvalidSuitOrder),
+            Err(_) => violations.push(InvariantViolation::InvalidRankOrder),
+            Ok(()) => {},
+        }
+
+        let ruleset = self.get_ruleset();
+        let temporary_reversals = ruleset.reversals_enabled
+            && ruleset.reversal_duration == ReversalDuration::UntilTrickCleared;
+        if self.is_temporarily_reversed() && !temporary_reversals {
+            violations.push(InvariantViolation::UnexpectedTemporaryReversal);
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+
+    /// Panics if the invariants don't hold. Only debug builds with the
+    /// `strict-invariants` feature check anything.
+    pub(crate) fn assert_invariants(&self) {
+        #[cfg(all(feature = "strict-invariants", debug_assertions))]
+        {
+            if let Err(violations) = self.check_invariants() {
+                panic!("round invariants broken: {:?}\n{:#?}", violations, self);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{PlayedCard, Rank, Suit};
+    use crate::game::{OrderContext, Player, RoundBuilder, Ruleset};
+    use crate::scenario::parse_cards;
+
+    #[test]
+    fn broken_rounds_report_every_violation() {
+        let round = Round::new(
+            vec![
+                Player::new("a".to_string(), parse_cards("3C").unwrap()),
+                Player::new("a".to_string(), parse_cards("4C").unwrap()),
+                Player::new("b".to_string(), parse_cards("5C").unwrap()),
+            ],
+            Some("z".to_string()),
+            Some(Hand::Single(PlayedCard::new(Rank::Two, Suit::Spades, false))),
+            Some("y".to_string()),
+            OrderContext::standard(),
+            Ruleset::default(),
+        );
+
+        // the duplicate also hides the second player's hand, so cards
+        // aren't conserved either
+        let violations = round.check_invariants().unwrap_err();
+        assert_eq!(violations.len(), 4);
+        assert_eq!(&violations[1..], &[
+            InvariantViolation::DuplicatePlayer("a".to_string()),
+            InvariantViolation::UnknownNextPlayer("z".to_string()),
+            InvariantViolation::UnknownLastPlayer("y".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn played_rounds_stay_consistent() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("5C").unwrap()))
+            .build()
+            .unwrap()
+            .submit_move("a", vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)])
+            .unwrap();
+
+        assert_eq!(round.check_invariants(), Ok(()));
+    }
+}
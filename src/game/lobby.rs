@@ -0,0 +1,293 @@
+use super::{Game, Round, Ruleset};
+use crate::cards::get_suit_array;
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+const TOKEN_LENGTH: usize = 21;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LobbyError {
+    Full,
+    AlreadyJoined,
+    AlreadyStarted,
+    NotEnoughPlayers,
+    NotHost,
+    UnknownToken,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Seat {
+    player_id: String,
+    token: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Spectator {
+    spectator_id: String,
+    token: String,
+}
+
+/// Players gathering for a game. Everyone who joins is handed a secret
+/// token to identify themselves with; the first seat is the host. The game
+/// is dealt as soon as the table is full or when the host starts it.
+///
+/// Spectators can come and go at any time. Their tokens never
+/// authenticate as a player, so they can't move.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lobby {
+    ruleset: Ruleset,
+    max_players: usize,
+    seats: Vec<Seat>,
+    round: Option<Round>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    spectators: Vec<Spectator>,
+}
+
+/// What every player may see of a lobby, without anyone's token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LobbyView {
+    pub host: String,
+    pub player_ids: Vec<String>,
+    pub max_players: usize,
+    pub started: bool,
+    #[serde(default)]
+    pub spectator_ids: Vec<String>,
+}
+
+impl Lobby {
+    /// Opens a lobby with `host_id` in the first seat, returning it with
+    /// the host's token.
+    pub fn new(host_id: &str, ruleset: Ruleset, max_players: usize) -> (Lobby, String) {
+        let token = generate_token();
+        let lobby = Lobby {
+            ruleset,
+            max_players: max_players.max(2),
+            seats: vec![Seat {
+                player_id: host_id.to_string(),
+                token: token.clone(),
+            }],
+            round: None,
+            spectators: vec![],
+        };
+
+        (lobby, token)
+    }
+
+    /// Takes a seat, returning the player's token. Spectators have to
+    /// stop watching before they can sit down.
+    pub fn join(&mut self, player_id: &str) -> Result<String, LobbyError> {
+        if self.round.is_some() {
+            return Err(LobbyError::AlreadyStarted);
+        }
+        let joined = self.seats.iter().any(|s| s.player_id == player_id)
+            || self.spectators.iter().any(|s| s.spectator_id == player_id);
+        if joined {
+            return Err(LobbyError::AlreadyJoined);
+        }
+        if self.seats.len() >= self.max_players {
+            return Err(LobbyError::Full);
+        }
+
+        let token = generate_token();
+        self.seats.push(Seat {
+            player_id: player_id.to_string(),
+            token: token.clone(),
+        });
+
+        if self.seats.len() == self.max_players {
+            self.deal();
+        }
+
+        Ok(token)
+    }
+
+    /// Gives up a seat before the game starts. If the host leaves, the next
+    /// player along becomes host.
+    pub fn leave(&mut self, token: &str) -> Result<(), LobbyError> {
+        if self.round.is_some() {
+            return Err(LobbyError::AlreadyStarted);
+        }
+
+        let index = self.seats.iter()
+            .position(|s| s.token == token)
+            .ok_or(LobbyError::UnknownToken)?;
+        self.seats.remove(index);
+
+        Ok(())
+    }
+
+    /// Starts watching the table, before or after the game starts,
+    /// returning the spectator's token.
+    pub fn watch(&mut self, spectator_id: &str) -> Result<String, LobbyError> {
+        let joined = self.seats.iter().any(|s| s.player_id == spectator_id)
+            || self.spectators.iter().any(|s| s.spectator_id == spectator_id);
+        if joined {
+            return Err(LobbyError::AlreadyJoined);
+        }
+
+        let token = generate_token();
+        self.spectators.push(Spectator {
+            spectator_id: spectator_id.to_string(),
+            token: token.clone(),
+        });
+
+        Ok(token)
+    }
+
+    pub fn stop_watching(&mut self, token: &str) -> Result<(), LobbyError> {
+        let index = self.spectators.iter()
+            .position(|s| s.token == token)
+            .ok_or(LobbyError::UnknownToken)?;
+        self.spectators.remove(index);
+
+        Ok(())
+    }
+
+    pub fn start(&mut self, host_token: &str) -> Result<Round, LobbyError> {
+        if self.round.is_some() {
+            return Err(LobbyError::AlreadyStarted);
+        }
+        if self.seats.first().map(|s| s.token.as_str()) != Some(host_token) {
+            return Err(LobbyError::NotHost);
+        }
+        if self.seats.len() < 2 {
+            return Err(LobbyError::NotEnoughPlayers);
+        }
+
+        Ok(self.deal())
+    }
+
+    /// The player a token belongs to.
+    pub fn authenticate(&self, token: &str) -> Option<&str> {
+        self.seats.iter()
+            .find(|s| s.token == token)
+            .map(|s| s.player_id.as_str())
+    }
+
+    /// The spectator a token belongs to.
+    pub fn authenticate_spectator(&self, token: &str) -> Option<&str> {
+        self.spectators.iter()
+            .find(|s| s.token == token)
+            .map(|s| s.spectator_id.as_str())
+    }
+
+    /// The dealt round, once the game has started.
+    pub fn get_round(&self) -> Option<&Round> {
+        self.round.as_ref()
+    }
+
+    pub fn view(&self) -> LobbyView {
+        LobbyView {
+            host: self.seats.first()
+                .map(|s| s.player_id.clone())
+                .unwrap_or_default(),
+            player_ids: self.seats.iter().map(|s| s.player_id.clone()).collect(),
+            max_players: self.max_players,
+            started: self.round.is_some(),
+            spectator_ids: self.spectators.iter().map(|s| s.spectator_id.clone()).collect(),
+        }
+    }
+
+    fn deal(&mut self) -> Round {
+        let player_ids: Vec<String> = self.seats.iter()
+            .map(|s| s.player_id.clone())
+            .collect();
+        let num_decks = if player_ids.len() > 4 { 2 } else { 1 };
+        let game = Game::new(
+            num_decks,
+            0,
+            &player_ids,
+            &get_suit_array(),
+            self.ruleset
+        ).expect("players can only join a lobby once");
+
+        let round = game.get_round().clone();
+        self.round = Some(round.clone());
+        round
+    }
+}
+
+fn generate_token() -> String {
+    let mut rng = rand::thread_rng();
+    (0..TOKEN_LENGTH).map(|_| rng.sample(Alphanumeric)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_game_is_dealt_when_the_table_fills() {
+        let (mut lobby, host_token) = Lobby::new("a", Ruleset::default(), 3);
+        let b_token = lobby.join("b").unwrap();
+
+        assert!(lobby.get_round().is_none());
+        assert_eq!(lobby.authenticate(&b_token), Some("b"));
+        assert_eq!(lobby.authenticate(&host_token), Some("a"));
+        assert_ne!(b_token, host_token);
+
+        lobby.join("c").unwrap();
+
+        assert_eq!(
+            lobby.get_round().unwrap().get_player_ids(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(lobby.join("d"), Err(LobbyError::AlreadyStarted));
+    }
+
+    #[test]
+    fn only_the_host_can_start_early() {
+        let (mut lobby, host_token) = Lobby::new("a", Ruleset::default(), 4);
+
+        assert_eq!(lobby.start(&host_token).err(), Some(LobbyError::NotEnoughPlayers));
+
+        let b_token = lobby.join("b").unwrap();
+
+        assert_eq!(lobby.join("b"), Err(LobbyError::AlreadyJoined));
+        assert_eq!(lobby.start(&b_token).err(), Some(LobbyError::NotHost));
+
+        let round = lobby.start(&host_token).unwrap();
+
+        assert_eq!(round.get_player("a").unwrap().get_card_count(), 26);
+        assert!(lobby.view().started);
+    }
+
+    #[test]
+    fn the_view_hides_tokens_and_follows_the_host() {
+        let (mut lobby, host_token) = Lobby::new("a", Ruleset::default(), 4);
+        lobby.join("b").unwrap();
+        lobby.leave(&host_token).unwrap();
+
+        let view = lobby.view();
+
+        assert_eq!(view.host, "b");
+        assert_eq!(view.player_ids, vec!["b"]);
+        assert_eq!(lobby.leave(&host_token), Err(LobbyError::UnknownToken));
+    }
+
+    #[test]
+    fn spectators_come_and_go_without_taking_a_seat() {
+        let (mut lobby, _) = Lobby::new("a", Ruleset::default(), 2);
+        let s_token = lobby.watch("s").unwrap();
+
+        assert_eq!(lobby.watch("a"), Err(LobbyError::AlreadyJoined));
+        assert_eq!(lobby.watch("s"), Err(LobbyError::AlreadyJoined));
+        assert_eq!(lobby.join("s"), Err(LobbyError::AlreadyJoined));
+        assert_eq!(lobby.authenticate(&s_token), None);
+        assert_eq!(lobby.authenticate_spectator(&s_token), Some("s"));
+
+        lobby.join("b").unwrap();
+        let t_token = lobby.watch("t").unwrap();
+        assert!(lobby.view().started);
+        assert_eq!(lobby.view().spectator_ids, vec!["s", "t"]);
+
+        lobby.stop_watching(&s_token).unwrap();
+        assert_eq!(lobby.stop_watching(&s_token), Err(LobbyError::UnknownToken));
+
+        let json = serde_json::to_string(&lobby).unwrap();
+        let lobby: Lobby = serde_json::from_str(&json).unwrap();
+        assert_eq!(lobby.view().spectator_ids, vec!["t"]);
+        assert_eq!(lobby.authenticate_spectator(&t_token), Some("t"));
+    }
+}
@@ -0,0 +1,362 @@
+use super::{Dealer, GameResult, Handicap};
+#[cfg(feature = "std")]
+use super::{Game, Ruleset};
+#[cfg(feature = "std")]
+use crate::cards::Suit;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use alloc::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MatchConfig {
+    /// The match ends once anyone's penalty score reaches this
+    pub target_score: Option<usize>,
+    /// The match ends after this many games
+    pub max_games: Option<usize>,
+    /// Penalty for resigning a game, in place of the cards left
+    pub resignation_penalty: usize,
+}
+
+impl Default for MatchConfig {
+    fn default() -> MatchConfig {
+        MatchConfig {
+            target_score: Some(30),
+            max_games: None,
+            resignation_penalty: 13,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchError {
+    /// Players can only join or leave between games
+    GameInProgress,
+    MatchOver,
+    AlreadySeated,
+    UnknownPlayer,
+    /// A match needs at least two players
+    TooFewPlayers,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Standing {
+    pub player_id: String,
+    pub penalty: usize,
+    /// Starting at 1 for the lowest penalty. Tied players share a position.
+    pub position: usize,
+}
+
+/// A series of games between the same players. Every game adds the cards a
+/// player was left holding, and any penalty points, to their penalty score,
+/// and the match ends when either limit in the config is reached.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Match {
+    config: MatchConfig,
+    dealer: Dealer,
+    scores: BTreeMap<String, usize>,
+    games_played: usize,
+    /// A game has been dealt and its result not yet recorded
+    #[serde(default)]
+    in_play: bool,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    handicaps: BTreeMap<String, Handicap>,
+}
+
+impl Match {
+    /// `player_ids` is the seating order. Returns `None` if `dealer_id`
+    /// isn't seated.
+    pub fn new(
+        player_ids: &[String],
+        dealer_id: &str,
+        config: MatchConfig,
+    ) -> Option<Match> {
+        let dealer = Dealer::new(player_ids, dealer_id)?;
+
+        Some(Match {
+            config,
+            dealer,
+            scores: player_ids.iter().map(|id| (id.clone(), 0)).collect(),
+            games_played: 0,
+            in_play: false,
+            handicaps: BTreeMap::new(),
+        })
+    }
+
+    /// Deals the next game, or `None` once the match is over.
+    #[cfg(feature = "std")]
+    pub fn deal_game(
+        &mut self,
+        num_decks: u8,
+        num_jokers: u8,
+        suit_order: &[Suit],
+        ruleset: Ruleset
+    ) -> Option<Game> {
+        if self.is_over() {
+            return None;
+        }
+
+        let game = self.dealer
+            .deal_game_with_handicaps(num_decks, num_jokers, suit_order, ruleset, &self.handicaps)
+            .expect("the seats were checked when the match was created");
+        self.in_play = true;
+
+        Some(game)
+    }
+
+    /// Adds a finished game's penalties. Returns the final standings if
+    /// this game ended the match.
+    pub fn record_result(&mut self, result: &GameResult) -> Option<Vec<Standing>> {
+        if self.is_over() {
+            return None;
+        }
+
+        for player in result.players.iter() {
+            let penalty = if player.resigned {
+                self.config.resignation_penalty
+            } else {
+                player.cards_left + player.penalty_points
+            };
+            if let Some(score) = self.scores.get_mut(&player.player_id) {
+                *score += penalty;
+            }
+        }
+        self.games_played += 1;
+        self.in_play = false;
+
+        if self.is_over() {
+            Some(self.get_standings())
+        } else {
+            None
+        }
+    }
+
+    /// Seats `player_id` between games, on the dealer's right so they deal
+    /// last, starting on a penalty of 0. Later games are dealt for the new
+    /// number of players, so deal modes that depend on it follow along.
+    pub fn add_player(&mut self, player_id: &str) -> Result<(), MatchError> {
+        self.check_between_games()?;
+        if !self.dealer.add_player(player_id) {
+            return Err(MatchError::AlreadySeated);
+        }
+
+        self.scores.insert(player_id.to_string(), 0);
+        Ok(())
+    }
+
+    /// Takes `player_id` out of the match between games, dropping their
+    /// score from the standings. Everyone else keeps theirs.
+    pub fn remove_player(&mut self, player_id: &str) -> Result<(), MatchError> {
+        self.check_between_games()?;
+        if !self.scores.contains_key(player_id) {
+            return Err(MatchError::UnknownPlayer);
+        }
+        if self.scores.len() <= 2 {
+            return Err(MatchError::TooFewPlayers);
+        }
+
+        self.dealer.remove_player(player_id);
+        self.scores.remove(player_id);
+        self.handicaps.remove(player_id);
+        Ok(())
+    }
+
+    /// Sets or clears the handicap `player_id` is dealt with from the next
+    /// game on.
+    pub fn set_handicap(
+        &mut self,
+        player_id: &str,
+        handicap: Option<Handicap>,
+    ) -> Result<(), MatchError> {
+        if !self.scores.contains_key(player_id) {
+            return Err(MatchError::UnknownPlayer);
+        }
+
+        match handicap {
+            Some(handicap) => self.handicaps.insert(player_id.to_string(), handicap),
+            None => self.handicaps.remove(player_id),
+        };
+        Ok(())
+    }
+
+    pub fn get_handicap(&self, player_id: &str) -> Option<Handicap> {
+        self.handicaps.get(player_id).copied()
+    }
+
+    /// The players in seating order.
+    pub fn get_player_ids(&self) -> Vec<String> {
+        self.dealer.get_player_ids()
+    }
+
+    fn check_between_games(&self) -> Result<(), MatchError> {
+        if self.is_over() {
+            return Err(MatchError::MatchOver);
+        }
+        if self.in_play {
+            return Err(MatchError::GameInProgress);
+        }
+
+        Ok(())
+    }
+
+    pub fn is_over(&self) -> bool {
+        let target_reached = self.config.target_score
+            .map(|target| self.scores.values().any(|&score| score >= target))
+            .unwrap_or(false);
+        let games_done = self.config.max_games
+            .map(|max| self.games_played >= max)
+            .unwrap_or(false);
+
+        target_reached || games_done
+    }
+
+    pub fn get_score(&self, player_id: &str) -> Option<usize> {
+        self.scores.get(player_id).cloned()
+    }
+
+    pub fn get_games_played(&self) -> usize {
+        self.games_played
+    }
+
+    pub fn get_dealer(&self) -> &str {
+        self.dealer.get_dealer()
+    }
+
+    /// Players from the lowest penalty score to the highest.
+    pub fn get_standings(&self) -> Vec<Standing> {
+        let mut scores: Vec<(&String, &usize)> = self.scores.iter().collect();
+        scores.sort_by_key(|(_, &penalty)| penalty);
+
+        let mut standings: Vec<Standing> = vec![];
+        for (i, (player_id, &penalty)) in scores.into_iter().enumerate() {
+            let position = match standings.last() {
+                Some(last) if last.penalty == penalty => last.position,
+                _ => i + 1,
+            };
+            standings.push(Standing {
+                player_id: player_id.clone(),
+                penalty,
+                position,
+            });
+        }
+
+        standings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::PlayerResult;
+
+    fn ids() -> Vec<String> {
+        vec!["a", "b", "c"].into_iter().map(String::from).collect()
+    }
+
+    fn result(cards_left: [usize; 3]) -> GameResult {
+        GameResult {
+            players: ids().into_iter()
+                .zip(cards_left.iter())
+                .enumerate()
+                .map(|(team, (player_id, &cards_left))| PlayerResult {
+                    player_id,
+                    team,
+                    finishing_position: None,
+                    cards_left,
+                    resigned: false,
+                    penalty_points: 0,
+                    tricks_won: 0,
+                    bombs_played: 0,
+                })
+                .collect(),
+            teams: vec![],
+        }
+    }
+
+    #[test]
+    fn the_match_ends_when_someone_reaches_the_target() {
+        let config = MatchConfig { target_score: Some(10), ..MatchConfig::default() };
+        let mut game_match = Match::new(&ids(), "a", config).unwrap();
+
+        assert_eq!(game_match.record_result(&result([0, 6, 3])), None);
+        let standings = game_match.record_result(&result([2, 4, 0])).unwrap();
+
+        assert!(game_match.is_over());
+        assert_eq!(
+            standings.iter()
+                .map(|s| (s.player_id.as_str(), s.penalty, s.position))
+                .collect::<Vec<_>>(),
+            vec![("a", 2, 1), ("c", 3, 2), ("b", 10, 3)]
+        );
+        assert!(game_match
+            .deal_game(1, 0, &crate::cards::get_suit_array(), Ruleset::default())
+            .is_none());
+    }
+
+    #[test]
+    fn the_match_can_end_after_a_number_of_games() {
+        let config = MatchConfig {
+            target_score: None,
+            max_games: Some(1),
+            ..MatchConfig::default()
+        };
+        let mut game_match = Match::new(&ids(), "a", config).unwrap();
+
+        let standings = game_match.record_result(&result([0, 1, 1])).unwrap();
+
+        assert_eq!(standings[1].position, 2);
+        assert_eq!(standings[2].position, 2);
+    }
+
+    #[test]
+    fn players_can_join_and_leave_between_games() {
+        let mut game_match = Match::new(&ids(), "a", MatchConfig::default()).unwrap();
+        game_match.record_result(&result([0, 6, 3]));
+
+        let suit_order = crate::cards::get_suit_array();
+        game_match.deal_game(1, 0, &suit_order, Ruleset::default()).unwrap();
+        assert_eq!(game_match.add_player("d"), Err(MatchError::GameInProgress));
+        game_match.record_result(&result([1, 0, 0]));
+
+        assert_eq!(game_match.add_player("b"), Err(MatchError::AlreadySeated));
+        assert_eq!(game_match.add_player("d"), Ok(()));
+        assert_eq!(game_match.remove_player("b"), Ok(()));
+        assert_eq!(game_match.remove_player("b"), Err(MatchError::UnknownPlayer));
+
+        assert_eq!(game_match.get_score("a"), Some(1));
+        assert_eq!(game_match.get_score("c"), Some(3));
+        assert_eq!(game_match.get_score("d"), Some(0));
+        assert_eq!(game_match.get_score("b"), None);
+        assert_eq!(game_match.get_player_ids().len(), 3);
+
+        let game = game_match.deal_game(1, 0, &suit_order, Ruleset::default()).unwrap();
+        assert_eq!(game.get_round().get_players().len(), 3);
+
+        game_match.record_result(&result([0, 0, 0]));
+        game_match.remove_player("a").unwrap();
+        assert_eq!(game_match.remove_player("c"), Err(MatchError::TooFewPlayers));
+    }
+
+    #[test]
+    fn handicaps_are_dealt_from_the_match_settings() {
+        let mut game_match = Match::new(&ids(), "a", MatchConfig::default()).unwrap();
+        assert_eq!(
+            game_match.set_handicap("d", Some(Handicap::Discard(1))),
+            Err(MatchError::UnknownPlayer)
+        );
+        game_match.set_handicap("b", Some(Handicap::ExtraCards(3))).unwrap();
+        game_match.set_handicap("c", Some(Handicap::Discard(1))).unwrap();
+
+        let game = game_match
+            .deal_game(1, 0, &crate::cards::get_suit_array(), Ruleset::default())
+            .unwrap();
+
+        let count = |id: &str| game.get_player(id).unwrap().get_card_count();
+        // b is dealt to first, so gets the odd card
+        assert_eq!(count("b"), 18 + 3);
+        assert_eq!(count("a") + count("c"), 52 - 21);
+        assert_eq!(game.get_handicap_discards("c"), 1);
+
+        game_match.set_handicap("b", None).unwrap();
+        assert_eq!(game_match.get_handicap("b"), None);
+    }
+}
@@ -0,0 +1,292 @@
+use super::{holds_two_or_joker, FinalCardRule, Hand, JokerUsage, Player, Round, Ruleset, SubmitError};
+use crate::cards::PlayedCard;
+use crate::prelude::*;
+use alloc::sync::Arc;
+use core::fmt;
+
+/// A move as the rules see it. Rules only run once the move is known to
+/// be the player's turn, to make a hand and to use cards they hold.
+pub struct MoveContext<'a> {
+    pub round: &'a Round,
+    pub player_id: &'a str,
+    pub cards: &'a [PlayedCard],
+    pub hand: Hand,
+    /// The player as they would be after the move
+    pub player_after: &'a Player,
+}
+
+/// A check every move has to pass, such as a house rule that a seven
+/// forces the next player to go lower.
+pub trait MoveRule: Send + Sync {
+    fn check(&self, context: &MoveContext) -> Result<(), SubmitError>;
+}
+
+/// The opening move can't be a pass and, if the ruleset says so, has to
+/// hold the lowest card dealt.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OpeningLead;
+
+impl MoveRule for OpeningLead {
+    fn check(&self, context: &MoveContext) -> Result<(), SubmitError> {
+        if context.round.get_last_move().is_some() {
+            return Ok(());
+        }
+
+        match context.round.check_starting_move(context.cards) {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Anything but a pass has to beat the hand on the table.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BeatsTable;
+
+impl MoveRule for BeatsTable {
+    fn check(&self, context: &MoveContext) -> Result<(), SubmitError> {
+        let last_move = match context.round.get_last_move() {
+            None | Some(Hand::Pass) => return Ok(()),
+            Some(last_move) => last_move,
+        };
+
+        if context.hand == Hand::Pass || context.hand.beats(
+            &last_move,
+            context.round.get_ruleset(),
+            &context.round.get_order_context(),
+        ) {
+            Ok(())
+        } else {
+            Err(SubmitError::HandNotHighEnough)
+        }
+    }
+}
+
+/// A player's last hand can't hold a joker.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoJokerToFinish;
+
+impl MoveRule for NoJokerToFinish {
+    fn check(&self, context: &MoveContext) -> Result<(), SubmitError> {
+        if context.player_after.get_card_count() == 0
+            && context.cards.iter().any(|c| c.get_is_joker()) {
+            return Err(SubmitError::CannotFinishWithJoker);
+        }
+
+        Ok(())
+    }
+}
+
+/// A player's last hand can't hold a two or a joker.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoTwoOrJokerToFinish;
+
+impl MoveRule for NoTwoOrJokerToFinish {
+    fn check(&self, context: &MoveContext) -> Result<(), SubmitError> {
+        if context.player_after.get_card_count() == 0
+            && holds_two_or_joker(context.cards) {
+            return Err(SubmitError::IllegalFinalCard);
+        }
+
+        Ok(())
+    }
+}
+
+/// A player's last hand can't be a bomb or reverse the orders.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrictFinish;
+
+impl MoveRule for StrictFinish {
+    fn check(&self, context: &MoveContext) -> Result<(), SubmitError> {
+        if context.player_after.get_card_count() > 0 {
+            return Ok(());
+        }
+        if context.hand.is_bomb() {
+            return Err(SubmitError::CannotFinishWithBomb);
+        }
+
+        let ruleset = context.round.get_ruleset();
+        if ruleset.reversals_enabled && ruleset.reversal_triggers.is_triggered_by(context.hand) {
+            return Err(SubmitError::CannotFinishWithReversal);
+        }
+
+        Ok(())
+    }
+}
+
+/// A player who has passed can only pass until the trick clears.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LockedOutAfterPass;
+
+impl MoveRule for LockedOutAfterPass {
+    fn check(&self, context: &MoveContext) -> Result<(), SubmitError> {
+        let passed = context.round.get_passed_players().iter()
+            .any(|id| id == context.player_id);
+        if passed && context.hand != Hand::Pass {
+            return Err(SubmitError::LockedOutOfTrick);
+        }
+
+        Ok(())
+    }
+}
+
+impl Ruleset {
+    /// The rules every move is checked against under this ruleset, in the
+    /// order they're checked.
+    pub fn get_move_rules(&self) -> Vec<Box<dyn MoveRule>> {
+        let mut rules: Vec<Box<dyn MoveRule>> = vec![
+            Box::new(OpeningLead),
+            Box::new(BeatsTable),
+        ];
+
+        if self.joker_usage == JokerUsage::NotOnFinishingHand {
+            rules.push(Box::new(NoJokerToFinish));
+        }
+        if self.final_card == FinalCardRule::Forbidden {
+            rules.push(Box::new(NoTwoOrJokerToFinish));
+        }
+        if self.pass_locks_out {
+            rules.push(Box::new(LockedOutAfterPass));
+        }
+        if self.strict_finish {
+            rules.push(Box::new(StrictFinish));
+        }
+
+        rules
+    }
+}
+
+/// Rules added to a round on top of the ruleset's. They aren't saved with
+/// the round, so they have to be added again after loading one.
+#[derive(Clone, Default)]
+pub(crate) struct MoveRules(Vec<Arc<dyn MoveRule>>);
+
+impl MoveRules {
+    pub(crate) fn add(&mut self, rule: Box<dyn MoveRule>) {
+        self.0.push(Arc::from(rule));
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = &dyn MoveRule> {
+        self.0.iter().map(|rule| rule.as_ref())
+    }
+}
+
+impl fmt::Debug for MoveRules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "MoveRules({})", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Rank;
+    use crate::scenario::{parse_played_cards, Scenario};
+
+    /// Twos can't be led.
+    struct NoLeadingTwos;
+
+    impl MoveRule for NoLeadingTwos {
+        fn check(&self, context: &MoveContext) -> Result<(), SubmitError> {
+            let leading = matches!(context.round.get_last_move(), None | Some(Hand::Pass));
+            if leading && context.cards.iter().any(|c| c.get_rank() == Rank::Two) {
+                return Err(SubmitError::HouseRule("twos can't be led".to_string()));
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn added_rules_are_checked_after_the_rulesets() {
+        let mut round = Scenario::new()
+            .player("a", "3C 2S")
+            .player("b", "4C 5C")
+            .lead("b")
+            .next_player("a")
+            .build()
+            .unwrap();
+        round.add_move_rule(Box::new(NoLeadingTwos));
+
+        assert_eq!(
+            round.validate_move("a", &parse_played_cards("2S").unwrap()),
+            Err(SubmitError::HouseRule("twos can't be led".to_string()))
+        );
+        assert_eq!(
+            round.validate_move("a", &parse_played_cards("3C 2S").unwrap()),
+            Err(SubmitError::InvalidHand)
+        );
+
+        let round = round.submit_move("a", parse_played_cards("3C").unwrap()).unwrap();
+        assert_eq!(
+            round.validate_move("b", &parse_played_cards("4C").unwrap()).err(),
+            None
+        );
+    }
+
+    #[test]
+    fn rulesets_turn_on_the_rules_they_need() {
+        assert_eq!(Ruleset::default().get_move_rules().len(), 2);
+
+        let ruleset = Ruleset {
+            joker_usage: JokerUsage::NotOnFinishingHand,
+            final_card: FinalCardRule::Forbidden,
+            pass_locks_out: true,
+            strict_finish: true,
+            ..Ruleset::default()
+        };
+        assert_eq!(ruleset.get_move_rules().len(), 6);
+    }
+
+    #[test]
+    fn players_who_pass_can_be_locked_out_of_the_trick() {
+        let ruleset = Ruleset { pass_locks_out: true, ..Ruleset::default() };
+        let round = Scenario::new()
+            .player("a", "3C 9C")
+            .player("b", "4C 6C")
+            .player("c", "5C 7C")
+            .last_move("c", "3D")
+            .next_player("a")
+            .ruleset(ruleset)
+            .build()
+            .unwrap();
+
+        let round = round.submit_move("a", vec![]).unwrap();
+        let round = round.submit_move("b", parse_played_cards("4C").unwrap()).unwrap();
+        let round = round.submit_move("c", parse_played_cards("5C").unwrap()).unwrap();
+
+        assert_eq!(
+            round.validate_move("a", &parse_played_cards("9C").unwrap()),
+            Err(SubmitError::LockedOutOfTrick)
+        );
+        assert_eq!(round.validate_move("a", &[]), Ok(Hand::Pass));
+    }
+
+    #[test]
+    fn strict_finishes_rule_out_bombs_and_reversals() {
+        let round = |ruleset: Ruleset, hand: &str| Scenario::new()
+            .player("a", hand)
+            .player("b", "3C 4C")
+            .lead("b")
+            .next_player("a")
+            .ruleset(ruleset)
+            .build()
+            .unwrap();
+        let strict = Ruleset { strict_finish: true, ..Ruleset::default() };
+        let quads = parse_played_cards("9C 9H 9D 9S 5D").unwrap();
+
+        assert_eq!(
+            round(strict, "9C 9H 9D 9S 5D").validate_move("a", &quads).err(),
+            Some(SubmitError::CannotFinishWithBomb)
+        );
+        assert!(round(strict, "9C 9H 9D 9S 5D 6D").validate_move("a", &quads).is_ok());
+        assert!(round(Ruleset::default(), "9C 9H 9D 9S 5D").validate_move("a", &quads).is_ok());
+
+        let mut reverses_on_kings = strict;
+        reverses_on_kings.reversal_triggers.rank = Some(Rank::King);
+        assert_eq!(
+            round(reverses_on_kings, "KC").validate_move("a", &parse_played_cards("KC").unwrap()),
+            Err(SubmitError::CannotFinishWithReversal)
+        );
+    }
+}
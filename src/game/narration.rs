@@ -0,0 +1,189 @@
+use super::{GameStatus, Hand, Player, Round};
+use crate::cards::Rank;
+use crate::i18n::{hand_name, Locale};
+use crate::prelude::*;
+
+/// Describes the round in full sentences, as `viewer` sees it, for screen
+/// readers and other clients that can't show the table. Players are named
+/// by their display names where they have them.
+pub fn narrate_round(round: &Round, viewer: &str, locale: Locale) -> String {
+    let players = round.get_players();
+    let name_of = |id: &str| -> String {
+        players.iter()
+            .find(|p| p.get_id() == id)
+            .and_then(|p| p.get_metadata().display_name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    let mut sentences = vec![];
+
+    sentences.push(match (round.get_status(), round.get_next_player()) {
+        (GameStatus::NotStarted | GameStatus::InProgress, Some(id)) if id == viewer => {
+            your_turn(locale)
+        },
+        (GameStatus::NotStarted | GameStatus::InProgress, Some(id)) => {
+            their_turn(&name_of(&id), locale)
+        },
+        _ => game_over(locale),
+    });
+
+    sentences.push(match (round.get_last_move(), round.get_last_player()) {
+        (Some(Hand::Pass), _) => table_clear(locale),
+        (Some(hand), Some(id)) if id == viewer => you_played(hand, locale),
+        (Some(hand), Some(id)) => they_played(hand, &name_of(&id), locale),
+        _ => nothing_played(locale),
+    });
+
+    if round.get_rank_order()[0] == Rank::Two {
+        sentences.push(order_reversed(locale));
+    }
+
+    let (yours, theirs): (Vec<Player>, Vec<Player>) = players.iter()
+        .cloned()
+        .partition(|p| p.get_id() == viewer);
+    for player in yours.iter() {
+        sentences.push(you_hold(player.get_card_count(), locale));
+    }
+    for player in theirs.iter() {
+        sentences.push(they_hold(&name_of(player.get_id()), player.get_card_count(), locale));
+    }
+
+    sentences.join(" ")
+}
+
+fn your_turn(locale: Locale) -> String {
+    match locale {
+        Locale::English => "It's your turn.".to_string(),
+        Locale::Filipino => "Ikaw na ang titira.".to_string(),
+    }
+}
+
+fn their_turn(name: &str, locale: Locale) -> String {
+    match locale {
+        Locale::English => format!("It's {}'s turn.", name),
+        Locale::Filipino => format!("Si {} na ang titira.", name),
+    }
+}
+
+fn game_over(locale: Locale) -> String {
+    match locale {
+        Locale::English => "The game is over.".to_string(),
+        Locale::Filipino => "Tapos na ang laro.".to_string(),
+    }
+}
+
+fn table_clear(locale: Locale) -> String {
+    match locale {
+        Locale::English => "The table is clear.".to_string(),
+        Locale::Filipino => "Malinis ang mesa.".to_string(),
+    }
+}
+
+fn nothing_played(locale: Locale) -> String {
+    match locale {
+        Locale::English => "No cards have been played yet.".to_string(),
+        Locale::Filipino => "Wala pang baraha na naitira.".to_string(),
+    }
+}
+
+fn you_played(hand: Hand, locale: Locale) -> String {
+    match locale {
+        Locale::English => format!("The last play was {} by you.", hand_name(hand, locale)),
+        Locale::Filipino => format!("Ang huling tira mo ay {}.", hand_name(hand, locale)),
+    }
+}
+
+fn they_played(hand: Hand, name: &str, locale: Locale) -> String {
+    match locale {
+        Locale::English => format!("The last play was {} by {}.", hand_name(hand, locale), name),
+        Locale::Filipino => format!("Ang huling tira ay {} ni {}.", hand_name(hand, locale), name),
+    }
+}
+
+fn order_reversed(locale: Locale) -> String {
+    match locale {
+        Locale::English => "The order is reversed.".to_string(),
+        Locale::Filipino => "Baligtad ang pagkakasunod.".to_string(),
+    }
+}
+
+fn cards(count: usize, locale: Locale) -> String {
+    match (locale, count) {
+        (Locale::English, 1) => "1 card".to_string(),
+        (Locale::English, n) => format!("{} cards", n),
+        (Locale::Filipino, n) => format!("{} na baraha", n),
+    }
+}
+
+fn you_hold(count: usize, locale: Locale) -> String {
+    match locale {
+        Locale::English => format!("You hold {}.", cards(count, locale)),
+        Locale::Filipino => format!("May hawak kang {}.", cards(count, locale)),
+    }
+}
+
+fn they_hold(name: &str, count: usize, locale: Locale) -> String {
+    match locale {
+        Locale::English => format!("{} holds {}.", name, cards(count, locale)),
+        Locale::Filipino => format!("Si {} ay may {}.", name, cards(count, locale)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::PlayerMetadata;
+    use crate::scenario::Scenario;
+
+    #[test]
+    fn rounds_are_narrated_for_the_viewer() {
+        let mut round = Scenario::new()
+            .player("a", "3C 4C 9H 10D 2S 6S 7S")
+            .player("b", "9D")
+            .last_move("b", "KC KS")
+            .next_player("a")
+            .build()
+            .unwrap();
+        let mut players = round.get_players();
+        players[1].set_metadata(PlayerMetadata {
+            display_name: Some("Maya".to_string()),
+            ..PlayerMetadata::default()
+        });
+        round = Round::new(
+            players,
+            round.get_next_player(),
+            round.get_last_move(),
+            round.get_last_player(),
+            round.get_order_context(),
+            round.get_ruleset(),
+        );
+
+        assert_eq!(
+            narrate_round(&round, "a", Locale::English),
+            "It's your turn. The last play was Pair of Kings by Maya. \
+             You hold 7 cards. Maya holds 1 card."
+        );
+        assert_eq!(
+            narrate_round(&round, "b", Locale::Filipino),
+            "Si a na ang titira. Ang huling tira mo ay Pares ng Hari. \
+             May hawak kang 1 na baraha. Si a ay may 7 na baraha."
+        );
+    }
+
+    #[test]
+    fn a_cleared_table_and_reversed_order_are_mentioned() {
+        let round = Scenario::new()
+            .player("a", "3C")
+            .player("b", "9D")
+            .lead("b")
+            .next_player("b")
+            .reversed()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            narrate_round(&round, "a", Locale::English),
+            "It's b's turn. The table is clear. The order is reversed. \
+             You hold 1 card. b holds 1 card."
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use super::{GameEvent, Hand, OrderContext};
+use crate::prelude::*;
+use core::cell::RefCell;
+use core::fmt;
+use alloc::rc::Rc;
+
+/// Callbacks fired by `Game` as moves are applied. All methods default to
+/// doing nothing so integrators only implement the events they care about.
+pub trait GameObserver {
+    fn on_move_played(&mut self, _player_id: &str, _hand: Hand) {}
+    fn on_trick_cleared(&mut self, _winner_id: &str) {}
+    fn on_order_reversed(&mut self, _orders: &OrderContext) {}
+    fn on_player_finished(&mut self, _player_id: &str, _position: usize) {}
+    fn on_game_over(&mut self, _winners: &[String]) {}
+    fn on_misdeal(&mut self, _player_id: &str) {}
+    fn on_redeal(&mut self) {}
+    /// Every event, alongside the callback for its kind.
+    fn on_event(&mut self, _event: &GameEvent) {}
+}
+
+/// Lets an observer be handed to a `Game` while the caller keeps a handle
+/// to read it back afterwards.
+impl<T: GameObserver> GameObserver for Rc<RefCell<T>> {
+    fn on_move_played(&mut self, player_id: &str, hand: Hand) {
+        self.borrow_mut().on_move_played(player_id, hand);
+    }
+
+    fn on_trick_cleared(&mut self, winner_id: &str) {
+        self.borrow_mut().on_trick_cleared(winner_id);
+    }
+
+    fn on_order_reversed(&mut self, orders: &OrderContext) {
+        self.borrow_mut().on_order_reversed(orders);
+    }
+
+    fn on_player_finished(&mut self, player_id: &str, position: usize) {
+        self.borrow_mut().on_player_finished(player_id, position);
+    }
+
+    fn on_game_over(&mut self, winners: &[String]) {
+        self.borrow_mut().on_game_over(winners);
+    }
+
+    fn on_misdeal(&mut self, player_id: &str) {
+        self.borrow_mut().on_misdeal(player_id);
+    }
+
+    fn on_redeal(&mut self) {
+        self.borrow_mut().on_redeal();
+    }
+
+    fn on_event(&mut self, event: &GameEvent) {
+        self.borrow_mut().on_event(event);
+    }
+}
+
+#[derive(Default)]
+pub struct Observers(Vec<Box<dyn GameObserver>>);
+
+impl Observers {
+    pub fn add(&mut self, observer: Box<dyn GameObserver>) {
+        self.0.push(observer);
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Passes `event` to every observer's callback for it and then to
+    /// `on_event`.
+    pub fn emit(&mut self, event: &GameEvent) {
+        self.notify(|o| {
+            match event {
+                GameEvent::CardsPlayed { player_id, hand } => o.on_move_played(player_id, *hand),
+                GameEvent::Passed { player_id } => o.on_move_played(player_id, Hand::Pass),
+                GameEvent::TrickCleared { winner_id } => o.on_trick_cleared(winner_id),
+                GameEvent::OrderReversed { orders } => o.on_order_reversed(orders),
+                GameEvent::PlayerFinished { player_id, position } => {
+                    o.on_player_finished(player_id, *position)
+                },
+                GameEvent::GameOver { winners } => o.on_game_over(winners),
+            }
+            o.on_event(event);
+        });
+    }
+
+    pub fn notify<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut dyn GameObserver),
+    {
+        for observer in self.0.iter_mut() {
+            f(observer.as_mut());
+        }
+    }
+}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
@@ -0,0 +1,204 @@
+use super::{
+    check_orders,
+    compare_hands_with_ruleset,
+    sort_hand,
+    sort_played_cards,
+    Hand,
+    Round,
+    RoundBuilder,
+    Ruleset,
+    SetupError,
+};
+use crate::cards::{get_rank_array, get_suit_array, Card, PlayedCard, Rank, Suit};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The suit and rank orders a round is played under, and whether they've
+/// been reversed from those it started with. Passing one of these rather
+/// than the two orders keeps an order from being paired with the wrong
+/// one, and a custom order is checked once, when it's made.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct OrderContext {
+    suit_order: Vec<Suit>,
+    rank_order: [Rank; 13],
+    reversed: bool,
+}
+
+impl OrderContext {
+    /// Custom orders, lowest first. The suit order lists each suit in
+    /// play once, and can be any set of them, such as all five for a big
+    /// table. The rank order has to list every rank exactly once.
+    pub fn new(suit_order: &[Suit], rank_order: [Rank; 13]) -> Result<OrderContext, SetupError> {
+        check_orders(suit_order, rank_order)?;
+
+        Ok(OrderContext { suit_order: suit_order.to_vec(), rank_order, reversed: false })
+    }
+
+    /// Clubs up to spades and threes up to twos.
+    pub fn standard() -> OrderContext {
+        OrderContext {
+            suit_order: get_suit_array().to_vec(),
+            rank_order: get_rank_array(),
+            reversed: false,
+        }
+    }
+
+    pub fn get_suit_order(&self) -> &[Suit] {
+        &self.suit_order
+    }
+
+    pub fn get_rank_order(&self) -> &[Rank; 13] {
+        &self.rank_order
+    }
+
+    /// Where `suit` sits in the suit order, lowest first.
+    pub fn suit_index(&self, suit: Suit) -> Option<usize> {
+        self.suit_order.iter().position(|&s| s == suit)
+    }
+
+    /// Where `rank` sits in the rank order, lowest first.
+    pub fn rank_index(&self, rank: Rank) -> Option<usize> {
+        self.rank_order.iter().position(|&r| r == rank)
+    }
+
+    pub fn lowest_rank(&self) -> Rank {
+        self.rank_order[0]
+    }
+
+    pub fn highest_rank(&self) -> Rank {
+        self.rank_order[self.rank_order.len() - 1]
+    }
+
+    pub fn lowest_suit(&self) -> Suit {
+        self.suit_order[0]
+    }
+
+    pub fn highest_suit(&self) -> Suit {
+        self.suit_order[self.suit_order.len() - 1]
+    }
+
+    /// Whether the suits in play are the standard four, in any order.
+    pub fn has_standard_suits(&self) -> bool {
+        self.suit_order.len() == 4
+            && get_suit_array().iter().all(|suit| self.suit_order.contains(suit))
+    }
+
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Both orders turned round, as a reversal leaves them.
+    pub fn reverse(mut self) -> OrderContext {
+        self.suit_order.reverse();
+        self.rank_order.reverse();
+        self.reversed = !self.reversed;
+        self
+    }
+
+    /// Whether `new_hand` beats `last_move` under these orders and every
+    /// comparison rule in `ruleset`.
+    pub fn compare_hands(&self, last_move: Hand, new_hand: Hand, ruleset: Ruleset) -> bool {
+        compare_hands_with_ruleset(last_move, new_hand, ruleset, self)
+    }
+
+    /// The hand from lowest to highest, with jokers at the top.
+    pub fn sort_hand(&self, hand: &[Card]) -> Vec<Card> {
+        sort_hand(hand, self)
+    }
+
+    /// Played cards from highest to lowest.
+    pub fn sort_played_cards(&self, cards: &[PlayedCard]) -> Vec<PlayedCard> {
+        sort_played_cards(cards, self)
+    }
+}
+
+impl Default for OrderContext {
+    fn default() -> OrderContext {
+        OrderContext::standard()
+    }
+}
+
+impl Round {
+    /// The orders the round is being played under right now.
+    pub fn get_order_context(&self) -> OrderContext {
+        OrderContext {
+            suit_order: self.get_suit_order(),
+            rank_order: self.get_rank_order(),
+            reversed: self.is_reversed(),
+        }
+    }
+}
+
+impl RoundBuilder {
+    /// Sets both orders, and whether they're a reversal, at once.
+    pub fn orders(self, orders: OrderContext) -> RoundBuilder {
+        self.suit_order(&orders.suit_order)
+            .rank_order(orders.rank_order)
+            .reversed(orders.reversed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_cards, parse_played_cards, Scenario};
+
+    #[test]
+    fn reversed_contexts_follow_the_round() {
+        let round = Scenario::new()
+            .player("a", "3C KS")
+            .player("b", "4C")
+            .last_move("b", "QS")
+            .next_player("a")
+            .reversed()
+            .build()
+            .unwrap();
+        let orders = round.get_order_context();
+        let king = Hand::build(parse_played_cards("KS").unwrap()).unwrap();
+        let queen = Hand::build(parse_played_cards("QS").unwrap()).unwrap();
+
+        assert_eq!(orders, OrderContext::standard().reverse());
+        assert!(orders.is_reversed());
+        assert!(!king.beats(&queen, Ruleset::default(), &orders));
+        assert!(king.beats(&queen, Ruleset::default(), &OrderContext::default()));
+        assert_eq!(
+            orders.sort_hand(&parse_cards("3C KS 2D").unwrap()),
+            parse_cards("2D KS 3C").unwrap()
+        );
+
+        let rebuilt = RoundBuilder::new()
+            .player(round.get_player("a").unwrap())
+            .orders(orders.clone())
+            .build()
+            .unwrap();
+        assert_eq!(rebuilt.get_order_context(), orders);
+    }
+
+    #[test]
+    fn custom_orders_are_checked_once() {
+        let mut suits = get_suit_array();
+        suits[0] = Suit::Spades;
+
+        assert_eq!(OrderContext::new(&suits, get_rank_array()), Err(SetupError::InvalidSuitOrder));
+        assert!(OrderContext::new(&get_suit_array(), get_rank_array()).is_ok());
+    }
+
+    #[test]
+    fn suit_orders_can_hold_any_set_of_suits() {
+        let five_suits = [Suit::Eagles, Suit::Clubs, Suit::Hearts, Suit::Diamonds, Suit::Spades];
+        let orders = OrderContext::new(&five_suits, get_rank_array()).unwrap();
+
+        assert_eq!(orders.lowest_suit(), Suit::Eagles);
+        assert!(!orders.has_standard_suits());
+        assert_eq!(
+            orders.sort_hand(&parse_cards("3S 3E 3C").unwrap()),
+            parse_cards("3E 3C 3S").unwrap()
+        );
+
+        let three_suits = OrderContext::new(&five_suits[2..], get_rank_array()).unwrap();
+        assert_eq!(three_suits.suit_index(Suit::Eagles), None);
+        assert!(OrderContext::standard().reverse().has_standard_suits());
+        assert_eq!(OrderContext::new(&[], get_rank_array()), Err(SetupError::InvalidSuitOrder));
+    }
+}
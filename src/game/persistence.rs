@@ -0,0 +1,144 @@
+use super::Round;
+use crate::prelude::*;
+use sha2::{Digest, Sha256};
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+const MAGIC: &[u8; 8] = b"PUSOYDOS";
+const VERSION: u16 = 1;
+const CHECKSUM_LEN: usize = 32;
+const HEADER_LEN: usize = MAGIC.len() + 2 + CHECKSUM_LEN;
+
+#[derive(Debug, PartialEq)]
+pub enum PersistError {
+    #[cfg(feature = "std")]
+    Io(io::ErrorKind),
+    NotASave,
+    UnsupportedVersion(u16),
+    ChecksumMismatch,
+    Corrupt(String),
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for PersistError {
+    fn from(err: io::Error) -> PersistError {
+        PersistError::Io(err.kind())
+    }
+}
+
+impl Round {
+    /// The round as `PUSOYDOS`, a little-endian format version, a SHA-256
+    /// checksum of the payload and then the JSON payload itself.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let payload = serde_json::to_vec(self)
+            .expect("rounds always serialize");
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&Sha256::digest(&payload));
+        bytes.extend_from_slice(&payload);
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Round, PersistError> {
+        if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+            return Err(PersistError::NotASave);
+        }
+
+        let version = u16::from_le_bytes([bytes[MAGIC.len()], bytes[MAGIC.len() + 1]]);
+        if version != VERSION {
+            return Err(PersistError::UnsupportedVersion(version));
+        }
+
+        let checksum = &bytes[MAGIC.len() + 2..HEADER_LEN];
+        let payload = &bytes[HEADER_LEN..];
+        if Sha256::digest(payload).as_slice() != checksum {
+            return Err(PersistError::ChecksumMismatch);
+        }
+
+        serde_json::from_slice(payload)
+            .map_err(|e| PersistError::Corrupt(e.to_string()))
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save_to<P: AsRef<Path>>(&self, path: P) -> Result<(), PersistError> {
+        fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn load_from<P: AsRef<Path>>(path: P) -> Result<Round, PersistError> {
+        Round::from_bytes(&fs::read(path)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Card, PlayedCard, Rank, Suit};
+    use crate::game::{Player, RoundBuilder};
+
+    fn get_round() -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+                Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Clubs },
+            ]))
+            .player(Player::new("b".to_string(), vec![
+                Card::Joker { deck_id: 0 },
+            ]))
+            .build()
+            .unwrap()
+            .submit_move("a", vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)])
+            .unwrap()
+    }
+
+    #[test]
+    fn rounds_survive_a_round_trip_through_bytes() {
+        let round = get_round();
+        let loaded = Round::from_bytes(&round.to_bytes()).unwrap();
+
+        assert!(loaded.diff(&round).is_empty());
+        assert_eq!(loaded.get_discarded(), round.get_discarded());
+    }
+
+    #[test]
+    fn tampered_saves_are_rejected() {
+        let mut bytes = get_round().to_bytes();
+        let last = bytes.len() - 2;
+        bytes[last] ^= 1;
+
+        assert_eq!(Round::from_bytes(&bytes).err(), Some(PersistError::ChecksumMismatch));
+        assert_eq!(Round::from_bytes(b"nope").err(), Some(PersistError::NotASave));
+
+        let mut bytes = get_round().to_bytes();
+        bytes[MAGIC.len()] = 9;
+        assert_eq!(
+            Round::from_bytes(&bytes).err(),
+            Some(PersistError::UnsupportedVersion(9))
+        );
+    }
+
+    #[test]
+    fn rounds_can_be_saved_to_disk() {
+        let path = std::env::temp_dir()
+            .join(format!("pusoy_dos_save_{}.bin", std::process::id()));
+        let round = get_round();
+
+        round.save_to(&path).unwrap();
+        let loaded = Round::load_from(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(loaded.unwrap().diff(&round).is_empty());
+        assert_eq!(
+            Round::load_from(&path).err(),
+            Some(PersistError::Io(io::ErrorKind::NotFound))
+        );
+    }
+}
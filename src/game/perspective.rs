@@ -0,0 +1,155 @@
+use super::{Hand, Round};
+use crate::cards::{Card, Rank, Suit};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Where `seat` sits relative to `viewer_seat` around a table of
+/// `seat_count`, counting in the order play passes, so the viewer is
+/// always 0.
+pub fn to_relative_seat(seat: usize, viewer_seat: usize, seat_count: usize) -> usize {
+    (seat + seat_count - viewer_seat) % seat_count
+}
+
+/// The seat in the round's own order for a seat relative to
+/// `viewer_seat`. Undoes `to_relative_seat`.
+pub fn to_absolute_seat(relative_seat: usize, viewer_seat: usize, seat_count: usize) -> usize {
+    (relative_seat + viewer_seat) % seat_count
+}
+
+/// Anything listed in seat order, rotated so the viewer's entry comes
+/// first and the rest follow in the order play passes.
+pub fn rotate_to_viewer<T: Clone>(seats: &[T], viewer_seat: usize) -> Vec<T> {
+    let mut rotated = seats.to_vec();
+    if !rotated.is_empty() {
+        rotated.rotate_left(viewer_seat % seats.len());
+    }
+    rotated
+}
+
+/// The round as one player sees it from their own seat: they are seat 0
+/// and everyone else follows in the order play passes, the way a table
+/// UI lays players out. Only the viewer's own cards are shown.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeatedView {
+    pub viewer: String,
+    pub hand: Vec<Card>,
+    /// Every player's id and the number of cards they hold, viewer first
+    pub seats: Vec<(String, usize)>,
+    /// Relative seat of the player to move
+    pub next_seat: Option<usize>,
+    /// Relative seat of the player who made the last move
+    pub last_seat: Option<usize>,
+    pub last_move: Option<Hand>,
+    pub suit_order: Vec<Suit>,
+    pub rank_order: [Rank; 13],
+}
+
+impl SeatedView {
+    /// `None` if the viewer isn't seated in the round.
+    pub fn from_round(round: &Round, viewer: &str) -> Option<SeatedView> {
+        let hand = round.get_sorted_hand(viewer)?;
+        let seats = round.get_seats_from(viewer)?;
+        let relative = |id: Option<String>| {
+            id.and_then(|id| seats.iter().position(|(seat, _)| *seat == id))
+        };
+
+        Some(SeatedView {
+            viewer: viewer.to_string(),
+            hand,
+            next_seat: relative(round.get_next_player()),
+            last_seat: relative(round.get_last_player()),
+            seats,
+            last_move: round.get_last_move(),
+            suit_order: round.get_suit_order(),
+            rank_order: round.get_rank_order(),
+        })
+    }
+}
+
+impl Round {
+    /// The seat `player_id` sits in, counting from 0 in the round's own
+    /// order.
+    pub fn get_seat(&self, player_id: &str) -> Option<usize> {
+        self.get_card_counts().iter().position(|(id, _)| id == player_id)
+    }
+
+    /// Where `player_id` sits relative to `viewer`, who is seat 0,
+    /// counting in the order play passes.
+    pub fn get_relative_seat(&self, viewer: &str, player_id: &str) -> Option<usize> {
+        let order = self.get_turn_order();
+        let viewer_seat = order.iter().position(|id| id == viewer)?;
+        let seat = order.iter().position(|id| id == player_id)?;
+
+        Some(to_relative_seat(seat, viewer_seat, order.len()))
+    }
+
+    /// Every player's id and card count, starting from `viewer` and going
+    /// round in the order play passes.
+    pub fn get_seats_from(&self, viewer: &str) -> Option<Vec<(String, usize)>> {
+        let counts = self.get_card_counts();
+        let seats: Vec<(String, usize)> = self.get_turn_order()
+            .into_iter()
+            .filter_map(|id| counts.iter().find(|(seat, _)| *seat == id).cloned())
+            .collect();
+        let viewer_seat = seats.iter().position(|(id, _)| id == viewer)?;
+
+        Some(rotate_to_viewer(&seats, viewer_seat))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Ruleset, TurnOrder};
+    use crate::scenario::{parse_cards, Scenario};
+
+    #[test]
+    fn seats_are_counted_from_the_viewer() {
+        let round = Scenario::new()
+            .player("a", "3C 4C")
+            .player("b", "5C")
+            .player("c", "9H 9D 2S")
+            .last_move("a", "4D")
+            .next_player("b")
+            .build()
+            .unwrap();
+
+        assert_eq!(round.get_relative_seat("b", "a"), Some(2));
+        assert_eq!(round.get_relative_seat("b", "c"), Some(1));
+        assert_eq!(round.get_relative_seat("x", "a"), None);
+        for seat in 0..3 {
+            assert_eq!(to_absolute_seat(to_relative_seat(seat, 1, 3), 1, 3), seat);
+        }
+
+        let view = SeatedView::from_round(&round, "c").unwrap();
+        assert_eq!(view.seats, vec![
+            ("c".to_string(), 3),
+            ("a".to_string(), 2),
+            ("b".to_string(), 1),
+        ]);
+        assert_eq!(view.hand, parse_cards("9H 9D 2S").unwrap());
+        assert_eq!(view.next_seat, Some(2));
+        assert_eq!(view.last_seat, Some(1));
+        assert!(SeatedView::from_round(&round, "x").is_none());
+    }
+
+    #[test]
+    fn seats_follow_the_turn_order() {
+        let ruleset = Ruleset { turn_order: TurnOrder::CounterClockwise, ..Ruleset::default() };
+        let round = Scenario::new()
+            .player("a", "3C 4C")
+            .player("b", "5C")
+            .player("c", "9H 9D 2S")
+            .ruleset(ruleset)
+            .build()
+            .unwrap();
+
+        assert_eq!(round.get_relative_seat("a", "c"), Some(1));
+        let view = SeatedView::from_round(&round, "a").unwrap();
+        assert_eq!(view.seats, vec![
+            ("a".to_string(), 2),
+            ("c".to_string(), 3),
+            ("b".to_string(), 1),
+        ]);
+    }
+}
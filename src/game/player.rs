@@ -1,5 +1,8 @@
-use crate::cards::{Card, PlayedCard};
+use super::{sort_hand, OrderContext};
+use crate::cards::{Card, CardMask, PlayedCard};
+use crate::prelude::*;
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
@@ -7,22 +10,55 @@ pub enum PlayerError {
     PlayerDoesntHaveCard,
 }
 
-#[wasm_bindgen]
+/// Details about a player for clients to show. The game itself never
+/// looks at these.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct PlayerMetadata {
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub avatar: Option<String>,
+    #[serde(default)]
+    pub is_bot: bool,
+}
+
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 pub struct Player {
     id: String,
     hand: Vec<Card>,
+    #[serde(default)]
+    metadata: PlayerMetadata,
 }
 
 impl Player {
     pub fn new(id: String, hand: Vec<Card>) -> Player {
-        Player { id, hand }
+        Player {
+            id,
+            hand,
+            metadata: PlayerMetadata::default(),
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: PlayerMetadata) -> Player {
+        self.metadata = metadata;
+        self
     }
 
     pub fn get_id(&self) -> &str {
         &self.id
     }
 
+    pub fn get_metadata(&self) -> &PlayerMetadata {
+        &self.metadata
+    }
+
+    pub fn set_metadata(&mut self, metadata: PlayerMetadata) {
+        self.metadata = metadata;
+    }
+
     pub fn get_hand(&self) -> Vec<Card> {
         self.hand.clone()
     }
@@ -31,6 +67,17 @@ impl Player {
         self.hand.len()
     }
 
+    /// The hand as a single integer, for sending over the wire. `None` if
+    /// the hand can't be packed for `num_decks` decks.
+    pub fn hand_as_bitmask(&self, num_decks: u8) -> Option<CardMask> {
+        CardMask::from_cards(&self.hand, num_decks)
+    }
+
+    /// A player holding the cards in `mask`, with no metadata.
+    pub fn from_bitmask(id: String, mask: CardMask) -> Player {
+        Player::new(id, mask.to_cards())
+    }
+
     pub fn play_move(&mut self, cards: Vec<PlayedCard>) -> Result<Player, PlayerError> {
         for card in cards.iter() {
             match self.hand.iter()
@@ -49,6 +96,36 @@ impl Player {
         Ok(self.clone())
     }
 
+    pub(crate) fn pick_up(&mut self, cards: Vec<Card>) {
+        self.hand.extend(cards);
+    }
+
+    pub(crate) fn is_sorted(&self, orders: &OrderContext) -> bool {
+        sort_hand(&self.hand, orders) == self.hand
+    }
+
+    pub(crate) fn sort_hand(&mut self, orders: &OrderContext) {
+        self.hand = sort_hand(&self.hand, orders);
+    }
+
+    /// Takes exactly these cards, deck ids and all, out of the hand. The
+    /// hand is left alone if any of them isn't in it.
+    pub(crate) fn remove_cards(&mut self, cards: &[Card]) -> Result<(), Card> {
+        let mut hand = self.hand.clone();
+        for card in cards {
+            let index = hand.iter().position(|c| c == card).ok_or(*card)?;
+            hand.remove(index);
+        }
+        self.hand = hand;
+
+        Ok(())
+    }
+
+    /// Empties the hand, returning the cards that were in it.
+    pub(crate) fn discard_hand(&mut self) -> Vec<Card> {
+        core::mem::take(&mut self.hand)
+    }
+
     pub fn has_card(&self, card: Card) -> bool {
         self.hand.contains(&card)
     }
@@ -66,6 +143,24 @@ mod tests {
         assert_eq!(player.get_id(), id);
     }
 
+    #[test]
+    fn metadata_travels_with_the_player() {
+        let metadata = PlayerMetadata {
+            display_name: Some("Ben".to_string()),
+            avatar: Some("fox".to_string()),
+            is_bot: true,
+        };
+        let player = Player::new(String::from("id1"), vec![])
+            .with_metadata(metadata.clone());
+
+        let json = serde_json::to_string(&player).unwrap();
+        let loaded: Player = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get_metadata(), &metadata);
+
+        let old: Player = serde_json::from_str(r#"{"id":"id1","hand":[]}"#).unwrap();
+        assert_eq!(old.get_metadata(), &PlayerMetadata::default());
+    }
+
     #[test]
     fn it_shows_number_of_cards_left() {
         let id = String::from("id1");
@@ -238,4 +333,20 @@ mod tests {
         assert!(new_player.is_ok());
 
     }
+
+    #[test]
+    fn hands_can_be_packed_into_a_bitmask() {
+        let hand = vec![
+            Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Hearts },
+            Card::Joker { deck_id: 0 },
+        ];
+        let player = Player::new(String::from("id1"), hand.clone());
+
+        let mask = player.hand_as_bitmask(1).unwrap();
+        assert_eq!(mask, CardMask::OneDeck(1 << 5 | 1 << 52));
+
+        let unpacked = Player::from_bitmask(String::from("id1"), mask);
+        assert_eq!(unpacked.get_hand(), hand);
+        assert_eq!(unpacked.get_id(), "id1");
+    }
 }
@@ -0,0 +1,137 @@
+use super::{RecordedMove, Round, SubmitError};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// What the solver of a puzzle has to achieve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+#[serde(rename_all = "snake_case")]
+pub enum PuzzleGoal {
+    /// Play every card in no more than this many of the solver's own
+    /// moves, passes included
+    GoOutWithin(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PuzzleError {
+    IllegalMove {
+        index: usize,
+        player_id: String,
+        error: SubmitError,
+    },
+    GoalNotMet,
+}
+
+/// A mid-game position for one player to solve, such as going out within
+/// three moves. Solutions give the moves of every player, so a puzzle can
+/// script how the other players respond.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Puzzle {
+    pub round: Round,
+    /// The player the goal is set for
+    pub player_id: String,
+    pub goal: PuzzleGoal,
+}
+
+impl Puzzle {
+    pub fn new(round: Round, player_id: &str, goal: PuzzleGoal) -> Puzzle {
+        Puzzle {
+            round,
+            player_id: player_id.to_string(),
+            goal,
+        }
+    }
+
+    /// Plays `moves` from the puzzle's position, checking each is legal
+    /// and that the goal has been met by the end of them.
+    pub fn check_solution(&self, moves: &[RecordedMove]) -> Result<(), PuzzleError> {
+        let mut round = self.round.clone();
+        let mut solver_moves = 0;
+
+        for (index, recorded) in moves.iter().enumerate() {
+            round = round.submit_move(&recorded.player_id, recorded.cards.clone())
+                .map_err(|error| PuzzleError::IllegalMove {
+                    index,
+                    player_id: recorded.player_id.clone(),
+                    error,
+                })?;
+
+            if recorded.player_id == self.player_id {
+                solver_moves += 1;
+            }
+        }
+
+        let went_out = round.get_player(&self.player_id)
+            .map(|p| p.get_card_count() == 0)
+            .unwrap_or(false);
+
+        match self.goal {
+            PuzzleGoal::GoOutWithin(limit) if went_out && solver_moves <= limit => Ok(()),
+            PuzzleGoal::GoOutWithin(_) => Err(PuzzleError::GoalNotMet),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_played_cards, Scenario};
+
+    fn get_puzzle() -> Puzzle {
+        let round = Scenario::new()
+            .player("a", "3C 3H 9D KS")
+            .player("b", "4C 10S 2S")
+            .next_player("a")
+            .lead("b")
+            .build()
+            .unwrap();
+
+        Puzzle::new(round, "a", PuzzleGoal::GoOutWithin(3))
+    }
+
+    fn moves(moves: &[(&str, &str)]) -> Vec<RecordedMove> {
+        moves.iter()
+            .map(|&(player_id, cards)| RecordedMove {
+                player_id: player_id.to_string(),
+                cards: parse_played_cards(cards).unwrap(),
+                annotations: vec![],
+                signature: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn solutions_must_meet_the_goal() {
+        let puzzle = get_puzzle();
+
+        assert_eq!(puzzle.check_solution(&moves(&[
+            ("a", "3C 3H"),
+            ("b", ""),
+            ("a", "9D"),
+            ("b", ""),
+            ("a", "KS"),
+        ])), Ok(()));
+
+        assert_eq!(puzzle.check_solution(&moves(&[
+            ("a", "3C"),
+            ("b", ""),
+            ("a", "3H"),
+            ("b", ""),
+            ("a", "9D"),
+        ])), Err(PuzzleError::GoalNotMet));
+    }
+
+    #[test]
+    fn illegal_moves_are_reported() {
+        let puzzle = get_puzzle();
+
+        assert_eq!(puzzle.check_solution(&moves(&[
+            ("a", "9D"),
+            ("b", "4C"),
+        ])), Err(PuzzleError::IllegalMove {
+            index: 1,
+            player_id: "b".to_string(),
+            error: SubmitError::HandNotHighEnough,
+        }));
+    }
+}
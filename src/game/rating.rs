@@ -0,0 +1,169 @@
+use super::GameResult;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use alloc::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EloConfig {
+    /// Most a player's rating can move in one game
+    pub k_factor: f64,
+    pub initial_rating: f64,
+}
+
+impl Default for EloConfig {
+    fn default() -> EloConfig {
+        EloConfig {
+            k_factor: 32.0,
+            initial_rating: 1500.0,
+        }
+    }
+}
+
+/// Elo ratings for a ladder of players. A game with several players is
+/// scored as a head-to-head result between every pair of them, decided by
+/// their team's placement, and the K factor is shared across those pairs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ratings {
+    config: EloConfig,
+    ratings: BTreeMap<String, f64>,
+}
+
+impl Ratings {
+    pub fn new(config: EloConfig) -> Ratings {
+        Ratings {
+            config,
+            ratings: BTreeMap::new(),
+        }
+    }
+
+    /// Unrated players start on the configured initial rating.
+    pub fn get_rating(&self, player_id: &str) -> f64 {
+        self.ratings.get(player_id)
+            .cloned()
+            .unwrap_or(self.config.initial_rating)
+    }
+
+    pub fn get_ratings(&self) -> &BTreeMap<String, f64> {
+        &self.ratings
+    }
+
+    /// Applies a finished game and returns how much each player's rating
+    /// changed.
+    pub fn update(&mut self, result: &GameResult) -> BTreeMap<String, f64> {
+        let placements: Vec<(String, usize)> = result.players.iter()
+            .map(|p| {
+                let placement = result.teams.iter()
+                    .find(|t| t.team == p.team)
+                    .map(|t| t.placement)
+                    .unwrap_or(usize::MAX);
+                (p.player_id.clone(), placement)
+            })
+            .collect();
+
+        let mut changes = BTreeMap::new();
+        if placements.len() < 2 {
+            return changes;
+        }
+
+        let k = self.config.k_factor / (placements.len() - 1) as f64;
+
+        for (id, placement) in placements.iter() {
+            let rating = self.get_rating(id);
+            let change: f64 = placements.iter()
+                .filter(|(other, _)| other != id)
+                .map(|(other, other_placement)| {
+                    let actual = if placement < other_placement {
+                        1.0
+                    } else if placement > other_placement {
+                        0.0
+                    } else {
+                        0.5
+                    };
+                    actual - expected_score(rating, self.get_rating(other))
+                })
+                .sum();
+
+            changes.insert(id.clone(), k * change);
+        }
+
+        for (id, change) in changes.iter() {
+            let rating = self.get_rating(id) + change;
+            self.ratings.insert(id.clone(), rating);
+        }
+
+        changes
+    }
+}
+
+/// Chance a player rated `rating` beats one rated `opponent`.
+pub fn expected_score(rating: f64, opponent: f64) -> f64 {
+    1.0 / (1.0 + libm::pow(10.0, (opponent - rating) / 400.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{PlayerResult, TeamResult};
+
+    fn get_result(order: &[&str], teams: &[usize]) -> GameResult {
+        let players = order.iter()
+            .zip(teams.iter())
+            .map(|(id, &team)| PlayerResult {
+                player_id: id.to_string(),
+                team,
+                finishing_position: None,
+                cards_left: 0,
+                resigned: false,
+                penalty_points: 0,
+                tricks_won: 0,
+                bombs_played: 0,
+            })
+            .collect();
+        let mut team_ids: Vec<usize> = teams.to_vec();
+        team_ids.dedup();
+        let teams = team_ids.into_iter()
+            .enumerate()
+            .map(|(i, team)| TeamResult {
+                team,
+                player_ids: vec![],
+                cards_left: 0,
+                placement: i + 1,
+            })
+            .collect();
+
+        GameResult { players, teams }
+    }
+
+    #[test]
+    fn equal_players_split_rating_by_placement() {
+        let mut ratings = Ratings::default();
+        let changes = ratings.update(&get_result(&["a", "b", "c", "d"], &[0, 1, 2, 3]));
+
+        assert!((changes["a"] - 16.0).abs() < 1e-9);
+        assert!(changes["b"] > 0.0);
+        assert!(changes["c"] < 0.0);
+        assert!((changes.values().sum::<f64>()).abs() < 1e-9);
+        assert!((ratings.get_rating("d") - 1484.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn upsets_move_ratings_further() {
+        let mut ratings = Ratings::default();
+        ratings.update(&get_result(&["a", "b"], &[0, 1]));
+        let favourite_wins = ratings.clone()
+            .update(&get_result(&["a", "b"], &[0, 1]))["a"];
+        let upset = ratings.update(&get_result(&["b", "a"], &[0, 1]))["b"];
+
+        assert!(upset > favourite_wins);
+    }
+
+    #[test]
+    fn partners_share_their_team_placement() {
+        let mut ratings = Ratings::default();
+        let changes = ratings.update(&get_result(&["a", "c", "b", "d"], &[0, 0, 1, 1]));
+
+        assert!((changes["a"] - changes["c"]).abs() < 1e-9);
+        assert!(changes["a"] > 0.0);
+        assert_eq!(ratings.get_rating("unknown"), 1500.0);
+    }
+}
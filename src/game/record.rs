@@ -0,0 +1,420 @@
+use super::{CardMismatch, ReplayCursor, Round, SubmitError};
+use crate::ai::{DecisionAudit, MoveReview};
+use crate::cards::PlayedCard;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A note attached to a move for reviewing the game. None of them affect
+/// replaying or verifying it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+#[serde(rename_all = "snake_case")]
+pub enum Annotation {
+    /// A short label such as `blunder` or an emoji reaction
+    Tag(String),
+    Comment(String),
+    /// An engine's evaluation of the position after the move
+    Eval(f64),
+    /// How the move compares with the engine's preferred move
+    Review(MoveReview),
+    /// What an automated player chose the move from
+    Decision(DecisionAudit),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedMove {
+    pub player_id: String,
+    pub cards: Vec<PlayedCard>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub annotations: Vec<Annotation>,
+    /// The player's ed25519 signature over the move, as hex. See
+    /// `move_signing_payload` for what's signed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// An alternative line of play branching off from another line.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Variation {
+    /// How many moves from the deal this shares with the line it branches
+    /// from
+    pub branch_point: usize,
+    pub moves: Vec<RecordedMove>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variations: Vec<Variation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VariationError {
+    /// The path doesn't lead to a variation
+    UnknownLine,
+    /// The line doesn't have that many moves
+    InvalidBranchPoint,
+    /// The variation's moves can't be played from its branch point
+    Illegal(VerifyError),
+}
+
+/// Everything needed to replay a game: the dealt round, every move in
+/// order and the finishing order that was claimed for it. Variations are
+/// kept as a tree alongside the moves that were actually played.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub initial_round: Round,
+    pub moves: Vec<RecordedMove>,
+    pub winners: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variations: Vec<Variation>,
+}
+
+impl GameRecord {
+    pub fn new(initial_round: Round) -> GameRecord {
+        GameRecord {
+            initial_round,
+            moves: vec![],
+            winners: vec![],
+            variations: vec![],
+        }
+    }
+
+    pub fn record_move(&mut self, player_id: &str, cards: Vec<PlayedCard>) {
+        self.moves.push(RecordedMove {
+            player_id: player_id.to_string(),
+            cards,
+            annotations: vec![],
+            signature: None,
+        });
+    }
+
+    /// Records a move along with the signature its player sent with it,
+    /// so the record can be checked later without trusting whoever kept
+    /// it.
+    pub fn record_signed_move(
+        &mut self,
+        player_id: &str,
+        cards: Vec<PlayedCard>,
+        signature: String,
+    ) {
+        self.moves.push(RecordedMove {
+            player_id: player_id.to_string(),
+            cards,
+            annotations: vec![],
+            signature: Some(signature),
+        });
+    }
+
+    /// Records a move an agent chose along with what it chose it from, so
+    /// the decision can be reproduced if it's disputed.
+    pub fn record_automated_move(
+        &mut self,
+        player_id: &str,
+        cards: Vec<PlayedCard>,
+        audit: DecisionAudit,
+    ) {
+        self.moves.push(RecordedMove {
+            player_id: player_id.to_string(),
+            cards,
+            annotations: vec![Annotation::Decision(audit)],
+            signature: None,
+        });
+    }
+
+    /// Attaches an annotation to the move at `index`. Returns false if
+    /// there's no such move.
+    pub fn annotate(&mut self, index: usize, annotation: Annotation) -> bool {
+        match self.moves.get_mut(index) {
+            Some(recorded) => {
+                recorded.annotations.push(annotation);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// The line at `path` as a record of its own, with every move from the
+    /// deal, ready to replay. Each step of the path indexes into the
+    /// variations of the line before it, and the empty path is the game as
+    /// it was played. The record has no winners or variations.
+    pub fn get_line(&self, path: &[usize]) -> Option<GameRecord> {
+        let mut moves = self.moves.clone();
+        let mut variations = &self.variations;
+
+        for &i in path {
+            let variation = variations.get(i)?;
+            moves.truncate(variation.branch_point);
+            moves.extend(variation.moves.iter().cloned());
+            variations = &variation.variations;
+        }
+
+        Some(GameRecord {
+            initial_round: self.initial_round.clone(),
+            moves,
+            winners: vec![],
+            variations: vec![],
+        })
+    }
+
+    /// Branches `variation` off the line at `path` once its moves have been
+    /// checked from the branch point. Returns its index among that line's
+    /// variations.
+    pub fn add_variation(
+        &mut self,
+        path: &[usize],
+        variation: Variation,
+    ) -> Result<usize, VariationError> {
+        let mut line = self.get_line(path).ok_or(VariationError::UnknownLine)?;
+        if variation.branch_point > line.moves.len() {
+            return Err(VariationError::InvalidBranchPoint);
+        }
+
+        line.moves.truncate(variation.branch_point);
+        line.moves.extend(variation.moves.iter().cloned());
+        ReplayCursor::new(&line).map_err(VariationError::Illegal)?;
+
+        let variations = path.iter()
+            .fold(&mut self.variations, |variations, &i| &mut variations[i].variations);
+        variations.push(variation);
+
+        Ok(variations.len() - 1)
+    }
+
+    /// The indexes of every move tagged with `tag`.
+    pub fn find_tagged(&self, tag: &str) -> Vec<usize> {
+        self.moves.iter()
+            .enumerate()
+            .filter(|(_, recorded)| recorded.annotations.iter().any(|a| match a {
+                Annotation::Tag(t) => t == tag,
+                _ => false,
+            }))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum VerifyError {
+    IllegalMove {
+        index: usize,
+        player_id: String,
+        error: SubmitError,
+    },
+    MoveAfterGameOver { index: usize },
+    CardsNotConserved {
+        index: usize,
+        mismatches: Vec<CardMismatch>,
+    },
+    GameNotFinished,
+    WinnersMismatch {
+        claimed: Vec<String>,
+        actual: Vec<String>,
+    },
+}
+
+/// Replays `record` from its initial round, checking every move is legal
+/// and that the finishing order matches the one claimed.
+pub fn verify_game(record: &GameRecord) -> Result<(), VerifyError> {
+    let mut round = record.initial_round.clone();
+    let mut winners: Vec<String> = vec![];
+
+    for (index, recorded) in record.moves.iter().enumerate() {
+        if round.is_game_over() {
+            return Err(VerifyError::MoveAfterGameOver { index });
+        }
+
+        round = round.submit_move(&recorded.player_id, recorded.cards.clone())
+            .map_err(|error| VerifyError::IllegalMove {
+                index,
+                player_id: recorded.player_id.clone(),
+                error,
+            })?;
+
+        round.verify_card_conservation()
+            .map_err(|mismatches| VerifyError::CardsNotConserved {
+                index,
+                mismatches,
+            })?;
+
+        let finished = round.get_player(&recorded.player_id)
+            .map(|p| p.get_card_count() == 0)
+            .unwrap_or(false);
+        if finished && !winners.contains(&recorded.player_id) {
+            winners.push(recorded.player_id.clone());
+        }
+    }
+
+    if round.get_next_player().is_some() {
+        return Err(VerifyError::GameNotFinished);
+    }
+
+    if winners != record.winners {
+        return Err(VerifyError::WinnersMismatch {
+            claimed: record.winners.clone(),
+            actual: winners,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Rank, Suit};
+    use crate::game::{Player, RoundBuilder};
+    use crate::scenario::parse_cards;
+
+    fn played(rank: Rank, suit: Suit) -> Vec<PlayedCard> {
+        vec![PlayedCard::new(rank, suit, false)]
+    }
+
+    fn get_record() -> GameRecord {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C AC").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("5C 6C").unwrap()))
+            .build()
+            .unwrap();
+
+        let mut record = GameRecord::new(round);
+        record.record_move("a", played(Rank::Three, Suit::Clubs));
+        record.record_move("b", played(Rank::Five, Suit::Clubs));
+        record.record_move("a", played(Rank::Ace, Suit::Clubs));
+        record.winners = vec!["a".to_string()];
+
+        record
+    }
+
+    #[test]
+    fn a_legal_game_verifies() {
+        assert_eq!(verify_game(&get_record()), Ok(()));
+    }
+
+    #[test]
+    fn illegal_moves_are_reported_with_their_index() {
+        let mut record = get_record();
+        record.moves[1].cards = played(Rank::Six, Suit::Spades);
+
+        assert_eq!(verify_game(&record), Err(VerifyError::IllegalMove {
+            index: 1,
+            player_id: "b".to_string(),
+            error: SubmitError::PlayerDoesntHaveCard,
+        }));
+    }
+
+    #[test]
+    fn the_claimed_winners_must_match() {
+        let mut record = get_record();
+        record.winners = vec!["b".to_string()];
+
+        assert_eq!(verify_game(&record), Err(VerifyError::WinnersMismatch {
+            claimed: vec!["b".to_string()],
+            actual: vec!["a".to_string()],
+        }));
+    }
+
+    #[test]
+    fn unfinished_and_overlong_games_are_rejected() {
+        let mut record = get_record();
+        let last = record.moves.pop().unwrap();
+
+        assert_eq!(verify_game(&record), Err(VerifyError::GameNotFinished));
+
+        record.moves.push(last);
+        record.record_move("b", played(Rank::Six, Suit::Clubs));
+
+        assert_eq!(
+            verify_game(&record),
+            Err(VerifyError::MoveAfterGameOver { index: 3 })
+        );
+    }
+
+    #[test]
+    fn annotations_survive_serialization() {
+        let mut record = get_record();
+        assert!(record.annotate(1, Annotation::Tag("blunder".to_string())));
+        assert!(record.annotate(1, Annotation::Eval(-2.5)));
+        assert!(!record.annotate(3, Annotation::Comment("gg".to_string())));
+
+        let json = serde_json::to_string(&record).unwrap();
+        let parsed: GameRecord = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.moves, record.moves);
+        assert_eq!(parsed.find_tagged("blunder"), vec![1]);
+        assert_eq!(verify_game(&parsed), Ok(()));
+    }
+
+    #[test]
+    fn variations_branch_into_a_tree() {
+        let mut record = get_record();
+        let six_instead = Variation {
+            branch_point: 1,
+            moves: vec![
+                RecordedMove {
+                    player_id: "b".to_string(),
+                    cards: played(Rank::Six, Suit::Clubs),
+                    annotations: vec![],
+                    signature: None,
+                },
+            ],
+            variations: vec![],
+        };
+        assert_eq!(record.add_variation(&[], six_instead), Ok(0));
+
+        let mut pass_instead = GameRecord::new(record.initial_round.clone());
+        pass_instead.record_move("a", vec![]);
+        pass_instead.record_move("b", played(Rank::Five, Suit::Clubs));
+        let pass_instead = Variation {
+            branch_point: 2,
+            moves: pass_instead.moves,
+            variations: vec![],
+        };
+        assert_eq!(record.add_variation(&[0], pass_instead), Ok(0));
+
+        let line = record.get_line(&[0, 0]).unwrap();
+        let cards: Vec<Vec<PlayedCard>> = line.moves.into_iter().map(|m| m.cards).collect();
+        assert_eq!(cards, vec![
+            played(Rank::Three, Suit::Clubs),
+            played(Rank::Six, Suit::Clubs),
+            vec![],
+            played(Rank::Five, Suit::Clubs),
+        ]);
+
+        let json = serde_json::to_string(&record).unwrap();
+        let parsed: GameRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.variations, record.variations);
+        assert_eq!(verify_game(&parsed), Ok(()));
+    }
+
+    #[test]
+    fn variations_must_be_playable() {
+        let mut record = get_record();
+        let variation = |branch_point, player_id: &str| Variation {
+            branch_point,
+            moves: vec![
+                RecordedMove {
+                    player_id: player_id.to_string(),
+                    cards: played(Rank::Ace, Suit::Clubs),
+                    annotations: vec![],
+                    signature: None,
+                },
+            ],
+            variations: vec![],
+        };
+
+        assert_eq!(
+            record.add_variation(&[], variation(1, "a")),
+            Err(VariationError::Illegal(VerifyError::IllegalMove {
+                index: 1,
+                player_id: "a".to_string(),
+                error: SubmitError::NotCurrentPlayer,
+            }))
+        );
+        assert_eq!(
+            record.add_variation(&[], variation(4, "a")),
+            Err(VariationError::InvalidBranchPoint)
+        );
+        assert_eq!(
+            record.add_variation(&[0], variation(0, "a")),
+            Err(VariationError::UnknownLine)
+        );
+        assert!(record.variations.is_empty());
+    }
+}
@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RedealError {
+    /// The ruleset doesn't let players vote for a redeal
+    Disabled,
+    UnknownPlayer,
+    /// Redeals can only be voted for before the first move
+    AlreadyStarted,
+    AlreadyVoted,
+    /// Not enough players have asked for a redeal yet
+    NotAgreed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RedealOutcome {
+    /// More players have to ask before the cards are redealt
+    Pending { votes: usize, needed: usize },
+    /// Enough players asked and the game is waiting to be redealt
+    Agreed,
+}
@@ -0,0 +1,63 @@
+use super::{PlayedHand, Round, SpectatorView};
+use crate::cards::Card;
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How many of the latest moves a rejoin snapshot carries, enough to show
+/// what a player missed while they were away without sending the game.
+pub const REJOIN_MOVES: usize = 8;
+
+/// Everything a reconnecting player needs to redraw the table, in one
+/// payload: the table as spectators see it, card counts included, their
+/// own hand, and the moves they may have missed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RejoinSnapshot {
+    pub player_id: String,
+    pub view: SpectatorView,
+    pub hand: Vec<Card>,
+    /// The last `REJOIN_MOVES` moves, passes included, oldest first
+    pub recent_moves: Vec<PlayedHand>,
+}
+
+impl Round {
+    /// A snapshot for `user_id` to rejoin from, showing no hand but
+    /// theirs. `None` if they aren't seated in the round.
+    pub fn rejoin_snapshot(&self, user_id: &str) -> Option<RejoinSnapshot> {
+        let hand = self.get_sorted_hand(user_id)?;
+
+        Some(RejoinSnapshot {
+            player_id: user_id.to_string(),
+            view: SpectatorView::from_round(self),
+            hand,
+            recent_moves: self.last_n_moves(REJOIN_MOVES),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_cards, parse_played_cards, Scenario};
+
+    #[test]
+    fn snapshots_show_only_the_rejoining_players_hand() {
+        let round = Scenario::new()
+            .player("a", "3C 9H 4D")
+            .player("b", "5C 6H KS")
+            .build()
+            .unwrap();
+        let round = round.submit_move("a", parse_played_cards("3C").unwrap()).unwrap();
+        let round = round.submit_move("b", parse_played_cards("5C").unwrap()).unwrap();
+
+        let snapshot = round.rejoin_snapshot("b").unwrap();
+
+        assert_eq!(snapshot.hand, parse_cards("6H KS").unwrap());
+        assert_eq!(snapshot.view.card_counts, vec![("a".to_string(), 2), ("b".to_string(), 2)]);
+        assert_eq!(snapshot.recent_moves, round.last_n_moves(2));
+        assert!(round.rejoin_snapshot("x").is_none());
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        assert!(!json.contains("Nine"));
+        assert_eq!(serde_json::from_str::<RejoinSnapshot>(&json).unwrap(), snapshot);
+    }
+}
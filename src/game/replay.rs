@@ -0,0 +1,175 @@
+use super::{GameRecord, RecordedMove, Round, VerifyError};
+use crate::prelude::*;
+
+/// How many moves apart the cursor keeps snapshots of the round.
+const SNAPSHOT_INTERVAL: usize = 16;
+
+/// Steps back and forth through a recorded game. Position `n` is the round
+/// after the first `n` moves, so position 0 is the deal.
+///
+/// The moves are checked once up front and the round is snapshotted every
+/// few moves, so seeking anywhere replays at most a handful of moves.
+#[derive(Debug, Clone)]
+pub struct ReplayCursor {
+    moves: Vec<RecordedMove>,
+    snapshots: Vec<Round>,
+    position: usize,
+    round: Round,
+}
+
+impl ReplayCursor {
+    pub fn new(record: &GameRecord) -> Result<ReplayCursor, VerifyError> {
+        let mut round = record.initial_round.clone();
+        let mut snapshots = vec![round.clone()];
+
+        for (index, recorded) in record.moves.iter().enumerate() {
+            if round.is_game_over() {
+                return Err(VerifyError::MoveAfterGameOver { index });
+            }
+
+            round = round.submit_move(&recorded.player_id, recorded.cards.clone())
+                .map_err(|error| VerifyError::IllegalMove {
+                    index,
+                    player_id: recorded.player_id.clone(),
+                    error,
+                })?;
+
+            if (index + 1) % SNAPSHOT_INTERVAL == 0 {
+                snapshots.push(round.clone());
+            }
+        }
+
+        Ok(ReplayCursor {
+            moves: record.moves.clone(),
+            round: record.initial_round.clone(),
+            snapshots,
+            position: 0,
+        })
+    }
+
+    pub fn get_move_count(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn get_position(&self) -> usize {
+        self.position
+    }
+
+    pub fn get_round(&self) -> &Round {
+        &self.round
+    }
+
+    /// The move that led to the current position, if any.
+    pub fn get_last_move(&self) -> Option<&RecordedMove> {
+        self.position.checked_sub(1).map(|i| &self.moves[i])
+    }
+
+    /// Moves to the round after `n` moves, or the end of the game if there
+    /// aren't that many.
+    pub fn seek(&mut self, n: usize) -> &Round {
+        let n = n.min(self.moves.len());
+
+        if n < self.position || n - self.position > SNAPSHOT_INTERVAL {
+            let snapshot = n / SNAPSHOT_INTERVAL;
+            self.round = self.snapshots[snapshot].clone();
+            self.position = snapshot * SNAPSHOT_INTERVAL;
+        }
+
+        while self.position < n {
+            self.apply_next();
+        }
+
+        &self.round
+    }
+
+    pub fn step_forward(&mut self) -> Option<&Round> {
+        if self.position == self.moves.len() {
+            return None;
+        }
+
+        self.apply_next();
+        Some(&self.round)
+    }
+
+    pub fn step_back(&mut self) -> Option<&Round> {
+        let previous = self.position.checked_sub(1)?;
+        Some(self.seek(previous))
+    }
+
+    fn apply_next(&mut self) {
+        let recorded = &self.moves[self.position];
+        self.round.submit_move_mut(&recorded.player_id, recorded.cards.clone())
+            .expect("moves are checked when the cursor is built");
+        self.position += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Card, PlayedCard, Rank, Suit};
+    use crate::game::{Player, RoundBuilder};
+
+    fn get_record() -> GameRecord {
+        let ranks = [Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven,
+            Rank::Eight, Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen];
+        let hand = |suit| ranks.iter()
+            .map(|&rank| Card::Standard { deck_id: 0, rank, suit })
+            .collect();
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), hand(Suit::Clubs)))
+            .player(Player::new("b".to_string(), hand(Suit::Spades)))
+            .build()
+            .unwrap();
+
+        let mut record = GameRecord::new(round);
+        for &rank in ranks.iter() {
+            record.record_move("a", vec![PlayedCard::new(rank, Suit::Clubs, false)]);
+            record.record_move("b", vec![PlayedCard::new(rank, Suit::Spades, false)]);
+        }
+        // a goes out with the queen, which ends the game
+        record.moves.pop();
+
+        record
+    }
+
+    fn hand_size(cursor: &ReplayCursor, player_id: &str) -> usize {
+        cursor.get_round().get_player(player_id).unwrap().get_card_count()
+    }
+
+    #[test]
+    fn the_cursor_can_seek_and_step_through_the_game() {
+        let mut cursor = ReplayCursor::new(&get_record()).unwrap();
+        assert_eq!(cursor.get_move_count(), 19);
+
+        cursor.seek(18);
+        assert_eq!(hand_size(&cursor, "a"), 1);
+        assert_eq!(hand_size(&cursor, "b"), 1);
+
+        cursor.step_back();
+        cursor.step_back();
+        assert_eq!(cursor.get_position(), 16);
+        assert_eq!(hand_size(&cursor, "a"), 2);
+        assert_eq!(cursor.get_last_move().unwrap().player_id, "b");
+
+        cursor.seek(1);
+        assert_eq!(hand_size(&cursor, "a"), 9);
+        assert_eq!(hand_size(&cursor, "b"), 10);
+
+        cursor.seek(100);
+        assert_eq!(cursor.get_position(), 19);
+        assert!(cursor.step_forward().is_none());
+        assert_eq!(cursor.get_round().get_next_player(), None);
+    }
+
+    #[test]
+    fn illegal_records_are_rejected() {
+        let mut record = get_record();
+        record.moves.swap(0, 1);
+
+        assert!(matches!(
+            ReplayCursor::new(&record),
+            Err(VerifyError::IllegalMove { index: 0, .. })
+        ));
+    }
+}
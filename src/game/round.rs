@@ -1,15 +1,36 @@
 use super::{
-    compare_hands,
+    check_orders,
+    check_player_ids,
+    remove_remaining,
+    FinalCardRule,
     Hand,
     Player,
-    Trick,
-    TrickType,
+    PlayerMetadata,
+    HandError,
+    JokerUsage,
+    LastCardRule,
+    MoveContext,
+    MoveRule,
+    MoveRules,
+    OrderContext,
+    ReversalDuration,
     Ruleset,
+    SetupError,
+    TableClearMode,
+    TurnOrder,
 };
 use crate::cards::{Card, PlayedCard, Rank, Suit};
+use crate::prelude::*;
 use serde::{Deserialize, Serialize};
+use alloc::collections::BTreeSet;
+use alloc::sync::Arc;
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// How many moves a round remembers for `last_n_moves`. Enough for a
+/// ticker of recent plays without every clone carrying the whole game.
+pub const RECENT_MOVES_KEPT: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 pub enum SubmitError {
     FirstRoundPass,
     FirstHandMustContainLowestCard,
@@ -17,17 +38,243 @@ pub enum SubmitError {
     NotCurrentPlayer,
     InvalidHand,
     PlayerDoesntHaveCard,
+    /// The ruleset only allows jokers in five card tricks
+    JokerOutsideFiveCardTrick,
+    /// The ruleset doesn't allow a joker in a player's last hand
+    CannotFinishWithJoker,
+    /// The ruleset doesn't allow going out on a two or a joker
+    IllegalFinalCard,
+    /// The ruleset doesn't allow a bomb as a player's last hand
+    CannotFinishWithBomb,
+    /// The ruleset doesn't allow a player's last hand to reverse the
+    /// orders
+    CannotFinishWithReversal,
+    /// The round is in a state play can't continue from
+    DeadGame,
+    /// The player passed earlier in the trick and the ruleset locks them
+    /// out of it
+    LockedOutOfTrick,
+    /// A rule added to the round turned the move down, for the reason
+    /// given
+    HouseRule(String),
+    /// The winner of the first trick has to pick up the kitty and discard
+    /// before anyone plays
+    KittyPending,
+    /// The game's clock is paused, and nobody can move until it's resumed
+    GamePaused,
+    /// The round tracks exact card copies and the player holds more than
+    /// one copy of a card they played without saying which
+    AmbiguousCardCopy,
+    /// The player has already gone out or resigned, so has nothing left
+    /// to play
+    PlayerAlreadyFinished,
+}
+
+/// Whether play can carry on from a round.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GameStatus {
+    /// Nobody has moved yet
+    NotStarted,
+    InProgress,
+    /// `placements` runs from first to last: players in the order they
+    /// went out, then anyone still holding cards, fewest first, then
+    /// resigned players, the last to resign first
+    Finished { placements: Vec<String> },
+    /// Play can't continue, which only happens to rounds put together
+    /// from bad data
+    Dead(DeadGameReason),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DeadGameReason {
+    /// The next player isn't seated in the round
+    UnknownNextPlayer(String),
+    /// There's a next player but nobody holds any cards
+    NoCardsLeft,
+    /// The next player has already gone out, so can't be asked to move
+    NextPlayerFinished(String),
+    /// The game isn't over but nobody is due to move
+    NoNextPlayer,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ResignError {
+    UnknownPlayer,
+    /// The player has already gone out or resigned
+    AlreadyOut,
+    GameOver,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LastCardError {
+    /// The ruleset doesn't use the last card rule
+    NotInPlay,
+    /// Only a player who has just gone down to one card can call it
+    NothingToDeclare,
+}
+
+/// Where a blind kitty is up to. The winner of the first trick picks it
+/// up, then discards as many cards face down before leading.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum KittyStage {
+    /// Face down until the first trick is won
+    #[default]
+    Closed,
+    PickUp(String),
+    Discard { player_id: String, count: usize },
+    /// Exchanged, or nobody could take it. What's left stays face down.
+    Done,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum KittyError {
+    NothingToPickUp,
+    NothingToDiscard,
+    /// Another player is due to take the kitty
+    NotKittyPlayer,
+    /// Exactly as many cards as were picked up have to go back
+    WrongDiscardCount(usize),
+    PlayerDoesntHaveCard(Card),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RoundError {
+    NoPlayers,
+    DuplicatePlayer(String),
+    UnknownNextPlayer(String),
+    UnknownLastPlayer(String),
+    /// The suit order doesn't list every suit exactly once
+    InvalidSuitOrder,
+    /// The rank order doesn't list every rank exactly once
+    InvalidRankOrder,
+    /// The seat order doesn't list every seated player exactly once
+    InvalidSeatOrder,
+    /// The same card from the same deck is held more than once
+    DuplicateCard(Card),
+}
+
+impl From<SetupError> for RoundError {
+    fn from(err: SetupError) -> RoundError {
+        match err {
+            SetupError::NoPlayers => RoundError::NoPlayers,
+            SetupError::DuplicatePlayer(id) => RoundError::DuplicatePlayer(id),
+            SetupError::InvalidSuitOrder => RoundError::InvalidSuitOrder,
+            SetupError::InvalidRankOrder => RoundError::InvalidRankOrder,
+            SetupError::InvalidSeatOrder => RoundError::InvalidSeatOrder,
+        }
+    }
+}
+
+/// A hand as it was played onto the table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct PlayedHand {
+    pub player_id: String,
+    pub hand: Hand,
+}
+
+/// How a cleared trick went: who won it, and how many times the lead was
+/// beaten before everyone else passed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct TrickRecord {
+    pub winner_id: String,
+    pub raises: usize,
 }
 
+/// Players and the card piles sit behind `Arc`s, so cloning a round is
+/// cheap and a move only copies the one player whose hand changed.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 pub struct Round {
-    players: Vec<Player>,
+    players: Vec<Arc<Player>>,
     next_player: Option<String>,
     last_move: Option<Hand>,
     last_player: Option<String>,
-    suit_order: [Suit; 4],
+    suit_order: Vec<Suit>,
     rank_order: [Rank; 13],
-    ruleset: Ruleset
+    #[cfg_attr(feature = "ts", ts(as = "super::RulesetFields"))]
+    ruleset: Ruleset,
+    #[serde(default)]
+    passed: Vec<String>,
+    #[serde(default)]
+    pass_count: usize,
+    #[serde(default)]
+    table: Vec<PlayedHand>,
+    #[serde(default)]
+    dealt: Arc<Vec<Card>>,
+    #[serde(default)]
+    discarded: Arc<Vec<Card>>,
+    #[serde(default)]
+    resigned: Vec<String>,
+    /// The orders are reversed from those the round started with
+    #[serde(default)]
+    reversed: bool,
+    /// The orders are reversed only until the current trick clears
+    #[serde(default)]
+    temporarily_reversed: bool,
+    /// A player down to one card who still has to call last card
+    #[serde(default)]
+    last_card_pending: Option<String>,
+    /// Every time a player missed calling last card
+    #[serde(default)]
+    missed_last_card: Vec<String>,
+    /// Who won each trick, in order
+    #[serde(default)]
+    trick_winners: Vec<String>,
+    /// How many times each trick in `trick_winners` was raised
+    #[serde(default)]
+    trick_raises: Vec<usize>,
+    /// Who played each bomb, in order
+    #[serde(default)]
+    bomb_players: Vec<String>,
+    /// Players who went out on a two or a joker
+    #[serde(default)]
+    illegal_finishers: Vec<String>,
+    /// Every time a player claimed the rest of the game and couldn't
+    /// have won it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    false_claims: Vec<String>,
+    /// Players who went out, in the order they did
+    #[serde(default)]
+    finished: Vec<String>,
+    /// Moves made so far, passes included
+    #[serde(default)]
+    move_count: usize,
+    /// Tricks cleared so far
+    #[serde(default)]
+    trick_count: usize,
+    /// The players still in agreed to end the game early
+    #[serde(default)]
+    conceded: bool,
+    /// Cards dealt face down for the winner of the first trick
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    kitty: Vec<Card>,
+    #[serde(default)]
+    kitty_stage: KittyStage,
+    /// The last `RECENT_MOVES_KEPT` moves, passes included, oldest first
+    #[serde(default)]
+    recent_moves: Vec<PlayedHand>,
+    /// Stored hands are re-sorted whenever the orders change or cards
+    /// are picked up
+    #[serde(default)]
+    keep_hands_sorted: bool,
+    /// Moves must say which copy of each card is played whenever the
+    /// player holds more than one
+    #[serde(default)]
+    strict_card_identity: bool,
+    /// Player ids in the order play passes when it isn't simply seat
+    /// order. Empty for seat order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    seat_order: Vec<String>,
+    /// Forks made for simulation skip history nobody will read
+    #[serde(skip)]
+    #[cfg_attr(feature = "ts", ts(skip))]
+    simulation: bool,
+    #[serde(skip)]
+    #[cfg_attr(feature = "ts", ts(skip))]
+    house_rules: MoveRules,
 }
 
 impl Round {
@@ -36,25 +283,100 @@ impl Round {
         next_player: Option<String>,
         last_move: Option<Hand>,
         last_player: Option<String>,
-        suit_order: [Suit; 4],
-        rank_order: [Rank; 13],
+        orders: OrderContext,
         ruleset: Ruleset
     ) -> Round {
+        let dealt = players.iter()
+            .flat_map(|p| p.get_hand())
+            .collect();
+
         Round {
-            players,
+            players: players.into_iter().map(Arc::new).collect(),
             next_player,
             last_move,
             last_player,
-            suit_order,
-            rank_order,
+            suit_order: orders.get_suit_order().to_vec(),
+            rank_order: *orders.get_rank_order(),
             ruleset,
+            passed: vec![],
+            pass_count: 0,
+            table: vec![],
+            dealt: Arc::new(dealt),
+            discarded: Arc::new(vec![]),
+            resigned: vec![],
+            reversed: orders.is_reversed(),
+            temporarily_reversed: false,
+            last_card_pending: None,
+            missed_last_card: vec![],
+            trick_winners: vec![],
+            trick_raises: vec![],
+            bomb_players: vec![],
+            illegal_finishers: vec![],
+            false_claims: vec![],
+            finished: vec![],
+            move_count: 0,
+            trick_count: 0,
+            conceded: false,
+            kitty: vec![],
+            kitty_stage: KittyStage::Closed,
+            recent_moves: vec![],
+            keep_hands_sorted: false,
+            strict_card_identity: false,
+            seat_order: vec![],
+            simulation: false,
+            house_rules: MoveRules::default(),
+        }
+    }
+
+    /// Like `new`, but checks the state makes sense first. Use this for
+    /// rounds rebuilt from data that can't be trusted, such as a client's.
+    /// Jokers are interchangeable, so only natural cards count as
+    /// duplicates.
+    pub fn try_new(
+        players: Vec<Player>,
+        next_player: Option<String>,
+        last_move: Option<Hand>,
+        last_player: Option<String>,
+        orders: OrderContext,
+        ruleset: Ruleset
+    ) -> Result<Round, RoundError> {
+        let player_ids: Vec<String> = players.iter()
+            .map(|p| p.get_id().to_string())
+            .collect();
+        check_player_ids(&player_ids)?;
+
+        if let Some(id) = next_player.as_ref().filter(|id| !player_ids.contains(id)) {
+            return Err(RoundError::UnknownNextPlayer(id.clone()));
+        }
+        if let Some(id) = last_player.as_ref().filter(|id| !player_ids.contains(id)) {
+            return Err(RoundError::UnknownLastPlayer(id.clone()));
+        }
+
+        check_orders(orders.get_suit_order(), *orders.get_rank_order())?;
+
+        let mut held = BTreeSet::new();
+        for card in players.iter().flat_map(|p| p.get_hand()) {
+            if card.get_rank().is_some() && !held.insert(card) {
+                return Err(RoundError::DuplicateCard(card));
+            }
         }
+
+        Ok(Round::new(
+            players,
+            next_player,
+            last_move,
+            last_player,
+            orders,
+            ruleset
+        ))
     }
 
     pub fn get_next_player(&self) -> Option<String> {
         match &self.next_player {
+            None if self.conceded => None,
             None => {
-                if self.get_players_still_in(&self.players).len() > 1 {
+                let opening = self.last_move.is_none();
+                if opening && self.get_players_still_in(&self.players).len() > 1 {
                     self.get_starting_player()
                 } else {
                     None
@@ -64,266 +386,1346 @@ impl Round {
         }
     }
 
+    /// Whether the game is yet to start, still going, over, or stuck in a
+    /// state it can never get out of.
+    pub fn get_status(&self) -> GameStatus {
+        match self.get_next_player() {
+            Some(id) if self.get_player(&id).is_none() => {
+                GameStatus::Dead(DeadGameReason::UnknownNextPlayer(id))
+            },
+            Some(_) if self.get_players_still_in(&self.players).is_empty() => {
+                GameStatus::Dead(DeadGameReason::NoCardsLeft)
+            },
+            Some(id) if self.get_player(&id).is_some_and(|p| p.get_card_count() == 0) => {
+                GameStatus::Dead(DeadGameReason::NextPlayerFinished(id))
+            },
+            Some(_) if self.move_count == 0
+                && self.last_move.is_none()
+                && self.resigned.is_empty() => GameStatus::NotStarted,
+            Some(_) => GameStatus::InProgress,
+            None if self.conceded || self.enough_players_out(&self.players) => GameStatus::Finished {
+                placements: self.get_placements(),
+            },
+            None => GameStatus::Dead(DeadGameReason::NoNextPlayer),
+        }
+    }
+
+    /// Whether the game has finished, rather than checking for a next
+    /// player, which a dead round lacks too.
+    pub fn is_game_over(&self) -> bool {
+        matches!(self.get_status(), GameStatus::Finished { .. })
+    }
+
+    /// Every player from first to last as things stand, as described for
+    /// `GameStatus::Finished`. Players out of a round rebuilt from data
+    /// have no recorded order and follow the rest who went out in seat
+    /// order.
+    pub fn get_placements(&self) -> Vec<String> {
+        let mut placements = self.finished.clone();
+        let mut holding = vec![];
+        for player in self.players.iter() {
+            let id = player.get_id().to_string();
+            if placements.contains(&id) || self.resigned.contains(&id) {
+                continue;
+            }
+            match player.get_card_count() {
+                0 => placements.push(id),
+                count => holding.push((count, id)),
+            }
+        }
+        holding.sort_by_key(|(count, _)| *count);
+
+        placements.extend(holding.into_iter().map(|(_, id)| id));
+        placements.extend(self.resigned.iter().rev().cloned());
+        placements
+    }
+
     pub fn submit_move(
         &self,
         user_id: &str,
         cards: Vec<PlayedCard>
     ) -> Result<Round, SubmitError> {
-        if user_id != self.get_next_player()
-            .expect("invalid_player") {
-            return Err(SubmitError::NotCurrentPlayer);
-        }
+        let mut round = self.clone();
+        round.submit_move_mut(user_id, cards)?;
+
+        Ok(round)
+    }
+
+    /// Applies a move in place, for callers holding a single authoritative
+    /// round. On error the round is left untouched.
+    pub fn submit_move_mut(
+        &mut self,
+        user_id: &str,
+        cards: Vec<PlayedCard>
+    ) -> Result<(), SubmitError> {
+        self.apply_move(user_id, cards, None)
+    }
+
+    /// As `submit_move`, with `copies` naming the exact card, deck id and
+    /// all, taken from the player's hand for each card in `cards`.
+    pub fn submit_exact_move(
+        &self,
+        user_id: &str,
+        cards: Vec<PlayedCard>,
+        copies: Vec<Card>,
+    ) -> Result<Round, SubmitError> {
+        let mut round = self.clone();
+        round.submit_exact_move_mut(user_id, cards, copies)?;
+
+        Ok(round)
+    }
 
-        let hand = Hand::build(cards.clone());
-        if hand.is_none() {
+    /// As `submit_move_mut`, taking exactly the cards in `copies` from
+    /// the player's hand. Each copy has to be the card played in the same
+    /// position in `cards`, or a joker where a joker is played.
+    pub fn submit_exact_move_mut(
+        &mut self,
+        user_id: &str,
+        cards: Vec<PlayedCard>,
+        copies: Vec<Card>,
+    ) -> Result<(), SubmitError> {
+        let matches = copies.len() == cards.len()
+            && cards.iter().zip(copies.iter()).all(|(card, copy)| match copy {
+                Card::Joker { .. } => card.get_is_joker(),
+                Card::Standard { rank, suit, .. } => !card.get_is_joker()
+                    && card.get_rank() == *rank
+                    && card.get_suit() == *suit,
+            });
+        if !matches {
             return Err(SubmitError::InvalidHand);
         }
 
-        if self.last_move == None {
+        self.apply_move(user_id, cards, Some(copies))
+    }
 
-            let starting_move_error = self.check_starting_move(
-                &cards
-            );
+    /// Whether plain moves are turned down when they leave open which
+    /// copy of a card the player meant.
+    pub fn set_strict_card_identity(&mut self, strict: bool) {
+        self.strict_card_identity = strict;
+    }
 
-            if starting_move_error.is_some() {
-                return Err(starting_move_error.unwrap());
-            }
+    pub fn has_strict_card_identity(&self) -> bool {
+        self.strict_card_identity
+    }
 
-        } else if self.last_move != Some(Hand::Pass)
-            && hand != Some(Hand::Pass) 
-            && !self.hand_beats_last_move(hand.unwrap()) {
-                return Err(SubmitError::HandNotHighEnough);
+    fn apply_move(
+        &mut self,
+        user_id: &str,
+        cards: Vec<PlayedCard>,
+        copies: Option<Vec<Card>>,
+    ) -> Result<(), SubmitError> {
+        let played = self.validate_move(user_id, &cards)?;
+        let hand = Some(played);
+
+        let index = self.players.iter()
+            .position(|p| p.get_id() == user_id)
+            .expect("invalid player!");
+        let held = self.players[index].get_hand();
+        match &copies {
+            Some(copies) if !holds_copies(&held, copies) => {
+                return Err(SubmitError::PlayerDoesntHaveCard);
+            },
+            None if self.strict_card_identity && is_ambiguous(&held, &cards) => {
+                return Err(SubmitError::AmbiguousCardCopy);
+            },
+            _ => {},
         }
 
-        let mut player = self.get_player(user_id)
-            .expect("invalid player!");
+        let remove = |player: &mut Player| match &copies {
+            Some(copies) => player.remove_cards(copies).is_ok(),
+            None => player.play_move(cards.clone()).is_ok(),
+        };
+        if !remove(&mut (*self.players[index]).clone()) {
+            return Err(SubmitError::PlayerDoesntHaveCard);
+        }
+
+        // every check is made above, so nothing below can fail and leave
+        // the round half changed
+        self.enforce_last_card();
+
+        let hand_before = self.players[index].get_hand();
+        let two_or_joker = holds_two_or_joker(&cards);
+
+        // a last card penalty only ever adds to a hand, so the cards are
+        // still there to remove
+        let removed = remove(Arc::make_mut(&mut self.players[index]));
+        debug_assert!(removed, "the cards were checked before the round changed");
 
-        match player.play_move(cards) {
-            Ok(p) => player = p,
-            _ => return Err(SubmitError::PlayerDoesntHaveCard)
+        Arc::make_mut(&mut self.discarded).extend(
+            remove_remaining(hand_before, &mut self.players[index].get_hand())
+        );
+
+        if let FinalCardRule::Penalty(_) = self.ruleset.final_card {
+            if two_or_joker && self.players[index].get_card_count() == 0 {
+                self.illegal_finishers.push(user_id.to_string());
+            }
         }
 
-        let players = self.get_updated_players(&player);
         let new_last_player = if hand == Some(Hand::Pass) {
             self.last_player.to_owned()
         } else {
             Some(user_id.to_string())
         };
 
-        let ( 
+        if hand == Some(Hand::Pass) {
+            self.passed.push(user_id.to_string());
+        }
+
+        let (
             new_last_move, next_player
-        ) = self.get_last_move_and_new_player(
-            user_id,
-            hand,
-            &new_last_player
-        );
+        ) = self.get_last_move_and_new_player_by_rule(user_id, hand, &new_last_player);
 
-        let output_next_player = if self.get_players_still_in(&players)
-            .len() > 1 {
-            Some(next_player)
-        } else {
+        if new_last_move == Some(Hand::Pass) {
+            self.passed.clear();
+        }
+
+        let table_cleared = new_last_move == Some(Hand::Pass)
+            && (self.last_move != Some(Hand::Pass) || hand != Some(Hand::Pass));
+        if let Some(winner) = new_last_player.as_ref().filter(|_| table_cleared) {
+            self.trick_winners.push(winner.clone());
+            if !self.simulation {
+                self.trick_raises.push(self.current_trick_length().saturating_sub(1));
+            }
+        }
+        if table_cleared {
+            self.trick_count += 1;
+            self.open_kitty(new_last_player.as_deref());
+        }
+        self.move_count += 1;
+        if hand.is_some_and(|hand| hand.is_bomb()) {
+            self.bomb_players.push(user_id.to_string());
+        }
+
+        if self.players[index].get_card_count() == 0 {
+            self.finished.push(user_id.to_string());
+        }
+
+        self.next_player = if self.enough_players_out(&self.players) {
             None
+        } else {
+            Some(next_player)
         };
 
         let (
             suit_order, rank_order
         ) = self.get_updated_suit_and_rank_order(hand);
 
-        Ok(Self::new(
-            players,
-            output_next_player,
-            new_last_move,
-            new_last_player,
-            suit_order,
-            rank_order,
-            self.ruleset
-        ))
-    }
+        if suit_order != self.suit_order {
+            self.reversed = !self.reversed;
+            if self.ruleset.reversal_duration == ReversalDuration::UntilTrickCleared {
+                self.temporarily_reversed = !self.temporarily_reversed;
+            }
+        }
 
-    pub fn get_player(&self, user_id: &str) -> Option<Player> {
-        for player in self.players.iter() {
-            if player.get_id() == user_id {
-                return Some(player.clone());
+        self.table = self.get_updated_table(user_id, hand);
+        if !self.simulation {
+            if self.recent_moves.len() == RECENT_MOVES_KEPT {
+                self.recent_moves.remove(0);
             }
+            self.recent_moves.push(PlayedHand {
+                player_id: user_id.to_string(),
+                hand: played,
+            });
+        }
+        self.pass_count = if hand == Some(Hand::Pass) {
+            self.pass_count + 1
+        } else {
+            0
+        };
+        self.last_move = new_last_move;
+        self.last_player = new_last_player;
+        self.suit_order = suit_order;
+        self.rank_order = rank_order;
+        self.restore_orders_if_cleared();
+
+        if self.ruleset.last_card != LastCardRule::Off
+            && self.players[index].get_card_count() == 1 {
+            self.last_card_pending = Some(user_id.to_string());
         }
+        if self.keep_hands_sorted {
+            self.sort_hands_mut();
+        }
+        self.assert_invariants();
 
-        None
+        Ok(())
     }
 
-    pub fn get_last_move(&self) -> Option<Hand> {
-        self.last_move
+    pub fn declare_last_card(&self, user_id: &str) -> Result<Round, LastCardError> {
+        let mut round = self.clone();
+        round.declare_last_card_mut(user_id)?;
+
+        Ok(round)
     }
 
-    pub fn get_last_player(&self) -> Option<String> {
-        match &self.last_player {
-            None => None,
-            Some(x) => Some(x.to_string())
+    /// Calls last card for a player who has just gone down to one card. It
+    /// has to be called before anyone makes the next move.
+    pub fn declare_last_card_mut(&mut self, user_id: &str) -> Result<(), LastCardError> {
+        if self.ruleset.last_card == LastCardRule::Off {
+            return Err(LastCardError::NotInPlay);
+        }
+        if self.last_card_pending.as_deref() != Some(user_id) {
+            return Err(LastCardError::NothingToDeclare);
         }
-    }
 
-    pub fn get_suit_order(&self) -> [Suit; 4] {
-        self.suit_order
+        self.last_card_pending = None;
+
+        Ok(())
     }
 
-    pub fn get_rank_order(&self) -> [Rank; 13] {
-        self.rank_order
+    /// The player who has to call last card before the next move, if any.
+    pub fn get_last_card_pending(&self) -> Option<String> {
+        self.last_card_pending.clone()
     }
 
-    fn check_starting_move(
-        &self,
-        cards:&[PlayedCard]) -> Option<SubmitError> {
-            if cards.is_empty() {
-                return Some(SubmitError::FirstRoundPass);
-            }
+    /// The winner of every trick cleared so far, in order.
+    pub fn get_trick_winners(&self) -> Vec<String> {
+        self.trick_winners.clone()
+    }
 
-            if !self.contains_lowest_card(cards.to_vec()) {
-                return Some(
-                    SubmitError::FirstHandMustContainLowestCard
-                );
-            }
+    /// Every trick cleared so far with how contested it was, in order.
+    /// Rounds saved before raises were counted have no history.
+    pub fn get_trick_history(&self) -> Vec<TrickRecord> {
+        if self.trick_raises.len() != self.trick_winners.len() {
+            return vec![];
+        }
 
-            None
+        self.trick_winners.iter()
+            .zip(self.trick_raises.iter())
+            .map(|(winner_id, &raises)| TrickRecord { winner_id: winner_id.clone(), raises })
+            .collect()
     }
 
-    fn get_starting_player(&self) -> Option<String> {
-        let lowest_card = Card::Standard {
-            deck_id: 0,
-            suit: self.suit_order[0],
-            rank: self.rank_order[0],
-        };
-        for player in self.players.iter() {
-            if player.has_card(lowest_card) {
-                return Some(player.get_id().to_string());
-            }
+    /// Hands played onto the trick in progress, the lead included and
+    /// passes left out. Zero once the table has cleared.
+    pub fn current_trick_length(&self) -> usize {
+        if self.last_move == Some(Hand::Pass) {
+            0
+        } else {
+            self.table.len()
         }
-        None
     }
 
-    fn get_updated_players(
-        &self,
-        player: &Player) -> Vec<Player> {
-        self.players.iter().map(|p| {
-            if p.get_id() == player.get_id() {
-                player.clone()
-            } else {
-                p.clone()
-            }
-        }).collect()
+    /// The player behind every bomb played so far, in order.
+    pub fn get_bomb_players(&self) -> Vec<String> {
+        self.bomb_players.clone()
     }
 
-    fn hand_beats_last_move(&self, cards: Hand) -> bool {
-        compare_hands(
-            self.last_move
-                .expect("cannot compare when no last_move"),
-            cards,
-            self.ruleset.flush_precedence,
-            self.suit_order,
-            self.rank_order,
-        )
+    /// Adds a rule every later move has to pass, after the ruleset's own.
+    pub fn add_move_rule(&mut self, rule: Box<dyn MoveRule>) {
+        self.house_rules.add(rule);
     }
 
-    fn contains_lowest_card(&self, cards: Vec<PlayedCard>) -> bool {
-        for &card in cards.iter() {
-            if card.get_rank() == self.rank_order[0] && card.get_suit() == self.suit_order[0] {
-                return true;
-            }
+    pub(crate) fn copy_move_rules(&mut self, from: &Round) {
+        self.house_rules = from.house_rules.clone();
+    }
+
+    /// A copy to play rollouts out on. Hands and the card piles stay
+    /// shared with this round until a move changes them, player metadata
+    /// and the recent move and raise history are left behind, and the
+    /// fork keeps no such history as it's played on. Everything that
+    /// decides what's legal, who moves next and how the game scores is
+    /// kept.
+    pub fn fork_for_simulation(&self) -> Round {
+        let players = self.players.iter()
+            .map(|player| if *player.get_metadata() == PlayerMetadata::default() {
+                Arc::clone(player)
+            } else {
+                Arc::new(Player::new(player.get_id().to_string(), player.get_hand()))
+            })
+            .collect();
+
+        Round {
+            players,
+            next_player: self.next_player.clone(),
+            last_move: self.last_move,
+            last_player: self.last_player.clone(),
+            suit_order: self.suit_order.clone(),
+            rank_order: self.rank_order,
+            ruleset: self.ruleset,
+            passed: self.passed.clone(),
+            pass_count: self.pass_count,
+            table: self.table.clone(),
+            dealt: Arc::clone(&self.dealt),
+            discarded: Arc::clone(&self.discarded),
+            resigned: self.resigned.clone(),
+            reversed: self.reversed,
+            temporarily_reversed: self.temporarily_reversed,
+            last_card_pending: self.last_card_pending.clone(),
+            missed_last_card: self.missed_last_card.clone(),
+            trick_winners: self.trick_winners.clone(),
+            trick_raises: vec![],
+            bomb_players: self.bomb_players.clone(),
+            illegal_finishers: self.illegal_finishers.clone(),
+            false_claims: self.false_claims.clone(),
+            finished: self.finished.clone(),
+            move_count: self.move_count,
+            trick_count: self.trick_count,
+            conceded: self.conceded,
+            kitty: self.kitty.clone(),
+            kitty_stage: self.kitty_stage.clone(),
+            recent_moves: vec![],
+            keep_hands_sorted: false,
+            strict_card_identity: self.strict_card_identity,
+            seat_order: self.seat_order.clone(),
+            simulation: true,
+            house_rules: self.house_rules.clone(),
         }
+    }
 
-        false
+    pub fn is_simulation(&self) -> bool {
+        self.simulation
     }
 
-    fn get_next_player_in_rotation(&self, user_id: &str) -> String {
-        if self.players.last()
-            .unwrap().get_id() == user_id {
-            return self.players.first()
-                .unwrap().get_id().to_string();
-        }
-        let mut index = 0;
-        for (i, player) in self.players.iter().enumerate() {
-            if player.get_id() == user_id {
-                index = i + 1;
+    /// Carries the seat order and card identity checks over to a round
+    /// dealt to the same players.
+    pub(crate) fn copy_table_settings(&mut self, from: &Round) {
+        self.seat_order = from.seat_order.clone();
+        self.strict_card_identity = from.strict_card_identity;
+    }
+
+    /// Whether the game was ended early by a concede vote. Players still
+    /// holding cards keep them and are scored on them.
+    pub fn is_conceded(&self) -> bool {
+        self.conceded
+    }
+
+    pub(crate) fn concede_mut(&mut self) {
+        self.conceded = true;
+        self.next_player = None;
+        self.last_card_pending = None;
+    }
+
+    /// Players who went out on a two or a joker under a penalty final card
+    /// rule.
+    pub fn get_illegal_finishers(&self) -> Vec<String> {
+        self.illegal_finishers.clone()
+    }
+
+    /// Players who made a false claim under a points penalty, once for
+    /// every claim.
+    pub fn get_false_claims(&self) -> Vec<String> {
+        self.false_claims.clone()
+    }
+
+    pub(crate) fn record_false_claim(&mut self, player_id: &str) {
+        self.false_claims.push(player_id.to_string());
+    }
+
+    /// Players who missed calling last card, once for every miss.
+    pub fn get_missed_last_card(&self) -> Vec<String> {
+        self.missed_last_card.clone()
+    }
+
+    /// Penalises a player who didn't call last card in time. A pick up
+    /// penalty takes the most recently played cards back off the discard
+    /// pile.
+    fn enforce_last_card(&mut self) {
+        let player_id = match self.last_card_pending.take() {
+            Some(player_id) => player_id,
+            None => return,
+        };
+
+        if let LastCardRule::PickUp(count) = self.ruleset.last_card {
+            let discarded = Arc::make_mut(&mut self.discarded);
+            let picked_up = discarded.split_off(discarded.len().saturating_sub(count));
+            if let Some(player) = self.players.iter_mut().find(|p| p.get_id() == player_id) {
+                Arc::make_mut(player).pick_up(picked_up);
             }
         }
+        self.missed_last_card.push(player_id);
+    }
 
-        self.players[index].get_id().to_string()
+    pub fn resign(&self, user_id: &str) -> Result<Round, ResignError> {
+        let mut round = self.clone();
+        round.resign_mut(user_id)?;
+
+        Ok(round)
     }
 
-    fn get_players_still_in(&self, players: &[Player]) -> Vec<Player> {
-        players.iter()
-            .filter(|p| !p.get_hand().is_empty())
-            .cloned()
-            .collect()
+    /// Takes a player out of the game. Their cards go to the discard pile
+    /// and, if it was their turn, play moves on as though they had passed.
+    pub fn resign_mut(&mut self, user_id: &str) -> Result<(), ResignError> {
+        self.resign_player(user_id)?;
+        self.assert_invariants();
+
+        Ok(())
     }
 
-    fn get_last_move_and_new_player(&self,
-            user_id: &str,
-            hand: Option<Hand>,
-            new_last_player: &Option<String>
-    ) -> (Option<Hand>, String) {
+    fn resign_player(&mut self, user_id: &str) -> Result<(), ResignError> {
+        let index = self.players.iter()
+            .position(|p| p.get_id() == user_id)
+            .ok_or(ResignError::UnknownPlayer)?;
 
-        let mut new_last_move = hand;
-        let mut next_player = self.get_next_player_in_rotation(
-            user_id
-        );
+        if self.get_next_player().is_none() {
+            return Err(ResignError::GameOver);
+        }
+        if self.players[index].get_card_count() == 0 {
+            return Err(ResignError::AlreadyOut);
+        }
 
-        if hand == Some(Hand::Pass) {
-            new_last_move = self.last_move;
+        let was_their_turn = self.get_next_player().as_deref() == Some(user_id);
+
+        let hand = Arc::make_mut(&mut self.players[index]).discard_hand();
+        Arc::make_mut(&mut self.discarded).extend(hand);
+        self.resigned.push(user_id.to_string());
+        if self.last_card_pending.as_deref() == Some(user_id) {
+            self.last_card_pending = None;
         }
 
-        if next_player == new_last_player.clone()
-            .unwrap_or_else(|| "invalid_player".to_string()) {
-            new_last_move = Some(Hand::Pass);
+        if self.enough_players_out(&self.players) {
+            self.next_player = None;
+            return Ok(());
         }
 
-        while self.get_player(&next_player)
-            .unwrap().get_hand().is_empty() {
+        if !was_their_turn {
+            return Ok(());
+        }
 
-            next_player = self.get_next_player_in_rotation(&next_player);
-            if next_player == new_last_player.clone()
-                .unwrap_or_else(|| "invalid_player".to_string()) {
-                new_last_move = Some(Hand::Pass);
-            }
+        let last_player = self.last_player.clone();
+        let (last_move, next_player) = self.get_last_move_and_new_player_by_rule(
+            user_id,
+            Some(Hand::Pass),
+            &last_player
+        );
+
+        // Nobody else holds the lowest card, so the next player leads
+        // whatever they like.
+        self.last_move = last_move.or(Some(Hand::Pass));
+        if self.last_move == Some(Hand::Pass) {
+            self.passed.clear();
         }
+        self.next_player = Some(next_player);
+        self.restore_orders_if_cleared();
 
-        (new_last_move, next_player)
+        Ok(())
     }
 
-    fn get_updated_suit_and_rank_order(
-        &self,
-        hand:Option<Hand>
-    ) -> ([Suit;4], [Rank;13]) {
-        let mut suit_order = self.suit_order;
-        let mut rank_order = self.rank_order;
+    /// Whether the suit and rank orders are reversed from those the round
+    /// started with, so clients needn't compare the orders themselves.
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    pub(crate) fn set_reversed(&mut self, reversed: bool) {
+        self.reversed = reversed;
+    }
+
+    /// Whether the orders will flip back when the current trick clears.
+    pub fn is_temporarily_reversed(&self) -> bool {
+        self.temporarily_reversed
+    }
+
+    pub(crate) fn set_temporarily_reversed(&mut self, reversed: bool) {
+        self.temporarily_reversed = reversed;
+    }
 
-        if self.ruleset.reversals_enabled {
-            if let Hand::FiveCardTrick(Trick{
-                    trick_type: TrickType::FourOfAKind,
-                    ..
-                }) = hand.unwrap_or(Hand::Pass) {
-                suit_order.reverse();
-                rank_order.reverse();
-            } 
+    fn restore_orders_if_cleared(&mut self) {
+        if self.temporarily_reversed && self.last_move == Some(Hand::Pass) {
+            self.suit_order.reverse();
+            self.rank_order.reverse();
+            self.reversed = !self.reversed;
+            self.temporarily_reversed = false;
         }
+    }
 
-        (suit_order, rank_order)
+    /// Players who resigned, in the order they did so.
+    pub fn get_resigned_players(&self) -> Vec<String> {
+        self.resigned.clone()
     }
 
-}
+    pub(crate) fn set_resigned_players(&mut self, resigned: Vec<String>) {
+        self.resigned = resigned;
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::cards::*;
-    use crate::game::FlushPrecedence;
+    /// The hand `cards` make under this round's ruleset and the rank
+    /// order in play, built just as `validate_move` builds it, or `None`
+    /// if they don't make one. `Hand::build` only knows the standard order,
+    /// so use this for any move made in a round.
+    pub fn build_hand(&self, cards: &[PlayedCard]) -> Option<Hand> {
+        Hand::try_build_in_order(cards.to_vec(), self.ruleset, &self.get_order_context()).ok()
+    }
 
-    static DEFAULT_SUIT_ORDER: [Suit; 4] =
-        [Suit::Clubs, Suit::Hearts, Suit::Diamonds, Suit::Spades];
+    /// Checks whether `user_id` could submit `cards` right now without
+    /// applying the move, returning the hand the cards would form.
+    pub fn validate_move(
+        &self,
+        user_id: &str,
+        cards: &[PlayedCard]
+    ) -> Result<Hand, SubmitError> {
+        if let GameStatus::Dead(_) = self.get_status() {
+            return Err(SubmitError::DeadGame);
+        }
 
-    static DEFAULT_RANK_ORDER: [Rank; 13] = [
-        Rank::Three,
-        Rank::Four,
-        Rank::Five,
-        Rank::Six,
+        if self.get_player(user_id).is_some_and(|p| p.get_card_count() == 0) {
+            return Err(SubmitError::PlayerAlreadyFinished);
+        }
+
+        if Some(user_id.to_string()) != self.get_next_player() {
+            return Err(SubmitError::NotCurrentPlayer);
+        }
+
+        if matches!(self.kitty_stage, KittyStage::PickUp(_) | KittyStage::Discard { .. }) {
+            return Err(SubmitError::KittyPending);
+        }
+
+        let orders = self.get_order_context();
+        // a joker can only stand in for a suit that's in play
+        if cards.iter().any(|card| orders.suit_index(card.get_suit()).is_none()) {
+            return Err(SubmitError::InvalidHand);
+        }
+
+        let hand = match Hand::try_build_in_order(cards.to_vec(), self.ruleset, &orders) {
+            Ok(hand) => hand,
+            // a player left with nothing but jokers may play them as any
+            // hand, or they'd never get out
+            Err(HandError::JokerOutsideFiveCardTrick) if self.holds_only_jokers(user_id) => {
+                let ruleset = Ruleset { joker_usage: JokerUsage::Anywhere, ..self.ruleset };
+                Hand::try_build_in_order(cards.to_vec(), ruleset, &orders)
+                    .map_err(|_| SubmitError::InvalidHand)?
+            },
+            Err(HandError::JokerOutsideFiveCardTrick) => {
+                return Err(SubmitError::JokerOutsideFiveCardTrick)
+            },
+            Err(_) => return Err(SubmitError::InvalidHand),
+        };
+
+        let mut player = self.get_player(user_id)
+            .ok_or(SubmitError::NotCurrentPlayer)?;
+
+        if player.play_move(cards.to_vec()).is_err() {
+            return Err(SubmitError::PlayerDoesntHaveCard);
+        }
+
+        let context = MoveContext {
+            round: self,
+            player_id: user_id,
+            cards,
+            hand,
+            player_after: &player,
+        };
+        for rule in self.ruleset.get_move_rules().iter() {
+            rule.check(&context)?;
+        }
+        for rule in self.house_rules.iter() {
+            rule.check(&context)?;
+        }
+
+        Ok(hand)
+    }
+
+    fn holds_only_jokers(&self, user_id: &str) -> bool {
+        self.get_player(user_id)
+            .is_some_and(|p| p.get_hand().iter().all(|c| c.get_rank().is_none()))
+    }
+
+    pub fn get_player(&self, user_id: &str) -> Option<Player> {
+        for player in self.players.iter() {
+            if player.get_id() == user_id {
+                return Some(player.as_ref().clone());
+            }
+        }
+
+        None
+    }
+
+    /// Every player in seating order.
+    pub fn get_players(&self) -> Vec<Player> {
+        self.players.iter()
+            .map(|p| p.as_ref().clone())
+            .collect()
+    }
+
+    /// Each player's id and how many cards they hold, in seating order,
+    /// without exposing the cards themselves.
+    pub fn get_card_counts(&self) -> Vec<(String, usize)> {
+        self.players.iter()
+            .map(|p| (p.get_id().to_string(), p.get_card_count()))
+            .collect()
+    }
+
+    pub fn get_last_move(&self) -> Option<Hand> {
+        self.last_move
+    }
+
+    pub fn get_last_player(&self) -> Option<String> {
+        self.last_player.clone()
+    }
+
+    pub fn get_suit_order(&self) -> Vec<Suit> {
+        self.suit_order.clone()
+    }
+
+    pub fn get_rank_order(&self) -> [Rank; 13] {
+        self.rank_order
+    }
+
+    pub fn get_ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    /// The hands played in the current trick, oldest first. Once the table
+    /// clears this still holds the finished trick until the next lead.
+    pub fn get_table(&self) -> Vec<PlayedHand> {
+        self.table.clone()
+    }
+
+    /// The last `n` moves, oldest first, passes included, for showing
+    /// recent plays. Only the last `RECENT_MOVES_KEPT` are remembered, so
+    /// asking for more returns those.
+    pub fn last_n_moves(&self, n: usize) -> Vec<PlayedHand> {
+        let start = self.recent_moves.len().saturating_sub(n);
+        self.recent_moves[start..].to_vec()
+    }
+
+    pub(crate) fn set_table(&mut self, table: Vec<PlayedHand>) {
+        self.table = table;
+    }
+
+    /// Swaps a player's hand for another, such as one sampled from the
+    /// cards they could be holding.
+    pub(crate) fn set_hand(&mut self, user_id: &str, hand: Vec<Card>) {
+        if let Some(player) = self.players.iter_mut().find(|p| p.get_id() == user_id) {
+            let metadata = player.get_metadata().clone();
+            *player = Arc::new(
+                Player::new(user_id.to_string(), hand).with_metadata(metadata)
+            );
+        }
+    }
+
+    /// Re-sorts every stored hand lowest first under the round's current
+    /// orders, the way hands are dealt.
+    pub fn sort_hands_mut(&mut self) {
+        let orders = self.get_order_context();
+        for player in self.players.iter_mut() {
+            if !player.is_sorted(&orders) {
+                Arc::make_mut(player).sort_hand(&orders);
+            }
+        }
+    }
+
+    pub fn sort_hands(&self) -> Round {
+        let mut round = self.clone();
+        round.sort_hands_mut();
+        round
+    }
+
+    /// Keeps every stored hand sorted from now on, re-sorting after a
+    /// reversal or a pick up, so clients and bots can rely on the order.
+    pub fn set_keep_hands_sorted(&mut self, keep_sorted: bool) {
+        self.keep_hands_sorted = keep_sorted;
+        if keep_sorted {
+            self.sort_hands_mut();
+        }
+    }
+
+    pub fn keeps_hands_sorted(&self) -> bool {
+        self.keep_hands_sorted
+    }
+
+    /// Passes play round the players in `player_ids` order, rather than
+    /// seat order, before the ruleset's turn order is applied. Every
+    /// seated player has to be listed exactly once; an empty list goes
+    /// back to seat order.
+    pub fn set_seat_order(&mut self, player_ids: Vec<String>) -> Result<(), SetupError> {
+        if !player_ids.is_empty() {
+            check_player_ids(&player_ids)?;
+            let seated = player_ids.len() == self.players.len()
+                && self.players.iter().all(|p| player_ids.iter().any(|id| id == p.get_id()));
+            if !seated {
+                return Err(SetupError::InvalidSeatOrder);
+            }
+        }
+
+        self.seat_order = player_ids;
+        Ok(())
+    }
+
+    /// Every player id in the order play passes, starting from the first
+    /// seat in the custom seat order or, without one, from seat 0.
+    pub fn get_turn_order(&self) -> Vec<String> {
+        let mut order: Vec<String> = if self.seat_order.is_empty() {
+            self.players.iter().map(|p| p.get_id().to_string()).collect()
+        } else {
+            self.seat_order.clone()
+        };
+        if self.ruleset.turn_order == TurnOrder::CounterClockwise && !order.is_empty() {
+            order[1..].reverse();
+        }
+
+        order
+    }
+
+    /// Takes `cards` out of a player's hand and out of play before the
+    /// first move. The hand is left alone if they don't hold them all.
+    pub(crate) fn set_aside_mut(&mut self, user_id: &str, cards: &[Card]) -> Result<(), Card> {
+        let index = match self.players.iter().position(|p| p.get_id() == user_id) {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+        Arc::make_mut(&mut self.players[index]).remove_cards(cards)?;
+        let dealt = Arc::make_mut(&mut self.dealt);
+        for card in cards.iter() {
+            if let Some(position) = dealt.iter().position(|c| c == card) {
+                dealt.remove(position);
+            }
+        }
+        self.assert_invariants();
+
+        Ok(())
+    }
+
+    /// The lowest natural card anyone still holds, which has to open the
+    /// round. This is only the first card of the orders when that card
+    /// was dealt, so stripped decks open on whatever is lowest instead.
+    pub fn get_lowest_card(&self) -> Option<PlayedCard> {
+        let orders = self.get_order_context();
+        let index = |card: &Card| (
+            card.get_rank().and_then(|rank| orders.rank_index(rank)),
+            card.get_suit().and_then(|suit| orders.suit_index(suit)),
+        );
+
+        self.players.iter()
+            .flat_map(|p| p.get_hand())
+            .filter(|c| c.get_rank().is_some())
+            .min_by_key(index)
+            .and_then(|card| match card {
+                Card::Standard { rank, suit, .. } => {
+                    Some(PlayedCard::new(rank, suit, false))
+                },
+                _ => None,
+            })
+    }
+
+    /// Every card dealt into the round, whether still held or played.
+    pub fn get_dealt_cards(&self) -> Vec<Card> {
+        self.dealt.to_vec()
+    }
+
+    /// Cards that have been played out of players' hands.
+    pub fn get_discarded(&self) -> Vec<Card> {
+        self.discarded.to_vec()
+    }
+
+    /// Adds cards that were played before the round was built, so they
+    /// count as dealt and discarded.
+    pub(crate) fn set_discarded(&mut self, discarded: Vec<Card>) {
+        Arc::make_mut(&mut self.dealt).extend(discarded.iter().cloned());
+        self.discarded = Arc::new(discarded);
+    }
+
+    /// The face down kitty. Clients shouldn't show these cards.
+    pub fn get_kitty(&self) -> Vec<Card> {
+        self.kitty.clone()
+    }
+
+    pub fn get_kitty_stage(&self) -> KittyStage {
+        self.kitty_stage.clone()
+    }
+
+    pub(crate) fn set_kitty(&mut self, kitty: Vec<Card>) {
+        self.kitty = kitty;
+    }
+
+    pub fn pick_up_kitty(&self, user_id: &str) -> Result<Round, KittyError> {
+        let mut round = self.clone();
+        round.pick_up_kitty_mut(user_id)?;
+
+        Ok(round)
+    }
+
+    /// Moves the kitty into the hand of the player who won the first
+    /// trick. They then have to discard as many cards.
+    pub fn pick_up_kitty_mut(&mut self, user_id: &str) -> Result<(), KittyError> {
+        match &self.kitty_stage {
+            KittyStage::PickUp(id) if id == user_id => {},
+            KittyStage::PickUp(_) => return Err(KittyError::NotKittyPlayer),
+            _ => return Err(KittyError::NothingToPickUp),
+        }
+        let index = self.players.iter()
+            .position(|p| p.get_id() == user_id)
+            .ok_or(KittyError::NotKittyPlayer)?;
+
+        let kitty = core::mem::take(&mut self.kitty);
+        self.kitty_stage = KittyStage::Discard {
+            player_id: user_id.to_string(),
+            count: kitty.len(),
+        };
+        Arc::make_mut(&mut self.dealt).extend(kitty.iter().cloned());
+        Arc::make_mut(&mut self.players[index]).pick_up(kitty);
+        if self.keep_hands_sorted {
+            self.sort_hands_mut();
+        }
+        self.assert_invariants();
+
+        Ok(())
+    }
+
+    pub fn discard_to_kitty(
+        &self,
+        user_id: &str,
+        cards: Vec<Card>,
+    ) -> Result<Round, KittyError> {
+        let mut round = self.clone();
+        round.discard_to_kitty_mut(user_id, cards)?;
+
+        Ok(round)
+    }
+
+    /// Puts `cards` face down as the new kitty, out of play for the rest
+    /// of the game. Play carries on once they're down.
+    pub fn discard_to_kitty_mut(
+        &mut self,
+        user_id: &str,
+        cards: Vec<Card>,
+    ) -> Result<(), KittyError> {
+        let count = match &self.kitty_stage {
+            KittyStage::Discard { player_id, count } if player_id == user_id => *count,
+            KittyStage::Discard { .. } => return Err(KittyError::NotKittyPlayer),
+            _ => return Err(KittyError::NothingToDiscard),
+        };
+        if cards.len() != count {
+            return Err(KittyError::WrongDiscardCount(count));
+        }
+        let index = self.players.iter()
+            .position(|p| p.get_id() == user_id)
+            .ok_or(KittyError::NotKittyPlayer)?;
+
+        Arc::make_mut(&mut self.players[index]).remove_cards(&cards)
+            .map_err(KittyError::PlayerDoesntHaveCard)?;
+        let dealt = Arc::make_mut(&mut self.dealt);
+        for card in cards.iter() {
+            if let Some(position) = dealt.iter().position(|c| c == card) {
+                dealt.remove(position);
+            }
+        }
+        self.kitty = cards;
+        self.kitty_stage = KittyStage::Done;
+        self.assert_invariants();
+
+        Ok(())
+    }
+
+    /// Offers the kitty to whoever won the first trick, if they're still
+    /// in to take it.
+    fn open_kitty(&mut self, winner: Option<&str>) {
+        if self.kitty_stage != KittyStage::Closed || self.kitty.is_empty() {
+            return;
+        }
+
+        let holds_cards = winner
+            .and_then(|id| self.get_player(id))
+            .is_some_and(|p| p.get_card_count() > 0);
+        self.kitty_stage = match winner {
+            Some(id) if holds_cards => KittyStage::PickUp(id.to_string()),
+            _ => KittyStage::Done,
+        };
+    }
+
+    /// Number of passes in a row since the last hand that wasn't a pass.
+    pub fn get_pass_count(&self) -> usize {
+        self.pass_count
+    }
+
+    /// Players who have passed since the current trick was led.
+    pub fn get_passed_players(&self) -> Vec<String> {
+        self.passed.clone()
+    }
+
+    pub(crate) fn set_passed_players(&mut self, passed: Vec<String>) {
+        self.passed = passed;
+    }
+
+    pub(crate) fn set_pass_count(&mut self, pass_count: usize) {
+        self.pass_count = pass_count;
+    }
+
+    /// How many moves have been made, passes included. Moves are numbered
+    /// from 0 in the order they're made, so this is also the number the
+    /// next move will get.
+    pub fn get_move_count(&self) -> usize {
+        self.move_count
+    }
+
+    /// Which trick is being played, starting at 0 for the opening trick.
+    pub fn get_trick_index(&self) -> usize {
+        self.trick_count
+    }
+
+    pub(crate) fn set_move_count(&mut self, move_count: usize) {
+        self.move_count = move_count;
+    }
+
+    pub(crate) fn set_trick_index(&mut self, trick_index: usize) {
+        self.trick_count = trick_index;
+    }
+
+    pub fn get_player_ids(&self) -> Vec<String> {
+        self.players.iter()
+            .map(|p| p.get_id().to_string())
+            .collect()
+    }
+
+    /// The team `user_id` plays for. Teams alternate seats in partnership
+    /// games; otherwise every player is their own team.
+    pub fn get_team(&self, user_id: &str) -> Option<usize> {
+        self.players.iter()
+            .position(|p| p.get_id() == user_id)
+            .map(|seat| self.get_team_for_seat(seat))
+    }
+
+    /// Player ids grouped by team, in team order.
+    pub fn get_teams(&self) -> Vec<Vec<String>> {
+        let mut teams: Vec<Vec<String>> = vec![];
+        for (seat, player) in self.players.iter().enumerate() {
+            let team = self.get_team_for_seat(seat);
+            if teams.len() <= team {
+                teams.resize(team + 1, vec![]);
+            }
+            teams[team].push(player.get_id().to_string());
+        }
+
+        teams
+    }
+
+    fn get_team_for_seat(&self, seat: usize) -> usize {
+        if self.ruleset.partnership {
+            seat % 2
+        } else {
+            seat
+        }
+    }
+
+    /// Whether enough players are out to end the game.
+    fn enough_players_out(&self, players: &[Arc<Player>]) -> bool {
+        if !self.ruleset.partnership {
+            return self.get_players_still_in(players).len() <= 1;
+        }
+
+        (0..2).any(|team| {
+            players.iter()
+                .enumerate()
+                .filter(|(seat, _)| self.get_team_for_seat(*seat) == team)
+                .all(|(_, p)| p.get_hand().is_empty())
+        })
+    }
+
+    pub(crate) fn check_starting_move(
+        &self,
+        cards:&[PlayedCard]) -> Option<SubmitError> {
+            if cards.is_empty() {
+                return Some(SubmitError::FirstRoundPass);
+            }
+
+            if self.ruleset.opening_requires_lowest_card
+                && !self.contains_lowest_card(cards.to_vec()) {
+                return Some(
+                    SubmitError::FirstHandMustContainLowestCard
+                );
+            }
+
+            None
+    }
+
+    fn get_starting_player(&self) -> Option<String> {
+        let lowest_card = self.get_lowest_card()?;
+        let is_lowest = |card: &Card| {
+            card.get_rank() == Some(lowest_card.get_rank())
+                && card.get_suit() == Some(lowest_card.get_suit())
+        };
+
+        self.players.iter()
+            .find(|p| p.get_hand().iter().any(is_lowest))
+            .map(|p| p.get_id().to_string())
+    }
+
+    fn contains_lowest_card(&self, cards: Vec<PlayedCard>) -> bool {
+        let lowest_card = match self.get_lowest_card() {
+            Some(card) => card,
+            None => return false,
+        };
+
+        cards.iter().any(|card| {
+            card.get_rank() == lowest_card.get_rank()
+                && card.get_suit() == lowest_card.get_suit()
+        })
+    }
+
+    fn get_next_player_in_rotation(&self, user_id: &str) -> String {
+        let order = self.get_turn_order();
+        let index = order.iter()
+            .position(|id| id == user_id)
+            .map(|i| (i + 1) % order.len())
+            .unwrap_or(0);
+
+        order.get(index).cloned().unwrap_or_else(|| user_id.to_string())
+    }
+
+    fn get_players_still_in(&self, players: &[Arc<Player>]) -> Vec<Arc<Player>> {
+        players.iter()
+            .filter(|p| !p.get_hand().is_empty())
+            .cloned()
+            .collect()
+    }
+
+    /// The last move and next player after `user_id` plays `hand`, with
+    /// the table cleared when the ruleset's table clear mode says so.
+    fn get_last_move_and_new_player_by_rule(
+        &self,
+        user_id: &str,
+        hand: Option<Hand>,
+        new_last_player: &Option<String>
+    ) -> (Option<Hand>, String) {
+        match self.ruleset.table_clear {
+            TableClearMode::ReturnToLastPlayer => {
+                self.get_last_move_and_new_player(user_id, hand, new_last_player)
+            },
+            TableClearMode::AllPassed => {
+                self.get_last_move_and_new_player_after_passes(
+                    user_id,
+                    hand,
+                    new_last_player,
+                    &self.players,
+                    &self.passed
+                )
+            },
+            TableClearMode::AfterPasses(passes) => {
+                self.get_last_move_and_new_player_after_pass_streak(
+                    user_id,
+                    hand,
+                    new_last_player,
+                    passes
+                )
+            },
+        }
+    }
+
+    fn get_last_move_and_new_player(&self,
+            user_id: &str,
+            hand: Option<Hand>,
+            new_last_player: &Option<String>
+    ) -> (Option<Hand>, String) {
+
+        let mut new_last_move = hand;
+        let mut next_player = self.get_next_player_in_rotation(
+            user_id
+        );
+
+        if hand == Some(Hand::Pass) {
+            new_last_move = self.last_move;
+        }
+
+        if next_player == new_last_player.clone()
+            .unwrap_or_else(|| "invalid_player".to_string()) {
+            new_last_move = Some(Hand::Pass);
+        }
+
+        // dead rounds are turned away before they get here, but the loop
+        // stays bounded in case no one has cards
+        for _ in 0..self.players.len() {
+            let has_cards = self.get_player(&next_player)
+                .is_some_and(|p| !p.get_hand().is_empty());
+            if has_cards {
+                break;
+            }
+
+            next_player = self.get_next_player_in_rotation(&next_player);
+            if next_player == new_last_player.clone()
+                .unwrap_or_else(|| "invalid_player".to_string()) {
+                new_last_move = Some(Hand::Pass);
+            }
+        }
+
+        (new_last_move, next_player)
+    }
+
+    fn get_last_move_and_new_player_after_passes(&self,
+            user_id: &str,
+            hand: Option<Hand>,
+            new_last_player: &Option<String>,
+            players: &[Arc<Player>],
+            passed: &[String]
+    ) -> (Option<Hand>, String) {
+        let new_last_move = if hand == Some(Hand::Pass) {
+            self.last_move
+        } else {
+            hand
+        };
+        let last_player = new_last_player.clone().unwrap_or_default();
+
+        let all_passed = players.iter()
+            .filter(|p| !p.get_hand().is_empty())
+            .filter(|p| p.get_id() != last_player)
+            .all(|p| passed.iter().any(|id| id == p.get_id()));
+
+        if !all_passed {
+            return (
+                new_last_move,
+                self.get_next_active_player(user_id, players)
+            );
+        }
+
+        let last_player_is_in = players.iter()
+            .any(|p| p.get_id() == last_player && !p.get_hand().is_empty());
+        let next_player = if last_player_is_in {
+            last_player
+        } else {
+            self.get_next_active_player(&last_player, players)
+        };
+
+        (Some(Hand::Pass), next_player)
+    }
+
+    fn get_last_move_and_new_player_after_pass_streak(&self,
+            user_id: &str,
+            hand: Option<Hand>,
+            new_last_player: &Option<String>,
+            passes: usize
+    ) -> (Option<Hand>, String) {
+        if hand != Some(Hand::Pass) {
+            return (hand, self.get_next_active_player(user_id, &self.players));
+        }
+        if self.pass_count + 1 < passes.max(1) {
+            return (self.last_move, self.get_next_active_player(user_id, &self.players));
+        }
+
+        let last_player = new_last_player.clone().unwrap_or_default();
+        let last_player_is_in = self.players.iter()
+            .any(|p| p.get_id() == last_player && !p.get_hand().is_empty());
+        let next_player = if last_player_is_in {
+            last_player
+        } else {
+            self.get_next_active_player(&last_player, &self.players)
+        };
+
+        (Some(Hand::Pass), next_player)
+    }
+
+    fn get_next_active_player(&self, user_id: &str, players: &[Arc<Player>]) -> String {
+        let mut next_player = self.get_next_player_in_rotation(user_id);
+        for _ in 0..players.len() {
+            let has_cards = players.iter()
+                .any(|p| p.get_id() == next_player && !p.get_hand().is_empty());
+            if has_cards {
+                break;
+            }
+            next_player = self.get_next_player_in_rotation(&next_player);
+        }
+
+        next_player
+    }
+
+    fn get_updated_table(
+        &self,
+        user_id: &str,
+        hand: Option<Hand>
+    ) -> Vec<PlayedHand> {
+        let hand = match hand {
+            None | Some(Hand::Pass) => return self.table.clone(),
+            Some(hand) => hand,
+        };
+
+        let mut table = if self.last_move == Some(Hand::Pass) {
+            vec![]
+        } else {
+            self.table.clone()
+        };
+        table.push(PlayedHand { player_id: user_id.to_string(), hand });
+
+        table
+    }
+
+    fn get_updated_suit_and_rank_order(
+        &self,
+        hand:Option<Hand>
+    ) -> (Vec<Suit>, [Rank;13]) {
+        let mut suit_order = self.suit_order.clone();
+        let mut rank_order = self.rank_order;
+
+        if self.ruleset.reversals_enabled
+            && self.ruleset.reversal_triggers
+                .is_triggered_by(hand.unwrap_or(Hand::Pass)) {
+            suit_order.reverse();
+            rank_order.reverse();
+        }
+
+        (suit_order, rank_order)
+    }
+
+}
+
+/// Whether `hand` holds every card in `copies`, counting repeats.
+fn holds_copies(hand: &[Card], copies: &[Card]) -> bool {
+    let mut hand = hand.to_vec();
+    copies.iter().all(|copy| match hand.iter().position(|c| c == copy) {
+        Some(index) => {
+            hand.remove(index);
+            true
+        },
+        None => false,
+    })
+}
+
+/// Whether playing `cards` from `hand` leaves open which copies are
+/// meant: the player holds more of some played card than they play, and
+/// those copies aren't all the same card.
+fn is_ambiguous(hand: &[Card], cards: &[PlayedCard]) -> bool {
+    let same = |card: &PlayedCard, other: &PlayedCard| {
+        card.get_is_joker() == other.get_is_joker()
+            && (card.get_is_joker()
+                || (card.get_rank() == other.get_rank() && card.get_suit() == other.get_suit()))
+    };
+
+    cards.iter().any(|card| {
+        let copies: Vec<&Card> = hand.iter()
+            .filter(|c| match c {
+                Card::Joker { .. } => card.get_is_joker(),
+                Card::Standard { rank, suit, .. } => !card.get_is_joker()
+                    && card.get_rank() == *rank
+                    && card.get_suit() == *suit,
+            })
+            .collect();
+        let played = cards.iter().filter(|other| same(card, other)).count();
+
+        copies.len() > played && copies.iter().any(|c| *c != copies[0])
+    })
+}
+
+pub(crate) fn holds_two_or_joker(cards: &[PlayedCard]) -> bool {
+    cards.iter().any(|c| c.get_is_joker() || c.get_rank() == Rank::Two)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{
+        CrossCountBombs,
+        DealMode,
+        FalseClaimPenalty,
+        FinalCardRule,
+        FlushPrecedence,
+        JokerUsage,
+        LastCardRule,
+        MisdealConditions,
+        RedealVote,
+        ReversalDuration,
+        ReversalTriggers,
+        RoundBuilder,
+        StraightComparison,
+        TableClearMode,
+        TrickRanking,
+        TrickType,
+        TurnOrder,
+    };
+
+    static DEFAULT_SUIT_ORDER: [Suit; 4] =
+        [Suit::Clubs, Suit::Hearts, Suit::Diamonds, Suit::Spades];
+
+    static DEFAULT_RANK_ORDER: [Rank; 13] = [
+        Rank::Three,
+        Rank::Four,
+        Rank::Five,
+        Rank::Six,
         Rank::Seven,
         Rank::Eight,
         Rank::Nine,
@@ -336,8 +1738,29 @@ mod tests {
     ];
 
     const DEFAULT_RULESET: Ruleset = Ruleset{
+        preset: None,
         reversals_enabled: true,
+        reversal_triggers: ReversalTriggers::classic(),
+        reversal_duration: ReversalDuration::RestOfGame,
         flush_precedence: FlushPrecedence::Rank,
+        straight_comparison: StraightComparison::RankThenSuit,
+        partnership: false,
+        opening_requires_lowest_card: true,
+        table_clear: TableClearMode::ReturnToLastPlayer,
+        joker_usage: JokerUsage::Anywhere,
+        royal_flush_tier: false,
+        misdeal: MisdealConditions::none(),
+        redeal_vote: RedealVote::Off,
+        deal_mode: DealMode::FullDeck,
+        last_card: LastCardRule::Off,
+        final_card: FinalCardRule::Any,
+        false_claim: FalseClaimPenalty::Resign,
+        trick_ranking: TrickRanking::standard(),
+        twos_in_straights: true,
+        pass_locks_out: false,
+        cross_count_bombs: CrossCountBombs::none(),
+        strict_finish: false,
+        turn_order: TurnOrder::Clockwise,
     };
 
     #[test]
@@ -354,29 +1777,256 @@ mod tests {
         }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
-
+
+        let players = vec![player_a, player_b];
+
+        let round = Round::new(
+            players,
+            None,
+            None,
+            None,
+            OrderContext::standard(),
+            DEFAULT_RULESET,
+        );
+
+        assert_eq!(round.get_next_player(), Some("a".to_string()));
+    }
+
+    #[test]
+    fn when_game_has_started_there_will_be_a_current_player() {
+        let a_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Three,
+            suit: Suit::Clubs,
+        }];
+        let b_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Four,
+            suit: Suit::Clubs,
+        }];
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
+        let players = vec![player_a, player_b];
+        let round = Round::new(
+            players,
+            Some("b".to_string()),
+            None,
+            None,
+            OrderContext::standard(),
+            DEFAULT_RULESET,
+        );
+
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+    }
+
+    #[test]
+    fn player_cannot_start_a_game_with_a_pass() {
+        let a_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Three,
+            suit: Suit::Clubs,
+        }];
+        let b_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Four,
+            suit: Suit::Clubs,
+        }];
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
+        let players = vec![player_a, player_b];
+        let round = Round::new(
+            players,
+            None,
+            None,
+            None,
+            OrderContext::standard(),
+            DEFAULT_RULESET,
+        );
+
+        let err = round.submit_move("a", vec![]).err().unwrap();
+
+        assert_eq!(err, SubmitError::FirstRoundPass);
+    }
+
+    #[test]
+    fn player_must_start_a_game_with_three_clubs() {
+        let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Four,
+            suit: Suit::Clubs,
+        }];
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
+        let players = vec![player_a, player_b];
+        let round = Round::new(
+            players,
+            None,
+            None,
+            None,
+            OrderContext::standard(),
+            DEFAULT_RULESET,
+        );
+        let played_hand = vec![
+            PlayedCard::new(Rank::Six, Suit::Clubs, false)
+        ];
+        let err = round.submit_move("a", played_hand)
+            .err().unwrap();
+        assert_eq!(
+            err,
+            SubmitError::FirstHandMustContainLowestCard
+        );
+    }
+
+    #[test]
+    fn lowest_card_holder_can_lead_any_hand_when_not_required() {
+        let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Four,
+            suit: Suit::Clubs,
+        }];
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
+        let players = vec![player_a, player_b];
+        let ruleset = Ruleset {
+            opening_requires_lowest_card: false,
+            ..DEFAULT_RULESET
+        };
+        let round = Round::new(
+            players,
+            None,
+            None,
+            None,
+            OrderContext::standard(),
+            ruleset,
+        );
+
+        let played_hand = vec![
+            PlayedCard::new(Rank::Six, Suit::Clubs, false)
+        ];
+        assert!(round.submit_move("a", played_hand.clone()).is_ok());
+        assert_eq!(
+            round.submit_move("b", played_hand).err(),
+            Some(SubmitError::NotCurrentPlayer)
+        );
+    }
+
+    #[test]
+    fn playing_a_valid_hand_returns_the_new_round() {
+        let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Four,
+            suit: Suit::Clubs,
+        }];
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
+        let players = vec![player_a, player_b];
+        let round = Round::new(
+            players,
+            None,
+            None,
+            None,
+            OrderContext::standard(),
+            DEFAULT_RULESET
+        );
+        let played_hand = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+        ];
+        assert!(round.submit_move("a", played_hand).is_ok());
+    }
+
+    #[test]
+    fn lower_hand_cannot_beat_last_move() {
+        let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Four,
+            suit: Suit::Clubs,
+        }];
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
         let players = vec![player_a, player_b];
-
+        let last_move = Some(Hand::Single(PlayedCard::new(
+            Rank::Three,
+            Suit::Clubs,
+            false,
+        )));
         let round = Round::new(
             players,
+            Some("a".to_string()),
+            last_move,
             None,
-            None,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET,
+            OrderContext::standard(),
+            DEFAULT_RULESET
         );
+        let played_hand = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+        ];
 
-        assert_eq!(round.get_next_player(), Some("a".to_string()));
+        let err = round.submit_move("a", played_hand).err().unwrap();
+        assert_eq!(err, SubmitError::HandNotHighEnough);
     }
 
     #[test]
-    fn when_game_has_started_there_will_be_a_current_player() {
-        let a_cards = vec![Card::Standard {
-            deck_id: 0,
-            rank: Rank::Three,
-            suit: Suit::Clubs,
-        }];
+    fn higher_hand_can_beat_last_move() {
+        let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
         let b_cards = vec![Card::Standard {
             deck_id: 0,
             rank: Rank::Four,
@@ -385,26 +2035,40 @@ mod tests {
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
         let players = vec![player_a, player_b];
+        let last_move = Some(Hand::Single(PlayedCard::new(
+            Rank::Three,
+            Suit::Clubs,
+            false,
+        )));
         let round = Round::new(
             players,
-            Some("b".to_string()),
-            None,
+            Some("a".to_string()),
+            last_move,
             None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET,
+            OrderContext::standard(),
+            DEFAULT_RULESET
         );
+        let played_hand = vec![
+            PlayedCard::new(Rank::Six, Suit::Clubs, false)
+        ];
 
-        assert_eq!(round.get_next_player(), Some("b".to_string()));
+        assert!(round.submit_move("a", played_hand).is_ok());
     }
 
     #[test]
-    fn player_cannot_start_a_game_with_a_pass() {
-        let a_cards = vec![Card::Standard {
-            deck_id: 0,
-            rank: Rank::Three,
-            suit: Suit::Clubs,
-        }];
+    fn invalid_player_cannot_make_a_move() {
+        let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
         let b_cards = vec![Card::Standard {
             deck_id: 0,
             rank: Rank::Four,
@@ -413,23 +2077,30 @@ mod tests {
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
         let players = vec![player_a, player_b];
+        let last_move = Some(Hand::Single(PlayedCard::new(
+            Rank::Three,
+            Suit::Clubs,
+            false,
+        )));
         let round = Round::new(
             players,
+            Some("a".to_string()),
+            last_move,
             None,
-            None,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET,
+            OrderContext::standard(),
+            DEFAULT_RULESET
         );
+        let played_hand = vec![
+            PlayedCard::new(Rank::Six, Suit::Clubs, false)
+        ];
 
-        let err = round.submit_move("a", vec![]).err().unwrap();
-
-        assert_eq!(err, SubmitError::FirstRoundPass);
+        let err = round.submit_move("b", played_hand)
+            .err().unwrap();
+        assert_eq!(err, SubmitError::NotCurrentPlayer);
     }
 
     #[test]
-    fn player_must_start_a_game_with_three_clubs() {
+    fn it_should_be_a_valid_hand() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
@@ -450,28 +2121,31 @@ mod tests {
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
         let players = vec![player_a, player_b];
+        let last_move = Some(Hand::Single(PlayedCard::new(
+            Rank::Three,
+            Suit::Clubs,
+            false,
+        )));
         let round = Round::new(
             players,
+            Some("a".to_string()),
+            last_move,
             None,
-            None,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET,
+            OrderContext::standard(),
+            DEFAULT_RULESET
         );
         let played_hand = vec![
-            PlayedCard::new(Rank::Six, Suit::Clubs, false)
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
         ];
+
         let err = round.submit_move("a", played_hand)
             .err().unwrap();
-        assert_eq!(
-            err,
-            SubmitError::FirstHandMustContainLowestCard
-        );
+        assert_eq!(err, SubmitError::InvalidHand);
     }
 
     #[test]
-    fn playing_a_valid_hand_returns_the_new_round() {
+    fn it_should_be_a_valid_hand_even_at_start() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
@@ -497,18 +2171,21 @@ mod tests {
             None,
             None,
             None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
         let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
         ];
-        assert!(round.submit_move("a", played_hand).is_ok());
+
+        let err = round.submit_move("a", played_hand)
+            .err().unwrap();
+        assert_eq!(err, SubmitError::InvalidHand);
     }
 
     #[test]
-    fn lower_hand_cannot_beat_last_move() {
+    fn player_cannot_play_cards_it_doesnt_hold() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
@@ -539,20 +2216,22 @@ mod tests {
             Some("a".to_string()),
             last_move,
             None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
         let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
         ];
 
-        let err = round.submit_move("a", played_hand).err().unwrap();
-        assert_eq!(err, SubmitError::HandNotHighEnough);
+        let err = round.submit_move("a", played_hand)
+            .err().unwrap();
+
+        assert_eq!(err, SubmitError::PlayerDoesntHaveCard);
+
     }
 
     #[test]
-    fn higher_hand_can_beat_last_move() {
+    fn player_can_only_play_a_card_once() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
@@ -573,29 +2252,129 @@ mod tests {
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
         let players = vec![player_a, player_b];
-        let last_move = Some(Hand::Single(PlayedCard::new(
-            Rank::Three,
-            Suit::Clubs,
-            false,
-        )));
+        let last_move = Some(
+            Hand::Pair(
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                ),
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                )
+            )
+        );
         let round = Round::new(
             players,
             Some("a".to_string()),
             last_move,
             None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
         let played_hand = vec![
-            PlayedCard::new(Rank::Six, Suit::Clubs, false)
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+            PlayedCard::new(Rank::Six, Suit::Clubs, false),
+        ];
+
+        let err = round.submit_move("a", played_hand)
+            .err().unwrap();
+
+        assert_eq!(err, SubmitError::PlayerDoesntHaveCard);
+
+    }
+
+    #[test]
+    fn playing_a_valid_card_removes_from_players_hand() {
+        let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Four,
+            suit: Suit::Clubs,
+        }];
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
+        let players = vec![player_a, player_b];
+        let round = Round::new(
+            players,
+            None,
+            None,
+            None,
+            OrderContext::standard(),
+            DEFAULT_RULESET
+        );
+        let played_hand = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+        ];
+
+        let new_round = round.submit_move("a", played_hand)
+            .unwrap();
+
+        let new_player_a = new_round.get_player("a").unwrap();
+
+        assert_eq!(new_player_a.get_hand().len(), 1);
+    }
+
+    #[test]
+    fn a_valid_move_is_set_as_last_move() {
+        let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![Card::Standard {
+            deck_id: 0,
+            rank: Rank::Four,
+            suit: Suit::Clubs,
+        }];
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
+        let players = vec![player_a, player_b];
+        let round = Round::new(
+            players,
+            None,
+            None,
+            None,
+            OrderContext::standard(),
+            DEFAULT_RULESET
+        );
+        let played_hand = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
         ];
 
-        assert!(round.submit_move("a", played_hand).is_ok());
+        let new_round = round.submit_move("a", played_hand)
+            .unwrap();
+
+        assert_eq!(
+            new_round.get_last_move(),
+            Some(Hand::Single(PlayedCard::new(
+                Rank::Three, Suit::Clubs, false
+            )))
+        );
     }
 
     #[test]
-    fn invalid_player_cannot_make_a_move() {
+    fn when_a_valid_move_is_made_the_next_player_rotates() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
@@ -616,35 +2395,34 @@ mod tests {
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
         let players = vec![player_a, player_b];
-        let last_move = Some(Hand::Single(PlayedCard::new(
-            Rank::Three,
-            Suit::Clubs,
-            false,
-        )));
         let round = Round::new(
             players,
-            Some("a".to_string()),
-            last_move,
             None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            None,
+            None,
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
         let played_hand = vec![
-            PlayedCard::new(Rank::Six, Suit::Clubs, false)
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
         ];
 
-        let err = round.submit_move("b", played_hand)
-            .err().unwrap();
-        assert_eq!(err, SubmitError::NotCurrentPlayer);
+        let new_round = round.submit_move("a", played_hand)
+            .unwrap();
+
+        assert_eq!(
+            new_round.get_next_player(),
+            Some("b".to_string())
+        );
+
     }
 
     #[test]
-    fn it_should_be_a_valid_hand() {
+    fn player_rotation_comes_back_round() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Three,
+                rank: Rank::Four,
                 suit: Suit::Clubs,
             },
             Card::Standard {
@@ -653,41 +2431,58 @@ mod tests {
                 suit: Suit::Clubs,
             },
         ];
-        let b_cards = vec![Card::Standard {
-            deck_id: 0,
-            rank: Rank::Four,
-            suit: Suit::Clubs,
-        }];
+        let b_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            }
+        ];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
         let players = vec![player_a, player_b];
-        let last_move = Some(Hand::Single(PlayedCard::new(
-            Rank::Three,
-            Suit::Clubs,
-            false,
-        )));
         let round = Round::new(
             players,
-            Some("a".to_string()),
-            last_move,
+            Some("b".to_string()),
+            None,
             None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
         let played_hand = vec![
-            PlayedCard::new(Rank::Six, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
         ];
 
-        let err = round.submit_move("a", played_hand)
-            .err().unwrap();
-        assert_eq!(err, SubmitError::InvalidHand);
+        let new_round = round.submit_move("b", played_hand)
+            .unwrap();
+
+        assert_eq!(
+            new_round.get_next_player(),
+            Some("a".to_string())
+        );
+
     }
 
     #[test]
-    fn it_should_be_a_valid_hand_even_at_start() {
+    fn passing_moves_without_changing_the_last_move() {
         let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Four,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
@@ -697,38 +2492,78 @@ mod tests {
                 deck_id: 0,
                 rank: Rank::Six,
                 suit: Suit::Clubs,
+            }
+        ];
+        let c_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
             },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            }
         ];
-        let b_cards = vec![Card::Standard {
-            deck_id: 0,
-            rank: Rank::Four,
-            suit: Suit::Clubs,
-        }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
-        let players = vec![player_a, player_b];
+        let player_c = Player::new("c".to_string(), c_cards);
+        let players = vec![player_a, player_b, player_c];
+        let last_move = Some(
+            Hand::Pair(
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                ),
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                )
+            )
+        );
+
         let round = Round::new(
             players,
-            None,
-            None,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            Some("b".to_string()),
+            last_move,
+            Some("a".to_string()),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
-        let played_hand = vec![
-            PlayedCard::new(Rank::Six, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-        ];
+        let played_hand = vec![];
 
-        let err = round.submit_move("a", played_hand)
-            .err().unwrap();
-        assert_eq!(err, SubmitError::InvalidHand);
+        let new_round = round.submit_move("b", played_hand)
+            .unwrap();
+
+        assert_eq!(
+            new_round.get_next_player(),
+            Some("c".to_string())
+        );
+
+        assert_eq!(
+            new_round.get_last_move(),
+            last_move
+        );
     }
 
     #[test]
-    fn player_cannot_play_cards_it_doesnt_hold() {
+    fn a_valid_move_switches_the_last_player() {
         let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Four,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
@@ -739,43 +2574,105 @@ mod tests {
                 rank: Rank::Six,
                 suit: Suit::Clubs,
             },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Spades,
+            }
+        ];
+        let c_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            }
         ];
-        let b_cards = vec![Card::Standard {
-            deck_id: 0,
-            rank: Rank::Four,
-            suit: Suit::Clubs,
-        }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
-        let players = vec![player_a, player_b];
-        let last_move = Some(Hand::Single(PlayedCard::new(
-            Rank::Three,
-            Suit::Clubs,
-            false,
-        )));
+        let player_c = Player::new("c".to_string(), c_cards);
+        let players = vec![player_a, player_b, player_c];
+        let last_move = Some(
+            Hand::Pair(
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                ),
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                )
+            )
+        );
+
         let round = Round::new(
             players,
-            Some("a".to_string()),
+            Some("b".to_string()),
             last_move,
             None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
         let played_hand = vec![
-            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+            PlayedCard::new(
+                Rank::Three,
+                Suit::Clubs,
+                false,
+            ),
+            PlayedCard::new(
+                Rank::Three,
+                Suit::Spades,
+                false,
+            )
         ];
 
-        let err = round.submit_move("a", played_hand)
-            .err().unwrap();
-
-        assert_eq!(err, SubmitError::PlayerDoesntHaveCard);
+        let new_round = round.submit_move("b", played_hand)
+            .unwrap();
 
+        assert_eq!(
+            new_round.get_last_player(),
+            Some("b".to_string())
+        );
     }
 
     #[test]
-    fn player_can_only_play_a_card_once() {
+    fn if_last_and_next_player_are_same_the_table_is_cleared() {
         let a_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Four,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+        ];
+        let b_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Six,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Spades,
+            }
+        ];
+        let c_cards = vec![
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
@@ -785,16 +2682,12 @@ mod tests {
                 deck_id: 0,
                 rank: Rank::Six,
                 suit: Suit::Clubs,
-            },
+            }
         ];
-        let b_cards = vec![Card::Standard {
-            deck_id: 0,
-            rank: Rank::Four,
-            suit: Suit::Clubs,
-        }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
-        let players = vec![player_a, player_b];
+        let player_c = Player::new("c".to_string(), c_cards);
+        let players = vec![player_a, player_b, player_c];
         let last_move = Some(
             Hand::Pair(
                 PlayedCard::new(
@@ -809,29 +2702,29 @@ mod tests {
                 )
             )
         );
+
         let round = Round::new(
             players,
-            Some("a".to_string()),
+            Some("b".to_string()),
             last_move,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            Some("c".to_string()),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
-        let played_hand = vec![
-            PlayedCard::new(Rank::Six, Suit::Clubs, false),
-            PlayedCard::new(Rank::Six, Suit::Clubs, false),
-        ];
+        let played_hand = vec![];
 
-        let err = round.submit_move("a", played_hand)
-            .err().unwrap();
+        let new_round = round.submit_move("b", played_hand)
+            .unwrap();
 
-        assert_eq!(err, SubmitError::PlayerDoesntHaveCard);
+        assert_eq!(
+            new_round.get_last_move(),
+            Some(Hand::Pass)
+        );
 
     }
 
     #[test]
-    fn playing_a_valid_card_removes_from_players_hand() {
+    fn any_card_beats_a_pass() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
@@ -840,9 +2733,9 @@ mod tests {
             },
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Six,
+                rank: Rank::Three,
                 suit: Suit::Clubs,
-            },
+            }
         ];
         let b_cards = vec![Card::Standard {
             deck_id: 0,
@@ -851,30 +2744,33 @@ mod tests {
         }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
+
         let players = vec![player_a, player_b];
+
         let round = Round::new(
             players,
-            None,
-            None,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            Some("a".to_string()),
+            Some(Hand::Pass),
+            Some("a".to_string()),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
+
         let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+            PlayedCard::new(
+                Rank::Three,
+                Suit::Clubs,
+                false,
+            ),
         ];
 
-        let new_round = round.submit_move("a", played_hand)
-            .unwrap();
-
-        let new_player_a = new_round.get_player("a").unwrap();
+        let new_round = round.submit_move("a", played_hand);
 
-        assert_eq!(new_player_a.get_hand().len(), 1);
+        assert!(new_round.is_ok());
     }
 
     #[test]
-    fn a_valid_move_is_set_as_last_move() {
+    fn players_with_no_cards_are_skipped() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
@@ -883,181 +2779,159 @@ mod tests {
             },
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Six,
+                rank: Rank::Three,
                 suit: Suit::Clubs,
-            },
+            }
         ];
-        let b_cards = vec![Card::Standard {
+        let b_cards = vec![];
+        let c_cards = vec![Card::Standard{
             deck_id: 0,
-            rank: Rank::Four,
+            rank: Rank::Three,
             suit: Suit::Clubs,
         }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
-        let players = vec![player_a, player_b];
+        let player_c = Player::new("c".to_string(), c_cards);
+
+        let players = vec![player_a, player_b, player_c];
+
+        let last_move = Some(
+            Hand::Pair(
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                ),
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                )
+            )
+        );
+
         let round = Round::new(
             players,
-            None,
-            None,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            Some("a".to_string()),
+            last_move,
+            Some("c".to_string()),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
-        let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false)
-        ];
 
-        let new_round = round.submit_move("a", played_hand)
-            .unwrap();
+        let played_hand = vec![];
+
+        let new_round = round.submit_move(
+            "a",
+            played_hand
+        ).unwrap();
 
         assert_eq!(
-            new_round.get_last_move(),
-            Some(Hand::Single(PlayedCard::new(
-                Rank::Three, Suit::Clubs, false
-            )))
+            new_round.get_next_player().unwrap(),
+            "c".to_string()
         );
     }
 
     #[test]
-    fn when_a_valid_move_is_made_the_next_player_rotates() {
+    fn once_the_game_is_over_the_next_player_is_none() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
+                rank: Rank::Four,
+                suit: Suit::Spades,
             },
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Six,
+                rank: Rank::Four,
                 suit: Suit::Clubs,
-            },
+            }
         ];
-        let b_cards = vec![Card::Standard {
+        let b_cards = vec![];
+        let c_cards = vec![Card::Standard{
             deck_id: 0,
-            rank: Rank::Four,
+            rank: Rank::Three,
             suit: Suit::Clubs,
         }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
-        let players = vec![player_a, player_b];
-        let round = Round::new(
-            players,
-            None,
-            None,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET
-        );
-        let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false)
-        ];
+        let player_c = Player::new("c".to_string(), c_cards);
 
-        let new_round = round.submit_move("a", played_hand)
-            .unwrap();
+        let players = vec![player_a, player_b, player_c];
 
-        assert_eq!(
-            new_round.get_next_player(),
-            Some("b".to_string())
+        let last_move = Some(
+            Hand::Pair(
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                ),
+                PlayedCard::new(
+                    Rank::Three,
+                    Suit::Clubs,
+                    false,
+                )
+            )
         );
 
-    }
-
-    #[test]
-    fn player_rotation_comes_back_round() {
-        let a_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Four,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Six,
-                suit: Suit::Clubs,
-            },
-        ];
-        let b_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Six,
-                suit: Suit::Clubs,
-            }
-        ];
-        let player_a = Player::new("a".to_string(), a_cards);
-        let player_b = Player::new("b".to_string(), b_cards);
-        let players = vec![player_a, player_b];
         let round = Round::new(
             players,
-            Some("b".to_string()),
-            None,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            Some("a".to_string()),
+            last_move,
+            Some("c".to_string()),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
+
         let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+            PlayedCard::new(
+                Rank::Four,
+                Suit::Spades,
+                false,
+            ),
+            PlayedCard::new(
+                Rank::Four,
+                Suit::Clubs,
+                false,
+            )
         ];
 
-        let new_round = round.submit_move("b", played_hand)
-            .unwrap();
+        let new_round = round.submit_move(
+            "a",
+            played_hand
+        ).unwrap();
 
-        assert_eq!(
-            new_round.get_next_player(),
-            Some("a".to_string())
+        assert!(
+            new_round.get_next_player().is_none(),
         );
 
     }
 
     #[test]
-    fn passing_moves_without_changing_the_last_move() {
+    fn when_player_wins_next_player_starts() {
         let a_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Four,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Six,
-                suit: Suit::Clubs,
-            },
-        ];
-        let b_cards = vec![
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
                 suit: Suit::Clubs,
             },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Six,
-                suit: Suit::Clubs,
-            }
-        ];
-        let c_cards = vec![
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
                 suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Six,
-                suit: Suit::Clubs,
             }
         ];
+        let b_cards = vec![];
+        let c_cards = vec![Card::Standard{
+            deck_id: 0,
+            rank: Rank::Three,
+            suit: Suit::Clubs,
+        }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
         let player_c = Player::new("c".to_string(), c_cards);
+
         let players = vec![player_a, player_b, player_c];
+
         let last_move = Some(
             Hand::Pair(
                 PlayedCard::new(
@@ -1075,76 +2949,115 @@ mod tests {
 
         let round = Round::new(
             players,
-            Some("b".to_string()),
+            Some("a".to_string()),
             last_move,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            Some("b".to_string()),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
-        let played_hand = vec![];
 
-        let new_round = round.submit_move("b", played_hand)
-            .unwrap();
+        let played_hand = vec![];
 
-        assert_eq!(
-            new_round.get_next_player(),
-            Some("c".to_string())
-        );
+        let new_round = round.submit_move(
+            "a",
+            played_hand
+        ).unwrap();
 
         assert_eq!(
-            new_round.get_last_move(),
-            last_move
+            new_round.get_last_move().unwrap(),
+            Hand::Pass
         );
     }
 
     #[test]
-    fn a_valid_move_switches_the_last_player() {
+    fn playing_on_pass_sets_player_as_last_move() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Four,
+                rank: Rank::Three,
                 suit: Suit::Clubs,
             },
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Six,
+                rank: Rank::Three,
                 suit: Suit::Clubs,
-            },
+            }
         ];
-        let b_cards = vec![
+        let b_cards = vec![];
+        let c_cards = vec![Card::Standard{
+            deck_id: 0,
+            rank: Rank::Three,
+            suit: Suit::Clubs,
+        }];
+        let player_a = Player::new("a".to_string(), a_cards);
+        let player_b = Player::new("b".to_string(), b_cards);
+        let player_c = Player::new("c".to_string(), c_cards);
+
+        let players = vec![player_a, player_b, player_c];
+
+        let last_move = Some(
+            Hand::Pass
+        );
+
+        let round = Round::new(
+            players,
+            Some("a".to_string()),
+            last_move,
+            Some("c".to_string()),
+            OrderContext::standard(),
+            DEFAULT_RULESET
+        );
+
+        let played_hand = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+        ];
+
+        let new_round = round.submit_move(
+            "a",
+            played_hand
+        ).unwrap();
+
+        assert_eq!(
+            new_round.get_last_player().unwrap(),
+            "a".to_string()
+        );
+    }
+
+    #[test]
+    fn when_there_are_two_players_left_pass_clears_the_table() {
+        let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
                 suit: Suit::Clubs,
             },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Six,
-                suit: Suit::Clubs,
-            },
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
-                suit: Suit::Spades,
+                suit: Suit::Clubs,
             }
         ];
-        let c_cards = vec![
-            Card::Standard {
+        let b_cards = vec![];
+        let c_cards = vec![];
+        let d_cards = vec![
+             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
                 suit: Suit::Clubs,
             },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Six,
-                suit: Suit::Clubs,
-            }
         ];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
         let player_c = Player::new("c".to_string(), c_cards);
-        let players = vec![player_a, player_b, player_c];
+        let player_d = Player::new("d".to_string(), d_cards);
+
+        let players = vec![
+            player_a,
+            player_b,
+            player_c,
+            player_d
+        ];
+
         let last_move = Some(
             Hand::Pair(
                 PlayedCard::new(
@@ -1162,50 +3075,44 @@ mod tests {
 
         let round = Round::new(
             players,
-            Some("b".to_string()),
+            Some("a".to_string()),
             last_move,
-            None,
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            Some("d".to_string()),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
-        let played_hand = vec![
-            PlayedCard::new(
-                Rank::Three,
-                Suit::Clubs,
-                false,
-            ),
-            PlayedCard::new(
-                Rank::Three,
-                Suit::Spades,
-                false,
-            )
-        ];
 
-        let new_round = round.submit_move("b", played_hand)
-            .unwrap();
+        let played_hand = vec![];
+
+        let new_round = round.submit_move(
+            "a",
+            played_hand
+        ).unwrap();
 
         assert_eq!(
-            new_round.get_last_player(),
-            Some("b".to_string())
+            new_round.get_next_player().unwrap(),
+            "d".to_string()
+        );
+
+        assert_eq!(
+            new_round.get_last_move().unwrap(),
+            Hand::Pass
         );
     }
 
     #[test]
-    fn if_last_and_next_player_are_same_the_table_is_cleared() {
+    fn when_reversals_are_enabled_4ofakind_reverses_orders() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Four,
+                rank: Rank::Three,
                 suit: Suit::Clubs,
             },
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Six,
+                rank: Rank::Three,
                 suit: Suit::Clubs,
             },
-        ];
-        let b_cards = vec![
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
@@ -1213,69 +3120,76 @@ mod tests {
             },
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Six,
+                rank: Rank::Three,
                 suit: Suit::Clubs,
             },
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Spades,
+                rank: Rank::Four,
+                suit: Suit::Clubs,
             }
         ];
-        let c_cards = vec![
+        let b_cards = vec![
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Three,
                 suit: Suit::Clubs,
             },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Six,
-                suit: Suit::Clubs,
-            }
         ];
+        let c_cards = vec![Card::Standard{
+            deck_id: 0,
+            rank: Rank::Three,
+            suit: Suit::Clubs,
+        }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
         let player_c = Player::new("c".to_string(), c_cards);
+
         let players = vec![player_a, player_b, player_c];
-        let last_move = Some(
-            Hand::Pair(
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                ),
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                )
-            )
-        );
+        let last_move = Some(Hand::Pass);
 
         let round = Round::new(
             players,
-            Some("b".to_string()),
+            Some("a".to_string()),
             last_move,
-            Some("c".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            Some("b".to_string()),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
-        let played_hand = vec![];
 
-        let new_round = round.submit_move("b", played_hand)
-            .unwrap();
+        let played_hand = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+        ];
+
+        let new_round = round.submit_move(
+            "a",
+            played_hand
+        ).unwrap();
+
+        let mut expected_suit_order = DEFAULT_SUIT_ORDER;
+        let mut expected_rank_order = DEFAULT_RANK_ORDER;
+        expected_suit_order.reverse();
+        expected_rank_order.reverse();
 
         assert_eq!(
-            new_round.get_last_move(),
-            Some(Hand::Pass)
+            new_round.get_suit_order(),
+            expected_suit_order
         );
 
+        assert_eq!(
+            new_round.get_rank_order(),
+            expected_rank_order
+        );
+        assert!(!round.is_reversed());
+        assert!(new_round.is_reversed());
     }
 
     #[test]
-    fn any_card_beats_a_pass() {
+    fn when_reversals_are_enabled_only_a_4or5ofakind_reverses() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
@@ -1286,43 +3200,80 @@ mod tests {
                 deck_id: 0,
                 rank: Rank::Three,
                 suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Four,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Four,
+                suit: Suit::Clubs,
             }
         ];
-        let b_cards = vec![Card::Standard {
+        let b_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+        ];
+        let c_cards = vec![Card::Standard{
             deck_id: 0,
-            rank: Rank::Four,
+            rank: Rank::Three,
             suit: Suit::Clubs,
         }];
         let player_a = Player::new("a".to_string(), a_cards);
         let player_b = Player::new("b".to_string(), b_cards);
+        let player_c = Player::new("c".to_string(), c_cards);
 
-        let players = vec![player_a, player_b];
+        let players = vec![player_a, player_b, player_c];
+        let last_move = Some(Hand::Pass);
 
         let round = Round::new(
             players,
             Some("a".to_string()),
-            Some(Hand::Pass),
-            Some("a".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            last_move,
+            Some("b".to_string()),
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
 
         let played_hand = vec![
-            PlayedCard::new(
-                Rank::Three,
-                Suit::Clubs,
-                false,
-            ),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
         ];
 
-        let new_round = round.submit_move("a", played_hand);
+        let new_round = round.submit_move(
+            "a",
+            played_hand
+        ).unwrap();
+
+        let expected_suit_order = DEFAULT_SUIT_ORDER;
+        let expected_rank_order = DEFAULT_RANK_ORDER;
 
-        assert!(new_round.is_ok());
+        assert_eq!(
+            new_round.get_suit_order(),
+            expected_suit_order
+        );
+
+        assert_eq!(
+            new_round.get_rank_order(),
+            expected_rank_order
+        );
     }
 
     #[test]
-    fn players_with_no_cards_are_skipped() {
+    fn when_reversals_are_not_enabled_no_reversals() {
         let a_cards = vec![
             Card::Standard {
                 deck_id: 0,
@@ -1333,9 +3284,30 @@ mod tests {
                 deck_id: 0,
                 rank: Rank::Three,
                 suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Four,
+                suit: Suit::Clubs,
             }
         ];
-        let b_cards = vec![];
+        let b_cards = vec![
+            Card::Standard {
+                deck_id: 0,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
+        ];
         let c_cards = vec![Card::Standard{
             deck_id: 0,
             rank: Rank::Three,
@@ -1346,60 +3318,91 @@ mod tests {
         let player_c = Player::new("c".to_string(), c_cards);
 
         let players = vec![player_a, player_b, player_c];
+        let last_move = Some(Hand::Pass);
 
-        let last_move = Some(
-            Hand::Pair(
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                ),
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                )
-            )
-        );
+        let ruleset = Ruleset {
+            preset: None,
+            reversals_enabled: false,
+            reversal_triggers: ReversalTriggers::classic(),
+            reversal_duration: ReversalDuration::RestOfGame,
+            flush_precedence: FlushPrecedence::Rank,
+            straight_comparison: StraightComparison::RankThenSuit,
+            partnership: false,
+            opening_requires_lowest_card: true,
+            table_clear: TableClearMode::ReturnToLastPlayer,
+            joker_usage: JokerUsage::Anywhere,
+            royal_flush_tier: false,
+            misdeal: MisdealConditions::none(),
+            redeal_vote: RedealVote::Off,
+            deal_mode: DealMode::FullDeck,
+            last_card: LastCardRule::Off,
+            final_card: FinalCardRule::Any,
+            false_claim: FalseClaimPenalty::Resign,
+            trick_ranking: TrickRanking::standard(),
+            twos_in_straights: true,
+            pass_locks_out: false,
+            cross_count_bombs: CrossCountBombs::none(),
+            strict_finish: false,
+            turn_order: TurnOrder::Clockwise,
+        };
 
         let round = Round::new(
             players,
             Some("a".to_string()),
             last_move,
-            Some("c".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET
+            Some("b".to_string()),
+            OrderContext::standard(),
+            ruleset
         );
 
-        let played_hand = vec![];
+        let played_hand = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+        ];
 
         let new_round = round.submit_move(
             "a",
             played_hand
         ).unwrap();
 
+        let expected_suit_order = DEFAULT_SUIT_ORDER;
+        let expected_rank_order = DEFAULT_RANK_ORDER;
+
         assert_eq!(
-            new_round.get_next_player().unwrap(),
-            "c".to_string()
+            new_round.get_suit_order(),
+            expected_suit_order
+        );
+
+        assert_eq!(
+            new_round.get_rank_order(),
+            expected_rank_order
         );
     }
 
     #[test]
-    fn once_the_game_is_over_the_next_player_is_none() {
+    fn deck_id_is_not_checked_when_move_played() {
         let a_cards = vec![
+            Card::Standard {
+                deck_id: 1,
+                rank: Rank::Three,
+                suit: Suit::Clubs,
+            },
             Card::Standard {
                 deck_id: 0,
                 rank: Rank::Four,
-                suit: Suit::Spades,
+                suit: Suit::Clubs,
             },
+        ];
+        let b_cards = vec![
             Card::Standard {
                 deck_id: 0,
-                rank: Rank::Four,
+                rank: Rank::Three,
                 suit: Suit::Clubs,
-            }
+            },
         ];
-        let b_cards = vec![];
         let c_cards = vec![Card::Standard{
             deck_id: 0,
             rank: Rank::Three,
@@ -1410,572 +3413,1052 @@ mod tests {
         let player_c = Player::new("c".to_string(), c_cards);
 
         let players = vec![player_a, player_b, player_c];
-
-        let last_move = Some(
-            Hand::Pair(
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                ),
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                )
-            )
-        );
+        let last_move = Some(Hand::Pass);
+        let ruleset = Ruleset {
+            preset: None,
+            reversals_enabled: false,
+            reversal_triggers: ReversalTriggers::classic(),
+            reversal_duration: ReversalDuration::RestOfGame,
+            flush_precedence: FlushPrecedence::Rank,
+            straight_comparison: StraightComparison::RankThenSuit,
+            partnership: false,
+            opening_requires_lowest_card: true,
+            table_clear: TableClearMode::ReturnToLastPlayer,
+            joker_usage: JokerUsage::Anywhere,
+            royal_flush_tier: false,
+            misdeal: MisdealConditions::none(),
+            redeal_vote: RedealVote::Off,
+            deal_mode: DealMode::FullDeck,
+            last_card: LastCardRule::Off,
+            final_card: FinalCardRule::Any,
+            false_claim: FalseClaimPenalty::Resign,
+            trick_ranking: TrickRanking::standard(),
+            twos_in_straights: true,
+            pass_locks_out: false,
+            cross_count_bombs: CrossCountBombs::none(),
+            strict_finish: false,
+            turn_order: TurnOrder::Clockwise,
+        };
 
         let round = Round::new(
             players,
             Some("a".to_string()),
             last_move,
-            Some("c".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET
+            Some("b".to_string()),
+            OrderContext::standard(),
+            ruleset
         );
 
         let played_hand = vec![
-            PlayedCard::new(
-                Rank::Four,
-                Suit::Spades,
-                false,
-            ),
-            PlayedCard::new(
-                Rank::Four,
-                Suit::Clubs,
-                false,
-            )
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
         ];
 
         let new_round = round.submit_move(
             "a",
             played_hand
+        );
+
+        assert!(new_round.is_ok());
+    }
+
+    fn get_four_player_round(table_clear: TableClearMode) -> Round {
+        let cards = |ranks: &[Rank]| -> Vec<Card> {
+            ranks.iter()
+                .map(|&rank| Card::Standard {
+                    deck_id: 0,
+                    rank,
+                    suit: Suit::Spades,
+                })
+                .collect()
+        };
+        let players = vec![
+            Player::new("a".to_string(), cards(&[Rank::Four, Rank::Ace])),
+            Player::new("b".to_string(), cards(&[Rank::Five, Rank::Ace])),
+            Player::new("c".to_string(), cards(&[Rank::Six, Rank::Ace])),
+            Player::new("d".to_string(), cards(&[Rank::Seven, Rank::Ace])),
+        ];
+        let ruleset = Ruleset {
+            table_clear,
+            ..DEFAULT_RULESET
+        };
+
+        Round::new(
+            players,
+            Some("a".to_string()),
+            Some(Hand::Pass),
+            Some("d".to_string()),
+            OrderContext::standard(),
+            ruleset
+        )
+    }
+
+    fn play_until_a_passes(round: Round) -> Round {
+        round
+            .submit_move("a", vec![PlayedCard::new(Rank::Four, Suit::Spades, false)])
+            .and_then(|r| r.submit_move("b", vec![]))
+            .and_then(|r| r.submit_move(
+                "c",
+                vec![PlayedCard::new(Rank::Six, Suit::Spades, false)]
+            ))
+            .and_then(|r| r.submit_move("d", vec![]))
+            .and_then(|r| r.submit_move("a", vec![]))
+            .unwrap()
+    }
+
+    #[test]
+    fn table_clears_once_everyone_else_has_passed_when_configured() {
+        let round = play_until_a_passes(
+            get_four_player_round(TableClearMode::AllPassed)
+        );
+
+        assert_eq!(round.get_last_move(), Some(Hand::Pass));
+        assert_eq!(round.get_next_player(), Some("c".to_string()));
+        assert!(round.get_passed_players().is_empty());
+    }
+
+    #[test]
+    fn table_clears_after_the_configured_run_of_passes() {
+        let round = play_until_a_passes(
+            get_four_player_round(TableClearMode::AfterPasses(2))
+        );
+
+        assert_eq!(round.get_last_move(), Some(Hand::Pass));
+        assert_eq!(round.get_next_player(), Some("c".to_string()));
+
+        let round = play_until_a_passes(
+            get_four_player_round(TableClearMode::AfterPasses(3))
+        );
+
+        assert_eq!(
+            round.get_last_move(),
+            Some(Hand::Single(PlayedCard::new(Rank::Six, Suit::Spades, false)))
+        );
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+        let round = round.submit_move("b", vec![]).unwrap();
+        assert_eq!(round.get_last_move(), Some(Hand::Pass));
+        assert_eq!(round.get_next_player(), Some("c".to_string()));
+    }
+
+    #[test]
+    fn table_only_clears_when_play_returns_by_default() {
+        let round = play_until_a_passes(
+            get_four_player_round(TableClearMode::ReturnToLastPlayer)
+        );
+
+        assert_eq!(
+            round.get_last_move(),
+            Some(Hand::Single(PlayedCard::new(Rank::Six, Suit::Spades, false)))
+        );
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+        assert_eq!(round.get_passed_players(), vec!["b", "d", "a"]);
+    }
+
+    #[test]
+    fn consecutive_passes_are_counted_until_a_hand_is_played() {
+        let round = get_four_player_round(TableClearMode::ReturnToLastPlayer)
+            .submit_move("a", vec![PlayedCard::new(Rank::Four, Suit::Spades, false)])
+            .and_then(|r| r.submit_move("b", vec![]))
+            .and_then(|r| r.submit_move("c", vec![]))
+            .unwrap();
+
+        assert_eq!(round.get_pass_count(), 2);
+
+        let round = round.submit_move(
+            "d",
+            vec![PlayedCard::new(Rank::Seven, Suit::Spades, false)]
         ).unwrap();
 
-        assert!(
-            new_round.get_next_player().is_none(),
+        assert_eq!(round.get_pass_count(), 0);
+    }
+
+    #[test]
+    fn submit_move_mut_updates_the_round_in_place() {
+        let four = PlayedCard::new(Rank::Four, Suit::Spades, false);
+        let mut round = get_four_player_round(TableClearMode::ReturnToLastPlayer);
+        let expected = round.submit_move("a", vec![four]).unwrap();
+
+        round.submit_move_mut("a", vec![four]).unwrap();
+
+        assert!(round.diff(&expected).is_empty());
+        assert_eq!(round.get_discarded(), expected.get_discarded());
+        assert_eq!(
+            round.submit_move_mut("c", vec![]),
+            Err(SubmitError::NotCurrentPlayer)
         );
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+    }
 
+    #[test]
+    fn submit_move_only_copies_the_player_that_moved() {
+        let four = PlayedCard::new(Rank::Four, Suit::Spades, false);
+        let round = get_four_player_round(TableClearMode::ReturnToLastPlayer);
+        let next_round = round.submit_move("a", vec![four]).unwrap();
+
+        assert!(!Arc::ptr_eq(&round.players[0], &next_round.players[0]));
+        assert!(Arc::ptr_eq(&round.players[1], &next_round.players[1]));
+        assert!(Arc::ptr_eq(&round.dealt, &next_round.dealt));
+        assert_eq!(round.get_player("a").unwrap().get_card_count(), 2);
     }
 
     #[test]
-    fn when_player_wins_next_player_starts() {
-        let a_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            }
-        ];
-        let b_cards = vec![];
-        let c_cards = vec![Card::Standard{
-            deck_id: 0,
-            rank: Rank::Three,
-            suit: Suit::Clubs,
-        }];
-        let player_a = Player::new("a".to_string(), a_cards);
-        let player_b = Player::new("b".to_string(), b_cards);
-        let player_c = Player::new("c".to_string(), c_cards);
+    fn table_holds_the_hands_played_in_the_current_trick() {
+        let four = PlayedCard::new(Rank::Four, Suit::Spades, false);
+        let six = PlayedCard::new(Rank::Six, Suit::Spades, false);
+        let round = get_four_player_round(TableClearMode::ReturnToLastPlayer)
+            .submit_move("a", vec![four])
+            .and_then(|r| r.submit_move("b", vec![]))
+            .and_then(|r| r.submit_move("c", vec![six]))
+            .unwrap();
+
+        assert_eq!(round.get_table(), vec![
+            PlayedHand { player_id: "a".to_string(), hand: Hand::Single(four) },
+            PlayedHand { player_id: "c".to_string(), hand: Hand::Single(six) },
+        ]);
+
+        let round = round.submit_move("d", vec![])
+            .and_then(|r| r.submit_move("a", vec![]))
+            .and_then(|r| r.submit_move("b", vec![]))
+            .unwrap();
+
+        assert_eq!(round.get_last_move(), Some(Hand::Pass));
+        assert_eq!(round.get_table().len(), 2);
+
+        let ace = PlayedCard::new(Rank::Ace, Suit::Spades, false);
+        let round = round.submit_move("c", vec![ace]).unwrap();
+
+        assert_eq!(round.get_table(), vec![
+            PlayedHand { player_id: "c".to_string(), hand: Hand::Single(ace) },
+        ]);
+    }
+
+    #[test]
+    fn recent_moves_include_passes_and_keep_only_the_latest() {
+        let four = PlayedCard::new(Rank::Four, Suit::Spades, false);
+        let six = PlayedCard::new(Rank::Six, Suit::Spades, false);
+        let round = get_four_player_round(TableClearMode::ReturnToLastPlayer);
+
+        assert_eq!(round.last_n_moves(3), vec![]);
+
+        let round = round.submit_move("a", vec![four])
+            .and_then(|r| r.submit_move("b", vec![]))
+            .and_then(|r| r.submit_move("c", vec![six]))
+            .unwrap();
+
+        assert_eq!(round.last_n_moves(2), vec![
+            PlayedHand { player_id: "b".to_string(), hand: Hand::Pass },
+            PlayedHand { player_id: "c".to_string(), hand: Hand::Single(six) },
+        ]);
+        assert_eq!(round.last_n_moves(10).len(), 3);
+        assert_eq!(round.last_n_moves(0), vec![]);
+
+        // older moves fall off once the round has remembered enough
+        let mut round = round;
+        let first = round.recent_moves[0].clone();
+        let filler = round.recent_moves[1].clone();
+        while round.recent_moves.len() < RECENT_MOVES_KEPT {
+            round.recent_moves.insert(1, filler.clone());
+        }
+        let round = round.submit_move("d", vec![]).unwrap();
+
+        assert_eq!(round.last_n_moves(usize::MAX).len(), RECENT_MOVES_KEPT);
+        assert_eq!(round.last_n_moves(1)[0].player_id, "d");
+        assert!(!round.last_n_moves(usize::MAX).contains(&first));
+    }
+
+    #[test]
+    fn players_are_listed_in_seating_order() {
+        let four = PlayedCard::new(Rank::Four, Suit::Spades, false);
+        let round = get_four_player_round(TableClearMode::ReturnToLastPlayer)
+            .submit_move("a", vec![four])
+            .unwrap();
+
+        let ids: Vec<String> = round.get_players().iter()
+            .map(|p| p.get_id().to_string())
+            .collect();
+
+        assert_eq!(ids, round.get_player_ids());
+        assert_eq!(round.get_card_counts(), vec![
+            ("a".to_string(), 1),
+            ("b".to_string(), 2),
+            ("c".to_string(), 2),
+            ("d".to_string(), 2),
+        ]);
+    }
+
+    #[test]
+    fn resigning_discards_the_hand_and_passes_the_turn() {
+        let round = get_four_player_round(TableClearMode::ReturnToLastPlayer)
+            .resign("a")
+            .unwrap();
+
+        assert_eq!(round.get_player("a").unwrap().get_card_count(), 0);
+        assert_eq!(round.get_discarded().len(), 2);
+        assert_eq!(round.get_resigned_players(), vec!["a".to_string()]);
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+        assert_eq!(round.verify_card_conservation(), Ok(()));
+        assert_eq!(round.resign("a").err(), Some(ResignError::AlreadyOut));
+        assert_eq!(round.resign("z").err(), Some(ResignError::UnknownPlayer));
+    }
+
+    #[test]
+    fn resigning_the_owner_of_the_trick_clears_the_table_on_the_way_round() {
+        let four = PlayedCard::new(Rank::Four, Suit::Spades, false);
+        let round = get_four_player_round(TableClearMode::ReturnToLastPlayer)
+            .submit_move("a", vec![four])
+            .and_then(|r| r.submit_move("b", vec![]))
+            .unwrap()
+            .resign("a")
+            .unwrap();
+
+        assert_eq!(round.get_next_player(), Some("c".to_string()));
+
+        let round = round.submit_move("c", vec![])
+            .and_then(|r| r.submit_move("d", vec![]))
+            .unwrap();
+
+        assert_eq!(round.get_last_move(), Some(Hand::Pass));
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+    }
+
+    #[test]
+    fn the_opener_resigning_frees_the_lead() {
+        let round = Round::new(
+            vec![
+                Player::new("a".to_string(), vec![Card::Standard {
+                    deck_id: 0, rank: Rank::Three, suit: Suit::Clubs,
+                }]),
+                Player::new("b".to_string(), vec![Card::Standard {
+                    deck_id: 0, rank: Rank::Four, suit: Suit::Clubs,
+                }]),
+                Player::new("c".to_string(), vec![Card::Standard {
+                    deck_id: 0, rank: Rank::Five, suit: Suit::Clubs,
+                }]),
+            ],
+            None,
+            None,
+            None,
+            OrderContext::standard(),
+            DEFAULT_RULESET
+        ).resign("a").unwrap();
 
-        let players = vec![player_a, player_b, player_c];
+        let four = PlayedCard::new(Rank::Four, Suit::Clubs, false);
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+        assert!(round.submit_move("b", vec![four]).is_ok());
+    }
 
-        let last_move = Some(
-            Hand::Pair(
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                ),
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                )
-            )
+    fn get_joker_round(joker_usage: JokerUsage) -> Round {
+        let spade = |rank| Card::Standard { deck_id: 0, rank, suit: Suit::Spades };
+        Round::new(
+            vec![
+                Player::new("a".to_string(), vec![
+                    Card::Joker { deck_id: 0 },
+                    spade(Rank::Four),
+                    spade(Rank::Five),
+                    spade(Rank::Six),
+                    spade(Rank::Seven),
+                ]),
+                Player::new("b".to_string(), vec![
+                    Card::Joker { deck_id: 1 },
+                    spade(Rank::Nine),
+                ]),
+            ],
+            Some("a".to_string()),
+            Some(Hand::Pass),
+            Some("b".to_string()),
+            OrderContext::standard(),
+            Ruleset { joker_usage, ..DEFAULT_RULESET }
+        )
+    }
+
+    #[test]
+    fn jokers_can_be_limited_to_five_card_tricks() {
+        let round = get_joker_round(JokerUsage::FiveCardTricksOnly);
+        let joker_as = |rank| PlayedCard::new(rank, Suit::Spades, true);
+        let spade = |rank| PlayedCard::new(rank, Suit::Spades, false);
+
+        assert_eq!(
+            round.submit_move("a", vec![joker_as(Rank::Two)]).err(),
+            Some(SubmitError::JokerOutsideFiveCardTrick)
         );
+        assert!(round.submit_move("a", vec![
+            joker_as(Rank::Three),
+            spade(Rank::Four),
+            spade(Rank::Five),
+            spade(Rank::Six),
+            spade(Rank::Seven),
+        ]).is_ok());
+    }
 
+    #[test]
+    fn a_player_left_with_only_jokers_can_play_them_under_five_card_tricks_only() {
         let round = Round::new(
-            players,
+            vec![
+                Player::new("a".to_string(), vec![Card::Joker { deck_id: 0 }]),
+                Player::new("b".to_string(), vec![
+                    Card::Standard { deck_id: 0, rank: Rank::Nine, suit: Suit::Spades },
+                ]),
+            ],
             Some("a".to_string()),
-            last_move,
+            Some(Hand::Pass),
             Some("b".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET
+            OrderContext::standard(),
+            Ruleset { joker_usage: JokerUsage::FiveCardTricksOnly, ..DEFAULT_RULESET }
         );
 
-        let played_hand = vec![];
+        let round = round.submit_move("a", vec![PlayedCard::new(Rank::Two, Suit::Spades, true)])
+            .unwrap();
+        assert_eq!(round.get_player("a").unwrap().get_card_count(), 0);
+    }
 
-        let new_round = round.submit_move(
-            "a",
-            played_hand
-        ).unwrap();
+    #[test]
+    fn jokers_only_stand_in_for_suits_in_play() {
+        let round = get_joker_round(JokerUsage::Anywhere);
 
         assert_eq!(
-            new_round.get_last_move().unwrap(),
-            Hand::Pass
+            round.submit_move("a", vec![PlayedCard::new(Rank::Two, Suit::Eagles, true)]).err(),
+            Some(SubmitError::InvalidHand)
         );
+        assert!(round.submit_move("a", vec![PlayedCard::new(Rank::Two, Suit::Spades, true)]).is_ok());
     }
 
     #[test]
-    fn playing_on_pass_sets_player_as_last_move() {
-        let a_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            }
-        ];
-        let b_cards = vec![];
-        let c_cards = vec![Card::Standard{
-            deck_id: 0,
-            rank: Rank::Three,
-            suit: Suit::Clubs,
-        }];
-        let player_a = Player::new("a".to_string(), a_cards);
-        let player_b = Player::new("b".to_string(), b_cards);
-        let player_c = Player::new("c".to_string(), c_cards);
+    fn jokers_can_be_barred_from_the_finishing_hand() {
+        let round = get_joker_round(JokerUsage::NotOnFinishingHand)
+            .submit_move("a", vec![PlayedCard::new(Rank::Four, Suit::Spades, false)])
+            .unwrap();
+        let joker = PlayedCard::new(Rank::Two, Suit::Spades, true);
 
-        let players = vec![player_a, player_b, player_c];
+        assert!(round.submit_move("b", vec![joker]).is_ok());
 
-        let last_move = Some(
-            Hand::Pass
+        let round = round.submit_move("b", vec![
+            PlayedCard::new(Rank::Nine, Suit::Spades, false)
+        ]).unwrap();
+        let round = round.submit_move("a", vec![]).unwrap();
+
+        assert_eq!(
+            round.submit_move("b", vec![joker]).err(),
+            Some(SubmitError::CannotFinishWithJoker)
         );
+    }
 
+    #[test]
+    fn temporary_reversals_end_when_the_trick_clears() {
+        let spade = |rank| Card::Standard { deck_id: 0, rank, suit: Suit::Spades };
+        let club = |rank| Card::Standard { deck_id: 0, rank, suit: Suit::Clubs };
         let round = Round::new(
-            players,
+            vec![
+                Player::new("a".to_string(), vec![
+                    spade(Rank::Nine),
+                    club(Rank::Nine),
+                    Card::Standard { deck_id: 0, rank: Rank::Nine, suit: Suit::Hearts },
+                    Card::Standard { deck_id: 0, rank: Rank::Nine, suit: Suit::Diamonds },
+                    club(Rank::Four),
+                    club(Rank::Five),
+                ]),
+                Player::new("b".to_string(), vec![spade(Rank::Two)]),
+            ],
             Some("a".to_string()),
-            last_move,
-            Some("c".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET
+            Some(Hand::Pass),
+            Some("b".to_string()),
+            OrderContext::standard(),
+            Ruleset {
+                reversal_duration: ReversalDuration::UntilTrickCleared,
+                ..DEFAULT_RULESET
+            }
         );
 
-        let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false)
-        ];
+        let round = round.submit_move("a", vec![
+            PlayedCard::new(Rank::Nine, Suit::Clubs, false),
+            PlayedCard::new(Rank::Nine, Suit::Hearts, false),
+            PlayedCard::new(Rank::Nine, Suit::Diamonds, false),
+            PlayedCard::new(Rank::Nine, Suit::Spades, false),
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+        ]).unwrap();
 
-        let new_round = round.submit_move(
-            "a",
-            played_hand
-        ).unwrap();
+        assert_eq!(round.get_rank_order()[0], Rank::Two);
+        assert!(round.is_temporarily_reversed());
+        assert!(round.is_reversed());
+        let json = serde_json::to_string(&round).unwrap();
+        assert!(serde_json::from_str::<Round>(&json).unwrap().is_reversed());
+
+        let round = round.submit_move("b", vec![]).unwrap();
+
+        assert_eq!(round.get_last_move(), Some(Hand::Pass));
+        assert_eq!(round.get_rank_order(), DEFAULT_RANK_ORDER);
+        assert_eq!(round.get_suit_order(), DEFAULT_SUIT_ORDER);
+        assert!(!round.is_temporarily_reversed());
+        assert!(!round.is_reversed());
+    }
+
+    #[test]
+    fn royal_flushes_can_beat_five_of_a_kind() {
+        let heart = |rank| Card::Standard { deck_id: 0, rank, suit: Suit::Hearts };
+        let royal: Vec<PlayedCard> = [Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace]
+            .iter()
+            .map(|&rank| PlayedCard::new(rank, Suit::Hearts, false))
+            .collect();
+        let five_nines = Hand::build(vec![
+            PlayedCard::new(Rank::Nine, Suit::Clubs, false),
+            PlayedCard::new(Rank::Nine, Suit::Hearts, false),
+            PlayedCard::new(Rank::Nine, Suit::Diamonds, false),
+            PlayedCard::new(Rank::Nine, Suit::Spades, false),
+            PlayedCard::new(Rank::Nine, Suit::Spades, true),
+        ]).unwrap();
+        let get_round = |royal_flush_tier| Round::new(
+            vec![
+                Player::new("a".to_string(), vec![Card::Joker { deck_id: 0 }]),
+                Player::new("b".to_string(), vec![
+                    heart(Rank::Ten),
+                    heart(Rank::Jack),
+                    heart(Rank::Queen),
+                    heart(Rank::King),
+                    heart(Rank::Ace),
+                    heart(Rank::Three),
+                ]),
+            ],
+            Some("b".to_string()),
+            Some(five_nines),
+            Some("a".to_string()),
+            OrderContext::standard(),
+            Ruleset { royal_flush_tier, ..DEFAULT_RULESET }
+        );
 
         assert_eq!(
-            new_round.get_last_player().unwrap(),
-            "a".to_string()
+            get_round(false).submit_move("b", royal.clone()).err(),
+            Some(SubmitError::HandNotHighEnough)
+        );
+
+        let round = get_round(true).submit_move("b", royal).unwrap();
+        assert_eq!(
+            round.get_last_move().and_then(|hand| hand.get_trick_type()),
+            Some(TrickType::RoyalFlush)
         );
     }
 
     #[test]
-    fn when_there_are_two_players_left_pass_clears_the_table() {
-        let a_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            }
-        ];
-        let b_cards = vec![];
-        let c_cards = vec![];
-        let d_cards = vec![
-             Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-        ];
-        let player_a = Player::new("a".to_string(), a_cards);
-        let player_b = Player::new("b".to_string(), b_cards);
-        let player_c = Player::new("c".to_string(), c_cards);
-        let player_d = Player::new("d".to_string(), d_cards);
-
+    fn try_new_accepts_a_consistent_round() {
         let players = vec![
-            player_a,
-            player_b,
-            player_c,
-            player_d
+            Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+                Card::Joker { deck_id: 0 },
+            ]),
+            Player::new("b".to_string(), vec![
+                Card::Standard { deck_id: 1, rank: Rank::Three, suit: Suit::Clubs },
+                Card::Joker { deck_id: 0 },
+            ]),
         ];
 
-        let last_move = Some(
-            Hand::Pair(
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                ),
-                PlayedCard::new(
-                    Rank::Three,
-                    Suit::Clubs,
-                    false,
-                )
-            )
-        );
-
-        let round = Round::new(
+        let round = Round::try_new(
             players,
             Some("a".to_string()),
-            last_move,
-            Some("d".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
+            None,
+            None,
+            OrderContext::standard(),
             DEFAULT_RULESET
         );
 
-        let played_hand = vec![];
+        assert!(round.is_ok());
+    }
 
-        let new_round = round.submit_move(
-            "a",
-            played_hand
-        ).unwrap();
+    #[test]
+    fn try_new_rejects_inconsistent_rounds() {
+        let three_clubs = Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs };
+        let players = vec![
+            Player::new("a".to_string(), vec![three_clubs]),
+            Player::new("b".to_string(), vec![three_clubs]),
+        ];
+        let try_new = |next_player: &str, last_player: &str, orders| {
+            Round::try_new(
+                players.clone(),
+                Some(next_player.to_string()),
+                None,
+                Some(last_player.to_string()),
+                orders,
+                DEFAULT_RULESET
+            ).err()
+        };
+        // orders sent by a client haven't been through `OrderContext::new`
+        let unchecked = |suit_order: [Suit; 4], rank_order: [Rank; 13]| {
+            serde_json::from_value::<OrderContext>(serde_json::json!({
+                "suit_order": suit_order,
+                "rank_order": rank_order,
+                "reversed": false,
+            })).unwrap()
+        };
+        let mut bad_rank_order = DEFAULT_RANK_ORDER;
+        bad_rank_order[0] = Rank::Two;
 
         assert_eq!(
-            new_round.get_next_player().unwrap(),
-            "d".to_string()
+            try_new("z", "a", OrderContext::standard()),
+            Some(RoundError::UnknownNextPlayer("z".to_string()))
         );
-
         assert_eq!(
-            new_round.get_last_move().unwrap(),
-            Hand::Pass
+            try_new("a", "y", OrderContext::standard()),
+            Some(RoundError::UnknownLastPlayer("y".to_string()))
+        );
+        assert_eq!(
+            try_new("a", "b", unchecked([Suit::Clubs; 4], DEFAULT_RANK_ORDER)),
+            Some(RoundError::InvalidSuitOrder)
+        );
+        assert_eq!(
+            try_new("a", "b", unchecked(DEFAULT_SUIT_ORDER, bad_rank_order)),
+            Some(RoundError::InvalidRankOrder)
+        );
+        assert_eq!(
+            try_new("a", "b", OrderContext::standard()),
+            Some(RoundError::DuplicateCard(three_clubs))
         );
     }
 
-    #[test]
-    fn when_reversals_are_enabled_4ofakind_reverses_orders() {
-        let a_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Four,
-                suit: Suit::Clubs,
-            }
-        ];
-        let b_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-        ];
-        let c_cards = vec![Card::Standard{
-            deck_id: 0,
-            rank: Rank::Three,
-            suit: Suit::Clubs,
-        }];
-        let player_a = Player::new("a".to_string(), a_cards);
-        let player_b = Player::new("b".to_string(), b_cards);
-        let player_c = Player::new("c".to_string(), c_cards);
+    fn get_last_card_round(last_card: LastCardRule) -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+                Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Clubs },
+            ]))
+            .player(Player::new("b".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Five, suit: Suit::Clubs },
+                Card::Standard { deck_id: 0, rank: Rank::Six, suit: Suit::Clubs },
+            ]))
+            .next_player("a")
+            .ruleset(Ruleset { last_card, ..DEFAULT_RULESET })
+            .build()
+            .unwrap()
+    }
 
-        let players = vec![player_a, player_b, player_c];
-        let last_move = Some(Hand::Pass);
+    #[test]
+    fn a_declared_last_card_goes_unpunished() {
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+        let five = PlayedCard::new(Rank::Five, Suit::Clubs, false);
+        let round = get_last_card_round(LastCardRule::PickUp(2))
+            .submit_move("a", vec![three])
+            .unwrap();
 
-        let round = Round::new(
-            players,
-            Some("a".to_string()),
-            last_move,
-            Some("b".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET
+        assert_eq!(round.get_last_card_pending(), Some("a".to_string()));
+        assert_eq!(
+            round.declare_last_card("b").err(),
+            Some(LastCardError::NothingToDeclare)
         );
 
-        let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Four, Suit::Clubs, false),
-        ];
+        let round = round.declare_last_card("a")
+            .unwrap()
+            .submit_move("b", vec![five])
+            .unwrap();
 
-        let new_round = round.submit_move(
-            "a",
-            played_hand
-        ).unwrap();
+        assert_eq!(round.get_player("a").unwrap().get_card_count(), 1);
+        assert!(round.get_missed_last_card().is_empty());
+    }
 
-        let mut expected_suit_order = DEFAULT_SUIT_ORDER;
-        let mut expected_rank_order = DEFAULT_RANK_ORDER;
-        expected_suit_order.reverse();
-        expected_rank_order.reverse();
+    #[test]
+    fn a_missed_last_card_picks_up_the_latest_cards() {
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+        let five = PlayedCard::new(Rank::Five, Suit::Clubs, false);
+        let round = get_last_card_round(LastCardRule::PickUp(2))
+            .submit_move("a", vec![three])
+            .and_then(|r| r.submit_move("b", vec![five]))
+            .unwrap();
+
+        // the penalty is paid before b's move lands, so only the three
+        // was there to pick up
+        let a = round.get_player("a").unwrap();
+        assert_eq!(a.get_card_count(), 2);
+        assert!(a.has_card(three.to_card()));
+        assert_eq!(round.get_discarded(), vec![five.to_card()]);
+        assert_eq!(round.get_missed_last_card(), vec!["a".to_string()]);
+        assert_eq!(round.verify_card_conservation(), Ok(()));
+    }
+
+    #[test]
+    fn rejected_moves_leave_a_pending_last_card_alone() {
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+        let mut round = get_last_card_round(LastCardRule::PickUp(2))
+            .submit_move("a", vec![three])
+            .unwrap();
+        let hash = round.state_hash();
 
         assert_eq!(
-            new_round.get_suit_order(),
-            expected_suit_order
+            round.submit_move_mut("b", vec![PlayedCard::new(Rank::King, Suit::Clubs, false)]),
+            Err(SubmitError::PlayerDoesntHaveCard)
+        );
+        assert_eq!(
+            round.submit_exact_move_mut(
+                "b",
+                vec![PlayedCard::new(Rank::Five, Suit::Clubs, false)],
+                vec![Card::Standard { deck_id: 1, rank: Rank::Five, suit: Suit::Clubs }],
+            ),
+            Err(SubmitError::PlayerDoesntHaveCard)
         );
+        assert_eq!(round.state_hash(), hash);
+        assert_eq!(round.get_last_card_pending(), Some("a".to_string()));
+        assert!(round.get_missed_last_card().is_empty());
+    }
+
+    #[test]
+    fn last_card_needs_the_rule_in_play() {
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+        let round = get_last_card_round(LastCardRule::Off)
+            .submit_move("a", vec![three])
+            .unwrap();
 
+        assert_eq!(round.get_last_card_pending(), None);
         assert_eq!(
-            new_round.get_rank_order(),
-            expected_rank_order
+            round.declare_last_card("a").err(),
+            Some(LastCardError::NotInPlay)
         );
     }
 
+    fn get_final_card_round(final_card: FinalCardRule) -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Two, suit: Suit::Clubs },
+            ]))
+            .player(Player::new("b".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Five, suit: Suit::Clubs },
+            ]))
+            .next_player("a")
+            .table_cleared("b")
+            .ruleset(Ruleset { final_card, ..DEFAULT_RULESET })
+            .build()
+            .unwrap()
+    }
+
     #[test]
-    fn when_reversals_are_enabled_only_a_4or5ofakind_reverses() {
-        let a_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Four,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Four,
-                suit: Suit::Clubs,
-            }
-        ];
-        let b_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-        ];
-        let c_cards = vec![Card::Standard{
-            deck_id: 0,
-            rank: Rank::Three,
-            suit: Suit::Clubs,
-        }];
-        let player_a = Player::new("a".to_string(), a_cards);
-        let player_b = Player::new("b".to_string(), b_cards);
-        let player_c = Player::new("c".to_string(), c_cards);
+    fn going_out_on_a_two_can_be_forbidden() {
+        let two = PlayedCard::new(Rank::Two, Suit::Clubs, false);
 
-        let players = vec![player_a, player_b, player_c];
-        let last_move = Some(Hand::Pass);
+        assert_eq!(
+            get_final_card_round(FinalCardRule::Forbidden).submit_move("a", vec![two]).err(),
+            Some(SubmitError::IllegalFinalCard)
+        );
+        assert!(get_final_card_round(FinalCardRule::Any).submit_move("a", vec![two]).is_ok());
+    }
 
-        let round = Round::new(
-            players,
-            Some("a".to_string()),
-            last_move,
-            Some("b".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            DEFAULT_RULESET
+    #[test]
+    fn going_out_on_a_two_can_be_penalised() {
+        let two = PlayedCard::new(Rank::Two, Suit::Clubs, false);
+        let round = get_final_card_round(FinalCardRule::Penalty(10))
+            .submit_move("a", vec![two])
+            .unwrap();
+
+        assert_eq!(round.get_player("a").unwrap().get_card_count(), 0);
+        assert_eq!(round.get_illegal_finishers(), vec!["a".to_string()]);
+        assert_eq!(
+            crate::game::get_game_result(&round, &["a".to_string()]).players[0].penalty_points,
+            10
         );
+    }
 
-        let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Four, Suit::Clubs, false),
-            PlayedCard::new(Rank::Four, Suit::Clubs, false),
-        ];
+    #[test]
+    fn moves_and_tricks_are_counted() {
+        let club = |rank| Card::Standard { deck_id: 0, rank, suit: Suit::Clubs };
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![club(Rank::Three), club(Rank::Four)]))
+            .player(Player::new("b".to_string(), vec![club(Rank::Two)]))
+            .move_count(10)
+            .trick_index(2)
+            .next_player("a")
+            .table_cleared("b")
+            .build()
+            .unwrap();
 
-        let new_round = round.submit_move(
-            "a",
-            played_hand
-        ).unwrap();
+        assert_eq!(round.get_move_count(), 10);
+        assert_eq!(round.get_trick_index(), 2);
 
-        let expected_suit_order = DEFAULT_SUIT_ORDER;
-        let expected_rank_order = DEFAULT_RANK_ORDER;
+        let round = round
+            .submit_move("a", vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)])
+            .unwrap()
+            .submit_move("b", vec![])
+            .unwrap();
 
+        assert_eq!(round.get_move_count(), 12);
+        assert_eq!(round.get_trick_index(), 3);
+    }
+
+    #[test]
+    fn rounds_that_cant_progress_are_dead() {
+        let three = Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs };
+        let four = Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Clubs };
+        let get_round = |a_hand: Vec<Card>, b_hand: Vec<Card>, next_player: Option<&str>| {
+            Round::new(
+                vec![
+                    Player::new("a".to_string(), a_hand),
+                    Player::new("b".to_string(), b_hand),
+                ],
+                next_player.map(|id| id.to_string()),
+                Some(Hand::Pass),
+                Some("b".to_string()),
+                OrderContext::standard(),
+                DEFAULT_RULESET,
+            )
+        };
+
+        assert_eq!(get_round(vec![three], vec![four], Some("a")).get_status(), GameStatus::InProgress);
         assert_eq!(
-            new_round.get_suit_order(),
-            expected_suit_order
+            get_round(vec![three], vec![], None).get_status(),
+            GameStatus::Finished { placements: vec!["b".to_string(), "a".to_string()] }
+        );
+        assert_eq!(
+            get_round(vec![three], vec![four], Some("z")).get_status(),
+            GameStatus::Dead(DeadGameReason::UnknownNextPlayer("z".to_string()))
+        );
+        assert_eq!(
+            get_round(vec![three], vec![four], None).get_status(),
+            GameStatus::Dead(DeadGameReason::NoNextPlayer)
         );
 
+        let empty = get_round(vec![], vec![], Some("a"));
+        assert_eq!(empty.get_status(), GameStatus::Dead(DeadGameReason::NoCardsLeft));
+        assert_eq!(empty.submit_move("a", vec![]).err(), Some(SubmitError::DeadGame));
+
+        let stuck = get_round(vec![], vec![four], Some("a"));
         assert_eq!(
-            new_round.get_rank_order(),
-            expected_rank_order
+            stuck.get_status(),
+            GameStatus::Dead(DeadGameReason::NextPlayerFinished("a".to_string()))
         );
+        assert_eq!(stuck.submit_move("a", vec![]).err(), Some(SubmitError::DeadGame));
     }
 
     #[test]
-    fn when_reversals_are_not_enabled_no_reversals() {
-        let a_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Four,
-                suit: Suit::Clubs,
-            }
-        ];
-        let b_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-        ];
-        let c_cards = vec![Card::Standard{
-            deck_id: 0,
-            rank: Rank::Three,
-            suit: Suit::Clubs,
-        }];
-        let player_a = Player::new("a".to_string(), a_cards);
-        let player_b = Player::new("b".to_string(), b_cards);
-        let player_c = Player::new("c".to_string(), c_cards);
+    fn finished_players_cant_move_again() {
+        use crate::scenario::{parse_played_cards, Scenario};
+
+        let round = Scenario::new()
+            .player("a", "3C")
+            .player("b", "5C 6C")
+            .player("c", "7C 8C")
+            .build()
+            .unwrap()
+            .submit_move("a", parse_played_cards("3C").unwrap())
+            .unwrap();
 
-        let players = vec![player_a, player_b, player_c];
-        let last_move = Some(Hand::Pass);
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+        assert_eq!(round.submit_move("a", vec![]).err(), Some(SubmitError::PlayerAlreadyFinished));
 
-        let ruleset = Ruleset {
-            reversals_enabled: false,
-            flush_precedence: FlushPrecedence::Rank
+        let round = round.submit_move("b", vec![]).unwrap();
+        let round = round.submit_move("c", vec![]).unwrap();
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+    }
+
+    #[test]
+    fn status_runs_from_not_started_to_placements() {
+        use crate::scenario::{parse_played_cards, Scenario};
+
+        let round = Scenario::new()
+            .player("a", "3C AC")
+            .player("b", "5C 6C 7C")
+            .player("c", "8C")
+            .player("d", "9C 10C")
+            .build()
+            .unwrap();
+        assert_eq!(round.get_status(), GameStatus::NotStarted);
+
+        let play = |round: Round, player_id: &str, cards: &str| {
+            round.submit_move(player_id, parse_played_cards(cards).unwrap()).unwrap()
         };
+        let round = play(round, "a", "3C");
+        assert_eq!(round.get_status(), GameStatus::InProgress);
+        assert!(!round.is_game_over());
+
+        let round = play(round, "b", "5C");
+        let round = play(round, "c", "8C");
+        let round = round.resign("d").unwrap();
+        let round = play(round, "a", "AC");
+
+        assert!(round.is_game_over());
+        assert_eq!(round.get_status(), GameStatus::Finished {
+            placements: vec![
+                "c".to_string(),
+                "a".to_string(),
+                "b".to_string(),
+                "d".to_string(),
+            ],
+        });
+    }
 
-        let round = Round::new(
-            players,
-            Some("a".to_string()),
-            last_move,
-            Some("b".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            ruleset
-        );
+    #[test]
+    fn the_first_trick_winner_takes_the_blind_kitty() {
+        use crate::scenario::{parse_cards, parse_played_cards, Scenario};
 
-        let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-            PlayedCard::new(Rank::Four, Suit::Clubs, false),
-        ];
+        let mut round = Scenario::new()
+            .player("a", "3C 4C 9D")
+            .player("b", "5C 6H 7S")
+            .build()
+            .unwrap();
+        round.set_kitty(parse_cards("KH KS").unwrap());
+        let play = |round: &Round, id: &str, cards: &str| {
+            round.submit_move(id, parse_played_cards(cards).unwrap())
+        };
 
-        let new_round = round.submit_move(
-            "a",
-            played_hand
-        ).unwrap();
+        let round = play(&round, "a", "3C").unwrap();
+        let round = play(&round, "b", "5C").unwrap();
+        assert_eq!(round.get_kitty_stage(), KittyStage::Closed);
+        let round = play(&round, "a", "").unwrap();
 
-        let expected_suit_order = DEFAULT_SUIT_ORDER;
-        let expected_rank_order = DEFAULT_RANK_ORDER;
+        assert_eq!(round.get_kitty_stage(), KittyStage::PickUp("b".to_string()));
+        assert_eq!(play(&round, "b", "7S").unwrap_err(), SubmitError::KittyPending);
+        assert_eq!(round.discard_to_kitty("b", vec![]).unwrap_err(), KittyError::NothingToDiscard);
+        assert_eq!(round.pick_up_kitty("a").unwrap_err(), KittyError::NotKittyPlayer);
 
+        let round = round.pick_up_kitty("b").unwrap();
+        assert_eq!(round.get_player("b").unwrap().get_card_count(), 4);
+        assert!(round.get_kitty().is_empty());
         assert_eq!(
-            new_round.get_suit_order(),
-            expected_suit_order
+            round.discard_to_kitty("b", parse_cards("KH").unwrap()).unwrap_err(),
+            KittyError::WrongDiscardCount(2)
         );
-
+        let nine = parse_cards("9D").unwrap();
         assert_eq!(
-            new_round.get_rank_order(),
-            expected_rank_order
+            round.discard_to_kitty("b", parse_cards("KH 9D").unwrap()).unwrap_err(),
+            KittyError::PlayerDoesntHaveCard(nine[0])
         );
+
+        let round = round.discard_to_kitty("b", parse_cards("KH 6H").unwrap()).unwrap();
+        assert_eq!(round.get_kitty_stage(), KittyStage::Done);
+        assert_eq!(round.get_kitty(), parse_cards("KH 6H").unwrap());
+        assert_eq!(round.get_player("b").unwrap().get_hand(), parse_cards("7S KS").unwrap());
+        assert_eq!(round.verify_card_conservation(), Ok(()));
+
+        assert!(play(&round, "b", "7S").is_ok());
     }
 
     #[test]
-    fn deck_id_is_not_checked_when_move_played() {
-        let a_cards = vec![
-            Card::Standard {
-                deck_id: 1,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Four,
-                suit: Suit::Clubs,
-            },
-        ];
-        let b_cards = vec![
-            Card::Standard {
-                deck_id: 0,
-                rank: Rank::Three,
-                suit: Suit::Clubs,
-            },
-        ];
-        let c_cards = vec![Card::Standard{
-            deck_id: 0,
-            rank: Rank::Three,
-            suit: Suit::Clubs,
-        }];
-        let player_a = Player::new("a".to_string(), a_cards);
-        let player_b = Player::new("b".to_string(), b_cards);
-        let player_c = Player::new("c".to_string(), c_cards);
+    fn cleared_tricks_record_their_raises() {
+        use crate::scenario::{parse_played_cards, Scenario};
 
-        let players = vec![player_a, player_b, player_c];
-        let last_move = Some(Hand::Pass);
-        let ruleset = Ruleset {
-            reversals_enabled: false,
-            flush_precedence: FlushPrecedence::Rank
+        let round = Scenario::new()
+            .player("a", "3C 9S 4D")
+            .player("b", "5C 6H 7S")
+            .build()
+            .unwrap();
+        let play = |round: &Round, id: &str, cards: &str| {
+            round.submit_move(id, parse_played_cards(cards).unwrap()).unwrap()
         };
 
-        let round = Round::new(
-            players,
-            Some("a".to_string()),
-            last_move,
-            Some("b".to_string()),
-            DEFAULT_SUIT_ORDER,
-            DEFAULT_RANK_ORDER,
-            ruleset
+        let round = play(&round, "a", "3C");
+        let round = play(&round, "b", "5C");
+        let round = play(&round, "a", "9S");
+        assert_eq!(round.current_trick_length(), 3);
+        assert!(round.get_trick_history().is_empty());
+
+        let round = play(&round, "b", "");
+        assert_eq!(round.current_trick_length(), 0);
+        let round = play(&round, "a", "4D");
+        assert_eq!(round.current_trick_length(), 1);
+        assert_eq!(round.get_trick_history(), vec![
+            TrickRecord { winner_id: "a".to_string(), raises: 2 },
+        ]);
+    }
+
+    #[test]
+    fn play_follows_the_turn_order_and_seat_order() {
+        use crate::scenario::{parse_played_cards, Scenario};
+
+        let ruleset = Ruleset { turn_order: TurnOrder::CounterClockwise, ..Ruleset::default() };
+        let scenario = Scenario::new()
+            .player("a", "3C 9S")
+            .player("b", "5C 6H")
+            .player("c", "7C 8H")
+            .player("d", "JC QH");
+        let three = parse_played_cards("3C").unwrap();
+
+        let round = scenario.clone().ruleset(ruleset).build().unwrap();
+        assert_eq!(round.get_turn_order(), vec!["a", "d", "c", "b"]);
+        let round = round.submit_move("a", three.clone()).unwrap();
+        assert_eq!(round.get_next_player(), Some("d".to_string()));
+
+        let mut round = scenario.build().unwrap();
+        round.set_seat_order(vec![
+            "a".to_string(), "c".to_string(), "b".to_string(), "d".to_string(),
+        ]).unwrap();
+        let round = round.submit_move("a", three).unwrap();
+        assert_eq!(round.get_next_player(), Some("c".to_string()));
+
+        let mut round = round;
+        assert_eq!(
+            round.set_seat_order(vec!["a".to_string(), "b".to_string()]),
+            Err(SetupError::InvalidSeatOrder)
         );
+        assert_eq!(
+            round.set_seat_order(vec![
+                "a".to_string(), "a".to_string(), "b".to_string(), "c".to_string(),
+            ]),
+            Err(SetupError::DuplicatePlayer("a".to_string()))
+        );
+    }
 
-        let played_hand = vec![
-            PlayedCard::new(Rank::Three, Suit::Clubs, false),
-        ];
+    #[test]
+    fn exact_moves_take_the_named_copies() {
+        use crate::scenario::parse_played_cards;
+
+        let three = |deck_id| Card::Standard { deck_id, rank: Rank::Three, suit: Suit::Clubs };
+        let four = |deck_id| Card::Standard { deck_id, rank: Rank::Four, suit: Suit::Clubs };
+        let mut round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![three(0), three(1), four(1)]))
+            .player(Player::new("b".to_string(), vec![four(0)]))
+            .next_player("a")
+            .build()
+            .unwrap();
+        let three_clubs = parse_played_cards("3C").unwrap();
 
-        let new_round = round.submit_move(
-            "a",
-            played_hand
+        assert!(round.submit_move("a", three_clubs.clone()).is_ok());
+        round.set_strict_card_identity(true);
+        assert_eq!(
+            round.submit_move("a", three_clubs.clone()).err(),
+            Some(SubmitError::AmbiguousCardCopy)
+        );
+        assert_eq!(
+            round.submit_exact_move("a", three_clubs.clone(), vec![four(1)]).err(),
+            Some(SubmitError::InvalidHand)
+        );
+        assert_eq!(
+            round.submit_exact_move("a", three_clubs.clone(), vec![three(2)]).err(),
+            Some(SubmitError::PlayerDoesntHaveCard)
         );
 
-        assert!(new_round.is_ok());
+        round.submit_exact_move_mut("a", three_clubs, vec![three(1)]).unwrap();
+        assert_eq!(round.get_player("a").unwrap().get_hand(), vec![three(0), four(1)]);
+        assert_eq!(round.get_discarded(), vec![three(1)]);
+
+        let four_clubs = parse_played_cards("4C").unwrap();
+        assert!(round.submit_move("b", four_clubs).is_ok());
     }
 
+    #[test]
+    fn simulation_forks_share_hands_and_drop_history() {
+        use crate::scenario::{parse_played_cards, Scenario};
+
+        let hands = Scenario::new()
+            .player("a", "3C 4C 9S")
+            .player("b", "5C 6C")
+            .build()
+            .unwrap();
+        let ann = hands.get_player("a").unwrap().with_metadata(PlayerMetadata {
+            display_name: Some("Ann".to_string()),
+            ..PlayerMetadata::default()
+        });
+        let round = RoundBuilder::new()
+            .player(ann)
+            .player(hands.get_player("b").unwrap())
+            .next_player("a")
+            .build()
+            .unwrap()
+            .submit_move("a", parse_played_cards("3C").unwrap())
+            .unwrap();
+
+        let fork = round.fork_for_simulation();
+        assert!(fork.is_simulation());
+        assert!(!round.is_simulation());
+        assert!(Arc::ptr_eq(&fork.players[1], &round.players[1]));
+        assert!(Arc::ptr_eq(&fork.dealt, &round.dealt));
+        assert_eq!(fork.get_player("a").unwrap().get_metadata(), &PlayerMetadata::default());
+        assert!(fork.last_n_moves(4).is_empty());
+        assert_eq!(fork.state_hash(), round.state_hash());
+
+        let played = parse_played_cards("5C").unwrap();
+        let forked = fork.submit_move("b", played.clone()).unwrap();
+        let full = round.submit_move("b", played).unwrap();
+        assert!(forked.last_n_moves(4).is_empty());
+        assert_eq!(forked.get_next_player(), full.get_next_player());
+        assert_eq!(forked.state_hash(), full.state_hash());
+    }
 }
@@ -0,0 +1,353 @@
+use super::{check_player_ids, Hand, OrderContext, PlayedHand, Player, Round, Ruleset, SetupError};
+use crate::cards::{get_rank_array, get_suit_array, Card, Rank, Suit};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum RoundBuilderError {
+    NoPlayers,
+    /// More than one player has this id
+    DuplicatePlayer(String),
+    LastMoveWithoutLastPlayer,
+    InvalidSuitOrder,
+    InvalidRankOrder,
+    InvalidSeatOrder,
+}
+
+impl From<SetupError> for RoundBuilderError {
+    fn from(err: SetupError) -> RoundBuilderError {
+        match err {
+            SetupError::NoPlayers => RoundBuilderError::NoPlayers,
+            SetupError::DuplicatePlayer(id) => RoundBuilderError::DuplicatePlayer(id),
+            SetupError::InvalidSuitOrder => RoundBuilderError::InvalidSuitOrder,
+            SetupError::InvalidRankOrder => RoundBuilderError::InvalidRankOrder,
+            SetupError::InvalidSeatOrder => RoundBuilderError::InvalidSeatOrder,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RoundBuilder {
+    players: Vec<Player>,
+    next_player: Option<String>,
+    last_move: Option<Hand>,
+    last_player: Option<String>,
+    suit_order: Vec<Suit>,
+    rank_order: [Rank; 13],
+    ruleset: Ruleset,
+    passed: Vec<String>,
+    pass_count: usize,
+    move_count: usize,
+    trick_index: usize,
+    table: Vec<PlayedHand>,
+    discarded: Vec<Card>,
+    resigned: Vec<String>,
+    reversed: bool,
+    temporarily_reversed: bool,
+    kitty: Vec<Card>,
+    seat_order: Vec<String>,
+}
+
+impl Default for RoundBuilder {
+    fn default() -> RoundBuilder {
+        RoundBuilder::new()
+    }
+}
+
+impl RoundBuilder {
+    pub fn new() -> RoundBuilder {
+        RoundBuilder {
+            players: vec![],
+            next_player: None,
+            last_move: None,
+            last_player: None,
+            suit_order: get_suit_array().to_vec(),
+            rank_order: get_rank_array(),
+            ruleset: Ruleset::default(),
+            passed: vec![],
+            pass_count: 0,
+            move_count: 0,
+            trick_index: 0,
+            table: vec![],
+            discarded: vec![],
+            resigned: vec![],
+            reversed: false,
+            temporarily_reversed: false,
+            kitty: vec![],
+            seat_order: vec![],
+        }
+    }
+
+    pub fn players(mut self, players: Vec<Player>) -> RoundBuilder {
+        self.players = players;
+        self
+    }
+
+    pub fn player(mut self, player: Player) -> RoundBuilder {
+        self.players.push(player);
+        self
+    }
+
+    pub fn next_player(mut self, id: &str) -> RoundBuilder {
+        self.next_player = Some(id.to_string());
+        self
+    }
+
+    pub fn last_move(mut self, hand: Hand, player_id: &str) -> RoundBuilder {
+        self.last_move = Some(hand);
+        self.last_player = Some(player_id.to_string());
+        self
+    }
+
+    pub fn table_cleared(mut self, last_player_id: &str) -> RoundBuilder {
+        self.last_move = Some(Hand::Pass);
+        self.last_player = Some(last_player_id.to_string());
+        self
+    }
+
+    pub fn suit_order(mut self, suit_order: &[Suit]) -> RoundBuilder {
+        self.suit_order = suit_order.to_vec();
+        self
+    }
+
+    pub fn rank_order(mut self, rank_order: [Rank; 13]) -> RoundBuilder {
+        self.rank_order = rank_order;
+        self
+    }
+
+    pub fn ruleset(mut self, ruleset: Ruleset) -> RoundBuilder {
+        self.ruleset = ruleset;
+        self
+    }
+
+    /// Players who have already passed on the current trick, for rulesets
+    /// that clear the table once everyone has passed.
+    pub fn passed_players(mut self, passed: Vec<String>) -> RoundBuilder {
+        self.passed = passed;
+        self
+    }
+
+    pub fn pass_count(mut self, pass_count: usize) -> RoundBuilder {
+        self.pass_count = pass_count;
+        self
+    }
+
+    /// Moves already made in the game, passes included.
+    pub fn move_count(mut self, move_count: usize) -> RoundBuilder {
+        self.move_count = move_count;
+        self
+    }
+
+    pub fn trick_index(mut self, trick_index: usize) -> RoundBuilder {
+        self.trick_index = trick_index;
+        self
+    }
+
+    pub fn table(mut self, table: Vec<PlayedHand>) -> RoundBuilder {
+        self.table = table;
+        self
+    }
+
+    /// Cards already played out of players' hands.
+    pub fn discarded(mut self, discarded: Vec<Card>) -> RoundBuilder {
+        self.discarded = discarded;
+        self
+    }
+
+    pub fn resigned_players(mut self, resigned: Vec<String>) -> RoundBuilder {
+        self.resigned = resigned;
+        self
+    }
+
+    /// The orders given are reversed from those the game started with.
+    pub fn reversed(mut self, reversed: bool) -> RoundBuilder {
+        self.reversed = reversed;
+        self
+    }
+
+    /// The orders given are a temporary reversal that flips back when the
+    /// current trick clears.
+    pub fn temporarily_reversed(mut self, reversed: bool) -> RoundBuilder {
+        self.temporarily_reversed = reversed;
+        self
+    }
+
+    /// Cards dealt face down for the winner of the first trick.
+    pub fn kitty(mut self, kitty: Vec<Card>) -> RoundBuilder {
+        self.kitty = kitty;
+        self
+    }
+
+    /// The order play passes round the players, if it isn't the order
+    /// they were added in.
+    pub fn seat_order(mut self, player_ids: Vec<String>) -> RoundBuilder {
+        self.seat_order = player_ids;
+        self
+    }
+
+    pub fn build(self) -> Result<Round, RoundBuilderError> {
+        let player_ids: Vec<String> = self.players.iter()
+            .map(|p| p.get_id().to_string())
+            .collect();
+        check_player_ids(&player_ids)?;
+        let orders = OrderContext::new(&self.suit_order, self.rank_order)?;
+
+        if self.last_move.is_some() && self.last_player.is_none() {
+            return Err(RoundBuilderError::LastMoveWithoutLastPlayer);
+        }
+
+        let mut round = Round::new(
+            self.players,
+            self.next_player,
+            self.last_move,
+            self.last_player,
+            orders,
+            self.ruleset,
+        );
+        round.set_passed_players(self.passed);
+        round.set_pass_count(self.pass_count);
+        round.set_move_count(self.move_count);
+        round.set_trick_index(self.trick_index);
+        round.set_table(self.table);
+        round.set_discarded(self.discarded);
+        round.set_resigned_players(self.resigned);
+        round.set_reversed(self.reversed || self.temporarily_reversed);
+        round.set_temporarily_reversed(self.temporarily_reversed);
+        round.set_kitty(self.kitty);
+        round.set_seat_order(self.seat_order)?;
+
+        Ok(round)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{
+        CrossCountBombs,
+        DealMode,
+        FalseClaimPenalty,
+        FinalCardRule,
+        FlushPrecedence,
+        JokerUsage,
+        LastCardRule,
+        MisdealConditions,
+        RedealVote,
+        ReversalDuration,
+        ReversalTriggers,
+        StraightComparison,
+        TableClearMode,
+        TrickRanking,
+        TurnOrder,
+    };
+
+    fn get_players() -> Vec<Player> {
+        vec![
+            Player::new(
+                "a".to_string(),
+                vec![Card::Standard {
+                    deck_id: 0,
+                    rank: Rank::Three,
+                    suit: Suit::Clubs,
+                }],
+            ),
+            Player::new(
+                "b".to_string(),
+                vec![Card::Standard {
+                    deck_id: 0,
+                    rank: Rank::Four,
+                    suit: Suit::Clubs,
+                }],
+            ),
+        ]
+    }
+
+    #[test]
+    fn it_requires_players() {
+        let err = RoundBuilder::new().build().err().unwrap();
+
+        assert_eq!(err, RoundBuilderError::NoPlayers);
+    }
+
+    #[test]
+    fn it_rejects_duplicate_player_ids() {
+        let mut players = get_players();
+        players.push(Player::new("a".to_string(), vec![]));
+
+        let err = RoundBuilder::new().players(players).build().err().unwrap();
+
+        assert_eq!(err, RoundBuilderError::DuplicatePlayer("a".to_string()));
+    }
+
+    #[test]
+    fn it_uses_standard_orders_by_default() {
+        let round = RoundBuilder::new()
+            .players(get_players())
+            .build()
+            .unwrap();
+
+        assert_eq!(round.get_suit_order(), get_suit_array());
+        assert_eq!(round.get_rank_order(), get_rank_array());
+        assert_eq!(round.get_next_player(), Some("a".to_string()));
+        assert_eq!(round.get_last_move(), None);
+    }
+
+    #[test]
+    fn it_can_set_the_state_of_the_table() {
+        let last_move = Hand::Single(
+            PlayedCard::new(Rank::Three, Suit::Clubs, false)
+        );
+        let round = RoundBuilder::new()
+            .players(get_players())
+            .next_player("b")
+            .last_move(last_move, "a")
+            .build()
+            .unwrap();
+
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+        assert_eq!(round.get_last_move(), Some(last_move));
+        assert_eq!(round.get_last_player(), Some("a".to_string()));
+    }
+
+    #[test]
+    fn it_can_override_orders_and_ruleset() {
+        let mut suit_order = get_suit_array();
+        suit_order.reverse();
+        let ruleset = Ruleset {
+            preset: None,
+            reversals_enabled: false,
+            reversal_triggers: ReversalTriggers::classic(),
+            reversal_duration: ReversalDuration::RestOfGame,
+            flush_precedence: FlushPrecedence::Suit,
+            straight_comparison: StraightComparison::RankThenSuit,
+            partnership: false,
+            opening_requires_lowest_card: true,
+            table_clear: TableClearMode::ReturnToLastPlayer,
+            joker_usage: JokerUsage::Anywhere,
+            royal_flush_tier: false,
+            misdeal: MisdealConditions::none(),
+            redeal_vote: RedealVote::Off,
+            deal_mode: DealMode::FullDeck,
+            last_card: LastCardRule::Off,
+            final_card: FinalCardRule::Any,
+            false_claim: FalseClaimPenalty::Resign,
+            trick_ranking: TrickRanking::standard(),
+            twos_in_straights: true,
+            pass_locks_out: false,
+            cross_count_bombs: CrossCountBombs::none(),
+            strict_finish: false,
+            turn_order: TurnOrder::Clockwise,
+        };
+
+        let round = RoundBuilder::new()
+            .player(get_players()[0].clone())
+            .suit_order(&suit_order)
+            .ruleset(ruleset)
+            .build()
+            .unwrap();
+
+        assert_eq!(round.get_suit_order(), suit_order);
+        assert_eq!(round.get_ruleset(), ruleset);
+    }
+}
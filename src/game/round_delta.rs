@@ -0,0 +1,160 @@
+use super::{Hand, Round};
+use crate::cards::{Card, Rank, Suit};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct CardsRemoved {
+    pub player_id: String,
+    pub cards: Vec<Card>,
+}
+
+/// What changed between two rounds of the same game. Fields are `None`
+/// when the value is unchanged, so clients can patch their copy of the
+/// state instead of being sent the whole round again.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct RoundDelta {
+    pub cards_removed: Vec<CardsRemoved>,
+    pub last_move: Option<Hand>,
+    pub last_player: Option<String>,
+    pub next_player: Option<String>,
+    /// Set when the game has finished and there is no next player
+    pub game_over: bool,
+    /// The new suit order, if it was reversed
+    pub suit_order: Option<Vec<Suit>>,
+    /// The new rank order, if it was reversed
+    pub rank_order: Option<[Rank; 13]>,
+}
+
+impl RoundDelta {
+    pub fn is_empty(&self) -> bool {
+        *self == RoundDelta::default()
+    }
+}
+
+impl Round {
+    pub fn diff(&self, other: &Round) -> RoundDelta {
+        let cards_removed = self.get_player_ids().iter()
+            .filter_map(|id| {
+                let before = self.get_player(id)?.get_hand();
+                let mut after = other.get_player(id)
+                    .map(|p| p.get_hand())
+                    .unwrap_or_default();
+                let cards = remove_remaining(before, &mut after);
+
+                if cards.is_empty() {
+                    None
+                } else {
+                    Some(CardsRemoved { player_id: id.to_string(), cards })
+                }
+            })
+            .collect();
+
+        let next_player = other.get_next_player();
+
+        RoundDelta {
+            cards_removed,
+            last_move: changed(self.get_last_move(), other.get_last_move()),
+            last_player: changed(self.get_last_player(), other.get_last_player()),
+            game_over: next_player.is_none() && self.get_next_player().is_some(),
+            next_player: changed(self.get_next_player(), next_player),
+            suit_order: changed(
+                Some(self.get_suit_order()),
+                Some(other.get_suit_order())
+            ),
+            rank_order: changed(
+                Some(self.get_rank_order()),
+                Some(other.get_rank_order())
+            ),
+        }
+    }
+}
+
+fn changed<T: PartialEq>(before: Option<T>, after: Option<T>) -> Option<T> {
+    if before == after {
+        None
+    } else {
+        after
+    }
+}
+
+/// The cards in `before` that are missing from `after`. Whatever is left
+/// in `after` was not in `before`.
+pub(crate) fn remove_remaining(before: Vec<Card>, after: &mut Vec<Card>) -> Vec<Card> {
+    before.into_iter()
+        .filter(|card| {
+            match after.iter().position(|c| c == card) {
+                Some(i) => {
+                    after.remove(i);
+                    false
+                },
+                None => true,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::PlayedCard;
+    use crate::game::{Player, RoundBuilder};
+    use crate::scenario::parse_cards;
+
+    fn get_round() -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C KC").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("9C 9S").unwrap()))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn a_round_has_no_diff_with_itself() {
+        let round = get_round();
+
+        assert!(round.diff(&round).is_empty());
+    }
+
+    #[test]
+    fn diff_describes_a_played_move() {
+        let round = get_round();
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+        let next_round = round.submit_move("a", vec![three]).unwrap();
+
+        let delta = round.diff(&next_round);
+
+        assert_eq!(delta.cards_removed, vec![CardsRemoved {
+            player_id: "a".to_string(),
+            cards: parse_cards("3C").unwrap(),
+        }]);
+        assert_eq!(delta.last_move, Some(Hand::Single(three)));
+        assert_eq!(delta.last_player, Some("a".to_string()));
+        assert_eq!(delta.next_player, Some("b".to_string()));
+        assert!(!delta.game_over);
+        assert_eq!(delta.suit_order, None);
+    }
+
+    #[test]
+    fn diff_includes_order_reversals() {
+        let round = get_round();
+        let mut suit_order = round.get_suit_order();
+        suit_order.reverse();
+        let reversed = RoundBuilder::new()
+            .players(vec![
+                round.get_player("a").unwrap(),
+                round.get_player("b").unwrap(),
+            ])
+            .suit_order(&suit_order)
+            .next_player("a")
+            .build()
+            .unwrap();
+
+        let delta = round.diff(&reversed);
+
+        assert_eq!(delta.suit_order, Some(suit_order));
+        assert_eq!(delta.rank_order, None);
+    }
+}
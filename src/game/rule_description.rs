@@ -0,0 +1,526 @@
+use super::{
+    CrossCountBombs,
+    FinalCardRule,
+    FlushPrecedence,
+    JokerUsage,
+    LastCardRule,
+    ReversalDuration,
+    ReversalTriggers,
+    Ruleset,
+    StraightComparison,
+    TableClearMode,
+    TrickType,
+    TurnOrder,
+};
+use crate::i18n::{rank_name, trick_type_name, Locale};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One rule of a ruleset as players would want it explained. Clients
+/// can word these however they like, or use `to_text`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RuleDescription {
+    NoReversals,
+    Reversals {
+        triggers: ReversalTriggers,
+        duration: ReversalDuration,
+    },
+    FlushPrecedence { precedence: FlushPrecedence },
+    Straights {
+        twos_in_straights: bool,
+        comparison: StraightComparison,
+    },
+    /// The five card tricks that can be played, lowest first
+    FiveCardTricks { order: Vec<TrickType> },
+    OpeningHand { requires_lowest_card: bool },
+    Bombs { cross_count: CrossCountBombs },
+    TableClear { mode: TableClearMode },
+    Jokers { usage: JokerUsage },
+    Passing { locks_out: bool },
+    Partnership,
+    LastCard { rule: LastCardRule },
+    FinalCard { rule: FinalCardRule },
+    StrictFinish,
+    CounterClockwise,
+}
+
+impl Ruleset {
+    /// The rules in play, in the order a table rules screen would list
+    /// them. Rules that are off and change nothing, such as last card,
+    /// are left out.
+    pub fn describe(&self) -> Vec<RuleDescription> {
+        let triggers = self.reversal_triggers;
+        let can_reverse = triggers.four_of_a_kind
+            || triggers.five_of_a_kind
+            || triggers.straight_flush
+            || triggers.rank.is_some();
+        let mut rules = vec![];
+
+        rules.push(if self.reversals_enabled && can_reverse {
+            RuleDescription::Reversals { triggers, duration: self.reversal_duration }
+        } else {
+            RuleDescription::NoReversals
+        });
+        rules.push(RuleDescription::OpeningHand {
+            requires_lowest_card: self.opening_requires_lowest_card,
+        });
+        rules.push(RuleDescription::FiveCardTricks { order: self.get_trick_order() });
+        rules.push(RuleDescription::FlushPrecedence { precedence: self.flush_precedence });
+        rules.push(RuleDescription::Straights {
+            twos_in_straights: self.twos_in_straights,
+            comparison: self.straight_comparison,
+        });
+        rules.push(RuleDescription::Bombs { cross_count: self.cross_count_bombs });
+        rules.push(RuleDescription::TableClear { mode: self.table_clear });
+        rules.push(RuleDescription::Passing { locks_out: self.pass_locks_out });
+        rules.push(RuleDescription::Jokers { usage: self.joker_usage });
+        if self.partnership {
+            rules.push(RuleDescription::Partnership);
+        }
+        if self.last_card != LastCardRule::Off {
+            rules.push(RuleDescription::LastCard { rule: self.last_card });
+        }
+        if self.final_card != FinalCardRule::Any {
+            rules.push(RuleDescription::FinalCard { rule: self.final_card });
+        }
+        if self.strict_finish {
+            rules.push(RuleDescription::StrictFinish);
+        }
+        if self.turn_order == TurnOrder::CounterClockwise {
+            rules.push(RuleDescription::CounterClockwise);
+        }
+
+        rules
+    }
+
+    /// Playable five card tricks from lowest to highest. Kinds sharing a
+    /// tier keep their usual order, as they do when compared.
+    fn get_trick_order(&self) -> Vec<TrickType> {
+        let mut order: Vec<TrickType> = [
+            TrickType::Straight,
+            TrickType::Flush,
+            TrickType::FullHouse,
+            TrickType::FourOfAKind,
+            TrickType::StraightFlush,
+            TrickType::FiveOfAKind,
+            TrickType::RoyalFlush,
+        ].iter()
+            .cloned()
+            .filter(|&t| t != TrickType::RoyalFlush || self.royal_flush_tier)
+            .filter(|&t| self.trick_ranking.get_tier(t).is_some())
+            .collect();
+        order.sort_by_key(|&t| self.trick_ranking.get_tier(t));
+
+        order
+    }
+}
+
+impl RuleDescription {
+    /// The rule as a sentence or two.
+    pub fn to_text(&self, locale: Locale) -> String {
+        match (self, locale) {
+            (RuleDescription::NoReversals, Locale::English) => {
+                "Nothing reverses the suit and rank orders.".to_string()
+            },
+            (RuleDescription::NoReversals, Locale::Filipino) => {
+                "Walang bumabaligtad sa pagkakasunod ng suit at ranggo.".to_string()
+            },
+            (RuleDescription::Reversals { triggers, duration }, _) => {
+                reversals(*triggers, *duration, locale)
+            },
+            (RuleDescription::FlushPrecedence { precedence }, _) => {
+                flush_precedence(*precedence, locale).to_string()
+            },
+            (RuleDescription::Straights { twos_in_straights, comparison }, _) => {
+                format!(
+                    "{} {}",
+                    twos_in_straight(*twos_in_straights, locale),
+                    straight_comparison(*comparison, locale)
+                )
+            },
+            (RuleDescription::FiveCardTricks { order }, Locale::English) => format!(
+                "Five card tricks rank from lowest to highest: {}.",
+                trick_names(order, locale)
+            ),
+            (RuleDescription::FiveCardTricks { order }, Locale::Filipino) => format!(
+                "Ang ranggo ng five card trick mula pinakamababa hanggang pinakamataas: {}.",
+                trick_names(order, locale)
+            ),
+            (RuleDescription::OpeningHand { requires_lowest_card }, _) => {
+                opening_hand(*requires_lowest_card, locale).to_string()
+            },
+            (RuleDescription::Bombs { cross_count }, _) => bombs(*cross_count, locale),
+            (RuleDescription::TableClear { mode }, _) => table_clear(*mode, locale),
+            (RuleDescription::Jokers { usage }, _) => jokers(*usage, locale).to_string(),
+            (RuleDescription::Passing { locks_out }, _) => {
+                passing(*locks_out, locale).to_string()
+            },
+            (RuleDescription::Partnership, Locale::English) => {
+                "Players in alternate seats are partners, and a team wins once all of \
+                 its players are out.".to_string()
+            },
+            (RuleDescription::Partnership, Locale::Filipino) => {
+                "Magkakampi ang magkasalit na upuan, at panalo ang koponan kapag naubos \
+                 na ang baraha ng lahat ng kasapi nito.".to_string()
+            },
+            (RuleDescription::LastCard { rule }, _) => last_card(*rule, locale),
+            (RuleDescription::FinalCard { rule }, _) => final_card(*rule, locale),
+            (RuleDescription::StrictFinish, Locale::English) => {
+                "Nobody can go out on a bomb or a hand that reverses the orders.".to_string()
+            },
+            (RuleDescription::StrictFinish, Locale::Filipino) => {
+                "Bawal lumabas gamit ang bomba o kamay na bumabaligtad sa pagkakasunod."
+                    .to_string()
+            },
+            (RuleDescription::CounterClockwise, Locale::English) => {
+                "Play passes counter-clockwise.".to_string()
+            },
+            (RuleDescription::CounterClockwise, Locale::Filipino) => {
+                "Pakontra sa ikot ng orasan ang takbo ng laro.".to_string()
+            },
+        }
+    }
+}
+
+fn join_list(items: &[String], locale: Locale) -> String {
+    let and = match locale {
+        Locale::English => "and",
+        Locale::Filipino => "at",
+    };
+
+    match items.split_last() {
+        Some((last, rest)) if !rest.is_empty() => {
+            format!("{} {} {}", rest.join(", "), and, last)
+        },
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
+
+fn trick_names(order: &[TrickType], locale: Locale) -> String {
+    order.iter()
+        .map(|&t| trick_type_name(t, locale))
+        .collect::<Vec<&str>>()
+        .join(", ")
+}
+
+fn reversals(triggers: ReversalTriggers, duration: ReversalDuration, locale: Locale) -> String {
+    let mut hands: Vec<String> = [
+        (triggers.four_of_a_kind, TrickType::FourOfAKind),
+        (triggers.five_of_a_kind, TrickType::FiveOfAKind),
+        (triggers.straight_flush, TrickType::StraightFlush),
+    ].iter()
+        .filter(|(on, _)| *on)
+        .map(|&(_, t)| trick_type_name(t, locale).to_string())
+        .collect();
+    if let Some(rank) = triggers.rank {
+        hands.push(match locale {
+            Locale::English => format!("any hand with a {}", rank_name(rank, locale)),
+            Locale::Filipino => format!("anumang kamay na may {}", rank_name(rank, locale)),
+        });
+    }
+    let or = match locale {
+        Locale::English => " or ",
+        Locale::Filipino => " o ",
+    };
+    let hands = hands.join(or);
+
+    match (locale, duration) {
+        (Locale::English, ReversalDuration::RestOfGame) => format!(
+            "{} reverses the suit and rank orders until another reversal.", hands
+        ),
+        (Locale::English, ReversalDuration::UntilTrickCleared) => format!(
+            "{} reverses the suit and rank orders until the trick clears.", hands
+        ),
+        (Locale::Filipino, ReversalDuration::RestOfGame) => format!(
+            "Ang {} ay bumabaligtad sa pagkakasunod ng suit at ranggo hanggang sa \
+             susunod na pagbaligtad.", hands
+        ),
+        (Locale::Filipino, ReversalDuration::UntilTrickCleared) => format!(
+            "Ang {} ay bumabaligtad sa pagkakasunod ng suit at ranggo hanggang \
+             malinis ang mesa.", hands
+        ),
+    }
+}
+
+fn flush_precedence(precedence: FlushPrecedence, locale: Locale) -> &'static str {
+    match (locale, precedence) {
+        (Locale::English, FlushPrecedence::Suit) => {
+            "Flushes are compared by suit first, then by their top card."
+        },
+        (Locale::English, FlushPrecedence::Rank) => {
+            "Flushes are compared by their top card's rank, then its suit."
+        },
+        (Locale::English, FlushPrecedence::AllCards) => {
+            "Flushes are compared card by card from the top, then by the top card's suit."
+        },
+        (Locale::Filipino, FlushPrecedence::Suit) => {
+            "Ang flush ay inihahambing muna ayon sa suit, saka sa pinakamataas na baraha."
+        },
+        (Locale::Filipino, FlushPrecedence::Rank) => {
+            "Ang flush ay inihahambing ayon sa ranggo ng pinakamataas na baraha, saka sa \
+             suit nito."
+        },
+        (Locale::Filipino, FlushPrecedence::AllCards) => {
+            "Ang flush ay inihahambing isa-isa mula sa pinakamataas na baraha, saka sa \
+             suit ng pinakamataas."
+        },
+    }
+}
+
+fn twos_in_straight(allowed: bool, locale: Locale) -> &'static str {
+    match (locale, allowed) {
+        (Locale::English, true) => "Twos can be played at the top of a straight.",
+        (Locale::English, false) => "Straights can't run up to a two.",
+        (Locale::Filipino, true) => "Puwede ang dos sa itaas ng straight.",
+        (Locale::Filipino, false) => "Hindi puwedeng umabot sa dos ang straight.",
+    }
+}
+
+fn straight_comparison(comparison: StraightComparison, locale: Locale) -> &'static str {
+    match (locale, comparison) {
+        (Locale::English, StraightComparison::RankThenSuit) => {
+            "Straights with the same top rank are split by the top card's suit."
+        },
+        (Locale::English, StraightComparison::RankOnly) => {
+            "Straights with the same top rank can't beat each other."
+        },
+        (Locale::Filipino, StraightComparison::RankThenSuit) => {
+            "Kapag pareho ang pinakamataas na ranggo, ang suit ng pinakamataas na baraha \
+             ang magpapasya."
+        },
+        (Locale::Filipino, StraightComparison::RankOnly) => {
+            "Kapag pareho ang pinakamataas na ranggo, hindi matatalo ng isa ang isa."
+        },
+    }
+}
+
+fn opening_hand(requires_lowest_card: bool, locale: Locale) -> &'static str {
+    match (locale, requires_lowest_card) {
+        (Locale::English, true) => "The first hand must include the lowest card.",
+        (Locale::English, false) => "Whoever holds the lowest card leads, with any hand.",
+        (Locale::Filipino, true) => "Ang unang tira ay dapat may pinakamababang baraha.",
+        (Locale::Filipino, false) => {
+            "Ang may hawak ng pinakamababang baraha ang mauuna, at kahit anong kamay ay \
+             puwede."
+        },
+    }
+}
+
+fn bombs(cross_count: CrossCountBombs, locale: Locale) -> String {
+    let bombs: Vec<String> = [
+        (cross_count.four_of_a_kind, TrickType::FourOfAKind),
+        (cross_count.five_of_a_kind, TrickType::FiveOfAKind),
+        (cross_count.straight_flush, TrickType::StraightFlush),
+    ].iter()
+        .filter(|(on, _)| *on)
+        .map(|&(_, t)| trick_type_name(t, locale).to_string())
+        .collect();
+
+    match (locale, bombs.is_empty()) {
+        (Locale::English, true) => {
+            "Five card tricks only beat other five card tricks.".to_string()
+        },
+        (Locale::English, false) => format!(
+            "{} can also beat singles, pairs and prials.",
+            join_list(&bombs, locale)
+        ),
+        (Locale::Filipino, true) => {
+            "Ang five card trick ay tumatalo lang sa kapwa five card trick.".to_string()
+        },
+        (Locale::Filipino, false) => format!(
+            "Ang {} ay puwede ring tumalo sa single, pares at trio.",
+            join_list(&bombs, locale)
+        ),
+    }
+}
+
+fn table_clear(mode: TableClearMode, locale: Locale) -> String {
+    match (locale, mode) {
+        (Locale::English, TableClearMode::ReturnToLastPlayer) => {
+            "The table clears when play comes back to whoever played last.".to_string()
+        },
+        (Locale::English, TableClearMode::AllPassed) => {
+            "The table clears once everyone else still in has passed.".to_string()
+        },
+        (Locale::English, TableClearMode::AfterPasses(passes)) => format!(
+            "The table clears after {} passes in a row.", passes
+        ),
+        (Locale::Filipino, TableClearMode::ReturnToLastPlayer) => {
+            "Malilinis ang mesa kapag bumalik ang tira sa huling tumira.".to_string()
+        },
+        (Locale::Filipino, TableClearMode::AllPassed) => {
+            "Malilinis ang mesa kapag nakapas na ang lahat ng iba pang naglalaro.".to_string()
+        },
+        (Locale::Filipino, TableClearMode::AfterPasses(passes)) => format!(
+            "Malilinis ang mesa pagkatapos ng {} sunod-sunod na pas.", passes
+        ),
+    }
+}
+
+fn passing(locks_out: bool, locale: Locale) -> &'static str {
+    match (locale, locks_out) {
+        (Locale::English, true) => "A player who passes sits out the rest of the trick.",
+        (Locale::English, false) => {
+            "A player who passes can play again when the turn comes back round."
+        },
+        (Locale::Filipino, true) => {
+            "Ang pumas ay hindi na makakatira hanggang malinis ang mesa."
+        },
+        (Locale::Filipino, false) => "Ang pumas ay puwede pang tumira pagbalik ng turno.",
+    }
+}
+
+fn jokers(usage: JokerUsage, locale: Locale) -> &'static str {
+    match (locale, usage) {
+        (Locale::English, JokerUsage::Anywhere) => "Jokers can stand in for any card.",
+        (Locale::English, JokerUsage::FiveCardTricksOnly) => {
+            "Jokers can only be played in five card tricks."
+        },
+        (Locale::English, JokerUsage::NotOnFinishingHand) => {
+            "Jokers can stand in for any card, but not in a player's last hand."
+        },
+        (Locale::Filipino, JokerUsage::Anywhere) => {
+            "Puwedeng gamitin ang joker bilang kahit anong baraha."
+        },
+        (Locale::Filipino, JokerUsage::FiveCardTricksOnly) => {
+            "Sa five card trick lang puwedeng gamitin ang joker."
+        },
+        (Locale::Filipino, JokerUsage::NotOnFinishingHand) => {
+            "Puwedeng gamitin ang joker bilang kahit anong baraha, pero hindi sa huling \
+             kamay."
+        },
+    }
+}
+
+fn last_card(rule: LastCardRule, locale: Locale) -> String {
+    match (locale, rule) {
+        (_, LastCardRule::Off) => String::new(),
+        (Locale::English, LastCardRule::PickUp(count)) => format!(
+            "A player down to one card must call last card or pick up the last {} \
+             cards played.", count
+        ),
+        (Locale::English, LastCardRule::Points(points)) => format!(
+            "A player down to one card must call last card or take {} penalty points.",
+            points
+        ),
+        (Locale::Filipino, LastCardRule::PickUp(count)) => format!(
+            "Ang may isang baraha na lang ay dapat magsabi ng last card, o pupulutin \
+             niya ang huling {} na barahang naitira.", count
+        ),
+        (Locale::Filipino, LastCardRule::Points(points)) => format!(
+            "Ang may isang baraha na lang ay dapat magsabi ng last card, o may multa \
+             siyang {} na puntos.", points
+        ),
+    }
+}
+
+fn final_card(rule: FinalCardRule, locale: Locale) -> String {
+    match (locale, rule) {
+        (_, FinalCardRule::Any) => String::new(),
+        (Locale::English, FinalCardRule::Forbidden) => {
+            "Nobody can go out on a two or a joker.".to_string()
+        },
+        (Locale::English, FinalCardRule::Penalty(points)) => format!(
+            "Going out on a two or a joker costs {} penalty points.", points
+        ),
+        (Locale::Filipino, FinalCardRule::Forbidden) => {
+            "Bawal lumabas gamit ang dos o joker.".to_string()
+        },
+        (Locale::Filipino, FinalCardRule::Penalty(points)) => format!(
+            "Ang paglabas gamit ang dos o joker ay may multang {} na puntos.", points
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Rank;
+    use crate::game::{RulesetPreset, TrickRanking};
+
+    #[test]
+    fn the_default_rules_are_described_in_order() {
+        let rules = Ruleset::default().describe();
+
+        assert_eq!(rules[0], RuleDescription::Reversals {
+            triggers: ReversalTriggers::classic(),
+            duration: ReversalDuration::RestOfGame,
+        });
+        assert_eq!(rules[2], RuleDescription::FiveCardTricks {
+            order: vec![
+                TrickType::Straight,
+                TrickType::Flush,
+                TrickType::FullHouse,
+                TrickType::FourOfAKind,
+                TrickType::StraightFlush,
+                TrickType::FiveOfAKind,
+            ],
+        });
+        assert_eq!(rules.len(), 9);
+        assert_eq!(
+            rules[0].to_text(Locale::English),
+            "Four of a Kind reverses the suit and rank orders until another reversal."
+        );
+        assert_eq!(
+            rules[1].to_text(Locale::Filipino),
+            "Ang unang tira ay dapat may pinakamababang baraha."
+        );
+        assert_eq!(
+            rules[5].to_text(Locale::English),
+            "Five card tricks only beat other five card tricks."
+        );
+    }
+
+    #[test]
+    fn descriptions_follow_the_flags() {
+        let ruleset = Ruleset {
+            reversal_triggers: ReversalTriggers {
+                rank: Some(Rank::Seven),
+                ..ReversalTriggers::any_bomb()
+            },
+            cross_count_bombs: CrossCountBombs {
+                four_of_a_kind: true,
+                straight_flush: true,
+                ..CrossCountBombs::none()
+            },
+            trick_ranking: TrickRanking::from_order(&[TrickType::Flush, TrickType::Straight]),
+            final_card: FinalCardRule::Penalty(5),
+            ..RulesetPreset::Partnership.get_ruleset()
+        };
+        let text: Vec<String> = ruleset.describe().iter()
+            .map(|rule| rule.to_text(Locale::English))
+            .collect();
+
+        assert_eq!(text[0], "Four of a Kind or Five of a Kind or Straight Flush or any hand \
+            with a Seven reverses the suit and rank orders until another reversal.");
+        assert_eq!(text[2], "Five card tricks rank from lowest to highest: Flush, Straight.");
+        assert_eq!(text[5], "Four of a Kind and Straight Flush can also beat singles, \
+            pairs and prials.");
+        assert_eq!(text[9], "Players in alternate seats are partners, and a team wins once \
+            all of its players are out.");
+        assert_eq!(text[10], "Going out on a two or a joker costs 5 penalty points.");
+
+        let no_triggers = Ruleset {
+            reversal_triggers: ReversalTriggers {
+                four_of_a_kind: false,
+                ..ReversalTriggers::classic()
+            },
+            ..Ruleset::default()
+        };
+        assert_eq!(no_triggers.describe()[0], RuleDescription::NoReversals);
+        assert_eq!(
+            RulesetPreset::NoReversals.get_ruleset().describe()[0],
+            RuleDescription::NoReversals
+        );
+    }
+
+    #[test]
+    fn descriptions_serialize_with_a_type_tag() {
+        let json = serde_json::to_string(&RuleDescription::Passing { locks_out: true }).unwrap();
+
+        assert_eq!(json, r#"{"type":"passing","locks_out":true}"#);
+    }
+}
@@ -1,14 +1,872 @@
+use super::{Hand, OrderContext, Trick, TrickType};
+use crate::cards::{get_short_deck_ranks, Card, Rank, Suit};
 use serde::{Deserialize, Serialize};
 
+/// How two flushes of the same kind are compared.
 #[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
 pub enum FlushPrecedence {
+    /// The suit of the flush first, then the top card's rank
     Suit,
-    Rank
+    /// The top card by rank, then its suit
+    Rank,
+    /// Each card in turn from the top down by rank, then the top card's
+    /// suit
+    AllCards,
 }
 
-#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+/// How two straights of the same top rank are compared.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum StraightComparison {
+    /// The suit of the top card decides
+    #[default]
+    RankThenSuit,
+    /// Only the rank of the top card counts, so neither beats the other
+    RankOnly,
+}
+
+/// When the table clears and the last player gets a free lead.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum TableClearMode {
+    /// As soon as play comes back round to the last player
+    #[default]
+    ReturnToLastPlayer,
+    /// Only once every other player still in has passed at least once
+    /// since the trick was led
+    AllPassed,
+    /// Once this many passes in a row follow the last hand played, however
+    /// many players are still in. Suits two player and team games, where
+    /// play coming back round says little about who could still beat it.
+    AfterPasses(usize),
+}
+
+/// Which way play passes round the table. Seats are numbered in the order
+/// players sit, so clockwise goes up through the seats and wraps back to
+/// the first.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum TurnOrder {
+    #[default]
+    Clockwise,
+    /// Down through the seats, from the first seat to the last
+    CounterClockwise,
+}
+
+/// Where jokers may be played.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum JokerUsage {
+    #[default]
+    Anywhere,
+    /// Only to complete a five card trick, unless they're all a player
+    /// has left
+    FiveCardTricksOnly,
+    /// Anywhere except in the hand that plays a player's last card
+    NotOnFinishingHand,
+}
+
+/// How long a reversal lasts.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum ReversalDuration {
+    /// The orders stay reversed until another reversal flips them back
+    #[default]
+    RestOfGame,
+    /// The orders go back to how they were once the trick clears
+    UntilTrickCleared,
+}
+
+/// Hands that flip the suit and rank orders when reversals are enabled.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct ReversalTriggers {
+    #[serde(default = "default_true")]
+    pub four_of_a_kind: bool,
+    #[serde(default)]
+    pub five_of_a_kind: bool,
+    #[serde(default)]
+    pub straight_flush: bool,
+    /// Any hand containing a card of this rank
+    #[serde(default)]
+    pub rank: Option<Rank>,
+}
+
+impl ReversalTriggers {
+    /// Only four of a kind reverses, as in the original game.
+    pub const fn classic() -> ReversalTriggers {
+        ReversalTriggers {
+            four_of_a_kind: true,
+            five_of_a_kind: false,
+            straight_flush: false,
+            rank: None,
+        }
+    }
+
+    /// Every bomb reverses: four and five of a kind and straight flushes.
+    pub fn any_bomb() -> ReversalTriggers {
+        ReversalTriggers {
+            four_of_a_kind: true,
+            five_of_a_kind: true,
+            straight_flush: true,
+            rank: None,
+        }
+    }
+
+    pub fn is_triggered_by(&self, hand: Hand) -> bool {
+        let by_rank = self.rank
+            .map(|rank| hand.to_cards().iter().any(|c| c.get_rank() == rank))
+            .unwrap_or(false);
+
+        by_rank || match hand {
+            Hand::FiveCardTrick(Trick { trick_type, .. }) => match trick_type {
+                TrickType::FourOfAKind => self.four_of_a_kind,
+                TrickType::FiveOfAKind => self.five_of_a_kind,
+                TrickType::StraightFlush
+                | TrickType::RoyalFlush => self.straight_flush,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Default for ReversalTriggers {
+    fn default() -> ReversalTriggers {
+        ReversalTriggers::classic()
+    }
+}
+
+/// Bombs that can be played on a single, pair or prial as well as on
+/// other five card tricks.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct CrossCountBombs {
+    #[serde(default)]
+    pub four_of_a_kind: bool,
+    #[serde(default)]
+    pub five_of_a_kind: bool,
+    /// Royal flushes count as straight flushes here
+    #[serde(default)]
+    pub straight_flush: bool,
+}
+
+impl CrossCountBombs {
+    /// Five card tricks only beat other five card tricks.
+    pub const fn none() -> CrossCountBombs {
+        CrossCountBombs {
+            four_of_a_kind: false,
+            five_of_a_kind: false,
+            straight_flush: false,
+        }
+    }
+
+    /// Whether `bomb` is one of the designated bombs and `other` a hand
+    /// with fewer cards it can be played on.
+    pub fn can_beat(&self, bomb: Hand, other: Hand) -> bool {
+        let designated = match bomb {
+            Hand::FiveCardTrick(Trick { trick_type, .. }) => match trick_type {
+                TrickType::FourOfAKind => self.four_of_a_kind,
+                TrickType::FiveOfAKind => self.five_of_a_kind,
+                TrickType::StraightFlush
+                | TrickType::RoyalFlush => self.straight_flush,
+                _ => false,
+            },
+            _ => false,
+        };
+
+        designated && matches!(other, Hand::Single(_) | Hand::Pair(..) | Hand::Prial(..))
+    }
+}
+
+impl Default for CrossCountBombs {
+    fn default() -> CrossCountBombs {
+        CrossCountBombs::none()
+    }
+}
+
+/// Where each kind of five card trick ranks. A trick in a higher tier
+/// beats any trick in a lower one, and tricks without a tier can't be
+/// played at all. Kinds sharing a tier fall back to their usual order.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct TrickRanking {
+    pub straight: Option<u8>,
+    pub flush: Option<u8>,
+    pub full_house: Option<u8>,
+    pub four_of_a_kind: Option<u8>,
+    pub straight_flush: Option<u8>,
+    pub five_of_a_kind: Option<u8>,
+    /// Only used when royal flushes are their own tier
+    pub royal_flush: Option<u8>,
+}
+
+impl TrickRanking {
+    /// Straights lowest, up to five of a kind and then royal flushes.
+    pub const fn standard() -> TrickRanking {
+        TrickRanking {
+            straight: Some(0),
+            flush: Some(1),
+            full_house: Some(2),
+            four_of_a_kind: Some(3),
+            straight_flush: Some(4),
+            five_of_a_kind: Some(5),
+            royal_flush: Some(6),
+        }
+    }
+
+    /// Ranks the trick types in `order` from lowest to highest. Any left
+    /// out can't be played.
+    pub fn from_order(order: &[TrickType]) -> TrickRanking {
+        let tier = |trick_type| order.iter()
+            .position(|&t| t == trick_type)
+            .map(|i| i as u8);
+
+        TrickRanking {
+            straight: tier(TrickType::Straight),
+            flush: tier(TrickType::Flush),
+            full_house: tier(TrickType::FullHouse),
+            four_of_a_kind: tier(TrickType::FourOfAKind),
+            straight_flush: tier(TrickType::StraightFlush),
+            five_of_a_kind: tier(TrickType::FiveOfAKind),
+            royal_flush: tier(TrickType::RoyalFlush),
+        }
+    }
+
+    pub fn get_tier(&self, trick_type: TrickType) -> Option<u8> {
+        match trick_type {
+            TrickType::Straight => self.straight,
+            TrickType::Flush => self.flush,
+            TrickType::FullHouse => self.full_house,
+            TrickType::FourOfAKind => self.four_of_a_kind,
+            TrickType::StraightFlush => self.straight_flush,
+            TrickType::FiveOfAKind => self.five_of_a_kind,
+            TrickType::RoyalFlush => self.royal_flush,
+        }
+    }
+}
+
+impl Default for TrickRanking {
+    fn default() -> TrickRanking {
+        TrickRanking::standard()
+    }
+}
+
+/// How the deck is made up before it's dealt.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum DealMode {
+    #[default]
+    FullDeck,
+    /// In three player games every copy of this card is taken out so the
+    /// hands come out even, at 17 cards each from a single deck. Other
+    /// player counts get the full deck.
+    StrippedForThree { rank: Rank, suit: Suit },
+    /// Every player is dealt this many cards and the rest of the deck is
+    /// set aside face down as a kitty, for quicker games. When the deck
+    /// can't go round that many times, each player gets an equal share
+    /// and only the odd cards are set aside.
+    Limited { cards_per_player: u8 },
+    /// Only the ranks from this one up to the ace are dealt, as in
+    /// regional variants played sevens or nines up, so sevens up is a 32
+    /// card deck. The ace also plays below the lowest rank in straights,
+    /// making A-7-8-9-10 the lowest straight sevens up.
+    ShortDeck { lowest_rank: Rank },
+    /// This many cards are dealt face down to a kitty before the hands.
+    /// Whoever wins the first trick picks it up and discards as many.
+    BlindKitty { cards: u8 },
+}
+
+impl DealMode {
+    /// The rank an ace stands in for when it's played below the lowest
+    /// rank of a short deck: the rank just past that one in the rank
+    /// order, on the far side from the rest of the deck. `None` for full
+    /// decks.
+    pub fn get_low_ace_rank(&self, orders: &OrderContext) -> Option<Rank> {
+        let lowest_rank = match *self {
+            DealMode::ShortDeck { lowest_rank } => lowest_rank,
+            _ => return None,
+        };
+        let ranks = get_short_deck_ranks(lowest_rank);
+        let lowest = orders.rank_index(*ranks.first()?)?;
+        let second = orders.rank_index(*ranks.get(1)?)?;
+
+        (2 * lowest).checked_sub(second)
+            .and_then(|i| orders.get_rank_order().get(i))
+            .copied()
+            .filter(|rank| !ranks.contains(rank))
+    }
+}
+
+/// Dealt hands weak enough that their holder may call a misdeal and have
+/// the cards redealt before play starts.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct MisdealConditions {
+    /// Nothing ranked above a ten. Jokers count as high cards.
+    #[serde(default)]
+    pub no_card_above_ten: bool,
+    /// No jack, queen or king
+    #[serde(default)]
+    pub no_face_cards: bool,
+}
+
+impl MisdealConditions {
+    /// Misdeals can't be called.
+    pub const fn none() -> MisdealConditions {
+        MisdealConditions {
+            no_card_above_ten: false,
+            no_face_cards: false,
+        }
+    }
+
+    pub fn is_misdeal(&self, hand: &[Card], orders: &OrderContext) -> bool {
+        let ten = orders.rank_index(Rank::Ten);
+        let above_ten = |card: &Card| match card.get_rank() {
+            Some(rank) => orders.rank_index(rank) > ten,
+            None => true,
+        };
+        let face_card = |card: &Card| matches!(
+            card.get_rank(),
+            Some(Rank::Jack) | Some(Rank::Queen) | Some(Rank::King)
+        );
+
+        (self.no_card_above_ten && !hand.iter().any(above_ten))
+            || (self.no_face_cards && !hand.iter().any(face_card))
+    }
+}
+
+/// Whether a player down to one card has to call "last card", and what
+/// they pay if the next move is made before they do.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum LastCardRule {
+    #[default]
+    Off,
+    /// They pick up this many of the most recently played cards
+    PickUp(usize),
+    /// This many points are added to their penalty for the game
+    Points(usize),
+}
+
+/// Whether a player may go out with a hand holding a two or a joker.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum FinalCardRule {
+    #[default]
+    Any,
+    /// The move is rejected
+    Forbidden,
+    /// The move stands but adds this many points to the player's penalty
+    /// for the game
+    Penalty(usize),
+}
+
+/// What a player pays for claiming the rest of the game with a hand that
+/// turns out to be beatable.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum FalseClaimPenalty {
+    /// They're resigned from the game
+    #[default]
+    Resign,
+    /// Play carries on with this many points added to their penalty for
+    /// the game
+    Points(usize),
+}
+
+/// How many players have to ask for a redeal, before the first move, for
+/// the cards to be shuffled and dealt again.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum RedealVote {
+    #[default]
+    Off,
+    /// At least this many players, or everyone at a smaller table
+    Players(u8),
+    /// More than half the table
+    Majority,
+    Unanimous,
+}
+
+impl RedealVote {
+    /// The votes needed at a table of `players`, or `None` when redeals
+    /// can't be voted for.
+    pub fn get_votes_needed(self, players: usize) -> Option<usize> {
+        match self {
+            RedealVote::Off => None,
+            RedealVote::Players(count) => Some((count as usize).clamp(1, players.max(1))),
+            RedealVote::Majority => Some(players / 2 + 1),
+            RedealVote::Unanimous => Some(players),
+        }
+    }
+}
+
+/// A named set of rules that games can refer to instead of spelling out
+/// every flag.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[serde(rename_all = "snake_case")]
+pub enum RulesetPreset {
+    Classic,
+    NoReversals,
+    Partnership,
+    SuitFlushes,
+}
+
+impl RulesetPreset {
+    pub fn from_name(name: &str) -> Option<RulesetPreset> {
+        match name {
+            "classic" => Some(RulesetPreset::Classic),
+            "no_reversals" => Some(RulesetPreset::NoReversals),
+            "partnership" => Some(RulesetPreset::Partnership),
+            "suit_flushes" => Some(RulesetPreset::SuitFlushes),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RulesetPreset::Classic => "classic",
+            RulesetPreset::NoReversals => "no_reversals",
+            RulesetPreset::Partnership => "partnership",
+            RulesetPreset::SuitFlushes => "suit_flushes",
+        }
+    }
+
+    pub fn get_ruleset(self) -> Ruleset {
+        let classic = Ruleset {
+            preset: Some(self),
+            ..Ruleset::default()
+        };
+
+        match self {
+            RulesetPreset::Classic => classic,
+            RulesetPreset::NoReversals => Ruleset {
+                reversals_enabled: false,
+                ..classic
+            },
+            RulesetPreset::Partnership => Ruleset {
+                partnership: true,
+                ..classic
+            },
+            RulesetPreset::SuitFlushes => Ruleset {
+                flush_precedence: FlushPrecedence::Suit,
+                ..classic
+            },
+        }
+    }
+}
+
+/// Serialized with the preset it started from, if any, followed only by
+/// the flags that differ from that preset. Without a preset every flag is
+/// written out, and flags missing when reading take their classic values.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "RulesetFields", into = "RulesetFields")]
 pub struct Ruleset {
+    /// The preset these rules were based on
+    pub preset: Option<RulesetPreset>,
+    /// Whether any hand can reverse the orders at all
     pub reversals_enabled: bool,
-    pub flush_precedence: FlushPrecedence
+    /// Which hands reverse the orders
+    pub reversal_triggers: ReversalTriggers,
+    pub reversal_duration: ReversalDuration,
+    pub flush_precedence: FlushPrecedence,
+    pub straight_comparison: StraightComparison,
+    /// Players play in teams that alternate seats, so in a four player
+    /// game partners sit opposite each other. The game ends as soon as
+    /// every member of one team is out.
+    pub partnership: bool,
+    /// The opening hand must include the lowest card. When off, the holder
+    /// of the lowest card still leads but may lead any hand.
+    pub opening_requires_lowest_card: bool,
+    pub table_clear: TableClearMode,
+    pub joker_usage: JokerUsage,
+    /// A straight flush from 10 to ace is its own trick type, beating five
+    /// of a kind
+    pub royal_flush_tier: bool,
+    /// Hands that let their holder call a misdeal
+    pub misdeal: MisdealConditions,
+    /// Whether the table can vote for a redeal before the first move
+    pub redeal_vote: RedealVote,
+    pub deal_mode: DealMode,
+    pub last_card: LastCardRule,
+    pub final_card: FinalCardRule,
+    pub false_claim: FalseClaimPenalty,
+    pub trick_ranking: TrickRanking,
+    /// Twos can be played at the top of a straight, above the ace. When
+    /// off, five cards running up to a two are no straight.
+    pub twos_in_straights: bool,
+    /// A player who passes sits out the rest of the trick, even if play
+    /// comes back round to them.
+    pub pass_locks_out: bool,
+    /// Bombs that can also beat singles, pairs and prials
+    pub cross_count_bombs: CrossCountBombs,
+    /// A player's last hand can't be a bomb or trigger a reversal, since
+    /// neither has anything left to act on once they're out.
+    pub strict_finish: bool,
+    /// Which way play passes round the table
+    pub turn_order: TurnOrder,
+}
+
+impl Ruleset {
+    /// The rules for a named preset such as `"classic"`.
+    pub fn from_name(name: &str) -> Option<Ruleset> {
+        RulesetPreset::from_name(name).map(RulesetPreset::get_ruleset)
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Ruleset {
+        Ruleset {
+            preset: None,
+            reversals_enabled: true,
+            reversal_triggers: ReversalTriggers::default(),
+            reversal_duration: ReversalDuration::RestOfGame,
+            flush_precedence: FlushPrecedence::Rank,
+            straight_comparison: StraightComparison::RankThenSuit,
+            partnership: false,
+            opening_requires_lowest_card: true,
+            table_clear: TableClearMode::ReturnToLastPlayer,
+            joker_usage: JokerUsage::Anywhere,
+            royal_flush_tier: false,
+            misdeal: MisdealConditions::none(),
+            redeal_vote: RedealVote::Off,
+            deal_mode: DealMode::FullDeck,
+            last_card: LastCardRule::Off,
+            final_card: FinalCardRule::Any,
+            false_claim: FalseClaimPenalty::Resign,
+            trick_ranking: TrickRanking::standard(),
+            twos_in_straights: true,
+            pass_locks_out: false,
+            cross_count_bombs: CrossCountBombs::none(),
+            strict_finish: false,
+            turn_order: TurnOrder::Clockwise,
+        }
+    }
 }
 
+fn default_true() -> bool {
+    true
+}
+
+/// The serialized form of `Ruleset`.
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS), ts(rename = "Ruleset"))]
+pub(crate) struct RulesetFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    preset: Option<RulesetPreset>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    reversals_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    reversal_triggers: Option<ReversalTriggers>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    reversal_duration: Option<ReversalDuration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    flush_precedence: Option<FlushPrecedence>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    straight_comparison: Option<StraightComparison>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    partnership: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    opening_requires_lowest_card: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    table_clear: Option<TableClearMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    joker_usage: Option<JokerUsage>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    royal_flush_tier: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    misdeal: Option<MisdealConditions>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    redeal_vote: Option<RedealVote>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    deal_mode: Option<DealMode>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    last_card: Option<LastCardRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    final_card: Option<FinalCardRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    false_claim: Option<FalseClaimPenalty>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    trick_ranking: Option<TrickRanking>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    twos_in_straights: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    pass_locks_out: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    cross_count_bombs: Option<CrossCountBombs>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    strict_finish: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "ts", ts(optional))]
+    turn_order: Option<TurnOrder>,
+}
+
+impl From<RulesetFields> for Ruleset {
+    fn from(fields: RulesetFields) -> Ruleset {
+        let base = fields.preset
+            .map(RulesetPreset::get_ruleset)
+            .unwrap_or_default();
+
+        Ruleset {
+            preset: fields.preset,
+            reversals_enabled: fields.reversals_enabled
+                .unwrap_or(base.reversals_enabled),
+            reversal_triggers: fields.reversal_triggers
+                .unwrap_or(base.reversal_triggers),
+            reversal_duration: fields.reversal_duration
+                .unwrap_or(base.reversal_duration),
+            flush_precedence: fields.flush_precedence
+                .unwrap_or(base.flush_precedence),
+            straight_comparison: fields.straight_comparison
+                .unwrap_or(base.straight_comparison),
+            partnership: fields.partnership
+                .unwrap_or(base.partnership),
+            opening_requires_lowest_card: fields.opening_requires_lowest_card
+                .unwrap_or(base.opening_requires_lowest_card),
+            table_clear: fields.table_clear
+                .unwrap_or(base.table_clear),
+            joker_usage: fields.joker_usage
+                .unwrap_or(base.joker_usage),
+            royal_flush_tier: fields.royal_flush_tier
+                .unwrap_or(base.royal_flush_tier),
+            misdeal: fields.misdeal
+                .unwrap_or(base.misdeal),
+            redeal_vote: fields.redeal_vote
+                .unwrap_or(base.redeal_vote),
+            deal_mode: fields.deal_mode
+                .unwrap_or(base.deal_mode),
+            last_card: fields.last_card
+                .unwrap_or(base.last_card),
+            final_card: fields.final_card
+                .unwrap_or(base.final_card),
+            false_claim: fields.false_claim
+                .unwrap_or(base.false_claim),
+            trick_ranking: fields.trick_ranking
+                .unwrap_or(base.trick_ranking),
+            twos_in_straights: fields.twos_in_straights
+                .unwrap_or(base.twos_in_straights),
+            pass_locks_out: fields.pass_locks_out
+                .unwrap_or(base.pass_locks_out),
+            cross_count_bombs: fields.cross_count_bombs
+                .unwrap_or(base.cross_count_bombs),
+            strict_finish: fields.strict_finish
+                .unwrap_or(base.strict_finish),
+            turn_order: fields.turn_order
+                .unwrap_or(base.turn_order),
+        }
+    }
+}
+
+impl From<Ruleset> for RulesetFields {
+    fn from(ruleset: Ruleset) -> RulesetFields {
+        let base = ruleset.preset.map(RulesetPreset::get_ruleset);
+        fn changed<T: PartialEq>(value: T, base: Option<T>) -> Option<T> {
+            if base.as_ref() == Some(&value) { None } else { Some(value) }
+        }
+
+        RulesetFields {
+            preset: ruleset.preset,
+            reversals_enabled: changed(
+                ruleset.reversals_enabled,
+                base.map(|b| b.reversals_enabled)
+            ),
+            reversal_triggers: changed(
+                ruleset.reversal_triggers,
+                base.map(|b| b.reversal_triggers)
+            ),
+            reversal_duration: changed(
+                ruleset.reversal_duration,
+                base.map(|b| b.reversal_duration)
+            ),
+            flush_precedence: changed(
+                ruleset.flush_precedence,
+                base.map(|b| b.flush_precedence)
+            ),
+            straight_comparison: changed(
+                ruleset.straight_comparison,
+                base.map(|b| b.straight_comparison)
+            ),
+            partnership: changed(
+                ruleset.partnership,
+                base.map(|b| b.partnership)
+            ),
+            opening_requires_lowest_card: changed(
+                ruleset.opening_requires_lowest_card,
+                base.map(|b| b.opening_requires_lowest_card)
+            ),
+            table_clear: changed(
+                ruleset.table_clear,
+                base.map(|b| b.table_clear)
+            ),
+            joker_usage: changed(
+                ruleset.joker_usage,
+                base.map(|b| b.joker_usage)
+            ),
+            royal_flush_tier: changed(
+                ruleset.royal_flush_tier,
+                base.map(|b| b.royal_flush_tier)
+            ),
+            misdeal: changed(
+                ruleset.misdeal,
+                base.map(|b| b.misdeal)
+            ),
+            redeal_vote: changed(
+                ruleset.redeal_vote,
+                base.map(|b| b.redeal_vote)
+            ),
+            deal_mode: changed(
+                ruleset.deal_mode,
+                base.map(|b| b.deal_mode)
+            ),
+            last_card: changed(
+                ruleset.last_card,
+                base.map(|b| b.last_card)
+            ),
+            final_card: changed(
+                ruleset.final_card,
+                base.map(|b| b.final_card)
+            ),
+            false_claim: changed(
+                ruleset.false_claim,
+                base.map(|b| b.false_claim)
+            ),
+            trick_ranking: changed(
+                ruleset.trick_ranking,
+                base.map(|b| b.trick_ranking)
+            ),
+            twos_in_straights: changed(
+                ruleset.twos_in_straights,
+                base.map(|b| b.twos_in_straights)
+            ),
+            pass_locks_out: changed(
+                ruleset.pass_locks_out,
+                base.map(|b| b.pass_locks_out)
+            ),
+            cross_count_bombs: changed(
+                ruleset.cross_count_bombs,
+                base.map(|b| b.cross_count_bombs)
+            ),
+            strict_finish: changed(
+                ruleset.strict_finish,
+                base.map(|b| b.strict_finish)
+            ),
+            turn_order: changed(
+                ruleset.turn_order,
+                base.map(|b| b.turn_order)
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{PlayedCard, Suit};
+
+    fn five_of(rank: Rank) -> Hand {
+        Hand::build(vec![
+            PlayedCard::new(rank, Suit::Clubs, false),
+            PlayedCard::new(rank, Suit::Hearts, false),
+            PlayedCard::new(rank, Suit::Diamonds, false),
+            PlayedCard::new(rank, Suit::Spades, false),
+            PlayedCard::new(rank, Suit::Spades, true),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn only_four_of_a_kind_reverses_by_default() {
+        let triggers = ReversalTriggers::default();
+
+        assert!(!triggers.is_triggered_by(five_of(Rank::Nine)));
+        assert!(ReversalTriggers::any_bomb().is_triggered_by(five_of(Rank::Nine)));
+    }
+
+    #[test]
+    fn a_rank_can_trigger_a_reversal() {
+        let triggers = ReversalTriggers {
+            rank: Some(Rank::Seven),
+            ..ReversalTriggers::default()
+        };
+        let seven = Hand::Single(PlayedCard::new(Rank::Seven, Suit::Clubs, false));
+        let eight = Hand::Single(PlayedCard::new(Rank::Eight, Suit::Clubs, false));
+
+        assert!(triggers.is_triggered_by(seven));
+        assert!(!triggers.is_triggered_by(eight));
+        assert!(!triggers.is_triggered_by(Hand::Pass));
+    }
+
+    #[test]
+    fn presets_serialize_by_name_with_overrides() {
+        let ruleset = Ruleset {
+            partnership: true,
+            ..Ruleset::from_name("classic").unwrap()
+        };
+
+        let json = serde_json::to_string(&ruleset).unwrap();
+
+        assert_eq!(json, r#"{"preset":"classic","partnership":true}"#);
+        assert_eq!(serde_json::from_str::<Ruleset>(&json).unwrap(), ruleset);
+        assert!(Ruleset::from_name("unknown").is_none());
+    }
+
+    #[test]
+    fn rulesets_without_a_preset_keep_every_flag() {
+        let ruleset = Ruleset {
+            flush_precedence: FlushPrecedence::Suit,
+            ..Ruleset::default()
+        };
+        let json = serde_json::to_value(ruleset).unwrap();
+
+        assert_eq!(json["flush_precedence"], "Suit");
+        assert_eq!(json["partnership"], false);
+        assert_eq!(serde_json::from_value::<Ruleset>(json).unwrap(), ruleset);
+
+        let old: Ruleset = serde_json::from_str(
+            r#"{"reversals_enabled":false,"flush_precedence":"Rank"}"#
+        ).unwrap();
+        assert!(!old.reversals_enabled);
+        assert!(old.opening_requires_lowest_card);
+    }
+
+    #[test]
+    fn a_hand_with_nothing_above_ten_can_be_a_misdeal() {
+        let conditions = MisdealConditions {
+            no_card_above_ten: true,
+            ..MisdealConditions::none()
+        };
+        let card = |rank| Card::Standard { deck_id: 0, rank, suit: Suit::Clubs };
+        let low = vec![card(Rank::Three), card(Rank::Nine), card(Rank::Ten)];
+        let with_two = vec![card(Rank::Three), card(Rank::Two)];
+        let with_joker = vec![card(Rank::Three), Card::Joker { deck_id: 0 }];
+
+        assert!(conditions.is_misdeal(&low, &OrderContext::standard()));
+        assert!(!conditions.is_misdeal(&with_two, &OrderContext::standard()));
+        assert!(!conditions.is_misdeal(&with_joker, &OrderContext::standard()));
+        assert!(!MisdealConditions::none().is_misdeal(&low, &OrderContext::standard()));
+    }
+}
@@ -0,0 +1,327 @@
+use super::{FalseClaimPenalty, FinalCardRule, LastCardRule, Round};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayerResult {
+    pub player_id: String,
+    pub team: usize,
+    /// Order the player went out in, starting at 1, or `None` if they
+    /// still held cards when the game ended
+    pub finishing_position: Option<usize>,
+    pub cards_left: usize,
+    /// Resigned players hold no cards but place below everyone who didn't
+    #[serde(default)]
+    pub resigned: bool,
+    /// Points for breaking rules such as missing a last card call, on top
+    /// of any cards left
+    #[serde(default)]
+    pub penalty_points: usize,
+    #[serde(default)]
+    pub tricks_won: usize,
+    /// Four of a kinds or better played
+    #[serde(default)]
+    pub bombs_played: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TeamResult {
+    pub team: usize,
+    pub player_ids: Vec<String>,
+    /// Cards still held across the whole team
+    pub cards_left: usize,
+    /// Starting at 1 for the winning team
+    pub placement: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameResult {
+    pub players: Vec<PlayerResult>,
+    /// Teams ordered by placement. Without partnerships every player is
+    /// a team of one.
+    pub teams: Vec<TeamResult>,
+}
+
+/// How a finished game turns into points. Higher scores are better under
+/// every scheme.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum ScoringScheme {
+    /// A point lost for every card left and every penalty point
+    #[default]
+    CardsLeft,
+    /// Points won for tricks and bombs, less a charge for every card left
+    /// and every penalty point
+    TrickBonus {
+        per_trick: i64,
+        per_bomb: i64,
+        per_card_left: i64,
+    },
+}
+
+impl ScoringScheme {
+    pub fn score(&self, player: &PlayerResult) -> i64 {
+        let penalty = (player.cards_left + player.penalty_points) as i64;
+
+        match *self {
+            ScoringScheme::CardsLeft => -penalty,
+            ScoringScheme::TrickBonus { per_trick, per_bomb, per_card_left } => {
+                player.tricks_won as i64 * per_trick
+                    + player.bombs_played as i64 * per_bomb
+                    - penalty * per_card_left
+            },
+        }
+    }
+}
+
+impl GameResult {
+    /// Every player's score under `scheme`, in seating order.
+    pub fn get_scores(&self, scheme: ScoringScheme) -> Vec<(String, i64)> {
+        self.players.iter()
+            .map(|p| (p.player_id.clone(), scheme.score(p)))
+            .collect()
+    }
+}
+
+/// Works out placements from the finished round and the order players went
+/// out in. Teams with a resigned player place last. Otherwise a team with
+/// everyone out places above one with cards left, then fewer combined cards
+/// left wins, then whoever had the earliest finisher.
+pub fn get_game_result(round: &Round, winners: &[String]) -> GameResult {
+    let resigned = round.get_resigned_players();
+    let trick_winners = round.get_trick_winners();
+    let bomb_players = round.get_bomb_players();
+    let missed_last_card = round.get_missed_last_card();
+    let points_per_miss = match round.get_ruleset().last_card {
+        LastCardRule::Points(points) => points,
+        _ => 0,
+    };
+    let false_claims = round.get_false_claims();
+    let points_per_false_claim = match round.get_ruleset().false_claim {
+        FalseClaimPenalty::Points(points) => points,
+        FalseClaimPenalty::Resign => 0,
+    };
+    let illegal_finishers = round.get_illegal_finishers();
+    let points_per_illegal_finish = match round.get_ruleset().final_card {
+        FinalCardRule::Penalty(points) => points,
+        _ => 0,
+    };
+
+    let players: Vec<PlayerResult> = round.get_player_ids().iter()
+        .map(|id| PlayerResult {
+            player_id: id.to_string(),
+            team: round.get_team(id).unwrap_or_default(),
+            finishing_position: winners.iter()
+                .position(|w| w == id)
+                .map(|p| p + 1),
+            cards_left: round.get_player(id)
+                .map(|p| p.get_card_count())
+                .unwrap_or_default(),
+            resigned: resigned.contains(id),
+            penalty_points: missed_last_card.iter()
+                .filter(|&missed| missed == id)
+                .count() * points_per_miss
+                + illegal_finishers.iter()
+                    .filter(|&finisher| finisher == id)
+                    .count() * points_per_illegal_finish
+                + false_claims.iter()
+                    .filter(|&claimer| claimer == id)
+                    .count() * points_per_false_claim,
+            tricks_won: trick_winners.iter().filter(|&w| w == id).count(),
+            bombs_played: bomb_players.iter().filter(|&p| p == id).count(),
+        })
+        .collect();
+
+    let mut teams: Vec<(bool, bool, usize, usize, TeamResult)> = round.get_teams()
+        .into_iter()
+        .enumerate()
+        .map(|(team, player_ids)| {
+            let members: Vec<&PlayerResult> = players.iter()
+                .filter(|p| p.team == team)
+                .collect();
+            let cards_left = members.iter().map(|p| p.cards_left).sum();
+            let best_finish = members.iter()
+                .filter_map(|p| p.finishing_position)
+                .min()
+                .unwrap_or(usize::MAX);
+
+            (
+                members.iter().any(|p| p.resigned),
+                cards_left > 0,
+                cards_left,
+                best_finish,
+                TeamResult { team, player_ids, cards_left, placement: 0 },
+            )
+        })
+        .collect();
+
+    teams.sort_by_key(|(resigned, has_cards, cards_left, best_finish, _)| {
+        (*resigned, *has_cards, *cards_left, *best_finish)
+    });
+
+    let teams = teams.into_iter()
+        .enumerate()
+        .map(|(i, (_, _, _, _, mut team))| {
+            team.placement = i + 1;
+            team
+        })
+        .collect();
+
+    GameResult { players, teams }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Card, PlayedCard, Rank, Suit};
+    use crate::game::{Player, RoundBuilder, Ruleset};
+    use crate::scenario::parse_cards;
+
+    fn get_round(partnership: bool) -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("4C 5C").unwrap()))
+            .player(Player::new("c".to_string(), vec![]))
+            .player(Player::new("d".to_string(), parse_cards("6C").unwrap()))
+            .next_player("a")
+            .ruleset(Ruleset { partnership, ..Ruleset::default() })
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn partners_sit_opposite_each_other() {
+        let round = get_round(true);
+
+        assert_eq!(round.get_teams(), vec![
+            vec!["a".to_string(), "c".to_string()],
+            vec!["b".to_string(), "d".to_string()],
+        ]);
+        assert_eq!(round.get_team("d"), Some(1));
+    }
+
+    #[test]
+    fn a_partnership_game_ends_when_one_team_is_out() {
+        let round = get_round(true);
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+
+        let new_round = round.submit_move("a", vec![three]).unwrap();
+
+        assert_eq!(new_round.get_next_player(), None);
+    }
+
+    #[test]
+    fn without_partnerships_play_continues() {
+        let round = get_round(false);
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+
+        let new_round = round.submit_move("a", vec![three]).unwrap();
+
+        assert_eq!(new_round.get_next_player(), Some("b".to_string()));
+    }
+
+    #[test]
+    fn results_are_aggregated_per_team() {
+        let round = get_round(true)
+            .submit_move(
+                "a",
+                vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)]
+            )
+            .unwrap();
+        let winners = vec!["c".to_string(), "a".to_string()];
+
+        let result = get_game_result(&round, &winners);
+
+        assert_eq!(result.teams[0].team, 0);
+        assert_eq!(result.teams[0].placement, 1);
+        assert_eq!(result.teams[1].cards_left, 3);
+        assert_eq!(result.teams[1].placement, 2);
+        assert_eq!(result.players[2].finishing_position, Some(1));
+        assert_eq!(result.players[1].finishing_position, None);
+    }
+
+    #[test]
+    fn individual_results_rank_by_cards_left() {
+        let round = get_round(false);
+
+        let result = get_game_result(&round, &["c".to_string()]);
+        let order: Vec<&str> = result.teams.iter()
+            .map(|t| t.player_ids[0].as_str())
+            .collect();
+
+        assert_eq!(order, vec!["c", "a", "d", "b"]);
+    }
+
+    #[test]
+    fn resigned_players_place_last() {
+        let round = get_round(false).resign("d").unwrap();
+
+        let result = get_game_result(&round, &["c".to_string()]);
+        let order: Vec<&str> = result.teams.iter()
+            .map(|t| t.player_ids[0].as_str())
+            .collect();
+
+        assert_eq!(order, vec!["c", "a", "b", "d"]);
+        assert!(result.players[3].resigned);
+        assert_eq!(result.players[3].finishing_position, None);
+    }
+
+    #[test]
+    fn missed_last_card_calls_score_penalty_points() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C 4C").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("5C 6C").unwrap()))
+            .next_player("a")
+            .ruleset(Ruleset { last_card: LastCardRule::Points(5), ..Ruleset::default() })
+            .build()
+            .unwrap()
+            .submit_move("a", vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)])
+            .and_then(|r| r.submit_move("b", vec![]))
+            .unwrap();
+
+        let result = get_game_result(&round, &[]);
+
+        assert_eq!(result.players[0].penalty_points, 5);
+        assert_eq!(result.players[0].cards_left, 1);
+        assert_eq!(result.players[1].penalty_points, 0);
+    }
+
+    #[test]
+    fn trick_bonus_scores_tricks_and_bombs() {
+        let four_threes = vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Hearts, false),
+            PlayedCard::new(Rank::Three, Suit::Diamonds, false),
+            PlayedCard::new(Rank::Three, Suit::Spades, false),
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+        ];
+        let mut a_hand: Vec<Card> = four_threes.iter().map(|c| c.to_card()).collect();
+        a_hand.push("9C".parse::<Card>().unwrap());
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), a_hand))
+            .player(Player::new("b".to_string(), parse_cards("5C 6C").unwrap()))
+            .next_player("a")
+            .build()
+            .unwrap()
+            .submit_move("a", four_threes)
+            .and_then(|r| r.submit_move("b", vec![]))
+            .unwrap();
+
+        let result = get_game_result(&round, &[]);
+        let scheme = ScoringScheme::TrickBonus {
+            per_trick: 3,
+            per_bomb: 5,
+            per_card_left: 1,
+        };
+
+        assert_eq!(result.players[0].tricks_won, 1);
+        assert_eq!(result.players[0].bombs_played, 1);
+        assert_eq!(result.get_scores(scheme), vec![
+            ("a".to_string(), 7),
+            ("b".to_string(), -2),
+        ]);
+        assert_eq!(result.get_scores(ScoringScheme::default()), vec![
+            ("a".to_string(), -1),
+            ("b".to_string(), -2),
+        ]);
+    }
+}
@@ -0,0 +1,343 @@
+use super::{Hand, HandError, Player, Round, RoundBuilder, RoundBuilderError, Ruleset};
+use crate::cards::{get_rank_array, get_suit_array, Card, CardMask, PlayedCard, Rank, Suit};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+const SHARE_CODE_VERSION: u8 = 1;
+const NONE: u8 = 0xff;
+const JOKER_BIT: u8 = 0x80;
+const BASE64URL: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum ShareCodeError {
+    /// The code has characters outside base64url
+    InvalidEncoding,
+    UnsupportedVersion(u8),
+    /// The code ends before the position does
+    Truncated,
+    /// A player id, card or order that can't be decoded
+    InvalidData,
+    InvalidHand(HandError),
+    InvalidRound(RoundBuilderError),
+}
+
+impl Round {
+    /// The position as a short base64url string that's safe to paste into
+    /// chat or a link: every hand, the last move, who's next, the suit
+    /// and rank orders and the ruleset. The rest of the round's history,
+    /// such as the discard pile and who has passed, is left out.
+    ///
+    /// Returns `None` when the hands can't be packed, which needs more
+    /// than two decks, when the round isn't played with the four standard
+    /// suits, or when a player id is over 255 bytes.
+    pub fn to_share_code(&self) -> Option<String> {
+        if !self.get_order_context().has_standard_suits() {
+            return None;
+        }
+        let players = self.get_players();
+        let num_decks = players.iter()
+            .flat_map(|p| p.get_hand())
+            .map(|card| match card {
+                Card::Standard { deck_id, .. } => deck_id + 1,
+                Card::Joker { .. } => 1,
+            })
+            .max()
+            .unwrap_or(1);
+        let index_of = |id: Option<String>| id
+            .and_then(|id| players.iter().position(|p| *p.get_id() == id))
+            .map(|index| index as u8)
+            .unwrap_or(NONE);
+        let mut bytes = vec![SHARE_CODE_VERSION, num_decks, players.len() as u8];
+
+        for player in players.iter() {
+            let id = player.get_id().as_bytes();
+            if id.len() > 255 {
+                return None;
+            }
+            bytes.push(id.len() as u8);
+            bytes.extend_from_slice(id);
+            match player.hand_as_bitmask(num_decks)? {
+                CardMask::OneDeck(mask) => bytes.extend_from_slice(&mask.to_le_bytes()),
+                CardMask::TwoDecks(mask) => bytes.extend_from_slice(&mask.to_le_bytes()),
+            }
+        }
+
+        bytes.push(index_of(self.get_next_player()));
+        bytes.push(index_of(self.get_last_player()));
+        let cards = match self.get_last_move() {
+            Some(Hand::Pass) | None => vec![],
+            Some(hand) => hand.to_cards(),
+        };
+        bytes.push(cards.len() as u8);
+        bytes.extend(cards.iter().map(|&card| encode_played_card(card)));
+
+        bytes.push(self.get_suit_order().iter()
+            .enumerate()
+            .fold(0, |byte, (i, &suit)| byte | (suit as u8) << (i * 2)));
+        for pair in self.get_rank_order().chunks(2) {
+            bytes.push(pair[0] as u8 | pair.get(1).map(|&r| (r as u8) << 4).unwrap_or(0));
+        }
+
+        let ruleset = self.get_ruleset();
+        if ruleset != Ruleset::default() {
+            bytes.extend(serde_json::to_vec(&ruleset).ok()?);
+        }
+
+        Some(encode_base64url(&bytes))
+    }
+
+    /// The position a share code was made from.
+    pub fn from_share_code(code: &str) -> Result<Round, ShareCodeError> {
+        let bytes = decode_base64url(code).ok_or(ShareCodeError::InvalidEncoding)?;
+        let mut reader = ByteReader { bytes: &bytes, position: 0 };
+
+        let version = reader.next()?;
+        if version != SHARE_CODE_VERSION {
+            return Err(ShareCodeError::UnsupportedVersion(version));
+        }
+        let num_decks = reader.next()?;
+        let player_count = reader.next()? as usize;
+        let mut ids = vec![];
+        let mut builder = RoundBuilder::new();
+
+        for _ in 0..player_count {
+            let id_length = reader.next()? as usize;
+            let id = String::from_utf8(reader.take(id_length)?.to_vec())
+                .map_err(|_| ShareCodeError::InvalidData)?;
+            let mask = match num_decks {
+                1 => {
+                    let mut mask = [0u8; 8];
+                    mask.copy_from_slice(reader.take(8)?);
+                    CardMask::OneDeck(u64::from_le_bytes(mask))
+                },
+                2 => {
+                    let mut mask = [0u8; 16];
+                    mask.copy_from_slice(reader.take(16)?);
+                    CardMask::TwoDecks(u128::from_le_bytes(mask))
+                },
+                _ => return Err(ShareCodeError::InvalidData),
+            };
+            builder = builder.player(Player::from_bitmask(id.clone(), mask));
+            ids.push(id);
+        }
+
+        let player_at = |index: u8| -> Result<Option<&String>, ShareCodeError> {
+            match index {
+                NONE => Ok(None),
+                index => ids.get(index as usize).map(Some).ok_or(ShareCodeError::InvalidData),
+            }
+        };
+        if let Some(id) = player_at(reader.next()?)? {
+            builder = builder.next_player(id);
+        }
+        let last_player = player_at(reader.next()?)?;
+        let card_count = reader.next()? as usize;
+        let cards = reader.take(card_count)?.iter()
+            .map(|&byte| decode_played_card(byte))
+            .collect::<Option<Vec<PlayedCard>>>()
+            .ok_or(ShareCodeError::InvalidData)?;
+
+        let suit_byte = reader.next()?;
+        let mut suit_order = get_suit_array();
+        for (i, suit) in suit_order.iter_mut().enumerate() {
+            *suit = get_suit_array()[(suit_byte >> (i * 2) & 0b11) as usize];
+        }
+        let rank_bytes = reader.take(7)?;
+        let mut rank_order = get_rank_array();
+        for (i, rank) in rank_order.iter_mut().enumerate() {
+            let nibble = rank_bytes[i / 2] >> (i % 2 * 4) & 0xf;
+            *rank = *get_rank_array().get(nibble as usize).ok_or(ShareCodeError::InvalidData)?;
+        }
+
+        let rest = reader.rest();
+        let ruleset = if rest.is_empty() {
+            Ruleset::default()
+        } else {
+            serde_json::from_slice(rest).map_err(|_| ShareCodeError::InvalidData)?
+        };
+
+        match (last_player, cards.is_empty()) {
+            (Some(id), true) => builder = builder.table_cleared(id),
+            (Some(id), false) => {
+                let hand = Hand::try_build_with(cards, ruleset)
+                    .map_err(ShareCodeError::InvalidHand)?;
+                builder = builder.last_move(hand, id);
+            },
+            (None, true) => (),
+            (None, false) => return Err(ShareCodeError::InvalidData),
+        }
+
+        builder.suit_order(&suit_order)
+            .rank_order(rank_order)
+            .ruleset(ruleset)
+            .build()
+            .map_err(ShareCodeError::InvalidRound)
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn next(&mut self) -> Result<u8, ShareCodeError> {
+        self.take(1).map(|bytes| bytes[0])
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], ShareCodeError> {
+        let bytes = self.bytes.get(self.position..self.position + count)
+            .ok_or(ShareCodeError::Truncated)?;
+        self.position += count;
+        Ok(bytes)
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.position..]
+    }
+}
+
+fn encode_played_card(card: PlayedCard) -> u8 {
+    let joker = if card.get_is_joker() { JOKER_BIT } else { 0 };
+    joker | (card.get_rank() as u8) << 2 | card.get_suit() as u8
+}
+
+fn decode_played_card(byte: u8) -> Option<PlayedCard> {
+    let rank: Rank = *get_rank_array().get((byte & !JOKER_BIT) as usize >> 2)?;
+    let suit: Suit = get_suit_array()[(byte & 0b11) as usize];
+    Some(PlayedCard::new(rank, suit, byte & JOKER_BIT != 0))
+}
+
+fn encode_base64url(bytes: &[u8]) -> String {
+    let mut code = String::new();
+    for chunk in bytes.chunks(3) {
+        let bits = chunk.iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, &byte)| bits | (byte as u32) << (16 - i * 8));
+        for i in 0..=chunk.len() {
+            code.push(BASE64URL[(bits >> (18 - i * 6) & 0x3f) as usize] as char);
+        }
+    }
+    code
+}
+
+fn decode_base64url(code: &str) -> Option<Vec<u8>> {
+    let values = code.bytes()
+        .map(|c| BASE64URL.iter().position(|&b| b == c).map(|v| v as u32))
+        .collect::<Option<Vec<u32>>>()?;
+    if values.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut bytes = vec![];
+    for chunk in values.chunks(4) {
+        let bits = chunk.iter()
+            .enumerate()
+            .fold(0u32, |bits, (i, &value)| bits | value << (18 - i * 6));
+        for i in 0..chunk.len() - 1 {
+            bytes.push((bits >> (16 - i * 8)) as u8);
+        }
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{FlushPrecedence, RulesetPreset};
+    use crate::scenario::{parse_cards, Scenario};
+
+    fn assert_same_position(round: &Round, shared: &Round) {
+        let hands = |round: &Round| -> Vec<(String, Vec<Card>)> {
+            round.get_players().iter()
+                .map(|p| {
+                    let mut hand = p.get_hand();
+                    hand.sort();
+                    (p.get_id().to_string(), hand)
+                })
+                .collect()
+        };
+        assert_eq!(hands(shared), hands(round));
+        assert_eq!(shared.get_next_player(), round.get_next_player());
+        assert_eq!(shared.get_last_move(), round.get_last_move());
+        assert_eq!(shared.get_last_player(), round.get_last_player());
+        assert_eq!(shared.get_suit_order(), round.get_suit_order());
+        assert_eq!(shared.get_rank_order(), round.get_rank_order());
+        assert_eq!(shared.get_ruleset(), round.get_ruleset());
+    }
+
+    #[test]
+    fn positions_round_trip_through_share_codes() {
+        let round = Scenario::new()
+            .player("a", "3C 4C 9H 10D 2S")
+            .player("bea", "9D JK")
+            .last_move("bea", "KC JK:KS")
+            .next_player("a")
+            .reversed()
+            .build()
+            .unwrap();
+
+        let code = round.to_share_code().unwrap();
+
+        assert!(code.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'-' || c == b'_'));
+        assert!(code.len() < 60, "{}", code);
+        assert_same_position(&round, &Round::from_share_code(&code).unwrap());
+    }
+
+    #[test]
+    fn cleared_tables_and_rulesets_are_kept() {
+        let round = Scenario::new()
+            .player("a", "3C")
+            .player("b", "9D")
+            .lead("b")
+            .next_player("b")
+            .ruleset(Ruleset {
+                flush_precedence: FlushPrecedence::AllCards,
+                ..RulesetPreset::NoReversals.get_ruleset()
+            })
+            .build()
+            .unwrap();
+
+        let shared = Round::from_share_code(&round.to_share_code().unwrap()).unwrap();
+
+        assert_same_position(&round, &shared);
+        assert_eq!(shared.get_last_move(), Some(Hand::Pass));
+    }
+
+    #[test]
+    fn bad_codes_are_rejected() {
+        let round = Scenario::new().player("a", "3C").player("b", "9D").build().unwrap();
+        let code = round.to_share_code().unwrap();
+
+        assert_eq!(Round::from_share_code("a+b/").err(), Some(ShareCodeError::InvalidEncoding));
+        assert_eq!(
+            Round::from_share_code(&code[..code.len() - 4]).err(),
+            Some(ShareCodeError::Truncated)
+        );
+        assert_eq!(
+            Round::from_share_code(&encode_base64url(&[9])).err(),
+            Some(ShareCodeError::UnsupportedVersion(9))
+        );
+    }
+
+    #[test]
+    fn five_suit_positions_have_no_share_code() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), parse_cards("3C 3E").unwrap()))
+            .player(Player::new("b".to_string(), parse_cards("9D").unwrap()))
+            .suit_order(&[Suit::Clubs, Suit::Hearts, Suit::Diamonds, Suit::Spades, Suit::Eagles])
+            .build()
+            .unwrap();
+
+        assert_eq!(round.to_share_code(), None);
+    }
+
+    #[test]
+    fn base64url_matches_the_standard_alphabet() {
+        assert_eq!(encode_base64url(b"pusoy"), "cHVzb3k");
+        assert_eq!(encode_base64url(&[0xfb, 0xff]), "-_8");
+        assert_eq!(decode_base64url("cHVzb3k"), Some(b"pusoy".to_vec()));
+        assert_eq!(decode_base64url("cHVzb"), None);
+    }
+}
@@ -0,0 +1,213 @@
+use super::{verify_game, GameRecord, Round, VerifyError};
+use crate::cards::PlayedCard;
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SignatureError {
+    /// The move at `index` wasn't signed
+    MissingSignature { index: usize },
+    /// There's no key to check the signature of the move at `index`
+    UnknownPlayer { index: usize, player_id: String },
+    /// The signature of the move at `index` isn't valid hex, or wasn't
+    /// made by the player's key over that move
+    BadSignature { index: usize },
+    /// The signatures hold but the game itself doesn't verify
+    Illegal(VerifyError),
+}
+
+/// The bytes a player signs for a move: the move's index, the hash of the
+/// round it was made in, the player and the cards. Tying the signature to
+/// the round means it can't be moved to another game or another point in
+/// this one.
+pub fn move_signing_payload(
+    round: &Round,
+    index: usize,
+    player_id: &str,
+    cards: &[PlayedCard],
+) -> Vec<u8> {
+    let cards = serde_json::to_string(cards).expect("played cards always serialize");
+
+    format!(
+        "pusoy_dos2 move|{}|{:016x}|{}:{}|{}",
+        index,
+        round.state_hash(),
+        player_id.len(),
+        player_id,
+        cards
+    ).into_bytes()
+}
+
+/// Signs the move at `index` of a game, made in `round`, returning the
+/// signature as hex ready for `GameRecord::record_signed_move`.
+pub fn sign_move(
+    key: &SigningKey,
+    round: &Round,
+    index: usize,
+    player_id: &str,
+    cards: &[PlayedCard],
+) -> String {
+    let payload = move_signing_payload(round, index, player_id, cards);
+
+    encode_hex(&key.sign(&payload).to_bytes())
+}
+
+/// Replays `record`, checking every move was signed by its player's key
+/// in `keys`, and then verifies the game as `verify_game` does. The keys
+/// should come from the players rather than the record's host, or a host
+/// could sign moves for them.
+pub fn verify_signed_game(
+    record: &GameRecord,
+    keys: &BTreeMap<String, VerifyingKey>,
+) -> Result<(), SignatureError> {
+    let mut round = record.initial_round.clone();
+
+    for (index, recorded) in record.moves.iter().enumerate() {
+        let signature = recorded.signature.as_ref()
+            .ok_or(SignatureError::MissingSignature { index })?;
+        let key = keys.get(&recorded.player_id)
+            .ok_or_else(|| SignatureError::UnknownPlayer {
+                index,
+                player_id: recorded.player_id.clone(),
+            })?;
+        let signature = decode_hex::<64>(signature)
+            .map(|bytes| Signature::from_bytes(&bytes))
+            .ok_or(SignatureError::BadSignature { index })?;
+        let payload = move_signing_payload(&round, index, &recorded.player_id, &recorded.cards);
+        key.verify(&payload, &signature)
+            .map_err(|_| SignatureError::BadSignature { index })?;
+
+        // verify_game reports illegal moves once the signatures are done
+        round = match round.submit_move(&recorded.player_id, recorded.cards.clone()) {
+            Ok(round) => round,
+            Err(_) => break,
+        };
+    }
+
+    verify_game(record).map_err(SignatureError::Illegal)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return None;
+    }
+
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Rank, Suit};
+    use crate::scenario::{parse_played_cards, Scenario};
+
+    fn get_keys() -> (SigningKey, SigningKey) {
+        (SigningKey::from_bytes(&[1; 32]), SigningKey::from_bytes(&[2; 32]))
+    }
+
+    fn get_signed_record() -> GameRecord {
+        let (a_key, b_key) = get_keys();
+        let mut round = Scenario::new()
+            .player("a", "3C AC")
+            .player("b", "5C 6C")
+            .build()
+            .unwrap();
+        let mut record = GameRecord::new(round.clone());
+
+        let moves = [("a", "3C"), ("b", "5C"), ("a", "AC")];
+        for (index, (player_id, cards)) in moves.iter().enumerate() {
+            let key = if *player_id == "a" { &a_key } else { &b_key };
+            let cards = parse_played_cards(cards).unwrap();
+            let signature = sign_move(key, &round, index, player_id, &cards);
+            round = round.submit_move(player_id, cards.clone()).unwrap();
+            record.record_signed_move(player_id, cards, signature);
+        }
+        record.winners = vec!["a".to_string()];
+
+        record
+    }
+
+    fn get_verifying_keys() -> BTreeMap<String, VerifyingKey> {
+        let (a_key, b_key) = get_keys();
+        let mut keys = BTreeMap::new();
+        keys.insert("a".to_string(), a_key.verifying_key());
+        keys.insert("b".to_string(), b_key.verifying_key());
+        keys
+    }
+
+    #[test]
+    fn signed_games_verify_and_survive_serialization() {
+        let record = get_signed_record();
+        let json = serde_json::to_string(&record).unwrap();
+        let parsed: GameRecord = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.moves, record.moves);
+        assert_eq!(verify_signed_game(&parsed, &get_verifying_keys()), Ok(()));
+    }
+
+    #[test]
+    fn tampered_moves_are_caught() {
+        let mut record = get_signed_record();
+        record.moves[1].cards = vec![PlayedCard::new(Rank::Six, Suit::Clubs, false)];
+        assert_eq!(
+            verify_signed_game(&record, &get_verifying_keys()),
+            Err(SignatureError::BadSignature { index: 1 })
+        );
+
+        // a valid signature moved to another point in the game
+        let mut record = get_signed_record();
+        record.moves[2].signature = record.moves[0].signature.clone();
+        assert_eq!(
+            verify_signed_game(&record, &get_verifying_keys()),
+            Err(SignatureError::BadSignature { index: 2 })
+        );
+
+        let mut record = get_signed_record();
+        record.moves[0].signature = Some("zz".to_string());
+        assert_eq!(
+            verify_signed_game(&record, &get_verifying_keys()),
+            Err(SignatureError::BadSignature { index: 0 })
+        );
+    }
+
+    #[test]
+    fn every_move_needs_a_signature_from_a_known_player() {
+        let mut record = get_signed_record();
+        record.moves[1].signature = None;
+        assert_eq!(
+            verify_signed_game(&record, &get_verifying_keys()),
+            Err(SignatureError::MissingSignature { index: 1 })
+        );
+
+        let mut keys = get_verifying_keys();
+        keys.remove("b");
+        assert_eq!(
+            verify_signed_game(&get_signed_record(), &keys),
+            Err(SignatureError::UnknownPlayer { index: 1, player_id: "b".to_string() })
+        );
+    }
+
+    #[test]
+    fn signed_games_must_still_be_legal() {
+        let mut record = get_signed_record();
+        record.winners = vec!["b".to_string()];
+
+        assert_eq!(
+            verify_signed_game(&record, &get_verifying_keys()),
+            Err(SignatureError::Illegal(VerifyError::WinnersMismatch {
+                claimed: vec!["b".to_string()],
+                actual: vec!["a".to_string()],
+            }))
+        );
+    }
+}
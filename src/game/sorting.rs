@@ -0,0 +1,108 @@
+use super::{sort_unplayed_cards, OrderContext, Round};
+use crate::cards::{Card, Rank, Suit};
+use crate::prelude::*;
+
+/// The hand from lowest to highest under the given orders, with jokers at
+/// the top.
+pub fn sort_hand(hand: &[Card], orders: &OrderContext) -> Vec<Card> {
+    let mut sorted = sort_unplayed_cards(hand, orders);
+    sorted.reverse();
+    sorted
+}
+
+/// The natural cards in the hand grouped by rank, lowest rank first, with
+/// each group sorted. Jokers belong to no rank and are left out.
+pub fn group_by_rank(hand: &[Card], orders: &OrderContext) -> Vec<(Rank, Vec<Card>)> {
+    let sorted = sort_hand(hand, orders);
+
+    orders.get_rank_order().iter()
+        .map(|&rank| {
+            let cards: Vec<Card> = sorted.iter()
+                .filter(|c| c.get_rank() == Some(rank))
+                .cloned()
+                .collect();
+            (rank, cards)
+        })
+        .filter(|(_, cards)| !cards.is_empty())
+        .collect()
+}
+
+/// The natural cards in the hand grouped by suit, lowest suit first, with
+/// each group sorted. Jokers belong to no suit and are left out.
+pub fn group_by_suit(hand: &[Card], orders: &OrderContext) -> Vec<(Suit, Vec<Card>)> {
+    let sorted = sort_hand(hand, orders);
+
+    orders.get_suit_order().iter()
+        .map(|&suit| {
+            let cards: Vec<Card> = sorted.iter()
+                .filter(|c| c.get_suit() == Some(suit))
+                .cloned()
+                .collect();
+            (suit, cards)
+        })
+        .filter(|(_, cards)| !cards.is_empty())
+        .collect()
+}
+
+impl Round {
+    /// A player's hand sorted under the round's current orders, so it
+    /// follows any reversal in play.
+    pub fn get_sorted_hand(&self, player_id: &str) -> Option<Vec<Card>> {
+        let player = self.get_player(player_id)?;
+        Some(self.get_order_context().sort_hand(&player.get_hand()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_cards, parse_played_cards, Scenario};
+
+    fn get_hand() -> Vec<Card> {
+        parse_cards("JK 2C 3S 3C KS").unwrap()
+    }
+
+    #[test]
+    fn hands_sort_lowest_first_with_jokers_on_top() {
+        assert_eq!(
+            sort_hand(&get_hand(), &OrderContext::standard()),
+            parse_cards("3C 3S KS 2C JK").unwrap()
+        );
+    }
+
+    #[test]
+    fn groups_follow_reversed_orders() {
+        let orders = OrderContext::standard().reverse();
+
+        assert_eq!(group_by_rank(&get_hand(), &orders), vec![
+            (Rank::Two, parse_cards("2C").unwrap()),
+            (Rank::King, parse_cards("KS").unwrap()),
+            (Rank::Three, parse_cards("3S 3C").unwrap()),
+        ]);
+        assert_eq!(group_by_suit(&get_hand(), &orders), vec![
+            (Suit::Spades, parse_cards("KS 3S").unwrap()),
+            (Suit::Clubs, parse_cards("2C 3C").unwrap()),
+        ]);
+    }
+
+    #[test]
+    fn kept_hands_are_resorted_after_a_reversal() {
+        let mut round = Scenario::new()
+            .player("a", "9C 9H 9D 9S 3C 4C 5C 6D")
+            .player("b", "2S")
+            .lead("a")
+            .next_player("a")
+            .build()
+            .unwrap();
+        let quad = parse_played_cards("9C 9H 9D 9S 3C").unwrap();
+        let unsorted = round.submit_move("a", quad.clone()).unwrap();
+        round.set_keep_hands_sorted(true);
+        round.submit_move_mut("a", quad).unwrap();
+
+        let sorted = parse_cards("6D 5C 4C").unwrap();
+        assert!(round.is_reversed());
+        assert_eq!(unsorted.get_player("a").unwrap().get_hand(), parse_cards("4C 5C 6D").unwrap());
+        assert_eq!(round.get_player("a").unwrap().get_hand(), sorted);
+        assert_eq!(unsorted.sort_hands().get_player("a").unwrap().get_hand(), sorted);
+    }
+}
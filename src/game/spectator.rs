@@ -0,0 +1,62 @@
+use super::{Hand, Round};
+use crate::cards::{Card, Rank, Suit};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// What someone watching a round may see: the table and how many cards
+/// each player holds, but none of the cards in anyone's hand.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectatorView {
+    /// Every player's id and the number of cards they hold, in seat order
+    pub card_counts: Vec<(String, usize)>,
+    pub next_player: Option<String>,
+    pub last_move: Option<Hand>,
+    pub last_player: Option<String>,
+    pub suit_order: Vec<Suit>,
+    pub rank_order: [Rank; 13],
+    /// Cards played in earlier tricks
+    pub discarded: Vec<Card>,
+    pub resigned_players: Vec<String>,
+}
+
+impl SpectatorView {
+    pub fn from_round(round: &Round) -> SpectatorView {
+        SpectatorView {
+            card_counts: round.get_card_counts(),
+            next_player: round.get_next_player(),
+            last_move: round.get_last_move(),
+            last_player: round.get_last_player(),
+            suit_order: round.get_suit_order(),
+            rank_order: round.get_rank_order(),
+            discarded: round.get_discarded(),
+            resigned_players: round.get_resigned_players(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::Scenario;
+
+    #[test]
+    fn spectators_see_card_counts_but_no_cards() {
+        let round = Scenario::new()
+            .player("a", "3C 4C 9H")
+            .player("b", "9D")
+            .last_move("b", "KC KS")
+            .next_player("a")
+            .build()
+            .unwrap();
+
+        let view = SpectatorView::from_round(&round);
+
+        assert_eq!(view.card_counts, vec![("a".to_string(), 3), ("b".to_string(), 1)]);
+        assert_eq!(view.last_player, Some("b".to_string()));
+        assert_eq!(view.next_player, Some("a".to_string()));
+
+        let json = serde_json::to_string(&view).unwrap();
+        assert!(!json.contains("hand"));
+        assert_eq!(serde_json::from_str::<SpectatorView>(&json).unwrap(), view);
+    }
+}
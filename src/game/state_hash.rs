@@ -0,0 +1,125 @@
+use super::{Hand, Round};
+use crate::cards::Card;
+use crate::prelude::*;
+use sha2::{Digest, Sha256};
+
+impl Round {
+    /// The stable encoding of the round that `state_hash` is taken over.
+    /// Sections are separated by `|` and list items by `,`. Player ids are
+    /// length prefixed and hands and the discard pile are sorted, so the
+    /// order cards happen to be held in doesn't matter.
+    pub fn serialize_state(&self) -> String {
+        let players = self.get_players().iter()
+            .map(|p| {
+                let mut hand = p.get_hand();
+                hand.sort();
+                format!("{}={}", encode_id(p.get_id()), encode_cards(&hand))
+            })
+            .collect::<Vec<String>>()
+            .join(",");
+        let last_move = self.get_last_move()
+            .map(|hand| match hand {
+                Hand::Pass => "pass".to_string(),
+                hand => hand.to_cards().iter()
+                    .map(|card| card.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            })
+            .unwrap_or_else(|| "-".to_string());
+        let mut discarded = self.get_discarded();
+        discarded.sort();
+
+        [
+            players,
+            encode_optional_id(self.get_next_player()),
+            last_move,
+            encode_optional_id(self.get_last_player()),
+            self.get_suit_order().iter().map(|s| s.to_string()).collect::<Vec<_>>().join(","),
+            self.get_rank_order().map(|r| r.to_string()).join(","),
+            encode_ids(&self.get_passed_players()),
+            self.get_pass_count().to_string(),
+            encode_cards(&discarded),
+            encode_ids(&self.get_resigned_players()),
+            self.is_temporarily_reversed().to_string(),
+            encode_optional_id(self.get_last_card_pending()),
+        ].join("|")
+    }
+
+    /// A 64 bit digest of the round's state, taken from SHA-256 of
+    /// `serialize_state`. It's the same on every platform and version of
+    /// the crate that encodes the state the same way, so a client and
+    /// server can compare hashes every turn to spot a desync.
+    pub fn state_hash(&self) -> u64 {
+        let digest = Sha256::digest(self.serialize_state().as_bytes());
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&digest[..8]);
+
+        u64::from_be_bytes(bytes)
+    }
+}
+
+fn encode_id(id: &str) -> String {
+    format!("{}:{}", id.len(), id)
+}
+
+fn encode_optional_id(id: Option<String>) -> String {
+    id.map(|id| encode_id(&id)).unwrap_or_else(|| "-".to_string())
+}
+
+fn encode_ids(ids: &[String]) -> String {
+    ids.iter().map(|id| encode_id(id)).collect::<Vec<String>>().join(",")
+}
+
+fn encode_cards(cards: &[Card]) -> String {
+    cards.iter()
+        .map(|card| {
+            let deck_id = match card {
+                Card::Standard { deck_id, .. } => deck_id,
+                Card::Joker { deck_id } => deck_id,
+            };
+            format!("{}:{}", deck_id, card)
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{PlayedCard, Rank, Suit};
+    use crate::game::{Player, RoundBuilder};
+    use crate::scenario::parse_cards;
+
+    fn get_round(a_hand: Vec<Card>) -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), a_hand))
+            .player(Player::new("b".to_string(), parse_cards("5C").unwrap()))
+            .next_player("a")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn the_hash_ignores_the_order_cards_are_held_in() {
+        let three = "3C".parse::<Card>().unwrap();
+        let four = "4C".parse::<Card>().unwrap();
+        let round = get_round(vec![three, four]);
+
+        assert_eq!(round.state_hash(), get_round(vec![four, three]).state_hash());
+        assert_eq!(
+            round.serialize_state(),
+            "1:a=0:3C 0:4C,1:b=0:5C|1:a|-|-|C,H,D,S|3,4,5,6,7,8,9,10,J,Q,K,A,2||0|||false|-"
+        );
+    }
+
+    #[test]
+    fn the_hash_changes_with_every_move() {
+        let round = get_round(parse_cards("3C 4C").unwrap());
+        let next = round
+            .submit_move("a", vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)])
+            .unwrap();
+
+        assert_ne!(round.state_hash(), next.state_hash());
+        assert_eq!(next.state_hash(), next.clone().state_hash());
+    }
+}
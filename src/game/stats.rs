@@ -0,0 +1,211 @@
+use super::{GameObserver, GameResult, Hand, OrderContext, Trick, TrickType};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use alloc::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub games_played: usize,
+    pub tricks_won: usize,
+    pub hands_played: usize,
+    pub passes: usize,
+    pub five_card_tricks: BTreeMap<TrickType, usize>,
+    pub reversals_triggered: usize,
+    /// Cards still held at the end of each game, summed
+    pub total_cards_left: usize,
+    /// Hands played to beat one already on the table
+    #[serde(default)]
+    pub raises: usize,
+}
+
+impl PlayerStats {
+    /// Average cards left in hand when a game ended.
+    pub fn average_cards_left(&self) -> f64 {
+        if self.games_played == 0 {
+            return 0.0;
+        }
+        self.total_cards_left as f64 / self.games_played as f64
+    }
+}
+
+/// Per-player metrics gathered across games. Attach it to a `Game` as an
+/// observer (shared through `Rc<RefCell<Stats>>` to read it back) and
+/// record each `GameResult` once the game is over.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Stats {
+    players: BTreeMap<String, PlayerStats>,
+    /// How many tricks were cleared after each number of raises
+    #[serde(default)]
+    trick_raises: BTreeMap<usize, usize>,
+    #[serde(skip)]
+    last_mover: Option<String>,
+    /// Hands played onto the trick in progress
+    #[serde(skip)]
+    trick_length: usize,
+}
+
+impl Stats {
+    pub fn get_player_stats(&self, player_id: &str) -> Option<&PlayerStats> {
+        self.players.get(player_id)
+    }
+
+    pub fn get_all(&self) -> &BTreeMap<String, PlayerStats> {
+        &self.players
+    }
+
+    /// How many tricks were cleared after each number of raises, from
+    /// uncontested leads at zero upwards.
+    pub fn get_trick_raises(&self) -> &BTreeMap<usize, usize> {
+        &self.trick_raises
+    }
+
+    /// Average raises before a trick cleared, as a measure of how
+    /// contested tricks were.
+    pub fn average_raises_per_trick(&self) -> f64 {
+        let tricks: usize = self.trick_raises.values().sum();
+        if tricks == 0 {
+            return 0.0;
+        }
+        let raises: usize = self.trick_raises.iter()
+            .map(|(raises, count)| raises * count)
+            .sum();
+        raises as f64 / tricks as f64
+    }
+
+    pub fn record_result(&mut self, result: &GameResult) {
+        for player in result.players.iter() {
+            let stats = self.entry(&player.player_id);
+            stats.games_played += 1;
+            stats.total_cards_left += player.cards_left;
+        }
+    }
+
+    fn entry(&mut self, player_id: &str) -> &mut PlayerStats {
+        self.players.entry(player_id.to_string()).or_default()
+    }
+}
+
+impl GameObserver for Stats {
+    fn on_move_played(&mut self, player_id: &str, hand: Hand) {
+        self.last_mover = Some(player_id.to_string());
+        let raised = hand != Hand::Pass && self.trick_length > 0;
+        if hand != Hand::Pass {
+            self.trick_length += 1;
+        }
+        let stats = self.entry(player_id);
+        if raised {
+            stats.raises += 1;
+        }
+
+        match hand {
+            Hand::Pass => stats.passes += 1,
+            Hand::FiveCardTrick(Trick { trick_type, .. }) => {
+                stats.hands_played += 1;
+                *stats.five_card_tricks.entry(trick_type).or_insert(0) += 1;
+            },
+            _ => stats.hands_played += 1,
+        }
+    }
+
+    fn on_trick_cleared(&mut self, winner_id: &str) {
+        self.entry(winner_id).tricks_won += 1;
+        let raises = self.trick_length.saturating_sub(1);
+        *self.trick_raises.entry(raises).or_insert(0) += 1;
+        self.trick_length = 0;
+    }
+
+    fn on_game_over(&mut self, _winners: &[String]) {
+        self.trick_length = 0;
+    }
+
+    fn on_order_reversed(&mut self, _orders: &OrderContext) {
+        if let Some(player_id) = self.last_mover.clone() {
+            self.entry(&player_id).reversals_triggered += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{PlayedCard, Rank, Suit};
+    use crate::game::{PlayerResult, TeamResult};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    fn four_threes() -> Hand {
+        Hand::build(vec![
+            PlayedCard::new(Rank::Three, Suit::Clubs, false),
+            PlayedCard::new(Rank::Three, Suit::Hearts, false),
+            PlayedCard::new(Rank::Three, Suit::Diamonds, false),
+            PlayedCard::new(Rank::Three, Suit::Spades, false),
+            PlayedCard::new(Rank::Four, Suit::Clubs, false),
+        ]).unwrap()
+    }
+
+    #[test]
+    fn stats_are_gathered_from_game_events() {
+        let stats = Rc::new(RefCell::new(Stats::default()));
+        let mut observer: Box<dyn GameObserver> = Box::new(stats.clone());
+
+        observer.on_move_played("a", four_threes());
+        observer.on_order_reversed(&OrderContext::standard().reverse());
+        observer.on_move_played("b", Hand::Pass);
+        observer.on_trick_cleared("a");
+        observer.on_move_played("b", Hand::Single(PlayedCard::new(Rank::Five, Suit::Clubs, false)));
+        observer.on_move_played("a", Hand::Single(PlayedCard::new(Rank::Six, Suit::Clubs, false)));
+        observer.on_move_played("b", Hand::Single(PlayedCard::new(Rank::Two, Suit::Clubs, false)));
+        observer.on_move_played("a", Hand::Pass);
+        observer.on_trick_cleared("b");
+
+        let stats = stats.borrow();
+        let a = stats.get_player_stats("a").unwrap();
+        let b = stats.get_player_stats("b").unwrap();
+
+        assert_eq!(a.five_card_tricks.get(&TrickType::FourOfAKind), Some(&1));
+        assert_eq!(a.hands_played, 2);
+        assert_eq!(a.reversals_triggered, 1);
+        assert_eq!(a.tricks_won, 1);
+        assert_eq!(a.raises, 1);
+        assert_eq!(b.passes, 1);
+        assert_eq!(b.reversals_triggered, 0);
+        assert_eq!(b.raises, 1);
+        assert_eq!(stats.get_trick_raises().get(&0), Some(&1));
+        assert_eq!(stats.get_trick_raises().get(&2), Some(&1));
+        assert_eq!(stats.average_raises_per_trick(), 1.0);
+    }
+
+    #[test]
+    fn results_are_averaged_over_games() {
+        let mut stats = Stats::default();
+        let result = |cards_left| GameResult {
+            players: vec![PlayerResult {
+                player_id: "a".to_string(),
+                team: 0,
+                finishing_position: None,
+                cards_left,
+                resigned: false,
+                penalty_points: 0,
+                tricks_won: 0,
+                bombs_played: 0,
+            }],
+            teams: vec![TeamResult {
+                team: 0,
+                player_ids: vec!["a".to_string()],
+                cards_left,
+                placement: 1,
+            }],
+        };
+
+        stats.record_result(&result(3));
+        stats.record_result(&result(0));
+
+        let a = stats.get_player_stats("a").unwrap();
+        assert_eq!(a.games_played, 2);
+        assert_eq!(a.average_cards_left(), 1.5);
+
+        let json = serde_json::to_string(&stats).unwrap();
+        let loaded: Stats = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.get_all(), stats.get_all());
+    }
+}
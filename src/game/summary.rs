@@ -0,0 +1,186 @@
+use super::{
+    compare_hands_with_ruleset,
+    get_game_result,
+    verify_game,
+    GameRecord,
+    Hand,
+    ScoringScheme,
+    VerifyError,
+};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct PlayerSummary {
+    pub player_id: String,
+    /// The placement of the player's team, starting at 1
+    pub placement: usize,
+    pub points: i64,
+    /// The strongest hand the player played, if they played any
+    pub biggest_hand: Option<Hand>,
+}
+
+/// Everything a results screen shows once a game is over.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct GameSummary {
+    /// Players from first place to last, with players who went out
+    /// earlier ahead of their partners
+    pub players: Vec<PlayerSummary>,
+    /// Hands played that reversed the suit and rank orders
+    pub reversals: usize,
+    /// The most hands played in a single trick
+    pub longest_trick: usize,
+}
+
+/// Replays a finished game to summarise it, scoring it under `scheme`.
+/// Hands with more cards count as bigger, and hands with the same number
+/// of cards are compared using the orders the game was dealt with.
+pub fn summarize_game(
+    record: &GameRecord,
+    scheme: ScoringScheme,
+) -> Result<GameSummary, VerifyError> {
+    verify_game(record)?;
+
+    let initial_round = &record.initial_round;
+    let ruleset = initial_round.get_ruleset();
+    let orders = initial_round.get_order_context();
+    let is_bigger = |hand: Hand, biggest: Option<Hand>| match biggest {
+        None => true,
+        Some(biggest) => {
+            let (size, biggest_size) = (hand.get_card_count(), biggest.get_card_count());
+            size > biggest_size || (size == biggest_size && compare_hands_with_ruleset(
+                biggest, hand, ruleset, &orders
+            ))
+        },
+    };
+
+    let mut round = initial_round.clone();
+    let mut biggest_hands: Vec<(String, Option<Hand>)> = round.get_player_ids()
+        .into_iter()
+        .map(|id| (id, None))
+        .collect();
+    let mut reversals = 0;
+    let mut trick_length = 0;
+    let mut longest_trick = 0;
+
+    for recorded in record.moves.iter() {
+        let tricks_won = round.get_trick_winners().len();
+        let played = round.build_hand(&recorded.cards);
+        round = round.submit_move(&recorded.player_id, recorded.cards.clone())
+            .expect("verified games only hold legal moves");
+
+        if let Some(hand) = played.filter(|&h| h != Hand::Pass) {
+            trick_length += 1;
+            longest_trick = longest_trick.max(trick_length);
+
+            if ruleset.reversals_enabled && ruleset.reversal_triggers.is_triggered_by(hand) {
+                reversals += 1;
+            }
+            if let Some((_, biggest)) = biggest_hands.iter_mut()
+                .find(|(id, _)| *id == recorded.player_id) {
+                if is_bigger(hand, *biggest) {
+                    *biggest = Some(hand);
+                }
+            }
+        }
+
+        if round.get_trick_winners().len() > tricks_won {
+            trick_length = 0;
+        }
+    }
+
+    let result = get_game_result(&round, &record.winners);
+    let mut players: Vec<(Option<usize>, PlayerSummary)> = result.players.iter()
+        .zip(biggest_hands)
+        .map(|(player, (_, biggest_hand))| {
+            let placement = result.teams.iter()
+                .find(|team| team.team == player.team)
+                .map(|team| team.placement)
+                .unwrap_or_default();
+
+            (player.finishing_position, PlayerSummary {
+                player_id: player.player_id.clone(),
+                placement,
+                points: scheme.score(player),
+                biggest_hand,
+            })
+        })
+        .collect();
+
+    players.sort_by_key(|(finish, player)| (player.placement, finish.unwrap_or(usize::MAX)));
+
+    Ok(GameSummary {
+        players: players.into_iter().map(|(_, player)| player).collect(),
+        reversals,
+        longest_trick,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{PlayedCard, Rank, Suit};
+    use crate::scenario::Scenario;
+
+    fn played(cards: &[(Rank, Suit)]) -> Vec<PlayedCard> {
+        cards.iter()
+            .map(|&(rank, suit)| PlayedCard::new(rank, suit, false))
+            .collect()
+    }
+
+    fn get_record() -> GameRecord {
+        let round = Scenario::new()
+            .player("a", "3C 4C 4H AC")
+            .player("b", "5C 6C 6H")
+            .build()
+            .unwrap();
+
+        let mut record = GameRecord::new(round);
+        record.record_move("a", played(&[(Rank::Three, Suit::Clubs)]));
+        record.record_move("b", played(&[(Rank::Five, Suit::Clubs)]));
+        record.record_move("a", played(&[(Rank::Ace, Suit::Clubs)]));
+        record.record_move("b", vec![]);
+        record.record_move("a", played(&[(Rank::Four, Suit::Clubs), (Rank::Four, Suit::Hearts)]));
+        record.winners = vec!["a".to_string()];
+
+        record
+    }
+
+    #[test]
+    fn players_are_listed_in_placement_order_with_their_biggest_hands() {
+        let summary = summarize_game(&get_record(), ScoringScheme::CardsLeft).unwrap();
+
+        assert_eq!(summary.players, vec![
+            PlayerSummary {
+                player_id: "a".to_string(),
+                placement: 1,
+                points: 0,
+                biggest_hand: Hand::build(played(&[
+                    (Rank::Four, Suit::Clubs),
+                    (Rank::Four, Suit::Hearts),
+                ])),
+            },
+            PlayerSummary {
+                player_id: "b".to_string(),
+                placement: 2,
+                points: -2,
+                biggest_hand: Hand::build(played(&[(Rank::Five, Suit::Clubs)])),
+            },
+        ]);
+        assert_eq!(summary.reversals, 0);
+        assert_eq!(summary.longest_trick, 3);
+    }
+
+    #[test]
+    fn unfinished_games_cant_be_summarised() {
+        let mut record = get_record();
+        record.moves.pop();
+
+        assert_eq!(
+            summarize_game(&record, ScoringScheme::CardsLeft),
+            Err(VerifyError::GameNotFinished)
+        );
+    }
+}
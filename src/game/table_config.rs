@@ -0,0 +1,236 @@
+use super::{check_orders, ClockConfig, DealMode, Ruleset, ScoringScheme, SetupError};
+#[cfg(feature = "std")]
+use super::{Game, OrderContext};
+use crate::cards::{get_rank_array, get_suit_array, Rank, Suit};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The decks a table deals from and the orders they're played in, lowest
+/// first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeckConfig {
+    pub decks: u8,
+    pub jokers: u8,
+    /// The suits the decks are made of, lowest first. Listing
+    /// `eagles` as well deals five-suit decks for bigger tables.
+    pub suit_order: Vec<Suit>,
+    pub rank_order: [Rank; 13],
+}
+
+impl Default for DeckConfig {
+    fn default() -> DeckConfig {
+        DeckConfig {
+            decks: 1,
+            jokers: 0,
+            suit_order: get_suit_array().to_vec(),
+            rank_order: get_rank_array(),
+        }
+    }
+}
+
+/// Everything that sets one table's house rules apart, in a single
+/// document operators can keep alongside a server. Anything left out
+/// takes its default, so `{}` is a standard game with no clock.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TableConfig {
+    pub ruleset: Ruleset,
+    pub deck: DeckConfig,
+    pub scoring: ScoringScheme,
+    /// Turn timers. Without one nobody is ever timed out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock: Option<ClockConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TableConfigError {
+    /// The document couldn't be read as a config, with the parser's
+    /// reason why. Misspelt fields end up here too.
+    Parse(String),
+    NoDecks,
+    InvalidSuitOrder,
+    InvalidRankOrder,
+    /// A limited deal that gives nobody any cards
+    NoCardsDealt,
+    /// Trick bonus scoring that rewards cards left in hand
+    NegativeCardPenalty,
+    /// A clock that starts with no time on it
+    NoTimeOnClock,
+    /// The table couldn't be dealt for the players given
+    Setup(SetupError),
+}
+
+impl From<SetupError> for TableConfigError {
+    fn from(err: SetupError) -> TableConfigError {
+        match err {
+            SetupError::InvalidSuitOrder => TableConfigError::InvalidSuitOrder,
+            SetupError::InvalidRankOrder => TableConfigError::InvalidRankOrder,
+            err => TableConfigError::Setup(err),
+        }
+    }
+}
+
+impl TableConfig {
+    pub fn from_json(json: &str) -> Result<TableConfig, TableConfigError> {
+        let config: TableConfig = serde_json::from_str(json)
+            .map_err(|err| TableConfigError::Parse(err.to_string()))?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    #[cfg(feature = "toml-config")]
+    pub fn from_toml(toml: &str) -> Result<TableConfig, TableConfigError> {
+        let config: TableConfig = toml::from_str(toml)
+            .map_err(|err| TableConfigError::Parse(err.message().to_string()))?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// Checks the settings make a playable table. Both loaders call this,
+    /// so it only needs calling on configs built in code.
+    pub fn validate(&self) -> Result<(), TableConfigError> {
+        if self.deck.decks == 0 {
+            return Err(TableConfigError::NoDecks);
+        }
+        check_orders(&self.deck.suit_order, self.deck.rank_order)?;
+
+        if let DealMode::Limited { cards_per_player: 0 } = self.ruleset.deal_mode {
+            return Err(TableConfigError::NoCardsDealt);
+        }
+        if let ScoringScheme::TrickBonus { per_card_left, .. } = self.scoring {
+            if per_card_left < 0 {
+                return Err(TableConfigError::NegativeCardPenalty);
+            }
+        }
+        if self.clock.map(|clock| clock.initial_ms == 0).unwrap_or(false) {
+            return Err(TableConfigError::NoTimeOnClock);
+        }
+
+        Ok(())
+    }
+
+    /// Deals a game at this table, with its clock started if it has one.
+    /// The config is checked first, so one built in code can't deal a
+    /// table the loaders would have refused.
+    #[cfg(feature = "std")]
+    pub fn deal(&self, player_ids: &[String]) -> Result<Game, TableConfigError> {
+        self.validate()?;
+
+        let mut game = Game::with_orders(
+            self.deck.decks,
+            self.deck.jokers,
+            player_ids,
+            OrderContext::new(&self.deck.suit_order, self.deck.rank_order)?,
+            self.ruleset,
+        )?;
+        if let Some(clock) = self.clock {
+            game.set_clock(clock);
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{ReversalDuration, TimeoutAction};
+
+    #[test]
+    fn configs_are_loaded_and_checked() {
+        let config = TableConfig::from_json(r#"{
+            "ruleset": { "preset": "classic", "reversal_duration": "UntilTrickCleared" },
+            "deck": { "decks": 2, "jokers": 2 },
+            "scoring": { "TrickBonus": { "per_trick": 2, "per_bomb": 5, "per_card_left": 1 } },
+            "clock": { "initial_ms": 60000, "increment_ms": 2000, "on_timeout": "Forfeit" }
+        }"#).unwrap();
+
+        assert_eq!(config.ruleset.reversal_duration, ReversalDuration::UntilTrickCleared);
+        assert_eq!(config.deck.decks, 2);
+        assert_eq!(config.deck.suit_order, get_suit_array());
+        assert_eq!(config.clock.unwrap().on_timeout, TimeoutAction::Forfeit);
+        assert_eq!(TableConfig::from_json("{}").unwrap(), TableConfig::default());
+
+        let ids = vec!["a".to_string(), "b".to_string()];
+        let game = config.deal(&ids).unwrap();
+        assert_eq!(game.get_round().get_dealt_cards().len(), 106);
+        assert!(game.get_clock().is_some());
+        assert_eq!(
+            config.deal(&["a".to_string(), "a".to_string()]).unwrap_err(),
+            TableConfigError::Setup(SetupError::DuplicatePlayer("a".to_string()))
+        );
+
+        let no_decks = TableConfig {
+            deck: DeckConfig { decks: 0, ..DeckConfig::default() },
+            ..TableConfig::default()
+        };
+        assert_eq!(no_decks.deal(&ids).unwrap_err(), TableConfigError::NoDecks);
+
+        assert!(matches!(
+            TableConfig::from_json(r#"{ "rulset": {} }"#),
+            Err(TableConfigError::Parse(_))
+        ));
+        assert_eq!(
+            TableConfig::from_json(r#"{ "deck": { "decks": 0 } }"#),
+            Err(TableConfigError::NoDecks)
+        );
+        assert_eq!(
+            TableConfig::from_json(r#"{ "deck": { "suit_order": ["clubs", "clubs", "hearts", "spades"] } }"#),
+            Err(TableConfigError::InvalidSuitOrder)
+        );
+        assert_eq!(
+            TableConfig::from_json(r#"{ "deck": { "suit_order": [] } }"#),
+            Err(TableConfigError::InvalidSuitOrder)
+        );
+        assert_eq!(
+            TableConfig::from_json(r#"{ "clock": { "initial_ms": 0, "increment_ms": 0, "on_timeout": "AutoPass" } }"#),
+            Err(TableConfigError::NoTimeOnClock)
+        );
+    }
+
+    #[test]
+    fn five_suit_tables_deal_eagles() {
+        let config = TableConfig::from_json(r#"{
+            "deck": { "suit_order": ["clubs", "hearts", "diamonds", "spades", "eagles"] }
+        }"#).unwrap();
+        let ids: Vec<String> = ["a", "b", "c", "d", "e"].iter().map(|id| id.to_string()).collect();
+        let game = config.deal(&ids).unwrap();
+        let round = game.get_round();
+
+        assert_eq!(round.get_dealt_cards().len(), 65);
+        assert!(round.get_players().iter().all(|p| p.get_card_count() == 13));
+        assert_eq!(game.get_suit_order(), config.deck.suit_order);
+        assert_eq!(round.get_order_context().highest_suit(), Suit::Eagles);
+    }
+
+    #[cfg(feature = "toml-config")]
+    #[test]
+    fn configs_can_be_written_in_toml() {
+        let config = TableConfig::from_toml(r#"
+            scoring = "CardsLeft"
+
+            [ruleset]
+            preset = "classic"
+            deal_mode = { Limited = { cards_per_player = 0 } }
+
+            [deck]
+            jokers = 2
+        "#);
+        assert_eq!(config, Err(TableConfigError::NoCardsDealt));
+
+        let config = TableConfig::from_toml(r#"
+            [deck]
+            jokers = 2
+
+            [clock]
+            initial_ms = 30000
+            increment_ms = 0
+            on_timeout = "AutoPass"
+        "#).unwrap();
+        assert_eq!(config.deck.jokers, 2);
+        assert_eq!(config.clock.unwrap().initial_ms, 30000);
+    }
+}
@@ -0,0 +1,83 @@
+use super::SubmitError;
+use crate::cards::PlayedCard;
+use core::fmt;
+#[cfg(feature = "telemetry")]
+use std::time::{Duration, Instant};
+
+/// Instrumentation for servers to wire into their metrics. Every move a
+/// `Game` is asked to play is timed and reported as either submitted or
+/// failed validation. All methods default to doing nothing.
+///
+/// Timing uses `Instant`, which is unavailable on `wasm32-unknown-unknown`,
+/// so this is for native builds.
+#[cfg(feature = "telemetry")]
+pub trait Telemetry {
+    fn on_submit(&mut self, _player_id: &str, _cards: &[PlayedCard], _elapsed: Duration) {}
+    fn on_validation_fail(&mut self, _player_id: &str, _error: &SubmitError, _elapsed: Duration) {}
+}
+
+/// The hook a `Game` reports to. Without the `telemetry` feature it holds
+/// nothing and reporting compiles away.
+#[derive(Default)]
+pub(crate) struct TelemetryHook {
+    #[cfg(feature = "telemetry")]
+    hook: Option<Box<dyn Telemetry>>,
+}
+
+impl TelemetryHook {
+    #[cfg(feature = "telemetry")]
+    pub(crate) fn set(&mut self, hook: Box<dyn Telemetry>) {
+        self.hook = Some(hook);
+    }
+
+    /// Runs `submit` for a move, reporting how it went and how long it
+    /// took.
+    #[cfg(feature = "telemetry")]
+    pub(crate) fn time_submit<T, F>(
+        &mut self,
+        player_id: &str,
+        cards: &[PlayedCard],
+        submit: F,
+    ) -> Result<T, SubmitError>
+    where
+        F: FnOnce() -> Result<T, SubmitError>,
+    {
+        let started = Instant::now();
+        let result = submit();
+
+        if let Some(hook) = self.hook.as_mut() {
+            let elapsed = started.elapsed();
+            match &result {
+                Ok(_) => hook.on_submit(player_id, cards, elapsed),
+                Err(error) => hook.on_validation_fail(player_id, error, elapsed),
+            }
+        }
+
+        result
+    }
+
+    #[cfg(not(feature = "telemetry"))]
+    #[inline(always)]
+    pub(crate) fn time_submit<T, F>(
+        &mut self,
+        _player_id: &str,
+        _cards: &[PlayedCard],
+        submit: F,
+    ) -> Result<T, SubmitError>
+    where
+        F: FnOnce() -> Result<T, SubmitError>,
+    {
+        submit()
+    }
+}
+
+impl fmt::Debug for TelemetryHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        #[cfg(feature = "telemetry")]
+        let hooked = self.hook.is_some();
+        #[cfg(not(feature = "telemetry"))]
+        let hooked = false;
+
+        write!(f, "TelemetryHook({})", hooked)
+    }
+}
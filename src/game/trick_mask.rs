@@ -0,0 +1,125 @@
+use super::TrickType;
+use crate::cards::PlayedCard;
+use crate::prelude::*;
+
+/// Bits 2 and 3 of every 4 bit rank count, set for counts of 4 or more
+const FOUR_OR_MORE: u64 = 0xC_CCCC_CCCC_CCCC;
+/// A run of five ranks, shifted down to the lowest rank in it
+const RUN: u16 = 0b11111;
+/// The position of the two in the rank bits
+const TWO_BIT: u16 = 1 << 12;
+
+/// Five cards packed into machine words, so a trick can be classified
+/// with a handful of bitwise operations instead of building a map of
+/// rank counts. Each rank gets a 4 bit count in `counts`, and a bit in
+/// `ranks`, in the standard order from three up to two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrickMask {
+    counts: u64,
+    ranks: u16,
+    suits: u8,
+}
+
+impl TrickMask {
+    pub fn new(cards: &[PlayedCard; 5]) -> TrickMask {
+        cards.iter().fold(TrickMask { counts: 0, ranks: 0, suits: 0 }, |mask, card| {
+            let rank = card.get_rank() as u32;
+            TrickMask {
+                counts: mask.counts + (1 << (4 * rank)),
+                ranks: mask.ranks | (1 << rank),
+                suits: mask.suits | (1 << card.get_suit() as u32),
+            }
+        })
+    }
+
+    /// The trick the cards make, as `Hand::try_build` would find it.
+    /// Royal flushes are left as straight flushes, since only a ruleset
+    /// sets them apart.
+    pub fn classify(self, twos_in_straights: bool) -> Option<TrickType> {
+        match self.ranks.count_ones() {
+            1 => Some(TrickType::FiveOfAKind),
+            2 if self.counts & FOUR_OR_MORE != 0 => Some(TrickType::FourOfAKind),
+            2 => Some(TrickType::FullHouse),
+            5 => {
+                let straight = self.ranks >> self.ranks.trailing_zeros() == RUN
+                    && (twos_in_straights || self.ranks & TWO_BIT == 0);
+                let flush = self.suits.count_ones() == 1;
+                match (straight, flush) {
+                    (true, true) => Some(TrickType::StraightFlush),
+                    (true, _) => Some(TrickType::Straight),
+                    (_, true) => Some(TrickType::Flush),
+                    _ => None,
+                }
+            },
+            // a pair or prial with odd cards, which no trick holds, but
+            // they can still be a flush
+            _ if self.suits.count_ones() == 1 => Some(TrickType::Flush),
+            _ => None,
+        }
+    }
+}
+
+/// Classifies many five card tricks at once, for solvers that look at
+/// every trick a hand could make. The result for each trick is the same
+/// as building it with `Hand::try_build`.
+pub fn classify_tricks(
+    tricks: &[[PlayedCard; 5]],
+    twos_in_straights: bool,
+) -> Vec<Option<TrickType>> {
+    tricks.iter()
+        .map(|cards| TrickMask::new(cards).classify(twos_in_straights))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{get_rank_array, get_suit_array};
+    use crate::game::{Hand, Ruleset};
+
+    fn get_all_tricks(cards: &[PlayedCard]) -> Vec<[PlayedCard; 5]> {
+        let mut tricks = vec![];
+        let n = cards.len();
+        for a in 0..n {
+            for b in a + 1..n {
+                for c in b + 1..n {
+                    for d in c + 1..n {
+                        for e in d + 1..n {
+                            tricks.push([cards[a], cards[b], cards[c], cards[d], cards[e]]);
+                        }
+                    }
+                }
+            }
+        }
+        tricks
+    }
+
+    #[test]
+    fn masks_classify_tricks_the_same_as_hands() {
+        // two suits of the ranks from nine up, with jokers doubling the
+        // top few, covers every kind of trick but five of a kind
+        let mut cards: Vec<PlayedCard> = get_rank_array()[6..].iter()
+            .flat_map(|&rank| vec![
+                PlayedCard::new(rank, get_suit_array()[0], false),
+                PlayedCard::new(rank, get_suit_array()[3], false),
+            ])
+            .collect();
+        cards.extend(get_rank_array()[9..].iter()
+            .map(|&rank| PlayedCard::new(rank, get_suit_array()[0], true)));
+        cards.extend(get_rank_array()[9..].iter()
+            .map(|&rank| PlayedCard::new(rank, get_suit_array()[3], true)));
+        let tricks = get_all_tricks(&cards);
+
+        for twos_in_straights in [true, false] {
+            let classified = classify_tricks(&tricks, twos_in_straights);
+
+            for (cards, trick_type) in tricks.iter().zip(classified) {
+                let ruleset = Ruleset { twos_in_straights, ..Ruleset::default() };
+                let expected = Hand::try_build_with(cards.to_vec(), ruleset)
+                    .ok()
+                    .and_then(|hand| hand.get_trick_type());
+                assert_eq!(trick_type, expected, "{:?}", cards);
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::cards::{Card, PlayedCard, Rank, Suit};
+use crate::game::{Hand, TrickType};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Locale {
+    #[default]
+    English,
+    Filipino,
+}
+
+pub fn rank_name(rank: Rank, locale: Locale) -> &'static str {
+    match locale {
+        Locale::English => match rank {
+            Rank::Three => "Three",
+            Rank::Four => "Four",
+            Rank::Five => "Five",
+            Rank::Six => "Six",
+            Rank::Seven => "Seven",
+            Rank::Eight => "Eight",
+            Rank::Nine => "Nine",
+            Rank::Ten => "Ten",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Queen",
+            Rank::King => "King",
+            Rank::Ace => "Ace",
+            Rank::Two => "Two",
+        },
+        Locale::Filipino => match rank {
+            Rank::Three => "Tres",
+            Rank::Four => "Kuwatro",
+            Rank::Five => "Singko",
+            Rank::Six => "Sais",
+            Rank::Seven => "Siyete",
+            Rank::Eight => "Otso",
+            Rank::Nine => "Nuwebe",
+            Rank::Ten => "Diyes",
+            Rank::Jack => "Jack",
+            Rank::Queen => "Reyna",
+            Rank::King => "Hari",
+            Rank::Ace => "Alas",
+            Rank::Two => "Dos",
+        },
+    }
+}
+
+fn plural_rank_name(rank: Rank, locale: Locale) -> String {
+    match (locale, rank) {
+        (Locale::English, Rank::Six) => "Sixes".to_string(),
+        (Locale::English, _) => format!("{}s", rank_name(rank, locale)),
+        (Locale::Filipino, _) => rank_name(rank, locale).to_string(),
+    }
+}
+
+pub fn suit_name(suit: Suit, _locale: Locale) -> &'static str {
+    match suit {
+        Suit::Clubs => "Clubs",
+        Suit::Hearts => "Hearts",
+        Suit::Diamonds => "Diamonds",
+        Suit::Spades => "Spades",
+        Suit::Eagles => "Eagles",
+    }
+}
+
+fn joker_name(_locale: Locale) -> &'static str {
+    "Joker"
+}
+
+fn of(locale: Locale) -> &'static str {
+    match locale {
+        Locale::English => "of",
+        Locale::Filipino => "ng",
+    }
+}
+
+pub fn card_name(card: Card, locale: Locale) -> String {
+    match card {
+        Card::Joker { .. } => joker_name(locale).to_string(),
+        Card::Standard { rank, suit, .. } => format!(
+            "{} {} {}",
+            rank_name(rank, locale),
+            of(locale),
+            suit_name(suit, locale)
+        ),
+    }
+}
+
+pub fn played_card_name(card: PlayedCard, locale: Locale) -> String {
+    let name = format!(
+        "{} {} {}",
+        rank_name(card.get_rank(), locale),
+        of(locale),
+        suit_name(card.get_suit(), locale)
+    );
+
+    if card.get_is_joker() {
+        format!("{} ({})", joker_name(locale), name)
+    } else {
+        name
+    }
+}
+
+pub fn trick_type_name(trick_type: TrickType, locale: Locale) -> &'static str {
+    match (locale, trick_type) {
+        (_, TrickType::Straight) => "Straight",
+        (_, TrickType::Flush) => "Flush",
+        (_, TrickType::FullHouse) => "Full House",
+        (Locale::English, TrickType::FourOfAKind) => "Four of a Kind",
+        (Locale::Filipino, TrickType::FourOfAKind) => "Kuwatro ng Parehas",
+        (_, TrickType::StraightFlush) => "Straight Flush",
+        (Locale::English, TrickType::FiveOfAKind) => "Five of a Kind",
+        (Locale::Filipino, TrickType::FiveOfAKind) => "Singko ng Parehas",
+        (_, TrickType::RoyalFlush) => "Royal Flush",
+    }
+}
+
+pub fn hand_name(hand: Hand, locale: Locale) -> String {
+    match hand {
+        Hand::Pass => match locale {
+            Locale::English => "Pass".to_string(),
+            Locale::Filipino => "Pas".to_string(),
+        },
+        Hand::Single(card) => played_card_name(card, locale),
+        Hand::Pair(card, _) => match locale {
+            Locale::English => format!(
+                "Pair of {}", plural_rank_name(card.get_rank(), locale)
+            ),
+            Locale::Filipino => format!(
+                "Pares ng {}", plural_rank_name(card.get_rank(), locale)
+            ),
+        },
+        Hand::Prial(card, _, _) => match locale {
+            Locale::English => format!(
+                "Prial of {}", plural_rank_name(card.get_rank(), locale)
+            ),
+            Locale::Filipino => format!(
+                "Trio ng {}", plural_rank_name(card.get_rank(), locale)
+            ),
+        },
+        Hand::FiveCardTrick(trick) => {
+            trick_type_name(trick.trick_type, locale).to_string()
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cards_can_be_named_in_english_and_filipino() {
+        let card = Card::Standard {
+            deck_id: 0,
+            rank: Rank::Three,
+            suit: Suit::Clubs,
+        };
+
+        assert_eq!(card_name(card, Locale::English), "Three of Clubs");
+        assert_eq!(card_name(card, Locale::Filipino), "Tres ng Clubs");
+        assert_eq!(
+            card_name(Card::Joker { deck_id: 0 }, Locale::English),
+            "Joker"
+        );
+    }
+
+    #[test]
+    fn hands_are_named_by_type() {
+        let six = PlayedCard::new(Rank::Six, Suit::Clubs, false);
+
+        assert_eq!(
+            hand_name(Hand::Pair(six, six), Locale::English),
+            "Pair of Sixes"
+        );
+        assert_eq!(
+            hand_name(Hand::Prial(six, six, six), Locale::Filipino),
+            "Trio ng Sais"
+        );
+        assert_eq!(hand_name(Hand::Pass, Locale::English), "Pass");
+    }
+
+    #[test]
+    fn played_jokers_show_what_they_stand_for() {
+        let joker = PlayedCard::new(Rank::Two, Suit::Spades, true);
+
+        assert_eq!(
+            hand_name(Hand::Single(joker), Locale::English),
+            "Joker (Two of Spades)"
+        );
+    }
+
+    #[test]
+    fn hands_display_with_english_names() {
+        let ace = PlayedCard::new(Rank::Ace, Suit::Hearts, false);
+
+        assert_eq!(Hand::Single(ace).to_string(), "Ace of Hearts");
+        assert_eq!(TrickType::FullHouse.to_string(), "Full House");
+    }
+}
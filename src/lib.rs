@@ -1,3 +1,35 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod prelude;
+
 pub mod cards;
 pub mod game;
 pub mod ai;
+pub mod i18n;
+pub mod protocol;
+pub mod render;
+pub mod scenario;
+pub mod tutorial;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "server")]
+pub mod server;
+
+#[cfg(feature = "mental_poker")]
+pub mod mental_poker;
+
+#[cfg(feature = "ts")]
+pub mod typescript;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "storage")]
+pub mod storage;
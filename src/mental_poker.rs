@@ -0,0 +1,545 @@
+//! Dealing without a trusted dealer, using SRA commutative encryption.
+//!
+//! Every player encrypts and shuffles the whole deck in turn, then in a
+//! second pass swaps their shuffle key for a separate key per card. A card
+//! can only be read once every player has handed over their key for it, so
+//! each player collects the keys for their own cards from everyone else and
+//! nobody else learns them. At the end of the game every player reveals
+//! their remaining keys and the complete deal is rebuilt as a `Round`.
+//!
+//! Cards are encoded as quadratic residues. Encryption keeps a value's
+//! Legendre symbol, so encoding some cards as non-residues would split the
+//! encrypted deck into two halves anyone could tell apart.
+//!
+//! The 64-bit prime keeps the arithmetic in `u128`; it demonstrates the
+//! protocol rather than resisting a well-resourced attacker.
+
+use crate::cards::{Card, Deck};
+use crate::game::{sort_hand, OrderContext, Player, Round, RoundBuilder, Ruleset};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// The largest prime below 2^64.
+const PRIME: u64 = 18_446_744_073_709_551_557;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MentalPokerError {
+    UnknownPlayer,
+    NotYourTurn,
+    WrongPhase,
+    WrongDeckSize,
+    /// Players never hand out the keys to their own cards before the
+    /// showdown
+    OwnCard(usize),
+    MissingKey(usize),
+    InvalidCard(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Phase {
+    /// Waiting for the player in this seat to encrypt and shuffle
+    Shuffle(usize),
+    /// Waiting for the player in this seat to apply their per-card keys
+    Lock(usize),
+    /// Players are exchanging the keys for each other's cards
+    Deal,
+    /// Every key has been revealed
+    Showdown,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SraKey {
+    encrypt: u64,
+    decrypt: u64,
+}
+
+impl SraKey {
+    pub fn generate<R: Rng>(rng: &mut R) -> SraKey {
+        loop {
+            let encrypt = rng.gen_range(3, PRIME - 1) | 1;
+            if let Some(decrypt) = mod_inverse(encrypt, PRIME - 1) {
+                return SraKey { encrypt, decrypt };
+            }
+        }
+    }
+
+    pub fn encrypt(&self, value: u64) -> u64 {
+        mod_pow(value, self.encrypt)
+    }
+
+    pub fn decrypt(&self, value: u64) -> u64 {
+        mod_pow(value, self.decrypt)
+    }
+
+    /// The half of the key that is safe to hand to another player.
+    pub fn get_decrypt_exponent(&self) -> u64 {
+        self.decrypt
+    }
+}
+
+/// The keys a player keeps to themselves until the showdown.
+#[derive(Debug, Clone)]
+pub struct PlayerSecrets {
+    shuffle_key: SraKey,
+    card_keys: Vec<SraKey>,
+}
+
+impl PlayerSecrets {
+    pub fn generate<R: Rng>(deck_size: usize, rng: &mut R) -> PlayerSecrets {
+        PlayerSecrets {
+            shuffle_key: SraKey::generate(rng),
+            card_keys: (0..deck_size).map(|_| SraKey::generate(rng)).collect(),
+        }
+    }
+
+    /// Encrypts every card with the shuffle key and shuffles the result.
+    pub fn shuffle<R: Rng>(&self, deck: &[u64], rng: &mut R) -> Vec<u64> {
+        let mut deck: Vec<u64> = deck.iter()
+            .map(|&c| self.shuffle_key.encrypt(c))
+            .collect();
+        deck.shuffle(rng);
+        deck
+    }
+
+    /// Replaces the shuffle key with a different key for each card.
+    pub fn lock(&self, deck: &[u64]) -> Vec<u64> {
+        deck.iter()
+            .zip(self.card_keys.iter())
+            .map(|(&c, key)| key.encrypt(self.shuffle_key.decrypt(c)))
+            .collect()
+    }
+
+    /// Decryption exponents for the given card positions.
+    pub fn reveal(&self, indexes: &[usize]) -> Vec<(usize, u64)> {
+        indexes.iter()
+            .filter_map(|&i| {
+                self.card_keys.get(i).map(|k| (i, k.get_decrypt_exponent()))
+            })
+            .collect()
+    }
+}
+
+/// The shared state of an encrypted deal. Every player keeps a copy and
+/// applies the same messages in the same order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MentalDeal {
+    player_ids: Vec<String>,
+    plain_deck: Vec<Card>,
+    deck: Vec<u64>,
+    phase: Phase,
+    /// Revealed decryption exponents, per card then per seat
+    keys: Vec<Vec<Option<u64>>>,
+}
+
+impl MentalDeal {
+    pub fn new(player_ids: &[String], num_decks: u8, num_jokers: u8) -> MentalDeal {
+        let plain_deck = Deck::new(num_decks, num_jokers).to_vec();
+        let deck = (0..plain_deck.len()).map(encode).collect();
+
+        MentalDeal {
+            player_ids: player_ids.to_vec(),
+            keys: vec![vec![None; player_ids.len()]; plain_deck.len()],
+            plain_deck,
+            deck,
+            phase: Phase::Shuffle(0),
+        }
+    }
+
+    pub fn get_phase(&self) -> Phase {
+        self.phase
+    }
+
+    /// The deck as it currently stands, for the next player to work on.
+    pub fn get_deck(&self) -> Vec<u64> {
+        self.deck.clone()
+    }
+
+    pub fn get_deck_size(&self) -> usize {
+        self.plain_deck.len()
+    }
+
+    /// Cards go round the table one at a time, so position `i` belongs to
+    /// seat `i % players`.
+    pub fn get_owner(&self, index: usize) -> &str {
+        &self.player_ids[index % self.player_ids.len()]
+    }
+
+    /// Positions of every card dealt to `player_id`.
+    pub fn get_cards_for(&self, player_id: &str) -> Vec<usize> {
+        (0..self.deck.len())
+            .filter(|&i| self.get_owner(i) == player_id)
+            .collect()
+    }
+
+    /// Positions `player_id` must reveal keys for during the deal.
+    pub fn get_cards_to_reveal(&self, player_id: &str) -> Vec<usize> {
+        (0..self.deck.len())
+            .filter(|&i| self.get_owner(i) != player_id)
+            .collect()
+    }
+
+    pub fn submit_shuffle(
+        &mut self,
+        player_id: &str,
+        deck: Vec<u64>,
+    ) -> Result<(), MentalPokerError> {
+        let seat = self.get_seat(player_id)?;
+        if self.phase != Phase::Shuffle(seat) {
+            return Err(self.turn_error(seat));
+        }
+        self.replace_deck(deck)?;
+
+        self.phase = if seat + 1 < self.player_ids.len() {
+            Phase::Shuffle(seat + 1)
+        } else {
+            Phase::Lock(0)
+        };
+
+        Ok(())
+    }
+
+    pub fn submit_lock(
+        &mut self,
+        player_id: &str,
+        deck: Vec<u64>,
+    ) -> Result<(), MentalPokerError> {
+        let seat = self.get_seat(player_id)?;
+        if self.phase != Phase::Lock(seat) {
+            return Err(self.turn_error(seat));
+        }
+        self.replace_deck(deck)?;
+
+        self.phase = if seat + 1 < self.player_ids.len() {
+            Phase::Lock(seat + 1)
+        } else {
+            Phase::Deal
+        };
+
+        Ok(())
+    }
+
+    /// Records keys `player_id` has revealed. During the deal these must
+    /// be for other players' cards; at the showdown anything goes.
+    pub fn submit_keys(
+        &mut self,
+        player_id: &str,
+        keys: Vec<(usize, u64)>,
+    ) -> Result<(), MentalPokerError> {
+        let seat = self.get_seat(player_id)?;
+        let showdown = match self.phase {
+            Phase::Deal => false,
+            Phase::Showdown => true,
+            _ => return Err(MentalPokerError::WrongPhase),
+        };
+
+        for &(index, _) in keys.iter() {
+            if index >= self.deck.len() {
+                return Err(MentalPokerError::InvalidCard(index));
+            }
+            if !showdown && self.get_owner(index) == player_id {
+                return Err(MentalPokerError::OwnCard(index));
+            }
+        }
+
+        for (index, key) in keys {
+            self.keys[index][seat] = Some(key);
+        }
+
+        Ok(())
+    }
+
+    /// Once every player has revealed the keys for everyone else's cards
+    /// the deal is done and play can start.
+    pub fn is_dealt(&self) -> bool {
+        self.phase == Phase::Deal
+            && (0..self.deck.len()).all(|i| {
+                let owner = i % self.player_ids.len();
+                self.keys[i].iter()
+                    .enumerate()
+                    .all(|(seat, key)| seat == owner || key.is_some())
+            })
+    }
+
+    /// Moves to the showdown, after which players reveal their own keys.
+    pub fn start_showdown(&mut self) -> Result<(), MentalPokerError> {
+        if !self.is_dealt() {
+            return Err(MentalPokerError::WrongPhase);
+        }
+        self.phase = Phase::Showdown;
+        Ok(())
+    }
+
+    /// Reads `player_id`'s own hand using their secret keys and the keys
+    /// the others have revealed.
+    pub fn decrypt_hand(
+        &self,
+        player_id: &str,
+        secrets: &PlayerSecrets,
+    ) -> Result<Vec<Card>, MentalPokerError> {
+        let seat = self.get_seat(player_id)?;
+
+        self.get_cards_for(player_id).into_iter()
+            .map(|index| {
+                let own_key = secrets.card_keys.get(index)
+                    .ok_or(MentalPokerError::InvalidCard(index))?
+                    .get_decrypt_exponent();
+                self.decrypt_card(index, |s| {
+                    if s == seat { Some(own_key) } else { None }
+                })
+            })
+            .collect()
+    }
+
+    /// Reads a card that every player has revealed the key for, e.g. one
+    /// that has been played.
+    pub fn open_card(&self, index: usize) -> Result<Card, MentalPokerError> {
+        self.decrypt_card(index, |_| None)
+    }
+
+    /// Rebuilds the full deal once every key is known, so the game can be
+    /// checked or replayed through the normal engine.
+    pub fn into_round(
+        &self,
+        orders: OrderContext,
+        ruleset: Ruleset,
+    ) -> Result<Round, MentalPokerError> {
+        if self.phase != Phase::Showdown {
+            return Err(MentalPokerError::WrongPhase);
+        }
+
+        let mut players = vec![];
+        for id in self.player_ids.iter() {
+            let cards = self.get_cards_for(id).into_iter()
+                .map(|index| self.open_card(index))
+                .collect::<Result<Vec<Card>, MentalPokerError>>()?;
+            players.push(Player::new(id.to_string(), sort_hand(&cards, &orders)));
+        }
+
+        Ok(RoundBuilder::new()
+            .players(players)
+            .orders(orders)
+            .ruleset(ruleset)
+            .build()
+            .expect("a deal always has players"))
+    }
+
+    fn decrypt_card<F>(&self, index: usize, extra_key: F) -> Result<Card, MentalPokerError>
+    where
+        F: Fn(usize) -> Option<u64>,
+    {
+        let mut value = *self.deck.get(index)
+            .ok_or(MentalPokerError::InvalidCard(index))?;
+
+        for (seat, key) in self.keys[index].iter().enumerate() {
+            let exponent = key.or_else(|| extra_key(seat))
+                .ok_or(MentalPokerError::MissingKey(index))?;
+            value = mod_pow(value, exponent);
+        }
+
+        decode(value, self.plain_deck.len())
+            .and_then(|i| self.plain_deck.get(i).cloned())
+            .ok_or(MentalPokerError::InvalidCard(index))
+    }
+
+    fn get_seat(&self, player_id: &str) -> Result<usize, MentalPokerError> {
+        self.player_ids.iter()
+            .position(|id| id == player_id)
+            .ok_or(MentalPokerError::UnknownPlayer)
+    }
+
+    fn turn_error(&self, seat: usize) -> MentalPokerError {
+        match self.phase {
+            Phase::Shuffle(s) | Phase::Lock(s) if s != seat => {
+                MentalPokerError::NotYourTurn
+            },
+            _ => MentalPokerError::WrongPhase,
+        }
+    }
+
+    fn replace_deck(&mut self, deck: Vec<u64>) -> Result<(), MentalPokerError> {
+        if deck.len() != self.deck.len() {
+            return Err(MentalPokerError::WrongDeckSize);
+        }
+        self.deck = deck;
+        Ok(())
+    }
+}
+
+/// Cards are numbered from 2 so that neither 0 nor 1, which encrypt to
+/// themselves, stand for a card, then squared so every card is a quadratic
+/// residue.
+fn encode(index: usize) -> u64 {
+    let root = index as u64 + 2;
+    mod_pow(root, 2)
+}
+
+/// Squaring can't be undone cheaply, so decoding looks the value up among
+/// the encodings of every card in the deck.
+fn decode(value: u64, deck_size: usize) -> Option<usize> {
+    (0..deck_size).find(|&i| encode(i) == value)
+}
+
+fn mod_pow(base: u64, exponent: u64) -> u64 {
+    let modulus = PRIME as u128;
+    let mut result: u128 = 1;
+    let mut base = base as u128 % modulus;
+    let mut exponent = exponent;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+
+    result as u64
+}
+
+fn mod_inverse(value: u64, modulus: u64) -> Option<u64> {
+    let (mut old_r, mut r) = (value as i128, modulus as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        let next_r = old_r - quotient * r;
+        old_r = r;
+        r = next_r;
+        let next_s = old_s - quotient * s;
+        old_s = s;
+        s = next_s;
+    }
+
+    if old_r != 1 {
+        return None;
+    }
+
+    Some(old_s.rem_euclid(modulus as i128) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn ids() -> Vec<String> {
+        vec!["a".to_string(), "b".to_string(), "c".to_string()]
+    }
+
+    fn legendre_symbol(value: u64) -> u64 {
+        mod_pow(value, (PRIME - 1) / 2)
+    }
+
+    fn run_deal(rng: &mut StdRng) -> (MentalDeal, Vec<PlayerSecrets>) {
+        let mut deal = MentalDeal::new(&ids(), 1, 0);
+        let secrets: Vec<PlayerSecrets> = ids().iter()
+            .map(|_| PlayerSecrets::generate(deal.get_deck_size(), rng))
+            .collect();
+
+        for (id, secret) in ids().iter().zip(secrets.iter()) {
+            let deck = secret.shuffle(&deal.get_deck(), rng);
+            deal.submit_shuffle(id, deck).unwrap();
+        }
+        for (id, secret) in ids().iter().zip(secrets.iter()) {
+            let deck = secret.lock(&deal.get_deck());
+            deal.submit_lock(id, deck).unwrap();
+        }
+        for (id, secret) in ids().iter().zip(secrets.iter()) {
+            let keys = secret.reveal(&deal.get_cards_to_reveal(id));
+            deal.submit_keys(id, keys).unwrap();
+        }
+
+        (deal, secrets)
+    }
+
+    #[test]
+    fn keys_are_commutative() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let a = SraKey::generate(&mut rng);
+        let b = SraKey::generate(&mut rng);
+        let card = encode(12);
+
+        let locked = b.encrypt(a.encrypt(card));
+
+        assert_eq!(a.decrypt(b.decrypt(locked)), card);
+        assert_eq!(b.decrypt(a.decrypt(locked)), card);
+    }
+
+    #[test]
+    fn every_card_is_a_quadratic_residue() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let key = SraKey::generate(&mut rng);
+        let deal = MentalDeal::new(&ids(), 1, 1);
+
+        assert!(deal.get_deck().iter().all(|&c| legendre_symbol(c) == 1));
+        assert!(deal.get_deck().iter()
+            .all(|&c| legendre_symbol(key.encrypt(c)) == 1));
+    }
+
+    #[test]
+    fn each_player_can_read_only_their_own_hand() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let (deal, secrets) = run_deal(&mut rng);
+
+        assert!(deal.is_dealt());
+
+        let hands: Vec<Vec<Card>> = ids().iter()
+            .zip(secrets.iter())
+            .map(|(id, secret)| deal.decrypt_hand(id, secret).unwrap())
+            .collect();
+        let mut all_cards: Vec<Card> = hands.concat();
+        all_cards.sort();
+        let mut deck = Deck::new(1, 0).to_vec();
+        deck.sort();
+
+        assert_eq!(all_cards, deck);
+        assert_eq!(deal.open_card(0), Err(MentalPokerError::MissingKey(0)));
+        assert!(deal.decrypt_hand("b", &secrets[0]).is_err());
+    }
+
+    #[test]
+    fn players_must_act_in_turn() {
+        let mut deal = MentalDeal::new(&ids(), 1, 0);
+        let deck = deal.get_deck();
+
+        assert_eq!(
+            deal.submit_shuffle("b", deck.clone()),
+            Err(MentalPokerError::NotYourTurn)
+        );
+        assert_eq!(
+            deal.submit_lock("a", deck.clone()),
+            Err(MentalPokerError::WrongPhase)
+        );
+        assert_eq!(
+            deal.submit_shuffle("a", vec![]),
+            Err(MentalPokerError::WrongDeckSize)
+        );
+    }
+
+    #[test]
+    fn own_keys_stay_hidden_until_the_showdown() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let (mut deal, secrets) = run_deal(&mut rng);
+
+        assert_eq!(
+            deal.submit_keys("a", secrets[0].reveal(&[0])),
+            Err(MentalPokerError::OwnCard(0))
+        );
+
+        deal.start_showdown().unwrap();
+        for (id, secret) in ids().iter().zip(secrets.iter()) {
+            let keys = secret.reveal(&deal.get_cards_for(id));
+            deal.submit_keys(id, keys).unwrap();
+        }
+
+        let round = deal.into_round(OrderContext::standard(), Ruleset::default())
+            .unwrap();
+        let a_hand = deal.decrypt_hand("a", &secrets[0]).unwrap();
+
+        assert_eq!(round.get_player("a").unwrap().get_card_count(), 18);
+        assert!(a_hand.iter().all(|c| round.get_player("a").unwrap().has_card(*c)));
+        assert_eq!(round.verify_card_conservation(), Ok(()));
+    }
+}
@@ -0,0 +1,8 @@
+//! The parts of the standard prelude that live in `alloc`, so modules read
+//! the same whether or not the `std` feature is on.
+
+pub(crate) use alloc::borrow::ToOwned;
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec::Vec;
+pub(crate) use alloc::{format, vec};
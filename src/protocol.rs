@@ -0,0 +1,283 @@
+use crate::cards::{CardMask, PlayedCard};
+use crate::game::{InvariantViolation, Round, RoundDelta, SubmitError};
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Messages sent from a client to the game server.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[serde(tag = "type", content = "payload")]
+#[serde(rename_all = "snake_case")]
+pub enum ClientMsg {
+    Join { player_id: String },
+    Play { cards: Vec<PlayedCard> },
+    Pass,
+    RequestState,
+    /// Sent when the client's `state_hash` no longer matches the one the
+    /// server last sent. The server answers with a full state update.
+    RequestResync { state_hash: String },
+    Leave,
+}
+
+/// Messages sent from the game server to its clients.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+#[serde(tag = "type", content = "payload")]
+#[serde(rename_all = "snake_case")]
+pub enum ServerMsg {
+    Joined { player_id: String },
+    StateUpdate(Box<Round>),
+    /// The changes made by a move, far smaller than the whole round. The
+    /// hash is of the round after the change, as hex so it survives being
+    /// parsed by javascript.
+    Delta { delta: RoundDelta, state_hash: String },
+    Error(SubmitError),
+    GameOver { winners: Vec<String> },
+    /// Every player's hand as a card mask, an integer in place of an
+    /// array of cards
+    Hands { hands: Vec<CompactHand> },
+}
+
+/// A player's hand in the compact wire format.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub struct CompactHand {
+    pub player_id: String,
+    #[cfg_attr(feature = "ts", ts(type = "string"))]
+    pub hand: CardMask,
+}
+
+impl ClientMsg {
+    /// The cards submitted by this message, if it represents a move.
+    pub fn to_move(&self) -> Option<Vec<PlayedCard>> {
+        match self {
+            ClientMsg::Play { cards } => Some(cards.clone()),
+            ClientMsg::Pass => Some(vec![]),
+            _ => None,
+        }
+    }
+}
+
+impl ServerMsg {
+    /// A delta update taking clients from `before` to `after`.
+    pub fn delta(before: &Round, after: &Round) -> ServerMsg {
+        ServerMsg::Delta {
+            delta: before.diff(after),
+            state_hash: format_state_hash(after),
+        }
+    }
+
+    /// The hands in `round` packed for a game of `num_decks` decks, or
+    /// `None` if they don't fit a card mask.
+    pub fn hands(round: &Round, num_decks: u8) -> Option<ServerMsg> {
+        let hands = round.get_players().iter()
+            .map(|player| Some(CompactHand {
+                player_id: player.get_id().to_string(),
+                hand: player.hand_as_bitmask(num_decks)?,
+            }))
+            .collect::<Option<Vec<CompactHand>>>()?;
+
+        Some(ServerMsg::Hands { hands })
+    }
+}
+
+/// Longest move message, in bytes, `apply_untrusted_move` will parse.
+/// A play of five cards fits comfortably.
+pub const MAX_UNTRUSTED_MOVE_LENGTH: usize = 1024;
+/// Most cards any hand can hold
+const MAX_HAND_SIZE: usize = 5;
+
+/// Why `apply_untrusted_move` turned a message away.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "ts", derive(ts_rs::TS))]
+pub enum RejectReason {
+    /// The message is longer than `MAX_UNTRUSTED_MOVE_LENGTH`
+    TooLong(usize),
+    /// The message isn't a client message, with serde's reason why
+    Malformed(String),
+    /// The message parsed, but isn't a play or a pass
+    NotAMove,
+    /// More cards than any hand holds
+    TooManyCards(usize),
+    /// The round itself is broken, so no move is applied to it
+    InconsistentRound(Vec<InvariantViolation>),
+    Rejected(SubmitError),
+}
+
+impl Round {
+    /// Parses `json` as a `ClientMsg` and plays it for `player_id`,
+    /// returning what changed. Made for feeding network input straight
+    /// in: oversized or malformed messages, impossible cards and rounds
+    /// left inconsistent by a bad load are all turned away rather than
+    /// panicking, and the round is only changed when the move is legal.
+    pub fn apply_untrusted_move(
+        &mut self,
+        player_id: &str,
+        json: &str,
+    ) -> Result<RoundDelta, RejectReason> {
+        if json.len() > MAX_UNTRUSTED_MOVE_LENGTH {
+            return Err(RejectReason::TooLong(json.len()));
+        }
+
+        let msg: ClientMsg = serde_json::from_str(json)
+            .map_err(|err| RejectReason::Malformed(err.to_string()))?;
+        let cards = msg.to_move().ok_or(RejectReason::NotAMove)?;
+        if cards.len() > MAX_HAND_SIZE {
+            return Err(RejectReason::TooManyCards(cards.len()));
+        }
+
+        self.check_invariants().map_err(RejectReason::InconsistentRound)?;
+
+        let before = self.clone();
+        self.submit_move_mut(player_id, cards).map_err(RejectReason::Rejected)?;
+
+        Ok(before.diff(self))
+    }
+}
+
+/// The round's `state_hash` as it's sent in protocol messages.
+pub fn format_state_hash(round: &Round) -> String {
+    format!("{:016x}", round.state_hash())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{Player, RoundBuilder};
+
+    #[test]
+    fn client_messages_round_trip_through_json() {
+        let msg = ClientMsg::Play {
+            cards: vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)],
+        };
+
+        let json = serde_json::to_string(&msg).unwrap();
+        let parsed: ClientMsg = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed, msg);
+        assert!(json.starts_with(r#"{"type":"play","payload":"#));
+    }
+
+    #[test]
+    fn pass_is_an_empty_move() {
+        let msg: ClientMsg = serde_json::from_str(r#"{"type":"pass"}"#)
+            .unwrap();
+
+        assert_eq!(msg.to_move(), Some(vec![]));
+        assert_eq!(ClientMsg::RequestState.to_move(), None);
+    }
+
+    #[test]
+    fn errors_are_tagged_with_the_submit_error() {
+        let msg = ServerMsg::Error(SubmitError::HandNotHighEnough);
+
+        assert_eq!(
+            serde_json::to_string(&msg).unwrap(),
+            r#"{"type":"error","payload":"HandNotHighEnough"}"#
+        );
+    }
+
+    #[test]
+    fn deltas_carry_the_hash_of_the_new_state() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+                Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Clubs },
+            ]))
+            .player(Player::new("b".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Five, suit: Suit::Clubs },
+            ]))
+            .build()
+            .unwrap();
+        let three = PlayedCard::new(Rank::Three, Suit::Clubs, false);
+        let next_round = round.submit_move("a", vec![three]).unwrap();
+
+        let msg = ServerMsg::delta(&round, &next_round);
+        let json = serde_json::to_string(&msg).unwrap();
+
+        match msg {
+            ServerMsg::Delta { delta, state_hash } => {
+                assert_eq!(delta, round.diff(&next_round));
+                assert_eq!(state_hash.len(), 16);
+                assert_eq!(state_hash, format_state_hash(&next_round));
+                assert_ne!(state_hash, format_state_hash(&round));
+            },
+            _ => panic!("expected a delta"),
+        }
+        assert!(json.len() < serde_json::to_string(&next_round).unwrap().len());
+    }
+
+    #[test]
+    fn resync_requests_are_tagged_with_the_client_hash() {
+        let msg: ClientMsg = serde_json::from_str(
+            r#"{"type":"request_resync","payload":{"state_hash":"00ff"}}"#
+        ).unwrap();
+
+        assert_eq!(msg, ClientMsg::RequestResync { state_hash: "00ff".to_string() });
+        assert_eq!(msg.to_move(), None);
+    }
+
+    #[test]
+    fn untrusted_moves_are_rejected_without_panicking() {
+        let mut round = crate::scenario::Scenario::new()
+            .player("a", "3C 4C")
+            .player("b", "5C")
+            .build()
+            .unwrap();
+        let untouched = round.clone();
+        let play = |cards: &str| format!(r#"{{"type":"play","payload":{{"cards":{}}}}}"#, cards);
+        let three = r#"{"rank":"three","suit":"clubs","is_joker":false}"#;
+
+        assert!(matches!(
+            round.apply_untrusted_move("a", &play(r#"[{"rank":"three","suit":"cups","is_joker":false}]"#)),
+            Err(RejectReason::Malformed(_))
+        ));
+        assert!(matches!(round.apply_untrusted_move("a", "{\"type\":"), Err(RejectReason::Malformed(_))));
+        assert_eq!(round.apply_untrusted_move("a", r#"{"type":"leave"}"#), Err(RejectReason::NotAMove));
+        assert_eq!(
+            round.apply_untrusted_move("a", &play(&format!("[{}]", [three; 6].join(",")))),
+            Err(RejectReason::TooManyCards(6))
+        );
+        assert_eq!(
+            round.apply_untrusted_move("a", &" ".repeat(MAX_UNTRUSTED_MOVE_LENGTH + 1)),
+            Err(RejectReason::TooLong(MAX_UNTRUSTED_MOVE_LENGTH + 1))
+        );
+        assert_eq!(
+            round.apply_untrusted_move("b", &play(&format!("[{}]", three))),
+            Err(RejectReason::Rejected(SubmitError::NotCurrentPlayer))
+        );
+        assert_eq!(round.state_hash(), untouched.state_hash());
+
+        let delta = round.apply_untrusted_move("a", &play(&format!("[{}]", three))).unwrap();
+        assert_eq!(delta, untouched.diff(&round));
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+
+        let mut broken = untouched.clone();
+        broken.set_temporarily_reversed(true);
+        assert_eq!(
+            broken.apply_untrusted_move("a", &play(&format!("[{}]", three))),
+            Err(RejectReason::InconsistentRound(vec![InvariantViolation::UnexpectedTemporaryReversal]))
+        );
+    }
+
+    #[test]
+    fn hands_are_sent_as_card_masks() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+            ]))
+            .player(Player::new("b".to_string(), vec![]))
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&ServerMsg::hands(&round, 1).unwrap()).unwrap();
+
+        assert_eq!(json, concat!(
+            r#"{"type":"hands","payload":{"hands":["#,
+            r#"{"player_id":"a","hand":"0000000000000001"},"#,
+            r#"{"player_id":"b","hand":"0000000000000000"}]}}"#,
+        ));
+        assert!(ServerMsg::hands(&round, 3).is_none());
+    }
+}
@@ -0,0 +1,5 @@
+mod symbols;
+mod table;
+
+pub use self::symbols::*;
+pub use self::table::*;
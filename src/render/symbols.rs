@@ -0,0 +1,158 @@
+use crate::cards::{Card, PlayedCard, Rank, Suit};
+use crate::game::Hand;
+use crate::prelude::*;
+
+const JOKER_GLYPH: char = '\u{1F0CF}';
+const CARD_BACK_GLYPH: char = '\u{1F0A0}';
+
+pub fn rank_symbol(rank: Rank) -> &'static str {
+    rank.notation()
+}
+
+pub fn suit_symbol(suit: Suit) -> char {
+    match suit {
+        Suit::Clubs => '♣',
+        Suit::Hearts => '♥',
+        Suit::Diamonds => '♦',
+        Suit::Spades => '♠',
+        Suit::Eagles => '🦅',
+    }
+}
+
+/// Compact form such as `3♣` or `J♥`; jokers render as `🃏`.
+pub fn card_symbol(card: Card) -> String {
+    match card {
+        Card::Standard { rank, suit, .. } => {
+            format!("{}{}", rank_symbol(rank), suit_symbol(suit))
+        },
+        Card::Joker { .. } => JOKER_GLYPH.to_string(),
+    }
+}
+
+/// Compact form of a played card; jokers are suffixed with `*`.
+pub fn played_card_symbol(card: PlayedCard) -> String {
+    let symbol = format!(
+        "{}{}",
+        rank_symbol(card.get_rank()),
+        suit_symbol(card.get_suit())
+    );
+
+    if card.get_is_joker() {
+        format!("{}*", symbol)
+    } else {
+        symbol
+    }
+}
+
+/// The single Unicode playing-card character for a card, e.g. `🃓`.
+/// Unicode has no eagles, so they show as the back of a card.
+pub fn card_glyph(card: Card) -> char {
+    match card {
+        Card::Standard { rank, suit, .. } => glyph(rank, suit),
+        Card::Joker { .. } => JOKER_GLYPH,
+    }
+}
+
+pub fn played_card_glyph(card: PlayedCard) -> char {
+    if card.get_is_joker() {
+        JOKER_GLYPH
+    } else {
+        glyph(card.get_rank(), card.get_suit())
+    }
+}
+
+pub fn cards_symbols(cards: &[Card]) -> String {
+    cards.iter()
+        .map(|&c| card_symbol(c))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+pub fn played_cards_symbols(cards: &[PlayedCard]) -> String {
+    cards.iter()
+        .map(|&c| played_card_symbol(c))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Compact form of a hand, e.g. `3♣ 3♥`, or `pass`.
+pub fn hand_symbols(hand: Hand) -> String {
+    match hand {
+        Hand::Pass => "pass".to_string(),
+        _ => played_cards_symbols(&hand.to_cards()),
+    }
+}
+
+fn glyph(rank: Rank, suit: Suit) -> char {
+    let suit_base: u32 = match suit {
+        Suit::Spades => 0x1F0A0,
+        Suit::Hearts => 0x1F0B0,
+        Suit::Diamonds => 0x1F0C0,
+        Suit::Clubs => 0x1F0D0,
+        Suit::Eagles => return CARD_BACK_GLYPH,
+    };
+    // the block includes a knight between the jack and queen
+    let rank_offset: u32 = match rank {
+        Rank::Ace => 1,
+        Rank::Two => 2,
+        Rank::Three => 3,
+        Rank::Four => 4,
+        Rank::Five => 5,
+        Rank::Six => 6,
+        Rank::Seven => 7,
+        Rank::Eight => 8,
+        Rank::Nine => 9,
+        Rank::Ten => 10,
+        Rank::Jack => 11,
+        Rank::Queen => 13,
+        Rank::King => 14,
+    };
+
+    core::char::from_u32(suit_base + rank_offset).unwrap_or(JOKER_GLYPH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cards_render_as_rank_and_suit_symbols() {
+        let card = Card::Standard {
+            deck_id: 0,
+            rank: Rank::Jack,
+            suit: Suit::Hearts,
+        };
+
+        assert_eq!(card_symbol(card), "J♥");
+        assert_eq!(card_symbol(Card::Joker { deck_id: 0 }), "🃏");
+    }
+
+    #[test]
+    fn cards_render_as_playing_card_glyphs() {
+        let three_clubs = Card::Standard {
+            deck_id: 0,
+            rank: Rank::Three,
+            suit: Suit::Clubs,
+        };
+        let queen_spades = Card::Standard {
+            deck_id: 0,
+            rank: Rank::Queen,
+            suit: Suit::Spades,
+        };
+
+        assert_eq!(card_glyph(three_clubs), '🃓');
+        assert_eq!(card_glyph(queen_spades), '🂭');
+        assert_eq!(card_glyph("QE".parse().unwrap()), '🂠');
+    }
+
+    #[test]
+    fn hands_render_as_compact_strings() {
+        let hand = Hand::Pair(
+            PlayedCard::new(Rank::Ten, Suit::Clubs, false),
+            PlayedCard::new(Rank::Ten, Suit::Diamonds, true),
+        );
+
+        assert_eq!(hand_symbols(hand), "10♣ 10♦*");
+        assert_eq!(hand_symbols(Hand::Pass), "pass");
+    }
+}
@@ -0,0 +1,127 @@
+use crate::game::{sort_hand, Hand, Round};
+use crate::prelude::*;
+
+const RULE: &str = "----------------------------------------";
+
+fn last_move_text(round: &Round) -> String {
+    match round.get_last_move() {
+        None => "none (opening lead)".to_string(),
+        Some(Hand::Pass) => "none (table clear)".to_string(),
+        Some(hand) => {
+            let cards = hand.to_cards().iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+            format!(
+                "{} [{}] by {}",
+                hand,
+                cards,
+                round.get_last_player().unwrap_or_default()
+            )
+        },
+    }
+}
+
+/// A plain-text view of the table as seen from `perspective`: the last
+/// move, whose turn it is, everyone's card counts and the viewer's own
+/// hand sorted low to high.
+pub fn render_text(round: &Round, perspective: &str) -> String {
+    let next_player = round.get_next_player();
+    let mut lines = vec![RULE.to_string()];
+
+    lines.push(format!("Last move: {}", last_move_text(round)));
+    lines.push(format!(
+        "Turn: {}",
+        next_player.clone().unwrap_or_else(|| "game over".to_string())
+    ));
+    lines.push("Players:".to_string());
+
+    for id in round.get_player_ids() {
+        let count = round.get_player(&id)
+            .map(|p| p.get_card_count())
+            .unwrap_or(0);
+        let marker = if next_player.as_deref() == Some(id.as_str()) {
+            ">"
+        } else {
+            " "
+        };
+        let you = if id == perspective { " (you)" } else { "" };
+        lines.push(format!("  {} {:<10} {:>2} cards{}", marker, id, count, you));
+    }
+
+    if let Some(player) = round.get_player(perspective) {
+        let hand = sort_hand(
+            &player.get_hand(),
+            &round.get_order_context()
+        );
+        let cards = hand.iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+        lines.push(format!("Your hand: {}", cards));
+    }
+
+    lines.push(RULE.to_string());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{Player, RoundBuilder};
+
+    #[test]
+    fn it_renders_the_table_from_a_players_perspective() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Two, suit: Suit::Spades },
+                Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Clubs },
+                Card::Joker { deck_id: 0 },
+            ]))
+            .player(Player::new("b".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Five, suit: Suit::Clubs },
+            ]))
+            .next_player("a")
+            .last_move(
+                Hand::Pair(
+                    PlayedCard::new(Rank::Three, Suit::Clubs, false),
+                    PlayedCard::new(Rank::Three, Suit::Hearts, false),
+                ),
+                "b"
+            )
+            .build()
+            .unwrap();
+
+        let expected = [
+            RULE,
+            "Last move: Pair of Threes [3C 3H] by b",
+            "Turn: a",
+            "Players:",
+            "  > a           3 cards (you)",
+            "    b           1 cards",
+            "Your hand: 4C 2S JK",
+            RULE,
+        ].join("\n");
+
+        assert_eq!(render_text(&round, "a"), expected);
+    }
+
+    #[test]
+    fn spectators_see_no_hand() {
+        let round = RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+            ]))
+            .player(Player::new("b".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Five, suit: Suit::Clubs },
+            ]))
+            .build()
+            .unwrap();
+
+        let text = render_text(&round, "spectator");
+
+        assert!(text.contains("Last move: none (opening lead)"));
+        assert!(!text.contains("Your hand"));
+    }
+}
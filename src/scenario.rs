@@ -0,0 +1,166 @@
+use crate::cards::{get_rank_array, get_suit_array, Card, ParseCardError, PlayedCard};
+use crate::game::{Hand, HandError, Player, Round, RoundBuilder, RoundBuilderError, Ruleset};
+use crate::prelude::*;
+
+#[derive(Debug, PartialEq)]
+pub enum ScenarioError {
+    InvalidCard(ParseCardError),
+    /// The cards given as the last move don't make a hand
+    InvalidHand(HandError),
+    InvalidRound(RoundBuilderError),
+}
+
+impl From<ParseCardError> for ScenarioError {
+    fn from(err: ParseCardError) -> ScenarioError {
+        ScenarioError::InvalidCard(err)
+    }
+}
+
+impl From<RoundBuilderError> for ScenarioError {
+    fn from(err: RoundBuilderError) -> ScenarioError {
+        ScenarioError::InvalidRound(err)
+    }
+}
+
+/// Describes a mid-game position with card notation instead of card
+/// literals, for tests here and in crates built on this one. Player "a"
+/// holding "3C 4C" with "b" having played "5C 5S" into reversed orders is
+/// `.player("a", "3C 4C").last_move("b", "5C 5S").reversed()`. Nothing is
+/// parsed until `build`, which reports the first mistake.
+#[derive(Debug, Clone, Default)]
+pub struct Scenario {
+    players: Vec<(String, String)>,
+    next_player: Option<String>,
+    last_move: Option<(String, String)>,
+    lead: Option<String>,
+    reversed: bool,
+    ruleset: Ruleset,
+}
+
+impl Scenario {
+    pub fn new() -> Scenario {
+        Scenario::default()
+    }
+
+    /// Seats a player holding `hand`, e.g. `"3C 10H JK"`.
+    pub fn player(mut self, id: &str, hand: &str) -> Scenario {
+        self.players.push((id.to_string(), hand.to_string()));
+        self
+    }
+
+    pub fn next_player(mut self, id: &str) -> Scenario {
+        self.next_player = Some(id.to_string());
+        self
+    }
+
+    /// The hand on the table, e.g. `"5C 5S"` or `"JK:2S"` for a joker
+    /// played as the two of spades.
+    pub fn last_move(mut self, player_id: &str, cards: &str) -> Scenario {
+        self.last_move = Some((player_id.to_string(), cards.to_string()));
+        self.lead = None;
+        self
+    }
+
+    /// The table was cleared after `player_id` won the last trick.
+    pub fn lead(mut self, player_id: &str) -> Scenario {
+        self.lead = Some(player_id.to_string());
+        self.last_move = None;
+        self
+    }
+
+    /// The suit and rank orders have been reversed.
+    pub fn reversed(mut self) -> Scenario {
+        self.reversed = true;
+        self
+    }
+
+    pub fn ruleset(mut self, ruleset: Ruleset) -> Scenario {
+        self.ruleset = ruleset;
+        self
+    }
+
+    pub fn build(self) -> Result<Round, ScenarioError> {
+        let mut builder = RoundBuilder::new().ruleset(self.ruleset);
+
+        for (id, hand) in self.players.iter() {
+            builder = builder.player(Player::new(id.to_string(), parse_cards(hand)?));
+        }
+        if let Some(id) = self.next_player.as_ref() {
+            builder = builder.next_player(id);
+        }
+        if let Some((player_id, cards)) = self.last_move.as_ref() {
+            let hand = Hand::try_build(parse_played_cards(cards)?)
+                .map_err(ScenarioError::InvalidHand)?;
+            builder = builder.last_move(hand, player_id);
+        }
+        if let Some(player_id) = self.lead.as_ref() {
+            builder = builder.table_cleared(player_id);
+        }
+        if self.reversed {
+            let mut suit_order = get_suit_array();
+            let mut rank_order = get_rank_array();
+            suit_order.reverse();
+            rank_order.reverse();
+            builder = builder.suit_order(&suit_order).rank_order(rank_order).reversed(true);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+/// Cards separated by whitespace, e.g. `"3C 10H JK"`.
+pub fn parse_cards(cards: &str) -> Result<Vec<Card>, ParseCardError> {
+    cards.split_whitespace().map(|card| card.parse()).collect()
+}
+
+/// Played cards separated by whitespace, e.g. `"5C JK:5S"`.
+pub fn parse_played_cards(cards: &str) -> Result<Vec<PlayedCard>, ParseCardError> {
+    cards.split_whitespace().map(|card| card.parse()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Rank, Suit};
+
+    #[test]
+    fn scenarios_build_the_described_round() {
+        let round = Scenario::new()
+            .player("a", "3C 4C")
+            .player("b", "9D 2S")
+            .last_move("b", "5C 5S")
+            .next_player("a")
+            .reversed()
+            .build()
+            .unwrap();
+
+        assert_eq!(round.get_player("a").unwrap().get_hand(), vec![
+            Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+            Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Clubs },
+        ]);
+        assert_eq!(round.get_last_move(), Some(Hand::Pair(
+            PlayedCard::new(Rank::Five, Suit::Clubs, false),
+            PlayedCard::new(Rank::Five, Suit::Spades, false),
+        )));
+        assert_eq!(round.get_last_player(), Some("b".to_string()));
+        assert_eq!(round.get_next_player(), Some("a".to_string()));
+        assert_eq!(round.get_rank_order()[0], Rank::Two);
+        assert_eq!(round.get_suit_order()[0], Suit::Spades);
+    }
+
+    #[test]
+    fn mistakes_are_reported_when_building() {
+        assert_eq!(
+            Scenario::new().player("a", "3C 1X").build().unwrap_err(),
+            ScenarioError::InvalidCard(ParseCardError::InvalidRank("1".to_string()))
+        );
+        assert_eq!(
+            Scenario::new().player("a", "3C").last_move("a", "5C 6S").build().unwrap_err(),
+            ScenarioError::InvalidHand(HandError::PairRanksDiffer)
+        );
+        assert_eq!(
+            Scenario::new().player("a", "3C").player("a", "4C").build().unwrap_err(),
+            ScenarioError::InvalidRound(RoundBuilderError::DuplicatePlayer("a".to_string()))
+        );
+    }
+}
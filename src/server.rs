@@ -0,0 +1,158 @@
+use crate::cards::PlayedCard;
+use crate::game::{Round, SubmitError};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+mod manager;
+
+pub use self::manager::*;
+
+const COMMAND_BUFFER: usize = 32;
+const UPDATE_BUFFER: usize = 16;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum ServerError {
+    Submit(SubmitError),
+    GameClosed,
+}
+
+enum Command {
+    SubmitMove {
+        player_id: String,
+        cards: Vec<PlayedCard>,
+        respond: oneshot::Sender<Result<Round, SubmitError>>,
+    },
+    GetState {
+        respond: oneshot::Sender<Round>,
+    },
+}
+
+/// Cloneable handle to a round owned by a background task. Moves are
+/// applied one at a time in the order they arrive, and every accepted move
+/// is broadcast to subscribers as a fresh `Round` snapshot.
+#[derive(Clone)]
+pub struct GameHandle {
+    commands: mpsc::Sender<Command>,
+    updates: broadcast::Sender<Round>,
+}
+
+impl GameHandle {
+    /// Spawns the game task on the current tokio runtime.
+    pub fn spawn(round: Round) -> GameHandle {
+        let (commands, receiver) = mpsc::channel(COMMAND_BUFFER);
+        let (updates, _) = broadcast::channel(UPDATE_BUFFER);
+
+        tokio::spawn(run(round, receiver, updates.clone()));
+
+        GameHandle { commands, updates }
+    }
+
+    pub async fn submit_move(
+        &self,
+        player_id: &str,
+        cards: Vec<PlayedCard>,
+    ) -> Result<Round, ServerError> {
+        let (respond, response) = oneshot::channel();
+        self.send(Command::SubmitMove {
+            player_id: player_id.to_string(),
+            cards,
+            respond,
+        }).await?;
+
+        response.await
+            .map_err(|_| ServerError::GameClosed)?
+            .map_err(ServerError::Submit)
+    }
+
+    pub async fn get_state(&self) -> Result<Round, ServerError> {
+        let (respond, response) = oneshot::channel();
+        self.send(Command::GetState { respond }).await?;
+
+        response.await.map_err(|_| ServerError::GameClosed)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Round> {
+        self.updates.subscribe()
+    }
+
+    async fn send(&self, command: Command) -> Result<(), ServerError> {
+        self.commands.send(command).await
+            .map_err(|_| ServerError::GameClosed)
+    }
+}
+
+async fn run(
+    mut round: Round,
+    mut commands: mpsc::Receiver<Command>,
+    updates: broadcast::Sender<Round>,
+) {
+    while let Some(command) = commands.recv().await {
+        match command {
+            Command::SubmitMove { player_id, cards, respond } => {
+                let result = round.submit_move(&player_id, cards);
+                if let Ok(new_round) = &result {
+                    round = new_round.clone();
+                    // no subscribers is not an error
+                    let _ = updates.send(round.clone());
+                }
+                let _ = respond.send(result);
+            },
+            Command::GetState { respond } => {
+                let _ = respond.send(round.clone());
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{Player, RoundBuilder};
+
+    fn get_round() -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+                Card::Standard { deck_id: 0, rank: Rank::Five, suit: Suit::Clubs },
+            ]))
+            .player(Player::new("b".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Clubs },
+                Card::Standard { deck_id: 0, rank: Rank::Six, suit: Suit::Clubs },
+            ]))
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn it_applies_moves_and_broadcasts_updates() {
+        let handle = GameHandle::spawn(get_round());
+        let mut updates = handle.subscribe();
+
+        let round = handle.submit_move(
+            "a",
+            vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)]
+        ).await.unwrap();
+
+        assert_eq!(round.get_next_player(), Some("b".to_string()));
+
+        let update = updates.recv().await.unwrap();
+        assert_eq!(update.get_next_player(), Some("b".to_string()));
+    }
+
+    #[tokio::test]
+    async fn it_returns_submit_errors() {
+        let handle = GameHandle::spawn(get_round());
+
+        let err = handle.submit_move(
+            "b",
+            vec![PlayedCard::new(Rank::Four, Suit::Clubs, false)]
+        ).await.err().unwrap();
+
+        assert_eq!(err, ServerError::Submit(SubmitError::NotCurrentPlayer));
+        assert_eq!(
+            handle.get_state().await.unwrap().get_next_player(),
+            Some("a".to_string())
+        );
+    }
+}
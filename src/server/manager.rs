@@ -0,0 +1,366 @@
+use crate::ai::AutoPlay;
+use crate::cards::PlayedCard;
+use crate::game::{get_move_events, GameEvent, RejoinSnapshot, Round, SpectatorView, SubmitError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum ManagerError {
+    UnknownGame,
+    GameExists,
+    Submit(SubmitError),
+    /// The id is already watching or seated at the game
+    AlreadyWatching,
+    UnknownSpectator,
+    /// Spectators can watch a game but never move in it
+    SpectatorCannotMove,
+    /// The id isn't seated at the game
+    UnknownPlayer,
+}
+
+/// Sent to a game's spectators after every move: the events it caused
+/// and the table as it stands afterwards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpectatorUpdate {
+    pub events: Vec<GameEvent>,
+    pub view: SpectatorView,
+}
+
+struct ManagedGame {
+    round: Round,
+    finished_at: Option<Instant>,
+    winners: Vec<String>,
+    spectators: HashMap<String, Sender<SpectatorUpdate>>,
+}
+
+impl ManagedGame {
+    fn apply(&mut self, player_id: &str, cards: Vec<PlayedCard>) -> Result<Round, ManagerError> {
+        if self.spectators.contains_key(player_id) {
+            return Err(ManagerError::SpectatorCannotMove);
+        }
+
+        let old_round = self.round.clone();
+        self.round.submit_move_mut(player_id, cards.clone())
+            .map_err(ManagerError::Submit)?;
+
+        let went_out = self.round.get_player(player_id)
+            .map(|p| p.get_card_count() == 0)
+            .unwrap_or(false);
+        if went_out && !self.winners.iter().any(|id| id == player_id) {
+            self.winners.push(player_id.to_string());
+        }
+        if self.finished_at.is_none() && self.round.is_game_over() {
+            self.finished_at = Some(Instant::now());
+        }
+
+        if !self.spectators.is_empty() {
+            let hand = old_round.build_hand(&cards)
+                .expect("accepted moves make a hand");
+            let update = SpectatorUpdate {
+                events: get_move_events(&old_round, &self.round, player_id, hand, &self.winners),
+                view: SpectatorView::from_round(&self.round),
+            };
+            // spectators who dropped their receiver stop watching
+            self.spectators.retain(|_, sender| sender.send(update.clone()).is_ok());
+        }
+
+        Ok(self.round.clone())
+    }
+}
+
+/// Many rounds keyed by game id, shared between threads. Each game has its
+/// own lock so moves at different tables never wait on each other.
+#[derive(Default)]
+pub struct GameManager {
+    games: RwLock<HashMap<String, Arc<Mutex<ManagedGame>>>>,
+}
+
+impl GameManager {
+    pub fn new() -> GameManager {
+        GameManager::default()
+    }
+
+    pub fn create_game(&self, game_id: &str, round: Round) -> Result<(), ManagerError> {
+        let mut games = self.games.write().expect("game map poisoned");
+        if games.contains_key(game_id) {
+            return Err(ManagerError::GameExists);
+        }
+
+        games.insert(game_id.to_string(), Arc::new(Mutex::new(ManagedGame {
+            round,
+            finished_at: None,
+            winners: vec![],
+            spectators: HashMap::new(),
+        })));
+
+        Ok(())
+    }
+
+    pub fn submit_move(
+        &self,
+        game_id: &str,
+        player_id: &str,
+        cards: Vec<PlayedCard>,
+    ) -> Result<Round, ManagerError> {
+        let game = self.get_game(game_id)?;
+        let mut game = game.lock().expect("game poisoned");
+
+        game.apply(player_id, cards)
+    }
+
+    /// Plays for `player_id`, e.g. after they disconnect, using `policy`
+    /// to pick a move against the game's current state.
+    pub fn auto_play(
+        &self,
+        game_id: &str,
+        player_id: &str,
+        policy: AutoPlay,
+    ) -> Result<Round, ManagerError> {
+        let game = self.get_game(game_id)?;
+        let mut game = game.lock().expect("game poisoned");
+
+        let cards = policy.get_move(&game.round, player_id)
+            .ok_or(ManagerError::Submit(SubmitError::NotCurrentPlayer))?;
+        game.apply(player_id, cards)
+    }
+
+    pub fn get_state(&self, game_id: &str) -> Result<Round, ManagerError> {
+        let game = self.get_game(game_id)?;
+        let game = game.lock().expect("game poisoned");
+
+        Ok(game.round.clone())
+    }
+
+    /// Starts `spectator_id` watching a game. Returns the table as it
+    /// stands, along with a receiver for an update after every move.
+    pub fn add_spectator(
+        &self,
+        game_id: &str,
+        spectator_id: &str,
+    ) -> Result<(SpectatorView, Receiver<SpectatorUpdate>), ManagerError> {
+        let game = self.get_game(game_id)?;
+        let mut game = game.lock().expect("game poisoned");
+
+        let seated = game.round.get_player(spectator_id).is_some();
+        if seated || game.spectators.contains_key(spectator_id) {
+            return Err(ManagerError::AlreadyWatching);
+        }
+
+        let (sender, receiver) = channel();
+        game.spectators.insert(spectator_id.to_string(), sender);
+
+        Ok((SpectatorView::from_round(&game.round), receiver))
+    }
+
+    pub fn remove_spectator(&self, game_id: &str, spectator_id: &str) -> Result<(), ManagerError> {
+        let game = self.get_game(game_id)?;
+        let mut game = game.lock().expect("game poisoned");
+
+        game.spectators.remove(spectator_id)
+            .map(|_| ())
+            .ok_or(ManagerError::UnknownSpectator)
+    }
+
+    pub fn get_spectator_ids(&self, game_id: &str) -> Result<Vec<String>, ManagerError> {
+        let game = self.get_game(game_id)?;
+        let game = game.lock().expect("game poisoned");
+
+        let mut ids: Vec<String> = game.spectators.keys().cloned().collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// The game's state with every hand hidden, as spectators see it.
+    pub fn get_spectator_view(&self, game_id: &str) -> Result<SpectatorView, ManagerError> {
+        let game = self.get_game(game_id)?;
+        let game = game.lock().expect("game poisoned");
+
+        Ok(SpectatorView::from_round(&game.round))
+    }
+
+    /// Everything `player_id` needs to pick the game back up after
+    /// reconnecting.
+    pub fn get_rejoin_snapshot(
+        &self,
+        game_id: &str,
+        player_id: &str,
+    ) -> Result<RejoinSnapshot, ManagerError> {
+        let game = self.get_game(game_id)?;
+        let game = game.lock().expect("game poisoned");
+
+        game.round.rejoin_snapshot(player_id).ok_or(ManagerError::UnknownPlayer)
+    }
+
+    pub fn remove_game(&self, game_id: &str) -> Option<Round> {
+        let game = self.games.write().expect("game map poisoned")
+            .remove(game_id)?;
+        let game = game.lock().expect("game poisoned");
+
+        Some(game.round.clone())
+    }
+
+    pub fn get_game_ids(&self) -> Vec<String> {
+        self.games.read().expect("game map poisoned")
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.games.read().expect("game map poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drops games that finished at least `max_age` ago, returning their
+    /// ids.
+    pub fn expire_finished(&self, max_age: Duration) -> Vec<String> {
+        let mut games = self.games.write().expect("game map poisoned");
+        let expired: Vec<String> = games.iter()
+            .filter(|(_, game)| {
+                game.lock().expect("game poisoned")
+                    .finished_at
+                    .map(|at| at.elapsed() >= max_age)
+                    .unwrap_or(false)
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in expired.iter() {
+            games.remove(id);
+        }
+
+        expired
+    }
+
+    fn get_game(&self, game_id: &str) -> Result<Arc<Mutex<ManagedGame>>, ManagerError> {
+        self.games.read().expect("game map poisoned")
+            .get(game_id)
+            .cloned()
+            .ok_or(ManagerError::UnknownGame)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::*;
+    use crate::game::{Player, RoundBuilder};
+    use std::thread;
+
+    fn get_round() -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+            ]))
+            .player(Player::new("b".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Clubs },
+            ]))
+            .build()
+            .unwrap()
+    }
+
+    fn three_clubs() -> Vec<PlayedCard> {
+        vec![PlayedCard::new(Rank::Three, Suit::Clubs, false)]
+    }
+
+    #[test]
+    fn games_are_played_independently_across_threads() {
+        let manager = Arc::new(GameManager::new());
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let manager = manager.clone();
+                thread::spawn(move || {
+                    let id = format!("table{}", i);
+                    manager.create_game(&id, get_round()).unwrap();
+                    manager.submit_move(&id, "a", three_clubs()).unwrap()
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap().get_next_player(), None);
+        }
+        assert_eq!(manager.len(), 8);
+    }
+
+    #[test]
+    fn errors_are_reported_per_game() {
+        let manager = GameManager::new();
+        manager.create_game("t", get_round()).unwrap();
+
+        assert_eq!(
+            manager.create_game("t", get_round()),
+            Err(ManagerError::GameExists)
+        );
+        assert_eq!(
+            manager.submit_move("t", "b", vec![]).err(),
+            Some(ManagerError::Submit(SubmitError::NotCurrentPlayer))
+        );
+        assert_eq!(manager.get_state("x").err(), Some(ManagerError::UnknownGame));
+        assert_eq!(manager.get_rejoin_snapshot("t", "x").err(), Some(ManagerError::UnknownPlayer));
+        assert!(manager.get_rejoin_snapshot("t", "b").is_ok());
+    }
+
+    #[test]
+    fn absent_players_can_be_played_for() {
+        let manager = GameManager::new();
+        manager.create_game("t", get_round()).unwrap();
+
+        assert_eq!(
+            manager.auto_play("t", "b", AutoPlay::PassOnly).err(),
+            Some(ManagerError::Submit(SubmitError::NotCurrentPlayer))
+        );
+
+        let round = manager.auto_play("t", "a", AutoPlay::PassOnly).unwrap();
+
+        assert_eq!(round.get_next_player(), None);
+        assert_eq!(manager.expire_finished(Duration::from_secs(0)), vec!["t"]);
+    }
+
+    #[test]
+    fn only_finished_games_expire() {
+        let manager = GameManager::new();
+        manager.create_game("done", get_round()).unwrap();
+        manager.create_game("playing", get_round()).unwrap();
+        manager.submit_move("done", "a", three_clubs()).unwrap();
+
+        assert_eq!(manager.expire_finished(Duration::from_secs(0)), vec!["done"]);
+        assert_eq!(manager.get_game_ids(), vec!["playing"]);
+        assert!(manager.remove_game("playing").is_some());
+        assert!(manager.is_empty());
+    }
+
+    #[test]
+    fn spectators_are_sent_events_but_cannot_move() {
+        let manager = GameManager::new();
+        manager.create_game("t", get_round()).unwrap();
+
+        let (view, updates) = manager.add_spectator("t", "s").unwrap();
+        assert_eq!(view.card_counts, vec![("a".to_string(), 1), ("b".to_string(), 1)]);
+        assert_eq!(manager.add_spectator("t", "s").err(), Some(ManagerError::AlreadyWatching));
+        assert_eq!(manager.add_spectator("t", "a").err(), Some(ManagerError::AlreadyWatching));
+        assert_eq!(
+            manager.submit_move("t", "s", three_clubs()).err(),
+            Some(ManagerError::SpectatorCannotMove)
+        );
+
+        manager.submit_move("t", "a", three_clubs()).unwrap();
+
+        let update = updates.recv().unwrap();
+        assert_eq!(update.view, manager.get_spectator_view("t").unwrap());
+        assert_eq!(update.events.last(), Some(&GameEvent::GameOver {
+            winners: vec!["a".to_string()],
+        }));
+        assert_eq!(manager.get_spectator_ids("t").unwrap(), vec!["s"]);
+
+        manager.remove_spectator("t", "s").unwrap();
+        assert_eq!(manager.remove_spectator("t", "s"), Err(ManagerError::UnknownSpectator));
+        assert!(updates.recv().is_err());
+    }
+}
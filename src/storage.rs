@@ -0,0 +1,320 @@
+//! Exports finished games to SQLite, so a small server can keep a
+//! history of its games and query stats from it without a persistence
+//! layer of its own.
+//!
+//! Every game gets a row in `games` holding its full record as JSON and
+//! its summary, a row per player in `game_players`, and a row per event
+//! in `game_events` in the order the events happened. Hands, records and
+//! events are stored in the same JSON the rest of the crate serializes.
+
+use crate::game::{
+    get_move_events,
+    summarize_game,
+    GameEvent,
+    GameRecord,
+    ScoringScheme,
+    VerifyError,
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS games (
+        id TEXT PRIMARY KEY,
+        record TEXT NOT NULL,
+        winners TEXT NOT NULL,
+        moves INTEGER NOT NULL,
+        reversals INTEGER NOT NULL,
+        longest_trick INTEGER NOT NULL
+    );
+    CREATE TABLE IF NOT EXISTS game_players (
+        game_id TEXT NOT NULL REFERENCES games(id),
+        player_id TEXT NOT NULL,
+        placement INTEGER NOT NULL,
+        points INTEGER NOT NULL,
+        biggest_hand TEXT,
+        PRIMARY KEY (game_id, player_id)
+    );
+    CREATE TABLE IF NOT EXISTS game_events (
+        game_id TEXT NOT NULL REFERENCES games(id),
+        seq INTEGER NOT NULL,
+        move_index INTEGER NOT NULL,
+        type TEXT NOT NULL,
+        player_id TEXT,
+        event TEXT NOT NULL,
+        PRIMARY KEY (game_id, seq)
+    );
+    CREATE INDEX IF NOT EXISTS game_players_by_player ON game_players (player_id);
+";
+
+#[derive(Debug)]
+pub enum StorageError {
+    Database(rusqlite::Error),
+    Json(serde_json::Error),
+    /// Only finished games that replay legally are exported
+    Verify(VerifyError),
+    /// A game with this id has already been exported
+    GameExists(String),
+}
+
+impl From<rusqlite::Error> for StorageError {
+    fn from(err: rusqlite::Error) -> StorageError {
+        StorageError::Database(err)
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(err: serde_json::Error) -> StorageError {
+        StorageError::Json(err)
+    }
+}
+
+impl From<VerifyError> for StorageError {
+    fn from(err: VerifyError) -> StorageError {
+        StorageError::Verify(err)
+    }
+}
+
+/// One player's totals across every exported game.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerHistory {
+    pub games_played: usize,
+    pub first_places: usize,
+    pub total_points: i64,
+}
+
+/// Writes finished games into a SQLite database, creating the tables the
+/// first time it's opened.
+pub struct SqliteExporter {
+    connection: Connection,
+    scheme: ScoringScheme,
+}
+
+impl SqliteExporter {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<SqliteExporter, StorageError> {
+        SqliteExporter::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<SqliteExporter, StorageError> {
+        SqliteExporter::with_connection(Connection::open_in_memory()?)
+    }
+
+    pub fn with_connection(connection: Connection) -> Result<SqliteExporter, StorageError> {
+        connection.execute_batch(SCHEMA)?;
+
+        Ok(SqliteExporter { connection, scheme: ScoringScheme::default() })
+    }
+
+    /// How games are scored for `game_players.points`.
+    pub fn scoring_scheme(mut self, scheme: ScoringScheme) -> SqliteExporter {
+        self.scheme = scheme;
+        self
+    }
+
+    /// The database, for running queries against the exported games.
+    pub fn get_connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Verifies and summarises `record`, then writes it, its players and
+    /// every event it gave rise to under `game_id` in one transaction.
+    pub fn export_game(&mut self, game_id: &str, record: &GameRecord) -> Result<(), StorageError> {
+        let summary = summarize_game(record, self.scheme)?;
+        let events = get_record_events(record);
+
+        let tx = self.connection.transaction()?;
+        let exists = tx.query_row(
+            "SELECT 1 FROM games WHERE id = ?1",
+            params![game_id],
+            |_| Ok(()),
+        ).optional()?.is_some();
+        if exists {
+            return Err(StorageError::GameExists(game_id.to_string()));
+        }
+
+        tx.execute(
+            "INSERT INTO games (id, record, winners, moves, reversals, longest_trick)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                game_id,
+                serde_json::to_string(record)?,
+                serde_json::to_string(&record.winners)?,
+                record.moves.len() as i64,
+                summary.reversals as i64,
+                summary.longest_trick as i64,
+            ],
+        )?;
+
+        for player in summary.players.iter() {
+            let biggest_hand = player.biggest_hand
+                .map(|hand| serde_json::to_string(&hand))
+                .transpose()?;
+            tx.execute(
+                "INSERT INTO game_players (game_id, player_id, placement, points, biggest_hand)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    game_id,
+                    player.player_id,
+                    player.placement as i64,
+                    player.points,
+                    biggest_hand,
+                ],
+            )?;
+        }
+
+        for (seq, (move_index, event)) in events.iter().enumerate() {
+            let value = serde_json::to_value(event)?;
+            tx.execute(
+                "INSERT INTO game_events (game_id, seq, move_index, type, player_id, event)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![
+                    game_id,
+                    seq as i64,
+                    *move_index as i64,
+                    value["type"].as_str(),
+                    value["player_id"].as_str(),
+                    value.to_string(),
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Reads back an exported game's record.
+    pub fn get_record(&self, game_id: &str) -> Result<Option<GameRecord>, StorageError> {
+        let json: Option<String> = self.connection.query_row(
+            "SELECT record FROM games WHERE id = ?1",
+            params![game_id],
+            |row| row.get(0),
+        ).optional()?;
+
+        Ok(json.map(|json| serde_json::from_str(&json)).transpose()?)
+    }
+
+    /// A player's totals over every game exported so far.
+    pub fn get_player_history(&self, player_id: &str) -> Result<PlayerHistory, StorageError> {
+        let (games_played, first_places, total_points): (i64, i64, i64) = self.connection.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(placement = 1), 0),
+                    COALESCE(SUM(points), 0)
+             FROM game_players WHERE player_id = ?1",
+            params![player_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        Ok(PlayerHistory {
+            games_played: games_played as usize,
+            first_places: first_places as usize,
+            total_points,
+        })
+    }
+}
+
+/// Every event in a verified game, each with the index of the move that
+/// caused it.
+fn get_record_events(record: &GameRecord) -> Vec<(usize, GameEvent)> {
+    let mut round = record.initial_round.clone();
+    let mut winners: Vec<String> = vec![];
+    let mut events = vec![];
+
+    for (index, recorded) in record.moves.iter().enumerate() {
+        let old_round = round.clone();
+        round.submit_move_mut(&recorded.player_id, recorded.cards.clone())
+            .expect("verified games only hold legal moves");
+
+        let went_out = round.get_player(&recorded.player_id)
+            .map(|p| p.get_card_count() == 0)
+            .unwrap_or(false);
+        if went_out && !winners.contains(&recorded.player_id) {
+            winners.push(recorded.player_id.clone());
+        }
+
+        let hand = old_round.build_hand(&recorded.cards)
+            .expect("verified games only hold legal moves");
+        events.extend(
+            get_move_events(&old_round, &round, &recorded.player_id, hand, &winners)
+                .into_iter()
+                .map(|event| (index, event))
+        );
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::{parse_played_cards, Scenario};
+
+    fn get_record() -> GameRecord {
+        let round = Scenario::new()
+            .player("a", "3C 4C 4H AC")
+            .player("b", "5C 6C 6H")
+            .build()
+            .unwrap();
+
+        let mut record = GameRecord::new(round);
+        for (player_id, cards) in [("a", "3C"), ("b", "5C"), ("a", "AC"), ("b", ""), ("a", "4C 4H")] {
+            record.record_move(player_id, parse_played_cards(cards).unwrap());
+        }
+        record.winners = vec!["a".to_string()];
+
+        record
+    }
+
+    #[test]
+    fn finished_games_are_exported_with_their_events() {
+        let mut exporter = SqliteExporter::open_in_memory().unwrap();
+        let record = get_record();
+        exporter.export_game("g1", &record).unwrap();
+
+        let connection = exporter.get_connection();
+        let types: Vec<String> = connection
+            .prepare("SELECT type FROM game_events WHERE game_id = 'g1' ORDER BY seq")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(types, vec![
+            "cards_played",
+            "cards_played",
+            "cards_played",
+            "passed",
+            "trick_cleared",
+            "cards_played",
+            "player_finished",
+            "game_over",
+        ]);
+
+        assert_eq!(exporter.get_player_history("a").unwrap(), PlayerHistory {
+            games_played: 1,
+            first_places: 1,
+            total_points: 0,
+        });
+        assert_eq!(exporter.get_player_history("b").unwrap().total_points, -2);
+        assert_eq!(exporter.get_record("g1").unwrap().unwrap().moves, record.moves);
+        assert!(exporter.get_record("g2").unwrap().is_none());
+
+        assert!(matches!(
+            exporter.export_game("g1", &record),
+            Err(StorageError::GameExists(id)) if id == "g1"
+        ));
+    }
+
+    #[test]
+    fn unfinished_games_are_not_exported() {
+        let mut exporter = SqliteExporter::open_in_memory().unwrap();
+        let mut record = get_record();
+        record.moves.pop();
+
+        assert!(matches!(
+            exporter.export_game("g1", &record),
+            Err(StorageError::Verify(VerifyError::GameNotFinished))
+        ));
+        assert_eq!(exporter.get_player_history("a").unwrap(), PlayerHistory::default());
+    }
+}
@@ -0,0 +1,200 @@
+use crate::cards::{get_rank_array, get_suit_array, Card, Deck, PlayedCard, Rank, Suit};
+use crate::game::{Game, Hand, Round, Ruleset};
+use proptest::prelude::*;
+use proptest::sample::{select, subsequence};
+
+impl Arbitrary for Rank {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Rank>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        select(get_rank_array().to_vec()).boxed()
+    }
+}
+
+impl Arbitrary for Suit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Suit>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        select(get_suit_array().to_vec()).boxed()
+    }
+}
+
+/// Mostly standard cards, with jokers about as often as they turn up in a
+/// deck with two of them.
+impl Arbitrary for Card {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Card>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            1 => Just(Card::Joker { deck_id: 0 }),
+            26 => (any::<Rank>(), any::<Suit>())
+                .prop_map(|(rank, suit)| Card::Standard { deck_id: 0, rank, suit }),
+        ].boxed()
+    }
+}
+
+impl Arbitrary for PlayedCard {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<PlayedCard>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<Rank>(), any::<Suit>(), prop::bool::weighted(0.05))
+            .prop_map(|(rank, suit, is_joker)| PlayedCard::new(rank, suit, is_joker))
+            .boxed()
+    }
+}
+
+/// Only valid hands, spread across every size rather than weighted by how
+/// often random cards would happen to form them.
+impl Arbitrary for Hand {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Hand>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            Just(Hand::Pass),
+            any::<PlayedCard>().prop_map(Hand::Single),
+            set_of_a_kind(2),
+            set_of_a_kind(3),
+            five_card_trick(),
+        ].boxed()
+    }
+}
+
+/// Rounds freshly dealt from a shuffled single deck to two to four
+/// players under the default rules.
+impl Arbitrary for Round {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Round>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (2..=4usize, 0..=2u8)
+            .prop_flat_map(|(players, jokers)| {
+                dealt_round(players, jokers, Ruleset::default())
+            })
+            .boxed()
+    }
+}
+
+/// A round dealt from a shuffled deck to `num_players` players with ids
+/// `"p1"`, `"p2"` and so on.
+pub fn dealt_round(
+    num_players: usize,
+    num_jokers: u8,
+    ruleset: Ruleset,
+) -> impl Strategy<Value = Round> {
+    let player_ids: Vec<String> = (1..=num_players)
+        .map(|i| format!("p{}", i))
+        .collect();
+
+    Just(Deck::new(1, num_jokers).to_vec())
+        .prop_shuffle()
+        .prop_map(move |cards| {
+            Game::from_deck(
+                &Deck::from_cards(cards),
+                1,
+                num_jokers,
+                &player_ids,
+                &get_suit_array(),
+                ruleset,
+            ).expect("the generated ids are unique").get_round().clone()
+        })
+}
+
+/// A pair or prial of natural cards.
+pub fn set_of_a_kind(size: usize) -> impl Strategy<Value = Hand> {
+    (any::<Rank>(), subsequence(get_suit_array().to_vec(), size))
+        .prop_map(|(rank, suits)| build(
+            suits.into_iter()
+                .map(|suit| PlayedCard::new(rank, suit, false))
+                .collect()
+        ))
+}
+
+/// A five card trick of natural cards, drawn evenly from straights,
+/// flushes, full houses and four of a kinds. Straight flushes turn up
+/// among the straights and flushes.
+pub fn five_card_trick() -> impl Strategy<Value = Hand> {
+    let ranks = get_rank_array().to_vec();
+    let suits = get_suit_array().to_vec();
+
+    let straight = (0..=8usize, prop::collection::vec(any::<Suit>(), 5))
+        .prop_map(|(start, suits)| build(
+            get_rank_array()[start..start + 5].iter()
+                .zip(suits)
+                .map(|(&rank, suit)| PlayedCard::new(rank, suit, false))
+                .collect()
+        ));
+    let flush = (subsequence(ranks.clone(), 5), any::<Suit>())
+        .prop_map(|(ranks, suit)| build(
+            ranks.into_iter()
+                .map(|rank| PlayedCard::new(rank, suit, false))
+                .collect()
+        ));
+    let full_house = (
+        subsequence(ranks.clone(), 2).prop_shuffle(),
+        subsequence(suits.clone(), 3),
+        subsequence(suits, 2),
+    )
+        .prop_map(|(ranks, three, two)| build(
+            three.into_iter()
+                .map(|suit| PlayedCard::new(ranks[0], suit, false))
+                .chain(two.into_iter().map(|suit| PlayedCard::new(ranks[1], suit, false)))
+                .collect()
+        ));
+    let four_of_a_kind = (subsequence(ranks, 2).prop_shuffle(), any::<Suit>())
+        .prop_map(|(ranks, kicker_suit)| build(
+            get_suit_array().iter()
+                .map(|&suit| PlayedCard::new(ranks[0], suit, false))
+                .chain(std::iter::once(PlayedCard::new(ranks[1], kicker_suit, false)))
+                .collect()
+        ));
+
+    prop_oneof![straight, flush, full_house, four_of_a_kind]
+}
+
+fn build(cards: Vec<PlayedCard>) -> Hand {
+    Hand::build(cards).expect("generated cards should form a hand")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn generated_hands_rebuild_to_themselves(hand in any::<Hand>()) {
+            prop_assert_eq!(Hand::build(hand.to_cards()), Some(hand));
+        }
+
+        #[test]
+        fn dealt_rounds_hold_the_whole_deck(round in any::<Round>()) {
+            let held: usize = round.get_card_counts().iter()
+                .map(|(_, count)| count)
+                .sum();
+
+            prop_assert_eq!(held, round.get_dealt_cards().len());
+            prop_assert!(round.get_next_player().is_some());
+        }
+
+        #[test]
+        fn untrusted_moves_never_panic(
+            mut round in any::<Round>(),
+            json in prop_oneof![
+                ".*",
+                prop::collection::vec(any::<PlayedCard>(), 0..8).prop_map(|cards| {
+                    serde_json::to_string(&crate::protocol::ClientMsg::Play { cards }).unwrap()
+                }),
+            ],
+        ) {
+            let player_id = round.get_next_player().unwrap();
+            let before = round.clone();
+            if round.apply_untrusted_move(&player_id, &json).is_err() {
+                prop_assert_eq!(round.state_hash(), before.state_hash());
+            }
+        }
+    }
+}
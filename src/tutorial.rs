@@ -0,0 +1,328 @@
+//! Scripted lessons played on the real engine. Each tutorial is a fixed
+//! deal and a list of steps: a prompt for the learner, the moves they're
+//! allowed to make, and how the other players answer. Every move still
+//! goes through `Round::submit_move`, so nothing a tutorial shows can
+//! differ from a real game.
+
+use crate::ai::get_legal_moves;
+use crate::cards::PlayedCard;
+use crate::game::{Round, SubmitError};
+use crate::prelude::*;
+use crate::scenario::{parse_played_cards, Scenario};
+use serde::{Deserialize, Serialize};
+
+const LEARNER: &str = "you";
+const OPPONENT: &str = "cpu";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TutorialStep {
+    /// What to tell the learner before they move
+    pub prompt: String,
+    /// The moves that count for this step, each still checked by the
+    /// engine. Empty allows any legal move. An empty move is the pass.
+    pub allowed_moves: Vec<Vec<PlayedCard>>,
+    /// Moves the other players make once the learner has moved, in order
+    pub responses: Vec<(String, Vec<PlayedCard>)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tutorial {
+    pub name: String,
+    pub title: String,
+    /// The player the prompts are written for
+    pub learner: String,
+    pub round: Round,
+    pub steps: Vec<TutorialStep>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum TutorialError {
+    /// Every step has been played
+    Finished,
+    /// The move is legal, but not one this step is teaching
+    NotAllowed,
+    Illegal(SubmitError),
+    /// A scripted response at this step was turned down by the engine,
+    /// which means the tutorial itself is wrong
+    BadScript { step: usize, error: SubmitError },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TutorialProgress {
+    NextStep,
+    Complete,
+}
+
+/// A tutorial being played through.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TutorialSession {
+    tutorial: Tutorial,
+    round: Round,
+    step: usize,
+}
+
+impl TutorialSession {
+    pub fn new(tutorial: Tutorial) -> TutorialSession {
+        TutorialSession {
+            round: tutorial.round.clone(),
+            tutorial,
+            step: 0,
+        }
+    }
+
+    pub fn get_round(&self) -> &Round {
+        &self.round
+    }
+
+    pub fn get_tutorial(&self) -> &Tutorial {
+        &self.tutorial
+    }
+
+    /// The index of the step the learner is on.
+    pub fn get_step_index(&self) -> usize {
+        self.step
+    }
+
+    pub fn get_step(&self) -> Option<&TutorialStep> {
+        self.tutorial.steps.get(self.step)
+    }
+
+    pub fn get_prompt(&self) -> Option<&str> {
+        self.get_step().map(|step| step.prompt.as_str())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.step >= self.tutorial.steps.len()
+    }
+
+    /// The moves the learner can make right now: the engine's legal
+    /// moves, narrowed to those the step allows.
+    pub fn get_legal_moves(&self) -> Vec<Vec<PlayedCard>> {
+        let step = match self.get_step() {
+            Some(step) => step,
+            None => return vec![],
+        };
+
+        get_legal_moves(&self.round, &self.tutorial.learner).into_iter()
+            .filter(|cards| is_allowed(step, cards))
+            .collect()
+    }
+
+    /// Plays the learner's move and the scripted responses to it, moving
+    /// on to the next step. Nothing changes if the move is turned down.
+    pub fn play(&mut self, cards: Vec<PlayedCard>) -> Result<TutorialProgress, TutorialError> {
+        let step = self.get_step().ok_or(TutorialError::Finished)?;
+        if !is_allowed(step, &cards) {
+            return Err(TutorialError::NotAllowed);
+        }
+
+        let mut round = self.round.submit_move(&self.tutorial.learner, cards)
+            .map_err(TutorialError::Illegal)?;
+        for (player_id, cards) in step.responses.iter() {
+            round = round.submit_move(player_id, cards.clone())
+                .map_err(|error| TutorialError::BadScript { step: self.step, error })?;
+        }
+
+        self.round = round;
+        self.step += 1;
+
+        Ok(if self.is_complete() {
+            TutorialProgress::Complete
+        } else {
+            TutorialProgress::NextStep
+        })
+    }
+}
+
+fn is_allowed(step: &TutorialStep, cards: &[PlayedCard]) -> bool {
+    let mut cards = cards.to_vec();
+    cards.sort();
+
+    step.allowed_moves.is_empty() || step.allowed_moves.iter().any(|allowed| {
+        let mut allowed = allowed.clone();
+        allowed.sort();
+        allowed == cards
+    })
+}
+
+/// The built in tutorials, from the first lesson to the last.
+pub fn get_tutorials() -> Vec<Tutorial> {
+    vec![singles(), pairs_and_passing(), five_card_tricks()]
+}
+
+pub fn get_tutorial(name: &str) -> Option<Tutorial> {
+    get_tutorials().into_iter().find(|tutorial| tutorial.name == name)
+}
+
+fn tutorial(
+    name: &str,
+    title: &str,
+    hands: (&str, &str),
+    steps: Vec<TutorialStep>,
+) -> Tutorial {
+    let round = Scenario::new()
+        .player(LEARNER, hands.0)
+        .player(OPPONENT, hands.1)
+        .build()
+        .expect("tutorial deals are valid");
+
+    Tutorial {
+        name: name.to_string(),
+        title: title.to_string(),
+        learner: LEARNER.to_string(),
+        round,
+        steps,
+    }
+}
+
+fn step(prompt: &str, allowed: &str, responses: &[&str]) -> TutorialStep {
+    let parse = |cards: &str| parse_played_cards(cards).expect("tutorial cards are valid");
+
+    TutorialStep {
+        prompt: prompt.to_string(),
+        allowed_moves: vec![parse(allowed)],
+        responses: responses.iter()
+            .map(|&cards| (OPPONENT.to_string(), parse(cards)))
+            .collect(),
+    }
+}
+
+fn singles() -> Tutorial {
+    tutorial("singles", "Playing singles", ("3C 7D KS", "5H 9C 2D JH"), vec![
+        step(
+            "Whoever holds the Three of Clubs, the lowest card, leads. That's you, so \
+             play it.",
+            "3C",
+            &["5H"],
+        ),
+        step(
+            "Your opponent beat it with the Five of Hearts. Beat that with a higher single, \
+             the Seven of Diamonds.",
+            "7D",
+            &["9C"],
+        ),
+        step(
+            "Play your last card, the King of Spades, to go out and win.",
+            "KS",
+            &[],
+        ),
+    ])
+}
+
+fn pairs_and_passing() -> Tutorial {
+    tutorial(
+        "pairs_and_passing",
+        "Pairs and passing",
+        ("3C 3D 6H KS", "4C 4S 5D 9C JD"),
+        vec![
+            step(
+                "You can lead a pair as long as it has the Three of Clubs in it. Play \
+                 your threes.",
+                "3C 3D",
+                &["4C 4S"],
+            ),
+            step(
+                "Nothing you hold beats a pair of fours, so pass. Your opponent leads next.",
+                "",
+                &["5D"],
+            ),
+            step("Beat the Five of Diamonds with your Six of Hearts.", "6H", &["9C"]),
+            step("Go out with the King of Spades.", "KS", &[]),
+        ],
+    )
+}
+
+fn five_card_tricks() -> Tutorial {
+    tutorial(
+        "five_card_tricks",
+        "Five card tricks",
+        ("3C 4D 5H 6S 7C 9D AS", "8H 8D 8S 10C 10H JC QS"),
+        vec![
+            step(
+                "Five cards in a row make a straight. Lead the straight from the Three of \
+                 Clubs up to the Seven.",
+                "3C 4D 5H 6S 7C",
+                &["8H 8D 8S 10C 10H"],
+            ),
+            step(
+                "A full house beats a straight, and you have nothing higher, so pass.",
+                "",
+                &["JC"],
+            ),
+            step("Beat the Jack of Clubs with your Ace of Spades.", "AS", &[""]),
+            step(
+                "Your opponent couldn't beat the ace, so you lead again. Go out with the \
+                 Nine of Diamonds.",
+                "9D",
+                &[],
+            ),
+        ],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_tutorial_can_be_played_through() {
+        for tutorial in get_tutorials() {
+            let name = tutorial.name.clone();
+            let mut session = TutorialSession::new(tutorial);
+
+            while !session.is_complete() {
+                let cards = session.get_legal_moves().pop()
+                    .unwrap_or_else(|| panic!("{} has no move at step {}", name, session.step));
+                session.play(cards).unwrap();
+            }
+
+            let learner = session.get_round().get_player(LEARNER).unwrap();
+            assert_eq!(learner.get_card_count(), 0, "{}", name);
+            assert_eq!(session.play(vec![]), Err(TutorialError::Finished));
+        }
+    }
+
+    #[test]
+    fn only_the_moves_a_step_teaches_are_accepted() {
+        let mut session = TutorialSession::new(get_tutorial("singles").unwrap());
+
+        assert_eq!(session.get_legal_moves(), vec![parse_played_cards("3C").unwrap()]);
+        assert_eq!(
+            session.play(parse_played_cards("KS").unwrap()),
+            Err(TutorialError::NotAllowed)
+        );
+        assert_eq!(
+            session.play(parse_played_cards("3C").unwrap()),
+            Ok(TutorialProgress::NextStep)
+        );
+        assert_eq!(session.get_round().get_last_move().unwrap().to_cards().len(), 1);
+        assert!(session.get_prompt().unwrap().contains("Seven of Diamonds"));
+    }
+
+    #[test]
+    fn the_engine_still_has_the_last_word() {
+        let mut tutorial = get_tutorial("singles").unwrap();
+        // a step allowing a card that can't beat what's on the table
+        tutorial.steps[1].allowed_moves = vec![];
+        let response = |cards| vec![(OPPONENT.to_string(), parse_played_cards(cards).unwrap())];
+        tutorial.steps[0].responses = response("2D");
+        let mut session = TutorialSession::new(tutorial.clone());
+        session.play(parse_played_cards("3C").unwrap()).unwrap();
+
+        assert_eq!(
+            session.play(parse_played_cards("7D").unwrap()),
+            Err(TutorialError::Illegal(SubmitError::HandNotHighEnough))
+        );
+
+        tutorial.steps[0].responses = response("3D");
+        let mut session = TutorialSession::new(tutorial);
+        assert_eq!(
+            session.play(parse_played_cards("3C").unwrap()),
+            Err(TutorialError::BadScript {
+                step: 0,
+                error: SubmitError::PlayerDoesntHaveCard,
+            })
+        );
+        assert_eq!(session.get_step_index(), 0);
+    }
+}
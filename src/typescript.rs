@@ -0,0 +1,37 @@
+use crate::game::{Hand, Round, RuleDescription, RulesetFields, SubmitError};
+use crate::protocol::{ClientMsg, ServerMsg};
+use std::path::Path;
+use ts_rs::{ExportError, TS};
+
+/// Writes TypeScript definitions matching the serde output of the game
+/// state and protocol messages into `out_dir`, one file per type along
+/// with every type they refer to.
+pub fn export_bindings(out_dir: &Path) -> Result<(), ExportError> {
+    Round::export_all_to(out_dir)?;
+    Hand::export_all_to(out_dir)?;
+    SubmitError::export_all_to(out_dir)?;
+    RulesetFields::export_all_to(out_dir)?;
+    RuleDescription::export_all_to(out_dir)?;
+    ClientMsg::export_all_to(out_dir)?;
+    ServerMsg::export_all_to(out_dir)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn ruleset_flags_are_optional() {
+        let out_dir = std::env::temp_dir().join("pusoy_dos2_bindings");
+        export_bindings(&out_dir).unwrap();
+
+        let ruleset = fs::read_to_string(out_dir.join("Ruleset.ts")).unwrap();
+
+        assert!(ruleset.contains("preset?: RulesetPreset"));
+        assert!(out_dir.join("DealMode.ts").exists());
+        assert!(out_dir.join("ServerMsg.ts").exists());
+    }
+}
@@ -0,0 +1,371 @@
+//! Functions exported to JavaScript. Rounds cross the boundary as plain JS
+//! objects in the same shape serde gives them everywhere else.
+
+use crate::ai::{Agent, CpuAgent, Difficulty, DifficultyAgent, MctsConfig, MctsSearch};
+use crate::cards::{Card, PlayedCard};
+use crate::game::{Round, SubmitError};
+use crate::i18n::Locale;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::task::Poll;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date)]
+    fn now() -> f64;
+}
+
+/// The cpu player's move for `player_id`, as an array of cards, or `null`
+/// when it isn't their turn. An empty array means pass.
+#[wasm_bindgen]
+pub fn suggest_move(round_js: JsValue, player_id: &str) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    to_js(&suggest_move_for(&round, player_id))
+}
+
+/// The move a built-in bot playing at `difficulty_js`, `"easy"`,
+/// `"medium"` or `"hard"`, makes for `player_id`, or `null` when it isn't
+/// their turn. The same seed always gives the same move.
+#[wasm_bindgen]
+pub fn bot_move(
+    round_js: JsValue,
+    player_id: &str,
+    difficulty_js: JsValue,
+    seed: u64,
+) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    let difficulty: Difficulty = serde_wasm_bindgen::from_value(difficulty_js)
+        .map_err(JsValue::from)?;
+    if round.get_next_player().as_deref() != Some(player_id) {
+        return Ok(JsValue::NULL);
+    }
+
+    to_js(&DifficultyAgent::new(difficulty, seed).choose_move(&round, player_id))
+}
+
+/// Every legal move for `player_id` as an array of card arrays, so a UI can
+/// highlight what can be played. An empty card array is the pass.
+#[wasm_bindgen]
+pub fn get_legal_moves(round_js: JsValue, player_id: &str) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    to_js(&crate::ai::get_legal_moves(&round, player_id))
+}
+
+/// Every player's id and how many cards they hold, in seat order, as
+/// `[id, count]` pairs, so opponents' hands can be drawn without reading
+/// their players.
+#[wasm_bindgen]
+pub fn get_card_counts(round_js: JsValue) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    to_js(&round.get_card_counts())
+}
+
+/// The strongest five card trick `player_id` could make from their hand
+/// under the round's rules, or `null` if they can't make one.
+#[wasm_bindgen]
+pub fn best_five_card_trick(round_js: JsValue, player_id: &str) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    let trick = round.get_player(player_id).and_then(|player| {
+        crate::game::best_five_card_trick(
+            &player.get_hand(),
+            &round.get_order_context(),
+            round.get_ruleset(),
+        )
+    });
+    to_js(&trick)
+}
+
+/// The suit order in play, lowest first, e.g. `["clubs", "hearts",
+/// "diamonds", "spades"]`. Reversals show up here as soon as they happen.
+#[wasm_bindgen]
+pub fn get_suit_order(round_js: JsValue) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    to_js(&round.get_suit_order())
+}
+
+/// The rank order in play, lowest first, e.g. `["three", ..., "two"]`.
+#[wasm_bindgen]
+pub fn get_rank_order(round_js: JsValue) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    to_js(&round.get_rank_order())
+}
+
+/// The round's ruleset, in the same shape rulesets are saved in.
+#[wasm_bindgen]
+pub fn get_ruleset(round_js: JsValue) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    to_js(&round.get_ruleset())
+}
+
+/// The round's rules as a list of tagged objects, e.g. `{ type:
+/// "passing", locks_out: false }`, for a table rules screen.
+#[wasm_bindgen]
+pub fn describe_rules(round_js: JsValue) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    to_js(&round.get_ruleset().describe())
+}
+
+/// The round's rules as sentences in `locale`, `"english"` or
+/// `"filipino"`.
+#[wasm_bindgen]
+pub fn describe_rules_text(round_js: JsValue, locale_js: JsValue) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    let locale: Locale = serde_wasm_bindgen::from_value(locale_js)
+        .map_err(JsValue::from)?;
+    let text: Vec<String> = round.get_ruleset().describe().iter()
+        .map(|rule| rule.to_text(locale))
+        .collect();
+    to_js(&text)
+}
+
+/// The round as a share code for links and chat, or `null` if it can't
+/// be packed.
+#[wasm_bindgen]
+pub fn to_share_code(round_js: JsValue) -> Result<Option<String>, JsValue> {
+    let round = from_js(round_js)?;
+    Ok(round.to_share_code())
+}
+
+/// The round a share code was made from, throwing for bad codes.
+#[wasm_bindgen]
+pub fn from_share_code(code: &str) -> Result<JsValue, JsValue> {
+    let round = Round::from_share_code(code)
+        .map_err(|err| JsValue::from_str(&format!("{:?}", err)))?;
+    to_js(&round)
+}
+
+/// The sprite name for a card, e.g. `"10H"` or `"JOKER_1"`.
+#[wasm_bindgen]
+pub fn get_asset_key(card_js: JsValue) -> Result<String, JsValue> {
+    let card: Card = serde_wasm_bindgen::from_value(card_js)
+        .map_err(JsValue::from)?;
+    Ok(card.asset_key())
+}
+
+/// The card a sprite name stands for, throwing for unknown names.
+#[wasm_bindgen]
+pub fn card_from_asset_key(key: &str) -> Result<JsValue, JsValue> {
+    let card = Card::from_asset_key(key)
+        .map_err(|err| JsValue::from_str(&err.to_string()))?;
+    to_js(&card)
+}
+
+/// Plays `cards_js`, an array of cards, for `player_id` and returns the
+/// round after the move. Illegal moves throw an error object as described
+/// by `ErrorCode`.
+#[wasm_bindgen]
+pub fn submit_move(
+    round_js: JsValue,
+    player_id: &str,
+    cards_js: JsValue,
+) -> Result<JsValue, JsValue> {
+    let round = from_js(round_js)?;
+    let cards: Vec<PlayedCard> = serde_wasm_bindgen::from_value(cards_js)
+        .map_err(JsValue::from)?;
+
+    match round.submit_move(player_id, cards) {
+        Ok(round) => to_js(&round),
+        Err(err) => Err(to_js_error(&err)),
+    }
+}
+
+/// An error that crosses into JS as `{ code, message, params }`. Codes are
+/// stable, so clients can key their own translations on them. `message`
+/// is an English template whose `{name}` placeholders are filled from
+/// `params`.
+pub trait ErrorCode {
+    fn get_code(&self) -> &'static str;
+    fn get_message_template(&self) -> &'static str;
+
+    fn get_params(&self) -> BTreeMap<&'static str, String> {
+        BTreeMap::new()
+    }
+}
+
+impl ErrorCode for SubmitError {
+    fn get_code(&self) -> &'static str {
+        match self {
+            SubmitError::FirstRoundPass => "first_round_pass",
+            SubmitError::FirstHandMustContainLowestCard => "first_hand_must_contain_lowest_card",
+            SubmitError::HandNotHighEnough => "hand_not_high_enough",
+            SubmitError::NotCurrentPlayer => "not_current_player",
+            SubmitError::InvalidHand => "invalid_hand",
+            SubmitError::PlayerDoesntHaveCard => "player_doesnt_have_card",
+            SubmitError::JokerOutsideFiveCardTrick => "joker_outside_five_card_trick",
+            SubmitError::CannotFinishWithJoker => "cannot_finish_with_joker",
+            SubmitError::IllegalFinalCard => "illegal_final_card",
+            SubmitError::CannotFinishWithBomb => "cannot_finish_with_bomb",
+            SubmitError::CannotFinishWithReversal => "cannot_finish_with_reversal",
+            SubmitError::DeadGame => "dead_game",
+            SubmitError::LockedOutOfTrick => "locked_out_of_trick",
+            SubmitError::HouseRule(_) => "house_rule",
+            SubmitError::KittyPending => "kitty_pending",
+            SubmitError::GamePaused => "game_paused",
+            SubmitError::AmbiguousCardCopy => "ambiguous_card_copy",
+            SubmitError::PlayerAlreadyFinished => "player_already_finished",
+        }
+    }
+
+    fn get_message_template(&self) -> &'static str {
+        match self {
+            SubmitError::FirstRoundPass => "The opening move can't be a pass",
+            SubmitError::FirstHandMustContainLowestCard => "The opening hand has to include the lowest card",
+            SubmitError::HandNotHighEnough => "That hand doesn't beat the one on the table",
+            SubmitError::NotCurrentPlayer => "It isn't your turn",
+            SubmitError::InvalidHand => "Those cards don't make a hand",
+            SubmitError::PlayerDoesntHaveCard => "You don't hold those cards",
+            SubmitError::JokerOutsideFiveCardTrick => "Jokers can only be played in five card tricks",
+            SubmitError::CannotFinishWithJoker => "You can't go out on a joker",
+            SubmitError::IllegalFinalCard => "You can't go out on a two or a joker",
+            SubmitError::CannotFinishWithBomb => "You can't go out on a bomb",
+            SubmitError::CannotFinishWithReversal => "You can't go out on a hand that reverses the order",
+            SubmitError::DeadGame => "This game can't carry on",
+            SubmitError::LockedOutOfTrick => "You passed, so you're out until the next trick",
+            SubmitError::HouseRule(_) => "{reason}",
+            SubmitError::KittyPending => "The kitty has to be picked up and discarded first",
+            SubmitError::GamePaused => "The game is paused",
+            SubmitError::AmbiguousCardCopy => "Say which copy of each card you're playing",
+            SubmitError::PlayerAlreadyFinished => "You've already gone out",
+        }
+    }
+
+    fn get_params(&self) -> BTreeMap<&'static str, String> {
+        let mut params = BTreeMap::new();
+        if let SubmitError::HouseRule(reason) = self {
+            params.insert("reason", reason.clone());
+        }
+
+        params
+    }
+}
+
+#[derive(Serialize)]
+struct JsError {
+    code: &'static str,
+    message: &'static str,
+    params: BTreeMap<&'static str, String>,
+}
+
+pub fn to_js_error<E: ErrorCode>(err: &E) -> JsValue {
+    let error = JsError {
+        code: err.get_code(),
+        message: err.get_message_template(),
+        params: err.get_params(),
+    };
+
+    to_js(&error).unwrap_or_else(|e| e)
+}
+
+/// A tree search for one player's move that runs a little at a time. Call
+/// `think` once per animation frame until it returns a move.
+#[wasm_bindgen]
+pub struct AiSearch {
+    search: MctsSearch,
+}
+
+#[wasm_bindgen]
+impl AiSearch {
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        round_js: JsValue,
+        player_id: &str,
+        iterations: usize,
+        seed: u64,
+    ) -> Result<AiSearch, JsValue> {
+        let round = from_js(round_js)?;
+        let config = MctsConfig { iterations, ..MctsConfig::default() };
+
+        Ok(AiSearch {
+            search: MctsSearch::new(&round, player_id, config, seed),
+        })
+    }
+
+    /// Searches for about `budget_ms` milliseconds. Returns `null` while
+    /// the search is unfinished, then the chosen move as an array of cards.
+    pub fn think(&mut self, budget_ms: f64) -> Result<JsValue, JsValue> {
+        match self.search.think_with_clock(budget_ms, now) {
+            Poll::Ready(cards) => to_js(&cards),
+            Poll::Pending => Ok(JsValue::NULL),
+        }
+    }
+}
+
+fn suggest_move_for(round: &Round, player_id: &str) -> Option<Vec<PlayedCard>> {
+    if round.get_next_player().as_deref() != Some(player_id) {
+        return None;
+    }
+
+    Some(CpuAgent.choose_move(round, player_id))
+}
+
+fn from_js(value: JsValue) -> Result<Round, JsValue> {
+    serde_wasm_bindgen::from_value(value).map_err(JsValue::from)
+}
+
+fn to_js<T: serde::Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(value).map_err(JsValue::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::{Card, Rank, Suit};
+    use crate::game::{Player, RoundBuilder};
+
+    fn get_round() -> Round {
+        RoundBuilder::new()
+            .player(Player::new("a".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Three, suit: Suit::Clubs },
+                Card::Standard { deck_id: 0, rank: Rank::Nine, suit: Suit::Hearts },
+            ]))
+            .player(Player::new("b".to_string(), vec![
+                Card::Standard { deck_id: 0, rank: Rank::Four, suit: Suit::Clubs },
+            ]))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn suggestions_are_legal_moves_for_the_current_player() {
+        let round = get_round();
+        let suggestion = suggest_move_for(&round, "a").unwrap();
+
+        assert!(round.validate_move("a", &suggestion).is_ok());
+        assert_eq!(suggest_move_for(&round, "b"), None);
+    }
+
+    #[test]
+    fn submit_errors_have_distinct_codes() {
+        let errors = vec![
+            SubmitError::FirstRoundPass,
+            SubmitError::FirstHandMustContainLowestCard,
+            SubmitError::HandNotHighEnough,
+            SubmitError::NotCurrentPlayer,
+            SubmitError::InvalidHand,
+            SubmitError::PlayerDoesntHaveCard,
+            SubmitError::JokerOutsideFiveCardTrick,
+            SubmitError::CannotFinishWithJoker,
+            SubmitError::IllegalFinalCard,
+            SubmitError::CannotFinishWithBomb,
+            SubmitError::CannotFinishWithReversal,
+            SubmitError::DeadGame,
+            SubmitError::LockedOutOfTrick,
+            SubmitError::HouseRule("no twos".to_string()),
+            SubmitError::KittyPending,
+            SubmitError::GamePaused,
+            SubmitError::AmbiguousCardCopy,
+            SubmitError::PlayerAlreadyFinished,
+        ];
+        let mut codes: Vec<&str> = errors.iter().map(|e| e.get_code()).collect();
+        codes.sort();
+        codes.dedup();
+
+        assert_eq!(codes.len(), errors.len());
+        assert_eq!(SubmitError::HandNotHighEnough.get_code(), "hand_not_high_enough");
+        assert_eq!(
+            SubmitError::HouseRule("no twos".to_string()).get_params().get("reason"),
+            Some(&"no twos".to_string())
+        );
+    }
+}